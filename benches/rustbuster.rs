@@ -17,7 +17,7 @@ fn fuzzrequest_body() -> FuzzRequest {
         uri: "http://localhost/".parse::<hyper::Uri>().unwrap(),
         http_method: "GET".to_owned(),
         http_headers: vec![],
-        http_body: "CSRFCSRF".to_owned(),
+        http_body: b"CSRFCSRF".to_vec(),
         user_agent: "ua".to_owned(),
         payload: vec!["1".to_owned()],
         csrf_uri: Some("http://localhost/".parse::<hyper::Uri>().unwrap()),
@@ -31,7 +31,7 @@ fn fuzzrequest_header() -> FuzzRequest {
         uri: "http://localhost/".parse::<hyper::Uri>().unwrap(),
         http_method: "GET".to_owned(),
         http_headers: vec![("X-CSRF-Token".to_owned(), "CSRFCSRF".to_owned())],
-        http_body: "body".to_owned(),
+        http_body: b"body".to_vec(),
         user_agent: "ua".to_owned(),
         payload: vec!["1".to_owned()],
         csrf_uri: Some("http://localhost/".parse::<hyper::Uri>().unwrap()),
@@ -45,7 +45,7 @@ fn fuzzrequest_url() -> FuzzRequest {
         uri: "http://localhost/CSRFCSRF".parse::<hyper::Uri>().unwrap(),
         http_method: "GET".to_owned(),
         http_headers: vec![],
-        http_body: "body".to_owned(),
+        http_body: b"body".to_vec(),
         user_agent: "ua".to_owned(),
         payload: vec!["1".to_owned()],
         csrf_uri: Some("http://localhost/".parse::<hyper::Uri>().unwrap()),
@@ -56,33 +56,22 @@ fn fuzzrequest_url() -> FuzzRequest {
 
 fn fuzzbuster_url_single() -> FuzzBuster {
     FuzzBuster {
-        n_threads: 1,
         ignore_certificate: true,
-        http_method: "GET".to_owned(),
-        http_body: "body".to_owned(),
+        http_body: b"body".to_vec(),
         user_agent: "ua".to_owned(),
         http_headers: vec![("Header".to_owned(), "Value".to_owned())],
         wordlist_paths: vec!["./examples/wordlist_short".to_owned()],
         url: "http://localhost/FUZZ".to_owned(),
-        include_status_codes: vec![],
         ignore_status_codes: vec!["404".to_owned()],
-        include_body: vec![],
-        ignore_body: vec![],
         no_progress_bar: true,
-        exit_on_connection_errors: false,
-        output: "".to_owned(),
-        csrf_url: None,
-        csrf_regex: None,
-        csrf_headers: None,
+        ..Default::default()
     }
 }
 
 fn fuzzbuster_url_multiple() -> FuzzBuster {
     FuzzBuster {
-        n_threads: 1,
         ignore_certificate: true,
-        http_method: "GET".to_owned(),
-        http_body: "body".to_owned(),
+        http_body: b"body".to_vec(),
         user_agent: "ua".to_owned(),
         http_headers: vec![("Header".to_owned(), "Value".to_owned())],
         wordlist_paths: vec![
@@ -90,48 +79,30 @@ fn fuzzbuster_url_multiple() -> FuzzBuster {
             "./examples/wordlist_short".to_owned(),
         ],
         url: "http://localhost/FUZZ/FUZZ".to_owned(),
-        include_status_codes: vec![],
         ignore_status_codes: vec!["404".to_owned()],
-        include_body: vec![],
-        ignore_body: vec![],
         no_progress_bar: true,
-        exit_on_connection_errors: false,
-        output: "".to_owned(),
-        csrf_url: None,
-        csrf_regex: None,
-        csrf_headers: None,
+        ..Default::default()
     }
 }
 
 fn fuzzbuster_header_single() -> FuzzBuster {
     FuzzBuster {
-        n_threads: 1,
         ignore_certificate: true,
-        http_method: "GET".to_owned(),
-        http_body: "body".to_owned(),
+        http_body: b"body".to_vec(),
         user_agent: "ua".to_owned(),
         http_headers: vec![("Header".to_owned(), "FUZZ".to_owned())],
         wordlist_paths: vec!["./examples/wordlist_short".to_owned()],
         url: "http://localhost/".to_owned(),
-        include_status_codes: vec![],
         ignore_status_codes: vec!["404".to_owned()],
-        include_body: vec![],
-        ignore_body: vec![],
         no_progress_bar: true,
-        exit_on_connection_errors: false,
-        output: "".to_owned(),
-        csrf_url: None,
-        csrf_regex: None,
-        csrf_headers: None,
+        ..Default::default()
     }
 }
 
 fn fuzzbuster_header_multiple() -> FuzzBuster {
     FuzzBuster {
-        n_threads: 1,
         ignore_certificate: true,
-        http_method: "GET".to_owned(),
-        http_body: "body".to_owned(),
+        http_body: b"body".to_vec(),
         user_agent: "ua".to_owned(),
         http_headers: vec![("FUZZ".to_owned(), "FUZZ".to_owned())],
         wordlist_paths: vec![
@@ -139,48 +110,30 @@ fn fuzzbuster_header_multiple() -> FuzzBuster {
             "./examples/wordlist_short".to_owned(),
         ],
         url: "http://localhost/".to_owned(),
-        include_status_codes: vec![],
         ignore_status_codes: vec!["404".to_owned()],
-        include_body: vec![],
-        ignore_body: vec![],
         no_progress_bar: true,
-        exit_on_connection_errors: false,
-        output: "".to_owned(),
-        csrf_url: None,
-        csrf_regex: None,
-        csrf_headers: None,
+        ..Default::default()
     }
 }
 
 fn fuzzbuster_body_single() -> FuzzBuster {
     FuzzBuster {
-        n_threads: 1,
         ignore_certificate: true,
-        http_method: "GET".to_owned(),
-        http_body: "FUZZ".to_owned(),
+        http_body: b"FUZZ".to_vec(),
         user_agent: "ua".to_owned(),
         http_headers: vec![("Header".to_owned(), "Value".to_owned())],
         wordlist_paths: vec!["./examples/wordlist_short".to_owned()],
         url: "http://localhost/".to_owned(),
-        include_status_codes: vec![],
         ignore_status_codes: vec!["404".to_owned()],
-        include_body: vec![],
-        ignore_body: vec![],
         no_progress_bar: true,
-        exit_on_connection_errors: false,
-        output: "".to_owned(),
-        csrf_url: None,
-        csrf_regex: None,
-        csrf_headers: None,
+        ..Default::default()
     }
 }
 
 fn fuzzbuster_body_multiple() -> FuzzBuster {
     FuzzBuster {
-        n_threads: 1,
         ignore_certificate: true,
-        http_method: "GET".to_owned(),
-        http_body: "FUZZ:FUZZ".to_owned(),
+        http_body: b"FUZZ:FUZZ".to_vec(),
         user_agent: "ua".to_owned(),
         http_headers: vec![("Header".to_owned(), "Value".to_owned())],
         wordlist_paths: vec![
@@ -188,16 +141,9 @@ fn fuzzbuster_body_multiple() -> FuzzBuster {
             "./examples/wordlist_short".to_owned(),
         ],
         url: "http://localhost/".to_owned(),
-        include_status_codes: vec![],
         ignore_status_codes: vec!["404".to_owned()],
-        include_body: vec![],
-        ignore_body: vec![],
         no_progress_bar: true,
-        exit_on_connection_errors: false,
-        output: "".to_owned(),
-        csrf_url: None,
-        csrf_regex: None,
-        csrf_headers: None,
+        ..Default::default()
     }
 }
 
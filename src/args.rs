@@ -1,12 +1,25 @@
 use clap::{App, Arg};
+use native_tls::Protocol;
+use std::sync::Arc;
 use terminal_size::{terminal_size, Height, Width};
 
+use crate::environment::{self, EnvArg};
+
 pub struct CommonArgs {
     pub no_banner: bool,
     pub no_progress_bar: bool,
+    pub progress_json: String,
     pub exit_on_connection_errors: bool,
     pub n_threads: usize,
     pub output: String,
+    pub output_append: bool,
+    pub known: String,
+    pub max_results: usize,
+    pub project: String,
+    pub quiet: bool,
+    pub color: bool,
+    pub format: String,
+    pub geoip: String,
 }
 
 pub struct WordlistArgs {
@@ -15,17 +28,52 @@ pub struct WordlistArgs {
 
 pub struct DNSArgs {
     pub domain: String,
+    pub seed_from_cert: bool,
+    pub brute_tlds: bool,
+    pub resolve_vhosts: bool,
+    pub absolute_uri: bool,
+    pub zone_walk: bool,
+    pub timeout: u64,
+    pub retries: usize,
+    pub output_format: String,
+    pub asn_lookup: bool,
 }
 
 pub struct HTTPArgs {
     pub user_agent: String,
     pub http_method: String,
-    pub http_body: String,
+    pub http_body: Vec<u8>,
     pub url: String,
     pub ignore_certificate: bool,
     pub http_headers: Vec<(String, String)>,
     pub include_status_codes: Vec<String>,
     pub ignore_status_codes: Vec<String>,
+    pub tls_min_version: Option<Protocol>,
+    pub tls_max_version: Option<Protocol>,
+    pub digest: Option<(String, String)>,
+    pub negotiate: Option<Arc<crate::negotiate::Negotiate>>,
+    pub aws_sigv4: Option<Arc<crate::sigv4::SigV4>>,
+    pub capture_headers: Vec<String>,
+    pub audit_headers: bool,
+    pub request_tag: Option<Arc<crate::correlation::RequestTag>>,
+    pub favicon: bool,
+}
+
+pub struct HarArgs {
+    pub har: String,
+    pub save_requests: String,
+    pub show_curl: bool,
+    pub burp: String,
+}
+
+impl HarArgs {
+    /// The requests and responses are only kept when something writes them
+    pub fn save_har(&self) -> bool {
+        !self.har.is_empty()
+            || !self.save_requests.is_empty()
+            || !self.burp.is_empty()
+            || self.show_curl
+    }
 }
 
 pub struct BodyArgs {
@@ -36,16 +84,87 @@ pub struct BodyArgs {
 pub struct DirArgs {
     pub append_slash: bool,
     pub extensions: Vec<String>,
+    pub rules: Vec<(String, Vec<String>)>,
+    pub raw_path: bool,
+    pub mutations: bool,
+    pub backslash: bool,
+    pub crawl: bool,
+    pub brute_force: bool,
+    pub adaptive: bool,
+    pub force_extensions: bool,
+    pub check_cors: bool,
+    pub check_head: bool,
+    pub probe_http10: bool,
+    pub wraps: Vec<(String, String)>,
+    pub range_threshold: u64,
+    pub range_size: usize,
+    pub skip_dirs: Vec<regex::Regex>,
+    pub both_schemes: bool,
+    pub ports: Vec<u16>,
+    pub tag_each_request: bool,
+}
+
+pub struct AuthArgs {
+    pub usernames: Vec<String>,
+    pub passwords: Vec<String>,
+    pub lockout_threshold: usize,
+    pub account_delay: u64,
 }
 
 pub struct TildeArgs {
     pub extension: Option<String>,
 }
 
+pub struct FrontingArgs {
+    pub domains: Vec<String>,
+}
+
+pub struct ChainArgs {
+    pub domain: String,
+    pub dir_wordlist: String,
+}
+
+pub struct WsArgs {
+    pub messages: Vec<String>,
+}
+
+pub struct SoapArgs {
+    pub operations: Vec<String>,
+    pub fuzz_params: Vec<String>,
+}
+
+pub struct ProjectArgs {
+    pub project: String,
+    pub export: String,
+    pub export_format: String,
+    pub resume: bool,
+}
+
+pub struct DiffArgs {
+    pub old: String,
+    pub new: String,
+}
+
+pub struct ImportArgs {
+    pub export: String,
+    pub host: String,
+    pub paths: String,
+    pub params: String,
+}
+
 pub struct FuzzArgs {
     pub csrf_url: Option<String>,
     pub csrf_regex: Option<String>,
     pub csrf_headers: Option<Vec<(String, String)>>,
+    pub raw_path: bool,
+    pub forms: Vec<String>,
+    pub jwt_claims: Option<String>,
+    pub jwt_secret_wordlist: Option<String>,
+    pub jwt_none: bool,
+    pub check_cors: bool,
+    pub mutate: bool,
+    pub auto_calibrate: bool,
+    pub hex_wordlists: bool,
 }
 
 pub fn set_common_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
@@ -61,6 +180,17 @@ pub fn set_common_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
             .long("no-banner")
             .help("Skips initial banner"),
     )
+    .arg(
+        Arg::with_name("no-color")
+            .long("no-color")
+            .help("Disables colored output"),
+    )
+    .arg(
+        Arg::with_name("quiet")
+            .long("quiet")
+            .help("Prints only one tab-separated line per finding")
+            .short("q"),
+    )
     .arg(
         Arg::with_name("threads")
             .long("threads")
@@ -84,13 +214,95 @@ pub fn set_common_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
             .default_value("")
             .takes_value(true),
     )
+    .arg(
+        Arg::with_name("format")
+            .long("format")
+            .help("Prints and saves each result with a template, e.g. '{method} {status} {length} {url} -> {redirect}'")
+            .default_value("")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("output-append")
+            .long("output-append")
+            .help("Merges the results into the output file instead of overwriting it"),
+    )
+    .arg(
+        Arg::with_name("known")
+            .long("known")
+            .help("Hides the results already present in the specified results file")
+            .default_value("")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("project")
+            .long("project")
+            .help("Records the scan and its findings in the specified project file")
+            .default_value("")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("geoip")
+            .long("geoip")
+            .help("Annotates the resolved and connected addresses with their country and city from the specified MaxMind database")
+            .default_value("")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("log-format")
+            .long("log-format")
+            .help("Sets the format of the log records")
+            .possible_values(&["text", "json"])
+            .default_value("text")
+            .takes_value(true),
+    )
     .arg(
         Arg::with_name("no-progress-bar")
             .long("no-progress-bar")
             .help("Disables the progress bar"),
     )
+    .arg(
+        Arg::with_name("progress-json")
+            .long("progress-json")
+            .help("Periodically writes the progress as JSON lines to the specified file or named pipe, - for stderr")
+            .default_value("")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("prefer-family")
+            .long("prefer-family")
+            .help("Prefers IPv4 or IPv6 addresses when connecting to targets and listing resolved domains")
+            .possible_values(&["4", "6"])
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("max-results")
+            .long("max-results")
+            .help("Stops the scan after the specified number of results, 0 means no limit")
+            .default_value("0")
+            .takes_value(true),
+    )
 }
 
+pub const COMMON_ENV: &[EnvArg] = &[
+    EnvArg::flag("verbose", Some('v')),
+    EnvArg::flag("no-banner", None),
+    EnvArg::flag("no-color", None),
+    EnvArg::flag("quiet", Some('q')),
+    EnvArg::value("threads", Some('t')).aliases(&["workers"]),
+    EnvArg::flag("exit-on-error", Some('K')),
+    EnvArg::value("output", Some('o')),
+    EnvArg::value("format", None),
+    EnvArg::flag("output-append", None),
+    EnvArg::value("known", None),
+    EnvArg::value("project", None),
+    EnvArg::value("geoip", None),
+    EnvArg::value("log-format", None),
+    EnvArg::flag("no-progress-bar", None),
+    EnvArg::value("progress-json", None),
+    EnvArg::value("prefer-family", None),
+    EnvArg::value("max-results", None),
+];
+
 pub fn set_http_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
     app.arg(
         Arg::with_name("include-status-codes")
@@ -123,10 +335,30 @@ pub fn set_http_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
             .help("Disables TLS certificate validation")
             .short("k"),
     )
+    .arg(
+        Arg::with_name("no-env-proxy")
+            .long("no-env-proxy")
+            .help("Ignores the HTTP_PROXY, HTTPS_PROXY and NO_PROXY environment variables"),
+    )
+    .arg(
+        Arg::with_name("no-favicon")
+            .long("no-favicon")
+            .help("Skips fetching the favicon of the target to fingerprint it"),
+    )
+    .arg(
+        Arg::with_name("tag-requests")
+            .long("tag-requests")
+            .help("Sends a random scan identifier in the X-Rustbuster-Id header of every request, to find the scan in the server logs"),
+    )
+    .arg(
+        Arg::with_name("probe-target")
+            .long("probe-target")
+            .help("Connects to the target and sends one request before the scan, aborting early when it is unreachable"),
+    )
     .arg(
         Arg::with_name("http-method")
             .long("http-method")
-            .help("Uses the specified HTTP method")
+            .help("Uses the specified HTTP method, in fuzz mode FUZZ takes the methods from the first wordlist")
             .short("X")
             .default_value("GET")
             .takes_value(true),
@@ -139,6 +371,12 @@ pub fn set_http_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
             .default_value("")
             .takes_value(true),
     )
+    .arg(
+        Arg::with_name("body-file")
+            .long("body-file")
+            .help("Uses the content of the specified file as HTTP body, overriding --http-body")
+            .takes_value(true),
+    )
     .arg(
         Arg::with_name("http-header")
             .long("http-header")
@@ -147,21 +385,221 @@ pub fn set_http_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
             .multiple(true)
             .takes_value(true),
     )
+    .arg(
+        Arg::with_name("tls-min")
+            .long("tls-min")
+            .help("Sets the minimum TLS version to negotiate, the cipher suites are always the system defaults")
+            .possible_values(&["1.0", "1.1", "1.2", "1.3"])
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("tls-max")
+            .long("tls-max")
+            .help("Sets the maximum TLS version to negotiate, the cipher suites are always the system defaults")
+            .possible_values(&["1.0", "1.1", "1.2", "1.3"])
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("digest")
+            .long("digest")
+            .help("Authenticates with HTTP Digest, e.g. --digest user:pass")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("negotiate")
+            .long("negotiate")
+            .help("Authenticates with SPNEGO/Kerberos using the ticket in the credentials cache")
+            .conflicts_with("digest"),
+    )
+    .arg(
+        Arg::with_name("keytab")
+            .long("keytab")
+            .help("Gets the Kerberos ticket from the specified keytab instead")
+            .requires("negotiate")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("aws-sigv4")
+            .long("aws-sigv4")
+            .help("Signs the requests with AWS SigV4, e.g. --aws-sigv4 us-east-1/execute-api")
+            .conflicts_with_all(&["digest", "negotiate"])
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("capture-header")
+            .long("capture-header")
+            .help("Records the specified response header for each result, e.g. --capture-header Server")
+            .multiple(true)
+            .number_of_values(1)
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("audit-headers")
+            .long("audit-headers")
+            .help("Audits the security headers of the responses and prints a summary per host"),
+    )
     .arg(
         Arg::with_name("url")
             .long("url")
             .alias("domain")
-            .help("Sets the target URL")
+            .help("Sets the target URL, the scheme is detected when omitted")
             .short("u")
             .takes_value(true)
-            .required(true),
+            .required_unless("input-nmap"),
+    )
+}
+
+pub const HTTP_ENV: &[EnvArg] = &[
+    EnvArg::value("include-status-codes", Some('s')),
+    EnvArg::value("ignore-status-codes", Some('S')),
+    EnvArg::value("user-agent", Some('a')),
+    EnvArg::flag("ignore-certificate", Some('k')).aliases(&["no-check-certificate"]),
+    EnvArg::flag("no-env-proxy", None),
+    EnvArg::flag("no-favicon", None),
+    EnvArg::flag("tag-requests", None),
+    EnvArg::flag("probe-target", None),
+    EnvArg::value("http-method", Some('X')),
+    EnvArg::value("http-body", Some('b')),
+    EnvArg::value("body-file", None),
+    EnvArg::values("http-header", Some('H')),
+    EnvArg::value("tls-min", None),
+    EnvArg::value("tls-max", None),
+    EnvArg::value("digest", None),
+    EnvArg::flag("negotiate", None),
+    EnvArg::value("keytab", None),
+    EnvArg::value("aws-sigv4", None),
+    EnvArg::values("capture-header", None),
+    EnvArg::flag("audit-headers", None),
+    EnvArg::value("url", Some('u')).aliases(&["domain"]),
+];
+
+pub fn set_nmap_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(
+        Arg::with_name("input-nmap")
+            .long("input-nmap")
+            .help("Scans the open HTTP(S) ports of an nmap XML report one after the other, instead of the URL")
+            .conflicts_with("url")
+            .takes_value(true),
+    )
+}
+
+pub const NMAP_ENV: &[EnvArg] = &[EnvArg::value("input-nmap", None)];
+
+pub fn set_slow_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(
+        Arg::with_name("slow-on")
+            .long("slow-on")
+            .help("Spaces the requests by the delay while the status class spikes in the latest responses, e.g. 403:2000ms or 5xx:5s")
+            .multiple(true)
+            .number_of_values(1)
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("max-rate-limit-wait")
+            .long("max-rate-limit-wait")
+            .help("Caps the pause asked by Retry-After or a rate limit reset, in seconds")
+            .default_value("300")
+            .takes_value(true),
+    )
+}
+
+pub const SLOW_ENV: &[EnvArg] = &[
+    EnvArg::values("slow-on", None),
+    EnvArg::value("max-rate-limit-wait", None),
+];
+
+pub fn set_har_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(
+        Arg::with_name("har")
+            .long("har")
+            .help("Saves the matched requests and responses in the specified HAR file")
+            .default_value("")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("save-requests")
+            .long("save-requests")
+            .help("Saves the raw request of every match in the specified directory")
+            .default_value("")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("burp")
+            .long("burp")
+            .help("Saves the matched requests and responses in the specified Burp XML file")
+            .default_value("")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("show-curl")
+            .long("show-curl")
+            .help("Shows a curl command line reproducing every match"),
+    )
+}
+
+pub const HAR_ENV: &[EnvArg] = &[
+    EnvArg::value("har", None),
+    EnvArg::value("save-requests", None),
+    EnvArg::value("burp", None),
+    EnvArg::flag("show-curl", None),
+];
+
+pub fn set_report_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(
+        Arg::with_name("sort")
+            .long("sort")
+            .help("Sorts the results in the final summary and the saved results")
+            .possible_values(&["status", "length", "url"])
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("group-by")
+            .long("group-by")
+            .help("Groups the results in the final summary and the saved results")
+            .possible_values(&["directory", "status"])
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("interactive")
+            .long("interactive")
+            .help("Pauses the scan on Enter to add or remove filters of the results"),
+    )
+}
+
+pub const REPORT_ENV: &[EnvArg] = &[
+    EnvArg::value("sort", None),
+    EnvArg::value("group-by", None),
+    EnvArg::flag("interactive", None),
+];
+
+pub fn set_subdomains_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(
+        Arg::with_name("input-subdomains")
+            .long("input-subdomains")
+            .help("Uses the subdomains of an amass or subfinder output as the candidates instead of the wordlist, the domain restricts them when given")
+            .conflicts_with_all(&["zone-walk", "brute-tlds"])
+            .takes_value(true),
     )
 }
 
+pub const SUBDOMAINS_ENV: &[EnvArg] = &[EnvArg::value("input-subdomains", None)];
+
+pub fn set_session_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(
+        Arg::with_name("session")
+            .long("session")
+            .help("Logs in with the sequence in the specified YAML file, logging in again when the session expires")
+            .takes_value(true),
+    )
+}
+
+pub const SESSION_ENV: &[EnvArg] = &[EnvArg::value("session", None)];
+
 pub fn set_body_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
     app.arg(
         Arg::with_name("ignore-string")
             .long("ignore-string")
+            .alias("fail-string")
             .help("Ignores results with specified string in the HTTP body")
             .short("x")
             .multiple(true)
@@ -178,6 +616,11 @@ pub fn set_body_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
     )
 }
 
+pub const BODY_ENV: &[EnvArg] = &[
+    EnvArg::values("ignore-string", Some('x')).aliases(&["fail-string"]),
+    EnvArg::values("include-string", Some('i')),
+];
+
 pub fn set_dir_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
     app.arg(
         Arg::with_name("extensions")
@@ -187,27 +630,222 @@ pub fn set_dir_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
             .default_value("")
             .use_delimiter(true),
     )
+    .arg(
+        Arg::with_name("extensions-file")
+            .long("extensions-file")
+            .help("Reads the extensions from the specified file, one per line")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("check-cors")
+            .long("check-cors")
+            .help("Re-requests the results with foreign origins to find CORS misconfigurations"),
+    )
+    .arg(
+        Arg::with_name("check-head")
+            .long("check-head")
+            .help("Re-requests the results with HEAD and reports Content-Length values not matching the GET body"),
+    )
+    .arg(
+        Arg::with_name("probe-http10")
+            .long("probe-http10")
+            .help("Re-requests the 403/404 results with HTTP/1.0, with and without Host, and reports different responses"),
+    )
+    .arg(
+        Arg::with_name("severity-rule")
+            .long("severity-rule")
+            .help(
+                "Tags the matching results with a severity before the built-in rules \
+                 (e.g. high:path=/backup, medium:status=500, low:body=debug)",
+            )
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1),
+    )
+    .arg(
+        Arg::with_name("force-extensions")
+            .long("force-extensions")
+            .help("Appends the extensions to every entry, not only to the ones with %EXT%"),
+    )
+    .arg(
+        Arg::with_name("wrap")
+            .long("wrap")
+            .help("Also tries every entry between the specified prefix and suffix (e.g. .:.swp, _:.old)")
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1),
+    )
+    .arg(
+        Arg::with_name("skip-dir")
+            .long("skip-dir")
+            .help("Never scans nor crawls into the directories matching the specified regex (e.g. ^/static/)")
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1),
+    )
+    .arg(
+        Arg::with_name("both-schemes")
+            .long("both-schemes")
+            .help("Requests every candidate over both http:// and https://, reporting the paths served differently")
+            .conflicts_with("input-nmap"),
+    )
+    .arg(
+        Arg::with_name("tag-each-request")
+            .long("tag-each-request")
+            .help("Appends the number of the scan entry to the X-Rustbuster-Id header, saved as request_id in the results")
+            .requires("tag-requests"),
+    )
+    .arg(
+        Arg::with_name("ports")
+            .long("ports")
+            .help("Scans the target on every specified port instead of the one of the URL (e.g. 80,443,8000-8100)")
+            .conflicts_with("input-nmap")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("range-threshold")
+            .long("range-threshold")
+            .help("Fetches only the beginning of the results with a larger Content-Length through a Range request, 0 disables it")
+            .default_value("0")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("range-size")
+            .long("range-size")
+            .help("Sets the number of bytes requested with --range-threshold")
+            .default_value("65536")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("rule")
+            .long("rule")
+            .help(
+                "Sets the status codes to accept for an extension, overriding the global filters \
+                 (e.g. php:200,500)",
+            )
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1),
+    )
     .arg(
         Arg::with_name("append-slash")
             .long("append-slash")
             .help("Tries to also append / to the base request")
             .short("f"),
     )
+    .arg(
+        Arg::with_name("mutations")
+            .long("mutations")
+            .help("Also tries each entry with %00, ;.js, ., ::$DATA and %20 appended"),
+    )
+    .arg(
+        Arg::with_name("backslash")
+            .long("backslash")
+            .help("Also tries each entry with the path separators encoded as %5c"),
+    )
+    .arg(
+        Arg::with_name("pipeline")
+            .long("pipeline")
+            .help("Sets the stages to run: dir, crawl to only list the linked directories, or crawl,dir to bust only under them")
+            .possible_values(&["crawl", "dir"])
+            .default_value("dir")
+            .use_delimiter(true),
+    )
+    .arg(
+        Arg::with_name("adaptive")
+            .long("adaptive")
+            .help("Adds the words found in the links, scripts and forms of the positive responses to the running scan"),
+    )
+    .arg(
+        Arg::with_name("raw-path")
+            .long("raw-path")
+            .help("Sends the payloads as-is, without dropping the ones that are not valid URIs"),
+    )
 }
 
+pub const DIR_ENV: &[EnvArg] = &[
+    EnvArg::value("extensions", Some('e')),
+    EnvArg::value("extensions-file", None),
+    EnvArg::flag("check-cors", None),
+    EnvArg::flag("check-head", None),
+    EnvArg::flag("probe-http10", None),
+    EnvArg::values("severity-rule", None),
+    EnvArg::flag("force-extensions", None),
+    EnvArg::values("wrap", None),
+    EnvArg::values("skip-dir", None),
+    EnvArg::flag("both-schemes", None),
+    EnvArg::flag("tag-each-request", None),
+    EnvArg::value("ports", None),
+    EnvArg::value("range-threshold", None),
+    EnvArg::value("range-size", None),
+    EnvArg::values("rule", None),
+    EnvArg::flag("append-slash", Some('f')),
+    EnvArg::flag("mutations", None),
+    EnvArg::flag("backslash", None),
+    EnvArg::value("pipeline", None),
+    EnvArg::flag("adaptive", None),
+    EnvArg::flag("raw-path", None),
+];
+
 pub fn set_wordlist_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
     app.arg(
         Arg::with_name("wordlist")
             .long("wordlist")
-            .help("Sets the wordlist")
+            .help("Sets the wordlist, or an inline list of words as inline:admin,root,test")
             .short("w")
             .takes_value(true)
             .multiple(true)
             .use_delimiter(true)
-            .required(true),
+            .required_unless_one(&[
+                "jwt-secret-wordlist",
+                "jwt-none",
+                "brute-tlds",
+                "payload",
+                "mutate",
+                "input-subdomains",
+                "generate",
+            ]),
+    )
+    .arg(
+        Arg::with_name("dedupe")
+            .long("dedupe")
+            .help("Removes the duplicated words from the wordlists"),
+    )
+    .arg(
+        Arg::with_name("trim")
+            .long("trim")
+            .help("Trims the whitespaces around the words, removing the blank lines"),
+    )
+    .arg(
+        Arg::with_name("skip-comments")
+            .long("skip-comments")
+            .help("Removes the words starting with #"),
+    )
+    .arg(
+        Arg::with_name("min-length")
+            .long("min-length")
+            .help("Removes the words shorter than the specified length")
+            .default_value("0")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("max-length")
+            .long("max-length")
+            .help("Removes the words longer than the specified length, 0 means no limit")
+            .default_value("0")
+            .takes_value(true),
     )
 }
 
+pub const WORDLIST_ENV: &[EnvArg] = &[
+    EnvArg::values("wordlist", Some('w')),
+    EnvArg::flag("dedupe", None),
+    EnvArg::flag("trim", None),
+    EnvArg::flag("skip-comments", None),
+    EnvArg::value("min-length", None),
+    EnvArg::value("max-length", None),
+];
+
 pub fn set_tilde_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
     app.arg(
         Arg::with_name("extension")
@@ -218,48 +856,182 @@ pub fn set_tilde_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
     )
 }
 
-pub fn set_dns_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+pub const TILDE_ENV: &[EnvArg] = &[EnvArg::value("extension", Some('e'))];
+
+pub fn set_fronting_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
     app.arg(
         Arg::with_name("domain")
             .long("domain")
-            .help("Uses the specified domain")
+            .help("Adds a hostname to send as SNI and Host, alongside the one of the URL")
             .short("d")
+            .multiple(true)
+            .number_of_values(1)
             .required(true)
             .takes_value(true),
     )
 }
 
-pub fn set_vhost_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+pub const FRONTING_ENV: &[EnvArg] = &[EnvArg::values("domain", Some('d'))];
+
+pub fn set_auth_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
     app.arg(
-        Arg::with_name("domain")
-            .long("domain")
-            .help("Uses the specified domain to bruteforce")
-            .short("d")
-            .required(true)
+        Arg::with_name("usernames")
+            .long("usernames")
+            .help("Sets the usernames wordlist, used in place of ^USER^")
+            .short("U")
+            .takes_value(true)
+            .required(true),
+    )
+    .arg(
+        Arg::with_name("passwords")
+            .long("passwords")
+            .help("Sets the passwords wordlist, used in place of ^PASS^")
+            .short("P")
+            .takes_value(true)
+            .required(true),
+    )
+    .arg(
+        Arg::with_name("lockout-threshold")
+            .long("lockout-threshold")
+            .help("Stops after this many consecutive 403/429 responses, 0 disables the check")
+            .default_value("3")
             .takes_value(true),
     )
     .arg(
-        Arg::with_name("ignore-string")
-            .long("ignore-string")
-            .help("Ignores results with specified string in the HTTP body")
-            .short("x")
-            .required(true)
-            .multiple(true)
+        Arg::with_name("account-delay")
+            .long("account-delay")
+            .help("Sets the minimum seconds between two attempts on the same account")
+            .default_value("0")
             .takes_value(true),
     )
 }
 
-pub fn set_fuzz_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+pub const AUTH_ENV: &[EnvArg] = &[
+    EnvArg::value("usernames", Some('U')),
+    EnvArg::value("passwords", Some('P')),
+    EnvArg::value("lockout-threshold", None),
+    EnvArg::value("account-delay", None),
+];
+
+pub fn set_dns_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
     app.arg(
-        Arg::with_name("csrf-url")
-            .long("csrf-url")
-            .help("Grabs the CSRF token via GET to csrf-url")
-            .requires("csrf-regex")
+        Arg::with_name("domain")
+            .long("domain")
+            .help("Uses the specified domain")
+            .short("d")
+            .required_unless("input-subdomains")
             .takes_value(true),
     )
     .arg(
-        Arg::with_name("csrf-regex")
-            .long("csrf-regex")
+        Arg::with_name("seed-from-cert")
+            .long("seed-from-cert")
+            .help("Adds the SAN entries of the target certificate and their labels under the domain to the candidates"),
+    )
+    .arg(
+        Arg::with_name("brute-tlds")
+            .long("brute-tlds")
+            .help("Tries the base name of the domain across a list of TLDs, the wordlist replaces the built-in list")
+            .conflicts_with("seed-from-cert"),
+    )
+    .arg(
+        Arg::with_name("zone-walk")
+            .long("zone-walk")
+            .help("Enumerates the zone by walking its NSEC records, falls back to the wordlist on NSEC3 or unsigned zones")
+            .conflicts_with("brute-tlds"),
+    )
+    .arg(
+        Arg::with_name("dns-timeout")
+            .long("dns-timeout")
+            .help("Sets the timeout of each DNS query in milliseconds")
+            .default_value("2000")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("dns-retries")
+            .long("dns-retries")
+            .help("Retries queries that time out or fail with SERVFAIL on the next resolver, NXDOMAIN is never retried")
+            .default_value("2")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("asn-lookup")
+            .long("asn-lookup")
+            .help("Maps the resolved addresses to their autonomous system and owner through Team Cymru's DNS service"),
+    )
+    .arg(
+        Arg::with_name("output-format")
+            .long("output-format")
+            .help("Sets the format of the output file, hosts writes '<ip> <fqdn>' lines for /etc/hosts or dnsmasq")
+            .possible_values(&["json", "hosts"])
+            .default_value("json")
+            .takes_value(true),
+    )
+}
+
+pub const DNS_ENV: &[EnvArg] = &[
+    EnvArg::value("domain", Some('d')),
+    EnvArg::flag("seed-from-cert", None),
+    EnvArg::flag("brute-tlds", None),
+    EnvArg::flag("zone-walk", None),
+    EnvArg::value("dns-timeout", None),
+    EnvArg::value("dns-retries", None),
+    EnvArg::flag("asn-lookup", None),
+    EnvArg::value("output-format", None),
+];
+
+pub fn set_vhost_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(
+        Arg::with_name("domain")
+            .long("domain")
+            .help("Uses the specified domain to bruteforce")
+            .short("d")
+            .required_unless("input-subdomains")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("seed-from-cert")
+            .long("seed-from-cert")
+            .help("Adds the SAN entries of the target certificate and their labels under the domain to the candidates"),
+    )
+    .arg(
+        Arg::with_name("ignore-string")
+            .long("ignore-string")
+            .help("Ignores results with specified string in the HTTP body, when omitted the response to a random Host is used as baseline")
+            .short("x")
+            .multiple(true)
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("resolve-vhosts")
+            .long("resolve-vhosts")
+            .help("Resolves the discovered vhosts via DNS to tell public records from hidden ones"),
+    )
+    .arg(
+        Arg::with_name("absolute-uri")
+            .long("absolute-uri")
+            .help("Probes each vhost a second time with an absolute request target (GET http://vhost/), for proxies routing on it"),
+    )
+}
+
+pub const VHOST_ENV: &[EnvArg] = &[
+    EnvArg::value("domain", Some('d')),
+    EnvArg::flag("seed-from-cert", None),
+    EnvArg::values("ignore-string", Some('x')),
+    EnvArg::flag("resolve-vhosts", None),
+    EnvArg::flag("absolute-uri", None),
+];
+
+pub fn set_fuzz_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(
+        Arg::with_name("csrf-url")
+            .long("csrf-url")
+            .help("Grabs the CSRF token via GET to csrf-url")
+            .requires("csrf-regex")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("csrf-regex")
+            .long("csrf-regex")
             .help("Grabs the CSRF token applying the specified RegEx")
             .requires("csrf-url")
             .takes_value(true),
@@ -272,21 +1044,470 @@ pub fn set_fuzz_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
             .multiple(true)
             .takes_value(true),
     )
+    .arg(
+        Arg::with_name("form")
+            .long("form")
+            .help(
+                "Sends a multipart/form-data body with the specified field, overriding \
+                 --http-body (e.g. user=FUZZ or file=@shell.php;type=FUZZ)",
+            )
+            .multiple(true)
+            .number_of_values(1)
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("jwt-claims")
+            .long("jwt-claims")
+            .help("Builds JWTs with the specified JSON claims as payloads for the last FUZZ")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("jwt-secret-wordlist")
+            .long("jwt-secret-wordlist")
+            .help("Signs the JWT claims with HS256 using each secret in the specified file")
+            .requires("jwt-claims")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("check-cors")
+            .long("check-cors")
+            .help("Re-requests the results with foreign origins to find CORS misconfigurations"),
+    )
+    .arg(
+        Arg::with_name("severity-rule")
+            .long("severity-rule")
+            .help(
+                "Tags the matching results with a severity before the built-in rules \
+                 (e.g. high:path=/backup, medium:status=500, low:body=debug)",
+            )
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1),
+    )
+    .arg(
+        Arg::with_name("payload")
+            .long("payload")
+            .help(
+                "Binds a wordlist to a marker, replacing every occurrence of it \
+                 (e.g. --payload FUZZ1=users.txt --payload FUZZ2=pass.txt)",
+            )
+            .conflicts_with_all(&["wordlist", "jwt-claims"])
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1),
+    )
+    .arg(
+        Arg::with_name("extract")
+            .long("extract")
+            .help(
+                "Captures a value from each response with the specified RegEx, \
+                 replacing EXTRACT in --next-url and --next-body",
+            )
+            .requires("next-url")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("next-url")
+            .long("next-url")
+            .help("Sends a follow-up request to the specified URL and reports its response")
+            .requires("extract")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("next-body")
+            .long("next-body")
+            .help("Sends the follow-up request as POST with the specified body")
+            .requires("next-url")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("mutate")
+            .long("mutate")
+            .help(
+                "Sends mutated variants of the HTTP body (bit flips, boundary numbers, \
+                 format strings and meta-characters) instead of using a wordlist",
+            )
+            .conflicts_with_all(&["wordlist", "payload", "jwt-claims"]),
+    )
+    .arg(
+        Arg::with_name("auto-calibrate")
+            .long("auto-calibrate")
+            .help(
+                "Sends a few random payloads first and only shows the responses whose status, \
+                 length, word count or latency deviate from theirs",
+            ),
+    )
+    .arg(
+        Arg::with_name("generate")
+            .long("generate")
+            .help(
+                "Generates the payloads of min to max chars of the charset as hydra -x, \
+                 a, A and 1 being the lowercase letters, the uppercase letters and the digits \
+                 (e.g. -g 4:6:a1!)",
+            )
+            .short("g")
+            .conflicts_with_all(&["payload", "mutate", "jwt-claims", "wordlist-encoding"])
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("wordlist-encoding")
+            .long("wordlist-encoding")
+            .help(
+                "Sets how the wordlist entries are encoded, hex entries are decoded and \
+                 substituted as raw bytes in the HTTP body, percent-encoded elsewhere",
+            )
+            .possible_values(&["text", "hex"])
+            .conflicts_with_all(&["mutate", "jwt-claims"])
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("jwt-none")
+            .long("jwt-none")
+            .help("Adds unsigned alg=none variants of the JWT claims")
+            .requires("jwt-claims"),
+    )
+    .arg(
+        Arg::with_name("raw-path")
+            .long("raw-path")
+            .help("Sends the payloads as-is, without dropping the ones that are not valid URIs"),
+    )
+}
+
+pub const FUZZ_ENV: &[EnvArg] = &[
+    EnvArg::value("csrf-url", None),
+    EnvArg::value("csrf-regex", None),
+    EnvArg::values("csrf-header", None),
+    EnvArg::values("form", None),
+    EnvArg::value("jwt-claims", None),
+    EnvArg::value("jwt-secret-wordlist", None),
+    EnvArg::flag("check-cors", None),
+    EnvArg::values("severity-rule", None),
+    EnvArg::values("payload", None),
+    EnvArg::value("extract", None),
+    EnvArg::value("next-url", None),
+    EnvArg::value("next-body", None),
+    EnvArg::flag("mutate", None),
+    EnvArg::flag("auto-calibrate", None),
+    EnvArg::value("generate", Some('g')),
+    EnvArg::value("wordlist-encoding", None),
+    EnvArg::flag("jwt-none", None),
+    EnvArg::flag("raw-path", None),
+];
+
+pub fn set_chain_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(
+        Arg::with_name("domain")
+            .long("domain")
+            .help("Uses the specified domain for the DNS stage")
+            .short("d")
+            .required_unless("input-subdomains")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("dir-wordlist")
+            .long("dir-wordlist")
+            .help("Sets the wordlist used to scan the resolved hosts")
+            .required(true)
+            .takes_value(true),
+    )
+}
+
+pub const CHAIN_ENV: &[EnvArg] = &[
+    EnvArg::value("domain", Some('d')),
+    EnvArg::value("dir-wordlist", None),
+];
+
+pub fn set_ws_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(
+        Arg::with_name("messages")
+            .long("messages")
+            .help("Sends each line of the specified file as the first message to the accepted endpoints")
+            .default_value("")
+            .takes_value(true),
+    )
+}
+
+pub const WS_ENV: &[EnvArg] = &[EnvArg::value("messages", None)];
+
+pub fn set_soap_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(
+        Arg::with_name("operation")
+            .long("operation")
+            .help("Fuzzes only the specified operations")
+            .multiple(true)
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("fuzz-param")
+            .long("fuzz-param")
+            .help("Puts the FUZZ marker in the specified parameters, defaults to the first one")
+            .multiple(true)
+            .takes_value(true),
+    )
+}
+
+pub const SOAP_ENV: &[EnvArg] = &[
+    EnvArg::values("operation", None),
+    EnvArg::values("fuzz-param", None),
+];
+
+pub fn set_project_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(
+        Arg::with_name("project")
+            .help("Sets the project file")
+            .required(true)
+            .index(1),
+    )
+    .arg(
+        Arg::with_name("export")
+            .long("export")
+            .help("Exports the consolidated findings to the specified file")
+            .default_value("")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("export-format")
+            .long("export-format")
+            .help("Sets the format of the export, defectdojo and faraday match the importers of these platforms")
+            .possible_values(&["json", "defectdojo", "faraday"])
+            .default_value("json")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("resume")
+            .long("resume")
+            .help("Runs again the last scan if it was interrupted")
+            .conflicts_with("export"),
+    )
+}
+
+pub fn set_daemon_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(
+        Arg::with_name("queue")
+            .help("Sets the job queue file")
+            .required(true)
+            .index(1),
+    )
+    .arg(
+        Arg::with_name("listen")
+            .long("listen")
+            .help("Sets the address of the control API")
+            .default_value("127.0.0.1:7070")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("max-jobs")
+            .long("max-jobs")
+            .help("Sets the amount of jobs running at the same time")
+            .default_value("2")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("results-dir")
+            .long("results-dir")
+            .help("Saves the results of every run in the specified directory")
+            .default_value("daemon-results")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("wordlists-dir")
+            .long("wordlists-dir")
+            .help("Lets the jobs use the wordlists of the specified directory, by relative path")
+            .default_value("")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("token")
+            .long("token")
+            .help("Sets the bearer token of the control API, a random one is printed when omitted")
+            .takes_value(true),
+    )
+}
+
+pub fn set_diff_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(
+        Arg::with_name("old")
+            .help("Sets the previous results file")
+            .required(true)
+            .index(1),
+    )
+    .arg(
+        Arg::with_name("new")
+            .help("Sets the current results file")
+            .required(true)
+            .index(2),
+    )
+}
+
+pub fn set_import_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(
+        Arg::with_name("export")
+            .help("Sets the Burp XML, ZAP report or ZAP URLs export")
+            .required(true)
+            .index(1),
+    )
+    .arg(
+        Arg::with_name("host")
+            .long("host")
+            .help("Keeps the requests of the specified host only")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("paths")
+            .long("paths")
+            .help("Appends the observed paths missing from the specified dir wordlist")
+            .required_unless("params")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("params")
+            .long("params")
+            .help("Appends the observed parameter names missing from the specified wordlist")
+            .takes_value(true),
+    )
+}
+
+type ArgGroup<'a, 'b> = (fn(App<'a, 'b>) -> App<'a, 'b>, &'static [EnvArg]);
+
+/// The argument groups of each scan mode, with the options they read from the environment
+fn mode_groups<'a, 'b>(mode: &str) -> Vec<ArgGroup<'a, 'b>> {
+    match mode {
+        "dir" => vec![
+            (set_common_args, COMMON_ENV),
+            (set_http_args, HTTP_ENV),
+            (set_har_args, HAR_ENV),
+            (set_body_args, BODY_ENV),
+            (set_session_args, SESSION_ENV),
+            (set_dir_args, DIR_ENV),
+            (set_report_args, REPORT_ENV),
+            (set_slow_args, SLOW_ENV),
+            (set_wordlist_args, WORDLIST_ENV),
+            (set_nmap_args, NMAP_ENV),
+        ],
+        "dns" => vec![
+            (set_common_args, COMMON_ENV),
+            (set_dns_args, DNS_ENV),
+            (set_wordlist_args, WORDLIST_ENV),
+            (set_subdomains_args, SUBDOMAINS_ENV),
+        ],
+        "vhost" => vec![
+            (set_common_args, COMMON_ENV),
+            (set_http_args, HTTP_ENV),
+            (set_har_args, HAR_ENV),
+            (set_vhost_args, VHOST_ENV),
+            (set_slow_args, SLOW_ENV),
+            (set_wordlist_args, WORDLIST_ENV),
+            (set_nmap_args, NMAP_ENV),
+            (set_subdomains_args, SUBDOMAINS_ENV),
+        ],
+        "tilde" => vec![
+            (set_common_args, COMMON_ENV),
+            (set_http_args, HTTP_ENV),
+            (set_tilde_args, TILDE_ENV),
+        ],
+        "fuzz" => vec![
+            (set_common_args, COMMON_ENV),
+            (set_http_args, HTTP_ENV),
+            (set_har_args, HAR_ENV),
+            (set_body_args, BODY_ENV),
+            (set_session_args, SESSION_ENV),
+            (set_fuzz_args, FUZZ_ENV),
+            (set_report_args, REPORT_ENV),
+            (set_slow_args, SLOW_ENV),
+            (set_wordlist_args, WORDLIST_ENV),
+        ],
+        "methods" | "graphql" => vec![
+            (set_common_args, COMMON_ENV),
+            (set_http_args, HTTP_ENV),
+            (set_wordlist_args, WORDLIST_ENV),
+        ],
+        "ws" => vec![
+            (set_common_args, COMMON_ENV),
+            (set_http_args, HTTP_ENV),
+            (set_ws_args, WS_ENV),
+            (set_wordlist_args, WORDLIST_ENV),
+        ],
+        "soap" => vec![
+            (set_common_args, COMMON_ENV),
+            (set_http_args, HTTP_ENV),
+            (set_body_args, BODY_ENV),
+            (set_soap_args, SOAP_ENV),
+            (set_wordlist_args, WORDLIST_ENV),
+        ],
+        "auth" => vec![
+            (set_common_args, COMMON_ENV),
+            (set_http_args, HTTP_ENV),
+            (set_auth_args, AUTH_ENV),
+        ],
+        "chain" => vec![
+            (set_common_args, COMMON_ENV),
+            (set_http_args, HTTP_ENV),
+            (set_body_args, BODY_ENV),
+            (set_chain_args, CHAIN_ENV),
+            (set_wordlist_args, WORDLIST_ENV),
+            (set_subdomains_args, SUBDOMAINS_ENV),
+        ],
+        "fronting" => vec![
+            (set_common_args, COMMON_ENV),
+            (set_http_args, HTTP_ENV),
+            (set_fronting_args, FRONTING_ENV),
+        ],
+        _ => Vec::new(),
+    }
+}
+
+pub fn set_mode_args<'a, 'b>(mode: &str, app: App<'a, 'b>) -> App<'a, 'b> {
+    mode_groups(mode)
+        .into_iter()
+        .fold(app, |app, (set_args, _)| set_args(app))
 }
 
-pub fn extract_common_args<'a>(submatches: &clap::ArgMatches<'a>) -> CommonArgs {
+pub fn env_args(mode: &str) -> Vec<EnvArg> {
+    mode_groups(mode)
+        .into_iter()
+        .flat_map(|(_, env)| env.iter().copied())
+        .collect()
+}
+
+pub fn apply_env_args(mut argv: Vec<String>) -> Vec<String> {
+    let specs = match argv.get(1) {
+        Some(mode) => env_args(mode),
+        None => return argv,
+    };
+
+    let env_args = environment::arguments(&specs, &argv[2..], |name| std::env::var(name).ok());
+    argv.splice(2..2, env_args);
+    argv
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    match value.parse::<T>() {
+        Ok(v) => Ok(v),
+        Err(_) => Err(format!("Invalid --{}: {}, expected a number", flag, value)),
+    }
+}
+
+pub fn extract_common_args<'a>(submatches: &clap::ArgMatches<'a>) -> Result<CommonArgs, String> {
     let mut no_banner = submatches.is_present("no-banner");
     let mut no_progress_bar = submatches.is_present("no-progress-bar");
     let exit_on_connection_errors = submatches.is_present("exit-on-error");
-    let n_threads = submatches
-        .value_of("threads")
-        .unwrap()
-        .parse::<usize>()
-        .expect("threads is a number");
+    let n_threads = parse_number("threads", submatches.value_of("threads").unwrap())?;
 
     let output = submatches.value_of("output").unwrap();
+    let output_append = submatches.is_present("output-append");
+    let known = submatches.value_of("known").unwrap();
+    let max_results = parse_number("max-results", submatches.value_of("max-results").unwrap())?;
+    let project = submatches.value_of("project").unwrap();
+    let progress_json = submatches.value_of("progress-json").unwrap();
+    let quiet = submatches.is_present("quiet");
+    let format = submatches.value_of("format").unwrap();
+    let geoip = submatches.value_of("geoip").unwrap();
+    let color = !quiet && !submatches.is_present("no-color") && terminal_size().is_some();
 
-    if let Some((Width(w), Height(h))) = terminal_size() {
+    if quiet {
+        no_banner = true;
+        no_progress_bar = true;
+    } else if let Some((Width(w), Height(h))) = terminal_size() {
         if w < 122 {
             no_banner = true;
         }
@@ -302,22 +1523,69 @@ pub fn extract_common_args<'a>(submatches: &clap::ArgMatches<'a>) -> CommonArgs
         no_progress_bar = true;
     }
 
-    CommonArgs {
+    Ok(CommonArgs {
         no_banner,
         no_progress_bar,
+        progress_json: progress_json.to_owned(),
         exit_on_connection_errors,
         n_threads,
         output: output.to_owned(),
-    }
+        output_append,
+        known: known.to_owned(),
+        max_results,
+        project: project.to_owned(),
+        quiet,
+        color,
+        format: format.to_owned(),
+        geoip: geoip.to_owned(),
+    })
 }
 
-pub fn extract_http_args<'a>(submatches: &clap::ArgMatches<'a>) -> HTTPArgs {
+pub fn extract_http_args<'a>(submatches: &clap::ArgMatches<'a>) -> Result<HTTPArgs, String> {
     let user_agent = submatches.value_of("user-agent").unwrap();
     let http_method = submatches.value_of("http-method").unwrap();
-    let http_body = submatches.value_of("http-body").unwrap();
-    let url = submatches.value_of("url").unwrap();
+    let http_body = match submatches.value_of("body-file") {
+        Some(path) => match std::fs::read(path) {
+            Ok(v) => v,
+            Err(e) => return Err(format!("Unable to read the body file: {}\n{}", path, e)),
+        },
+        None => submatches
+            .value_of("http-body")
+            .unwrap()
+            .as_bytes()
+            .to_vec(),
+    };
     let ignore_certificate = submatches.is_present("ignore-certificate");
-    let http_headers: Vec<(String, String)> = if submatches.is_present("http-header") {
+    let (tls_min_version, tls_max_version) = match crate::tls::version_range(
+        submatches.value_of("tls-min"),
+        submatches.value_of("tls-max"),
+    ) {
+        Ok(v) => v,
+        Err(e) => return Err(e.to_string()),
+    };
+    let digest = match submatches.value_of("digest") {
+        Some(credentials) => match credentials.find(':') {
+            Some(i) => Some((credentials[..i].to_owned(), credentials[i + 1..].to_owned())),
+            None => return Err("Digest credentials must be in the form user:pass".to_owned()),
+        },
+        None => None,
+    };
+    let aws_sigv4 = match submatches.value_of("aws-sigv4") {
+        Some(scope) => {
+            let sigv4 = crate::sigv4::AwsCredentials::load()
+                .and_then(|credentials| crate::sigv4::SigV4::new(scope, credentials));
+            match sigv4 {
+                Ok(v) => Some(Arc::new(v)),
+                Err(e) => return Err(e.to_string()),
+            }
+        }
+        None => None,
+    };
+    let capture_headers = submatches
+        .values_of("capture-header")
+        .map(|v| v.map(|h| h.to_owned()).collect())
+        .unwrap_or_default();
+    let mut http_headers: Vec<(String, String)> = if submatches.is_present("http-header") {
         submatches
             .values_of("http-header")
             .unwrap()
@@ -348,24 +1616,123 @@ pub fn extract_http_args<'a>(submatches: &clap::ArgMatches<'a>) -> HTTPArgs {
         })
         .map(|s| s.to_string())
         .collect::<Vec<String>>();
+    let request_tag = if submatches.is_present("tag-requests") {
+        let request_tag = crate::correlation::RequestTag::new();
+        // the dir mode sends the identifier of each entry instead
+        if !submatches.is_present("tag-each-request") {
+            http_headers.push((
+                crate::correlation::HEADER.to_owned(),
+                request_tag.scan_id.to_owned(),
+            ));
+        }
+        Some(Arc::new(request_tag))
+    } else {
+        None
+    };
 
-    HTTPArgs {
+    Ok(HTTPArgs {
         user_agent: user_agent.to_owned(),
         http_method: http_method.to_owned(),
-        http_body: http_body.to_owned(),
-        url: url.to_owned(),
+        http_body,
+        url: submatches.value_of("url").unwrap().to_owned(),
         ignore_certificate,
         http_headers,
         include_status_codes,
         ignore_status_codes,
+        tls_min_version,
+        tls_max_version,
+        digest,
+        negotiate: None,
+        aws_sigv4,
+        capture_headers,
+        audit_headers: submatches.is_present("audit-headers"),
+        request_tag,
+        favicon: !submatches.is_present("no-favicon"),
+    })
+}
+
+/// Completes the HTTP arguments with what needs the network: the scheme of a URL given
+/// without one and the Kerberos ticket, then prints the scan identifier
+pub fn prepare_http_args<'a>(
+    submatches: &clap::ArgMatches<'a>,
+    mut http_args: HTTPArgs,
+) -> Result<HTTPArgs, String> {
+    http_args.url = with_scheme(&http_args.url)?;
+
+    if submatches.is_present("negotiate") {
+        let negotiate = crate::negotiate::Negotiate::load(submatches.value_of("keytab"))
+            .map_err(|e| e.to_string())?;
+        let host = http_args
+            .url
+            .parse::<hyper::Uri>()
+            .ok()
+            .and_then(|uri| uri.host().map(|h| h.to_owned()))
+            .unwrap_or_default();
+        let negotiate = Arc::new(negotiate);
+        if let Err(e) = negotiate.start(&host) {
+            return Err(e.to_string());
+        }
+
+        http_args.negotiate = Some(negotiate);
+    }
+
+    if let Some(request_tag) = &http_args.request_tag {
+        crate::say!("{}", crate::banner::scan_id(&request_tag.scan_id));
     }
+
+    Ok(http_args)
 }
 
-pub fn extract_dns_args<'a>(submatches: &clap::ArgMatches<'a>) -> DNSArgs {
+pub fn extract_dns_args<'a>(submatches: &clap::ArgMatches<'a>) -> Result<DNSArgs, String> {
     let domain = submatches.value_of("domain").unwrap_or("");
+    let seed_from_cert = submatches.is_present("seed-from-cert");
+    let brute_tlds = submatches.is_present("brute-tlds");
+    let resolve_vhosts = submatches.is_present("resolve-vhosts");
+    let absolute_uri = submatches.is_present("absolute-uri");
+    let zone_walk = submatches.is_present("zone-walk");
+    let timeout = parse_number(
+        "dns-timeout",
+        submatches.value_of("dns-timeout").unwrap_or("2000"),
+    )?;
+    let retries = parse_number(
+        "dns-retries",
+        submatches.value_of("dns-retries").unwrap_or("2"),
+    )?;
+    let output_format = submatches.value_of("output-format").unwrap_or("json");
+    let asn_lookup = submatches.is_present("asn-lookup");
 
-    DNSArgs {
+    Ok(DNSArgs {
         domain: domain.to_owned(),
+        seed_from_cert,
+        brute_tlds,
+        resolve_vhosts,
+        absolute_uri,
+        zone_walk,
+        timeout,
+        retries,
+        output_format: output_format.to_owned(),
+        asn_lookup,
+    })
+}
+
+pub fn extract_session_args<'a>(
+    submatches: &clap::ArgMatches<'a>,
+) -> Result<Option<std::sync::Arc<crate::session::Session>>, String> {
+    match submatches.value_of("session") {
+        Some(path) => match crate::session::Session::load(path) {
+            Ok(v) => Ok(Some(std::sync::Arc::new(v))),
+            Err(e) => Err(e.to_string()),
+        },
+        None => Ok(None),
+    }
+}
+
+pub fn extract_har_args<'a>(submatches: &clap::ArgMatches<'a>) -> HarArgs {
+    HarArgs {
+        har: submatches.value_of("har").unwrap().to_owned(),
+        save_requests: submatches.value_of("save-requests").unwrap().to_owned(),
+        show_curl: submatches.is_present("show-curl"),
+        burp: submatches.value_of("burp").unwrap().to_owned(),
     }
 }
 
@@ -395,18 +1762,130 @@ pub fn extract_body_args<'a>(submatches: &clap::ArgMatches<'a>) -> BodyArgs {
     }
 }
 
-pub fn extract_dir_args<'a>(submatches: &clap::ArgMatches<'a>) -> DirArgs {
+pub fn extract_dir_args<'a>(submatches: &clap::ArgMatches<'a>) -> Result<DirArgs, String> {
     let append_slash = submatches.is_present("append-slash");
-    let extensions = submatches
+    let mut extensions = submatches
         .values_of("extensions")
         .unwrap()
         .filter(|e| !e.is_empty())
         .map(|s| s.to_owned())
         .collect::<Vec<String>>();
-    DirArgs {
+
+    if let Some(path) = submatches.value_of("extensions-file") {
+        let content = match crate::wordlist::read(path) {
+            Ok(v) => v,
+            Err(e) => {
+                return Err(format!(
+                    "Unable to read the extensions file: {}\n{}",
+                    path, e
+                ))
+            }
+        };
+
+        for line in content.lines() {
+            let extension = line.trim().trim_start_matches('.');
+            if extension.is_empty() || extension.starts_with('#') {
+                continue;
+            }
+
+            if !extensions.iter().any(|e| e == extension) {
+                extensions.push(extension.to_owned());
+            }
+        }
+    }
+
+    let mut rules = Vec::new();
+    for rule in submatches.values_of("rule").unwrap_or_default() {
+        let mut parts = rule.splitn(2, ':');
+        let extension = parts.next().unwrap().trim().trim_start_matches('.');
+        let codes = parts
+            .next()
+            .unwrap_or("")
+            .split(',')
+            .map(|c| c.trim())
+            .filter(|c| !c.is_empty())
+            .map(|c| c.to_owned())
+            .collect::<Vec<String>>();
+
+        if extension.is_empty() || codes.is_empty() {
+            return Err(format!("Invalid rule: {}, expected extension:codes", rule));
+        }
+
+        rules.push((extension.to_owned(), codes));
+    }
+
+    let mut wraps = Vec::new();
+    for wrap in submatches.values_of("wrap").unwrap_or_default() {
+        match wrap.find(':') {
+            Some(i) if wrap.len() > 1 => {
+                wraps.push((wrap[..i].to_owned(), wrap[i + 1..].to_owned()))
+            }
+            _ => return Err(format!("Invalid wrap: {}, expected prefix:suffix", wrap)),
+        }
+    }
+
+    let range_threshold = match submatches.value_of("range-threshold").unwrap().parse() {
+        Ok(v) => v,
+        Err(e) => return Err(format!("Invalid range threshold: {}", e)),
+    };
+    let range_size = match submatches.value_of("range-size").unwrap().parse() {
+        Ok(v) if v > 0 => v,
+        Ok(_) => return Err("The range size must be greater than 0".to_owned()),
+        Err(e) => return Err(format!("Invalid range size: {}", e)),
+    };
+
+    let mut skip_dirs = Vec::new();
+    for pattern in submatches.values_of("skip-dir").unwrap_or_default() {
+        match regex::Regex::new(pattern) {
+            Ok(v) => skip_dirs.push(v),
+            Err(e) => return Err(format!("Invalid skip-dir regex: {}\n{}", pattern, e)),
+        }
+    }
+
+    let ports = match submatches
+        .value_of("ports")
+        .map(crate::dirbuster::ports::parse_ports)
+    {
+        Some(Ok(v)) => v,
+        Some(Err(e)) => return Err(e.to_string()),
+        None => Vec::new(),
+    };
+
+    let stages: Vec<&str> = submatches.values_of("pipeline").unwrap().collect();
+    let (crawl, brute_force) = match stages.as_slice() {
+        ["dir"] => (false, true),
+        ["crawl"] => (true, false),
+        ["crawl", "dir"] => (true, true),
+        _ => {
+            return Err(format!(
+                "Invalid pipeline: {}, expected dir, crawl or crawl,dir",
+                stages.join(",")
+            ));
+        }
+    };
+
+    Ok(DirArgs {
         append_slash,
         extensions,
-    }
+        rules,
+        raw_path: submatches.is_present("raw-path"),
+        mutations: submatches.is_present("mutations"),
+        backslash: submatches.is_present("backslash"),
+        crawl,
+        brute_force,
+        adaptive: submatches.is_present("adaptive"),
+        force_extensions: submatches.is_present("force-extensions"),
+        check_cors: submatches.is_present("check-cors"),
+        check_head: submatches.is_present("check-head"),
+        probe_http10: submatches.is_present("probe-http10"),
+        wraps,
+        range_threshold,
+        range_size,
+        skip_dirs,
+        both_schemes: submatches.is_present("both-schemes"),
+        ports,
+        tag_each_request: submatches.is_present("tag-each-request"),
+    })
 }
 
 pub fn extract_fuzz_args<'a>(submatches: &clap::ArgMatches<'a>) -> FuzzArgs {
@@ -433,35 +1912,221 @@ pub fn extract_fuzz_args<'a>(submatches: &clap::ArgMatches<'a>) -> FuzzArgs {
         csrf_url,
         csrf_regex,
         csrf_headers,
+        raw_path: submatches.is_present("raw-path"),
+        forms: submatches
+            .values_of("form")
+            .map(|v| v.map(|f| f.to_owned()).collect())
+            .unwrap_or_default(),
+        jwt_claims: submatches.value_of("jwt-claims").map(|v| v.to_owned()),
+        jwt_secret_wordlist: submatches
+            .value_of("jwt-secret-wordlist")
+            .map(|v| v.to_owned()),
+        jwt_none: submatches.is_present("jwt-none"),
+        check_cors: submatches.is_present("check-cors"),
+        mutate: submatches.is_present("mutate"),
+        auto_calibrate: submatches.is_present("auto-calibrate"),
+        hex_wordlists: submatches.value_of("wordlist-encoding") == Some("hex"),
+    }
+}
+
+pub fn extract_severity_rules<'a>(
+    submatches: &clap::ArgMatches<'a>,
+) -> Result<Vec<crate::severity::SeverityRule>, String> {
+    let mut rules = Vec::new();
+    for rule in submatches.values_of("severity-rule").unwrap_or_default() {
+        match crate::severity::parse_rule(rule) {
+            Some(v) => rules.push(v),
+            None => return Err(format!("Invalid severity rule: {}", rule)),
+        }
+    }
+
+    Ok(rules)
+}
+
+pub fn extract_slow_rules<'a>(
+    submatches: &clap::ArgMatches<'a>,
+) -> Result<Vec<crate::ratelimit::SlowRule>, String> {
+    let mut rules = Vec::new();
+    for rule in submatches.values_of("slow-on").unwrap_or_default() {
+        match crate::ratelimit::SlowRule::parse(rule) {
+            Ok(v) => rules.push(v),
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+
+    Ok(rules)
+}
+
+pub fn extract_max_rate_limit_wait<'a>(
+    submatches: &clap::ArgMatches<'a>,
+) -> Result<std::time::Duration, String> {
+    let value = submatches.value_of("max-rate-limit-wait").unwrap_or("300");
+    match value.parse::<u64>() {
+        Ok(v) => Ok(std::time::Duration::from_secs(v)),
+        Err(_) => Err(format!(
+            "Invalid --max-rate-limit-wait: {}, expected seconds",
+            value
+        )),
+    }
+}
+
+pub fn extract_report_order<'a>(
+    submatches: &clap::ArgMatches<'a>,
+) -> (
+    Option<crate::report::SortKey>,
+    Option<crate::report::GroupBy>,
+) {
+    (
+        submatches
+            .value_of("sort")
+            .and_then(crate::report::SortKey::parse),
+        submatches
+            .value_of("group-by")
+            .and_then(crate::report::GroupBy::parse),
+    )
+}
+
+pub fn extract_follow_up<'a>(
+    submatches: &clap::ArgMatches<'a>,
+) -> Result<Option<crate::fuzzbuster::FollowUp>, String> {
+    let (extract, url) = match (
+        submatches.value_of("extract"),
+        submatches.value_of("next-url"),
+    ) {
+        (Some(extract), Some(url)) => (extract, url),
+        _ => return Ok(None),
+    };
+
+    let extract = match regex::Regex::new(extract) {
+        Ok(v) => v,
+        Err(e) => return Err(format!("Invalid extract regex: {}", e)),
+    };
+
+    check_url(url)?;
+
+    Ok(Some(crate::fuzzbuster::FollowUp {
+        extract,
+        url: url.to_owned(),
+        body: submatches.value_of("next-body").unwrap_or("").to_owned(),
+    }))
+}
+
+pub fn extract_payloads<'a>(
+    submatches: &clap::ArgMatches<'a>,
+) -> Result<Vec<(String, String)>, String> {
+    let mut payloads: Vec<(String, String)> = Vec::new();
+    for payload in submatches.values_of("payload").unwrap_or_default() {
+        let (marker, path) = match payload.find('=') {
+            Some(i) if i > 0 => (&payload[..i], &payload[i + 1..]),
+            _ => {
+                return Err(format!(
+                    "Invalid payload: {}, expected MARKER=wordlist",
+                    payload
+                ))
+            }
+        };
+
+        if payloads.iter().any(|(m, _)| m == marker) {
+            return Err(format!("Payload marker bound more than once: {}", marker));
+        }
+
+        if !crate::wordlist::exists(path) {
+            return Err(format!("Specified wordlist does not exist: {}", path));
+        }
+
+        payloads.push((marker.to_owned(), path.to_owned()));
+    }
+
+    Ok(payloads)
+}
+
+pub fn extract_generator<'a>(
+    submatches: &clap::ArgMatches<'a>,
+) -> Result<Option<crate::fuzzbuster::generator::Generator>, String> {
+    match submatches.value_of("generate") {
+        Some(spec) => match crate::fuzzbuster::generator::Generator::parse(spec) {
+            Ok(v) => Ok(Some(v)),
+            Err(e) => Err(e.to_string()),
+        },
+        None => Ok(None),
+    }
+}
+
+/// Adds the scheme spoken by the target to a URL given without one, e.g. example.com:8443
+fn with_scheme(url: &str) -> Result<String, String> {
+    if url.contains("://") {
+        return Ok(url.to_owned());
+    }
+
+    match crate::reachability::detect_scheme(url) {
+        Ok(scheme) => {
+            let url = format!("{}://{}", scheme, url);
+            crate::say!("{}", crate::banner::detected_scheme(&url));
+            Ok(url)
+        }
+        Err(e) => Err(format!("Unable to detect the scheme of {}, {}", url, e)),
     }
 }
 
 pub fn url_is_valid(url: &str) -> bool {
-    match url.parse::<hyper::Uri>() {
+    match check_url(url) {
+        Ok(_) => true,
         Err(e) => {
-            error!(
-                "Invalid URL: {}, consider adding a protocol like http:// or https://",
-                e
-            );
-            return false;
+            error!("{}", e);
+            false
         }
+    }
+}
+
+fn check_url(url: &str) -> Result<(), String> {
+    match url.parse::<hyper::Uri>() {
+        Err(e) => Err(format!(
+            "Invalid URL: {}, consider adding a protocol like http:// or https://",
+            e
+        )),
         Ok(v) => match v.scheme_part() {
-            Some(s) => {
-                if s != "http" && s != "https" {
-                    error!("Invalid URL: invalid protocol, only http:// or https:// are supported");
-                    return false;
-                } else {
-                    return true;
-                }
-            }
+            Some(s) if s != "http" && s != "https" => Err(
+                "Invalid URL: invalid protocol, only http:// or https:// are supported".to_owned(),
+            ),
+            Some(_) => Ok(()),
             None => {
-                error!("Invalid URL: missing protocol, consider adding http:// or https://");
-                return false;
+                Err("Invalid URL: missing protocol, consider adding http:// or https://".to_owned())
             }
         },
     }
 }
 
+fn read_lines(path: &str) -> Result<Vec<String>, String> {
+    match crate::wordlist::read(path) {
+        Ok(v) => Ok(v
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(|l| l.to_owned())
+            .collect()),
+        Err(e) => Err(format!("Unable to read the wordlist: {}\n{}", path, e)),
+    }
+}
+
+pub fn extract_auth_args<'a>(submatches: &clap::ArgMatches<'a>) -> Result<AuthArgs, String> {
+    let usernames = read_lines(submatches.value_of("usernames").unwrap())?;
+    let passwords = read_lines(submatches.value_of("passwords").unwrap())?;
+    let lockout_threshold = match submatches.value_of("lockout-threshold").unwrap().parse() {
+        Ok(v) => v,
+        Err(e) => return Err(format!("Invalid lockout threshold: {}", e)),
+    };
+    let account_delay = match submatches.value_of("account-delay").unwrap().parse() {
+        Ok(v) => v,
+        Err(e) => return Err(format!("Invalid account delay: {}", e)),
+    };
+
+    Ok(AuthArgs {
+        usernames,
+        passwords,
+        lockout_threshold,
+        account_delay,
+    })
+}
+
 pub fn extract_tilde_args<'a>(submatches: &clap::ArgMatches<'a>) -> TildeArgs {
     let extension = match submatches.value_of("extension") {
         Some(v) => Some(v.to_owned()),
@@ -471,28 +2136,201 @@ pub fn extract_tilde_args<'a>(submatches: &clap::ArgMatches<'a>) -> TildeArgs {
     TildeArgs { extension }
 }
 
-pub fn extract_wordlist_args<'a>(submatches: &clap::ArgMatches<'a>) -> Result<WordlistArgs, ()> {
-    let wordlist_paths = submatches
-        .values_of("wordlist")
+pub fn extract_fronting_args<'a>(submatches: &clap::ArgMatches<'a>) -> FrontingArgs {
+    let domains = submatches
+        .values_of("domain")
         .unwrap()
-        .map(|w| w.to_owned())
-        .collect::<Vec<String>>();
+        .map(|v| v.to_owned())
+        .collect();
+
+    FrontingArgs { domains }
+}
 
-    let all_wordlists_exist = wordlist_paths
+pub fn extract_wordlist_filter<'a>(
+    submatches: &clap::ArgMatches<'a>,
+) -> Result<crate::wordlist::WordlistFilter, String> {
+    let mut lengths = Vec::new();
+    for name in ["min-length", "max-length"].iter() {
+        match submatches.value_of(name).unwrap_or("0").parse::<usize>() {
+            Ok(v) => lengths.push(v),
+            Err(e) => return Err(format!("Invalid {}: {}", name, e)),
+        }
+    }
+
+    Ok(crate::wordlist::WordlistFilter {
+        dedupe: submatches.is_present("dedupe"),
+        trim: submatches.is_present("trim"),
+        skip_comments: submatches.is_present("skip-comments"),
+        min_length: lengths[0],
+        max_length: lengths[1],
+    })
+}
+
+pub fn extract_wordlist_args<'a>(
+    submatches: &clap::ArgMatches<'a>,
+) -> Result<WordlistArgs, String> {
+    let values: Vec<&str> = submatches
+        .values_of("wordlist")
+        .map(|v| v.collect())
+        .unwrap_or_default();
+    let indices: Vec<usize> = submatches
+        .indices_of("wordlist")
+        .map(|v| v.collect())
+        .unwrap_or_default();
+
+    let mut wordlist_paths: Vec<String> = Vec::new();
+    let mut inline = false;
+    for (i, value) in values.iter().enumerate() {
+        if inline && i > 0 && indices[i] == indices[i - 1] + 1 {
+            wordlist_paths
+                .last_mut()
+                .unwrap()
+                .push_str(&format!(",{}", value));
+            continue;
+        }
+
+        inline = value.starts_with(crate::wordlist::INLINE_PREFIX);
+        wordlist_paths.push(value.to_string());
+    }
+
+    if let Some(wordlist_path) = wordlist_paths
         .iter()
-        .map(|wordlist_path| {
-            if std::fs::metadata(wordlist_path).is_err() {
-                error!("Specified wordlist does not exist: {}", wordlist_path);
-                return false;
-            } else {
-                return true;
+        .find(|wordlist_path| !crate::wordlist::exists(wordlist_path))
+    {
+        return Err(format!(
+            "Specified wordlist does not exist: {}",
+            wordlist_path
+        ));
+    }
+
+    Ok(WordlistArgs { wordlist_paths })
+}
+
+pub fn extract_chain_args<'a>(submatches: &clap::ArgMatches<'a>) -> Result<ChainArgs, String> {
+    let dir_wordlist = submatches.value_of("dir-wordlist").unwrap();
+    if !crate::wordlist::exists(dir_wordlist) {
+        return Err(format!(
+            "Specified wordlist does not exist: {}",
+            dir_wordlist
+        ));
+    }
+
+    Ok(ChainArgs {
+        domain: submatches.value_of("domain").unwrap_or("").to_owned(),
+        dir_wordlist: dir_wordlist.to_owned(),
+    })
+}
+
+pub fn extract_subdomains<'a>(
+    submatches: &clap::ArgMatches<'a>,
+) -> Result<Option<Vec<String>>, String> {
+    let path = match submatches.value_of("input-subdomains") {
+        Some(v) => v,
+        None => return Ok(None),
+    };
+
+    match crate::wordlist::read(path) {
+        Ok(v) => {
+            let domain = submatches.value_of("domain").unwrap_or("");
+            let subdomains = crate::subdomains::parse(&v, domain);
+            if subdomains.is_empty() {
+                return Err(format!("No subdomain in {}", path));
             }
-        })
-        .fold(true, |acc, e| acc && e);
 
-    if !all_wordlists_exist {
-        return Err(());
+            Ok(Some(subdomains))
+        }
+        Err(e) => Err(format!("Unable to read the subdomains: {}\n{}", path, e)),
+    }
+}
+
+pub fn extract_ws_args<'a>(submatches: &clap::ArgMatches<'a>) -> Result<WsArgs, String> {
+    let path = submatches.value_of("messages").unwrap();
+    if path.is_empty() {
+        return Ok(WsArgs {
+            messages: Vec::new(),
+        });
     }
 
-    Ok(WordlistArgs { wordlist_paths })
+    match crate::wordlist::read(path) {
+        Ok(v) => Ok(WsArgs {
+            messages: v
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(|line| line.to_owned())
+                .collect(),
+        }),
+        Err(e) => Err(format!("Unable to read the messages file: {}\n{}", path, e)),
+    }
+}
+
+pub fn extract_soap_args<'a>(submatches: &clap::ArgMatches<'a>) -> SoapArgs {
+    SoapArgs {
+        operations: submatches
+            .values_of("operation")
+            .map(|v| v.map(|o| o.to_owned()).collect())
+            .unwrap_or_default(),
+        fuzz_params: submatches
+            .values_of("fuzz-param")
+            .map(|v| v.map(|p| p.to_owned()).collect())
+            .unwrap_or_default(),
+    }
+}
+
+pub fn extract_project_args<'a>(submatches: &clap::ArgMatches<'a>) -> ProjectArgs {
+    ProjectArgs {
+        project: submatches.value_of("project").unwrap().to_owned(),
+        export: submatches.value_of("export").unwrap().to_owned(),
+        export_format: submatches.value_of("export-format").unwrap().to_owned(),
+        resume: submatches.is_present("resume"),
+    }
+}
+
+pub fn extract_daemon_args<'a>(
+    submatches: &clap::ArgMatches<'a>,
+) -> Result<crate::daemon::DaemonConfig, String> {
+    let listen = match submatches.value_of("listen").unwrap().parse() {
+        Ok(v) => v,
+        Err(e) => return Err(format!("Invalid listen address: {}", e)),
+    };
+    let max_jobs = match submatches.value_of("max-jobs").unwrap().parse::<usize>() {
+        Ok(v) if v > 0 => v,
+        _ => return Err("Invalid max jobs, it must be a positive number".to_owned()),
+    };
+
+    let token = match submatches.value_of("token") {
+        Some(v) if v.len() >= 16 => v.to_owned(),
+        Some(_) => return Err("The token must be at least 16 characters long".to_owned()),
+        None => match crate::daemon::generate_token() {
+            Some(v) => {
+                crate::say!("[+] Token\t: {}", v);
+                v
+            }
+            None => return Err("Unable to generate a token, set one with --token".to_owned()),
+        },
+    };
+
+    Ok(crate::daemon::DaemonConfig {
+        queue: submatches.value_of("queue").unwrap().to_owned(),
+        listen,
+        max_jobs,
+        results_dir: submatches.value_of("results-dir").unwrap().to_owned(),
+        wordlists_dir: submatches.value_of("wordlists-dir").unwrap().to_owned(),
+        token,
+    })
+}
+
+pub fn extract_import_args<'a>(submatches: &clap::ArgMatches<'a>) -> ImportArgs {
+    ImportArgs {
+        export: submatches.value_of("export").unwrap().to_owned(),
+        host: submatches.value_of("host").unwrap_or("").to_owned(),
+        paths: submatches.value_of("paths").unwrap_or("").to_owned(),
+        params: submatches.value_of("params").unwrap_or("").to_owned(),
+    }
+}
+
+pub fn extract_diff_args<'a>(submatches: &clap::ArgMatches<'a>) -> DiffArgs {
+    DiffArgs {
+        old: submatches.value_of("old").unwrap().to_owned(),
+        new: submatches.value_of("new").unwrap().to_owned(),
+    }
 }
@@ -0,0 +1,275 @@
+use futures::Stream;
+use hyper::{
+    rt::{self, Future},
+    Body, Client, Request, StatusCode,
+};
+use hyper_tls::HttpsConnector;
+use indicatif::{ProgressBar, ProgressStyle};
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+pub mod result_processor;
+
+use result_processor::{
+    save_auth_results, FailureSignature, LockoutDetector, SingleAuthScanResult,
+};
+
+use crate::proxy::ProxyConnector;
+
+#[derive(Debug, Clone)]
+pub struct AuthBuster {
+    pub n_threads: usize,
+    pub ignore_certificate: bool,
+    pub http_method: String,
+    pub http_body: String,
+    pub user_agent: String,
+    pub http_headers: Vec<(String, String)>,
+    pub url: String,
+    pub usernames: Vec<String>,
+    pub passwords: Vec<String>,
+    pub no_progress_bar: bool,
+    pub progress_json: String,
+    pub exit_on_connection_errors: bool,
+    pub output: String,
+    pub output_append: bool,
+    pub tls_min_version: Option<native_tls::Protocol>,
+    pub tls_max_version: Option<native_tls::Protocol>,
+    pub lockout_threshold: usize,
+    pub account_delay: u64,
+}
+
+fn fill(template: &str, username: &str, password: &str) -> String {
+    crate::template::expand(
+        &template
+            .replace("^USER^", username)
+            .replace("^PASS^", password),
+        username,
+    )
+}
+
+impl AuthBuster {
+    fn make_request_future(
+        &self,
+        client: &Client<HttpsConnector<ProxyConnector>>,
+        username: String,
+        password: String,
+    ) -> impl Future<Item = SingleAuthScanResult, Error = ()> {
+        let url = fill(&self.url, &username, &password);
+        let mut target = SingleAuthScanResult {
+            url: url.clone(),
+            method: self.http_method.clone(),
+            username: username.clone(),
+            password: password.clone(),
+            status: StatusCode::default().to_string(),
+            error: None,
+            body: "".to_owned(),
+        };
+        let mut target_err = target.clone();
+        let mut request_builder = Request::builder();
+
+        for (header, value) in self.http_headers.iter() {
+            request_builder.header(
+                fill(header, &username, &password).as_str(),
+                fill(value, &username, &password).as_str(),
+            );
+        }
+
+        let request = match request_builder
+            .header("User-Agent", &self.user_agent[..])
+            .method(&self.http_method[..])
+            .uri(&url)
+            .body(Body::from(fill(&self.http_body, &username, &password)))
+        {
+            Ok(v) => v,
+            Err(e) => {
+                target_err.error = Some(e.to_string());
+                return futures::future::Either::A(futures::future::ok(target_err));
+            }
+        };
+
+        futures::future::Either::B(
+            client
+                .request(request)
+                .and_then(move |res| {
+                    target.status = res.status().to_string();
+                    res.into_body().concat2().map(move |body| {
+                        target.body = String::from_utf8_lossy(&body).into_owned();
+                        target
+                    })
+                })
+                .or_else(move |e| {
+                    target_err.error = Some(e.to_string());
+                    Ok(target_err)
+                }),
+        )
+    }
+
+    fn send_attempts(
+        &self,
+        client: &Client<HttpsConnector<ProxyConnector>>,
+        attempts: Vec<(String, String)>,
+        bar: &ProgressBar,
+    ) -> Vec<SingleAuthScanResult> {
+        let results = Arc::new(Mutex::new(Vec::new()));
+        let results_writer = results.clone();
+        let bar = bar.clone();
+        let buster = self.clone();
+        let client = client.clone();
+        let stream = futures::stream::iter_ok(attempts)
+            .map(move |(username, password)| {
+                buster.make_request_future(&client, username, password)
+            })
+            .buffer_unordered(self.n_threads)
+            .for_each(move |res| {
+                bar.inc(1);
+                results_writer.lock().unwrap().push(res);
+                Ok(())
+            });
+
+        rt::run(stream);
+
+        let results = results.lock().unwrap().clone();
+        results
+    }
+
+    pub fn run(self) -> Vec<SingleAuthScanResult> {
+        let mut tls_connector_builder = native_tls::TlsConnector::builder();
+        tls_connector_builder.danger_accept_invalid_certs(self.ignore_certificate);
+        tls_connector_builder.min_protocol_version(self.tls_min_version);
+        tls_connector_builder.max_protocol_version(self.tls_max_version);
+        let tls_connector = tls_connector_builder
+            .build()
+            .expect("TLS initialization failed");
+        let http_connector = ProxyConnector::new(self.n_threads);
+        let https_connector = HttpsConnector::from((http_connector, tls_connector));
+        let client = Client::builder().build(https_connector);
+
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let known_bad = vec![
+            (format!("rb{:x}", nonce), format!("rb{:x}", nonce / 7)),
+            (format!("rb{:x}", nonce / 3), format!("rb{:x}", nonce / 11)),
+        ];
+        let samples = self.send_attempts(&client, known_bad, &ProgressBar::hidden());
+        if let Some(e) = samples.iter().find_map(|s| s.error.as_ref()) {
+            error!("{} - {:?}", self.url, e);
+            crate::exit_code::abort();
+            warn!("Check connectivity to the target");
+            return Vec::new();
+        }
+
+        let signature = match FailureSignature::learn(&samples) {
+            Some(v) => v,
+            None => {
+                error!("Unable to learn the failure response, the known-bad attempts got different status codes");
+                return Vec::new();
+            }
+        };
+        crate::say!(
+            "[?] Failure response\t: {} ({} fragments)\n",
+            signature.status,
+            signature.fragments.len()
+        );
+
+        let total = self.usernames.len() * self.passwords.len();
+        let bar = if self.no_progress_bar {
+            ProgressBar::hidden()
+        } else {
+            ProgressBar::new(total as u64)
+        };
+        bar.set_draw_delta(10);
+        bar.set_style(ProgressStyle::default_bar()
+            .template("{spinner} [{elapsed_precise}] {bar:40.red/white} {pos:>7}/{len:7} ETA: {eta_precise}")
+            .progress_chars("#>-"));
+
+        let mut lockout = LockoutDetector::new(&signature.status, self.lockout_threshold);
+        let mut found: Vec<SingleAuthScanResult> = Vec::new();
+        let mut journal = crate::output::Journal::open(&self.output);
+        let mut errors = crate::summary::ErrorSummary::default();
+        let mut progress = crate::progress::ProgressEvents::open(&self.progress_json);
+        let mut done = 0;
+        let mut round_started: Option<Instant> = None;
+        // one password per account per round, so every account gets at most one attempt per delay
+        'rounds: for password in self.passwords.iter() {
+            let attempts: Vec<(String, String)> = self
+                .usernames
+                .iter()
+                .filter(|u| !found.iter().any(|f| &f.username == *u))
+                .map(|u| (u.to_owned(), password.to_owned()))
+                .collect();
+            if attempts.is_empty() {
+                break;
+            }
+
+            if let Some(started) = round_started {
+                let delay = Duration::from_secs(self.account_delay);
+                if started.elapsed() < delay {
+                    thread::sleep(delay - started.elapsed());
+                }
+            }
+
+            round_started = Some(Instant::now());
+            done += attempts.len();
+            for res in self.send_attempts(&client, attempts, &bar) {
+                errors.add_status(&res.status);
+                if let Some(e) = &res.error {
+                    error!("{}:{} - {:?}", res.username, res.password, e);
+                    errors.add_error(e);
+                    if self.exit_on_connection_errors {
+                        crate::exit_code::abort();
+                        warn!("Check connectivity to the target");
+                        break 'rounds;
+                    }
+
+                    continue;
+                }
+
+                if lockout.observe(&res.status) {
+                    warn!(
+                        "Possible lockout detected after {} blocked responses, stopping",
+                        self.lockout_threshold
+                    );
+                    break 'rounds;
+                }
+
+                if lockout.is_blocking(&res.status) || signature.matches(&res) {
+                    continue;
+                }
+
+                let line = format!("[+] {}:{}\t{}", res.username, res.password, res.status);
+                if crate::output::is_quiet() {
+                    println!("{}\t{}\t{}", res.username, res.password, res.status);
+                } else if self.no_progress_bar {
+                    println!("{}", line);
+                } else {
+                    bar.println(line);
+                }
+
+                journal.record(&res);
+                found.push(res);
+            }
+
+            progress.update(done, total, found.len());
+        }
+
+        progress.finish(done, total, found.len());
+        bar.finish();
+        if !errors.is_empty() {
+            crate::say!("{}", errors.report());
+        }
+
+        crate::say!("{}", crate::banner::ending_time());
+
+        if !self.output.is_empty() {
+            save_auth_results(&self.output, &found, self.output_append);
+        }
+        journal.finish();
+
+        found
+    }
+}
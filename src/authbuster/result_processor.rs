@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+use std::{fs::File, io::Write, path::Path};
+
+use crate::output::merge_results;
+
+const FRAGMENT_SEPARATORS: &[char] = &['\n', '<', '>', '"', '{', '}', ','];
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SingleAuthScanResult {
+    pub url: String,
+    pub method: String,
+    pub username: String,
+    pub password: String,
+    pub status: String,
+    pub error: Option<String>,
+    #[serde(skip)]
+    pub body: String,
+}
+
+impl SingleAuthScanResult {
+    pub fn normalized_body(&self) -> String {
+        let mut body = self.body.clone();
+        for value in [&self.username, &self.password].iter() {
+            if !value.is_empty() {
+                body = body.replace(value.as_str(), "");
+            }
+        }
+
+        body
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailureSignature {
+    pub status: String,
+    pub fragments: Vec<String>,
+}
+
+impl FailureSignature {
+    pub fn learn(samples: &[SingleAuthScanResult]) -> Option<Self> {
+        let first = samples.first()?;
+        if samples.iter().any(|s| s.status != first.status) {
+            return None;
+        }
+
+        let bodies: Vec<String> = samples.iter().map(|s| s.normalized_body()).collect();
+        let mut fragments: Vec<String> = Vec::new();
+        for fragment in bodies[0].split(FRAGMENT_SEPARATORS) {
+            let fragment = fragment.trim();
+            if fragment.is_empty() || fragments.iter().any(|f| f == fragment) {
+                continue;
+            }
+
+            if bodies.iter().all(|b| b.contains(fragment)) {
+                fragments.push(fragment.to_owned());
+            }
+        }
+
+        Some(FailureSignature {
+            status: first.status.clone(),
+            fragments,
+        })
+    }
+
+    pub fn matches(&self, res: &SingleAuthScanResult) -> bool {
+        let body = res.normalized_body();
+        res.status == self.status && self.fragments.iter().all(|f| body.contains(f.as_str()))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LockoutDetector {
+    baseline_status: String,
+    threshold: usize,
+    consecutive: usize,
+}
+
+impl LockoutDetector {
+    pub fn new(baseline_status: &str, threshold: usize) -> Self {
+        LockoutDetector {
+            baseline_status: baseline_status.to_owned(),
+            threshold,
+            consecutive: 0,
+        }
+    }
+
+    pub fn is_blocking(&self, status: &str) -> bool {
+        let code = status.split(' ').next().unwrap_or("");
+        (code == "403" || code == "429") && !self.baseline_status.starts_with(code)
+    }
+
+    pub fn observe(&mut self, status: &str) -> bool {
+        if self.is_blocking(status) {
+            self.consecutive += 1;
+        } else {
+            self.consecutive = 0;
+        }
+
+        self.threshold > 0 && self.consecutive >= self.threshold
+    }
+}
+
+pub fn save_auth_results(path: &str, results: &[SingleAuthScanResult], append: bool) {
+    let json_string = if append {
+        serde_json::to_string(&merge_results(path, results, |r| {
+            (r.url.clone(), r.username.clone(), r.password.clone())
+        }))
+        .unwrap()
+    } else {
+        serde_json::to_string(&results).unwrap()
+    };
+
+    let mut file = match File::create(Path::new(path)) {
+        Ok(f) => f,
+        Err(e) => {
+            error!("Error while creating file: {}\n{}", path, e);
+            return;
+        }
+    };
+
+    match file.write_all(json_string.as_bytes()) {
+        Ok(_) => debug!("Results saved to: {}", path),
+        Err(e) => error!("Error while writing results to file: {}\n{}", path, e),
+    };
+}
@@ -1,5 +1,7 @@
 use chrono::Local;
 
+use crate::certificate::CertificateInfo;
+
 const VERSION: &'static str = env!("CARGO_PKG_VERSION");
 
 pub fn generate() -> String {
@@ -36,6 +38,137 @@ pub fn configuration(mode: &str, url: &str, threads: &str, wordlist: &str) -> St
     )
 }
 
+pub fn certificate(info: &CertificateInfo) -> String {
+    format!(
+        "[+] Certificate\t: {}
+[+] Issuer\t: {}
+[+] Expires\t: {}
+[+] SANs\t\t: {}
+",
+        info.common_name,
+        info.issuer,
+        info.not_after,
+        info.subject_alt_names.join(", ")
+    )
+}
+
+pub fn favicon(hash: i32, product: Option<&str>) -> String {
+    format!(
+        "[+] Favicon hash\t: {} ({})",
+        hash,
+        product.unwrap_or("unknown")
+    )
+}
+
+pub fn max_results(n: usize) -> String {
+    format!(
+        "[!] Stopped after {} results, the partial results are kept",
+        n
+    )
+}
+
+pub fn known_results(skipped: usize) -> String {
+    format!("[?] Known results not shown\t: {}", skipped)
+}
+
+pub fn wordlist_filtered(path: &str, removed: usize, total: usize) -> String {
+    format!(
+        "[?] Wordlist\t: {} ({} of {} candidates removed)",
+        path, removed, total
+    )
+}
+
+pub fn vhost_baseline(label: &str, status: &str, length: usize) -> String {
+    format!(
+        "[?] {}\t: {} ({} bytes), vhosts answering the same are ignored",
+        label, status, length
+    )
+}
+
+pub fn scan_id(id: &str) -> String {
+    format!("[?] Scan ID\t: {}", id)
+}
+
+pub fn interactive() -> String {
+    "[?] Interactive\t: press Enter to pause the scan and change the filters".to_owned()
+}
+
+pub fn detected_scheme(url: &str) -> String {
+    format!("[?] Detected scheme\t: {}", url)
+}
+
+pub fn reachable(status: &str) -> String {
+    format!("[+] Target reachable\t: {}", status)
+}
+
+pub fn sni_default(certificate: Option<&CertificateInfo>) -> String {
+    match certificate {
+        Some(certificate) => format!(
+            "[?] Default certificate\t: {}, vhosts presenting another one are reported",
+            certificate.common_name
+        ),
+        None => "[?] Default certificate\t: none, vhosts presenting one are reported".to_owned(),
+    }
+}
+
+pub fn soft404(host: &str, status: &str, length: usize) -> String {
+    format!(
+        "[?] Soft-404 {}\t: {} ({} bytes), the paths answering the same are ignored",
+        host, status, length
+    )
+}
+
+pub fn fuzz_baseline(baseline: &crate::fuzzbuster::calibrate::Baseline) -> String {
+    let statuses: Vec<String> = baseline.statuses.iter().map(|s| s.to_string()).collect();
+    format!(
+        "[?] Baseline\t: {} ({:.0} bytes, {:.0} words, {:.0}ms over {} requests), only the responses deviating from it are shown",
+        statuses.join(","),
+        baseline.length.mean,
+        baseline.words.mean,
+        baseline.latency.mean,
+        baseline.samples
+    )
+}
+
+pub fn zone_walked(n: usize) -> String {
+    format!("[+] Zone walk\t: {} names found through NSEC", n)
+}
+
+pub fn zone_walk_nsec3(iterations: u16) -> String {
+    format!(
+        "[!] Zone walk\t: the zone uses NSEC3 ({} iterations), falling back to the wordlist",
+        iterations
+    )
+}
+
+pub fn zone_walk_unsigned() -> String {
+    "[!] Zone walk\t: no NSEC records found, falling back to the wordlist".to_owned()
+}
+
+pub fn crawled_directories(directories: &[String]) -> String {
+    let mut lines = vec![format!("[+] Crawled directories\t: {}", directories.len())];
+    for directory in directories.iter() {
+        lines.push(format!("    {}", directory));
+    }
+
+    lines.join("\n")
+}
+
+pub fn skipped_dirs(count: usize) -> String {
+    format!(
+        "[?] Skipped\t: {} candidates under the --skip-dir directories",
+        count
+    )
+}
+
+pub fn learned_words(count: usize) -> String {
+    format!("[+] Learned words\t: {}", count)
+}
+
+pub fn rate_budget(budget: &crate::ratelimit::RateBudget) -> String {
+    format!("[?] Rate limit budget\t: {}", budget)
+}
+
 pub fn starting_time() -> String {
     format!(
         "[?] Started at\t: {}\n",
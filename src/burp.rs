@@ -0,0 +1,99 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::DateTime;
+use std::fs;
+
+use crate::har::{raw_request, HarEntry, HarResponse};
+
+fn cdata(value: &str) -> String {
+    format!("<![CDATA[{}]]>", value.replace("]]>", "]]]]><![CDATA[>"))
+}
+
+fn raw_response(response: &HarResponse) -> Vec<u8> {
+    let mut raw = format!(
+        "{} {} {}\r\n",
+        response.http_version, response.status, response.status_text
+    );
+    for header in response.headers.iter() {
+        raw.push_str(&format!("{}: {}\r\n", header.name, header.value));
+    }
+    raw.push_str("\r\n");
+
+    let mut raw = raw.into_bytes();
+    raw.extend_from_slice(&response.content.bytes());
+    raw
+}
+
+/// The names Burp shows in its MIME type column
+fn mime_type(content_type: &str) -> &'static str {
+    let content_type = content_type.to_lowercase();
+    [
+        ("html", "HTML"),
+        ("json", "JSON"),
+        ("xml", "XML"),
+        ("javascript", "script"),
+        ("css", "CSS"),
+        ("image", "image"),
+        ("text", "text"),
+    ]
+    .iter()
+    .find(|(pattern, _)| content_type.contains(pattern))
+    .map(|(_, name)| *name)
+    .unwrap_or("")
+}
+
+fn item(entry: &HarEntry) -> Option<String> {
+    let uri = entry.request.url.parse::<hyper::Uri>().ok()?;
+    let protocol = uri.scheme_str().unwrap_or("http");
+    let port = uri
+        .port_u16()
+        .unwrap_or(if protocol == "https" { 443 } else { 80 });
+    let path = uri.path_and_query().map(|p| p.as_str()).unwrap_or("/");
+    let name = uri.path().rsplit('/').next().unwrap_or("");
+    let extension = match name.rfind('.') {
+        Some(i) if i + 1 < name.len() => &name[i + 1..],
+        _ => "null",
+    };
+    let time = DateTime::parse_from_rfc3339(&entry.started_date_time)
+        .map(|t| t.format("%a %b %d %H:%M:%S %z %Y").to_string())
+        .unwrap_or_default();
+
+    Some(format!(
+        "  <item>\n    <time>{}</time>\n    <url>{}</url>\n    <host ip=\"\">{}</host>\n    \
+         <port>{}</port>\n    <protocol>{}</protocol>\n    <method>{}</method>\n    \
+         <path>{}</path>\n    <extension>{}</extension>\n    \
+         <request base64=\"true\">{}</request>\n    <status>{}</status>\n    \
+         <responselength>{}</responselength>\n    <mimetype>{}</mimetype>\n    \
+         <response base64=\"true\">{}</response>\n    <comment></comment>\n  </item>\n",
+        time,
+        cdata(&entry.request.url),
+        uri.host()?,
+        port,
+        protocol,
+        cdata(&entry.request.method),
+        cdata(path),
+        extension,
+        cdata(&STANDARD.encode(raw_request(&entry.request))),
+        entry.response.status,
+        entry.response.content.size,
+        mime_type(&entry.response.content.mime_type),
+        cdata(&STANDARD.encode(raw_response(&entry.response))),
+    ))
+}
+
+/// Burp's "Save items" XML, the requests and responses are base64 encoded
+pub fn items(entries: &[HarEntry]) -> String {
+    let mut xml = "<?xml version=\"1.0\"?>\n<items burpVersion=\"\" exportTime=\"\">\n".to_owned();
+    for item in entries.iter().filter_map(item) {
+        xml.push_str(&item);
+    }
+    xml.push_str("</items>\n");
+
+    xml
+}
+
+pub fn save_items(path: &str, entries: &[HarEntry]) {
+    match fs::write(path, items(entries)) {
+        Ok(_) => debug!("Burp items saved to: {}", path),
+        Err(e) => error!("Error while writing Burp items to file: {}\n{}", path, e),
+    };
+}
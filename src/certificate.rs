@@ -0,0 +1,293 @@
+use futures::Future;
+use hyper::{
+    client::connect::{Connect, Destination},
+    rt, Uri,
+};
+use hyper_tls::{HttpsConnector, MaybeHttpsStream};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::mpsc::channel, time::Duration};
+use tokio_timer::Timeout;
+
+use crate::proxy::ProxyConnector;
+
+const OID_COMMON_NAME: &[u8] = &[0x55, 0x04, 0x03];
+const OID_SUBJECT_ALT_NAME: &[u8] = &[0x55, 0x1d, 0x11];
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CertificateInfo {
+    pub host: String,
+    pub common_name: String,
+    pub issuer: String,
+    pub subject_alt_names: Vec<String>,
+    pub not_after: String,
+}
+
+impl CertificateInfo {
+    /// Whether both describe the same certificate, whatever the name it was presented for
+    pub fn same_certificate(&self, other: &CertificateInfo) -> bool {
+        self.common_name == other.common_name
+            && self.issuer == other.issuer
+            && self.subject_alt_names == other.subject_alt_names
+            && self.not_after == other.not_after
+    }
+}
+
+struct Tlv<'a> {
+    tag: u8,
+    value: &'a [u8],
+}
+
+fn read_tlv(data: &[u8]) -> Option<(Tlv<'_>, &[u8])> {
+    let tag = *data.first()?;
+    let first_len = *data.get(1)? as usize;
+    let (len, header_len) = if first_len & 0x80 == 0 {
+        (first_len, 2)
+    } else {
+        let n_bytes = first_len & 0x7f;
+        if n_bytes == 0 || n_bytes > 4 {
+            return None;
+        }
+
+        let mut len = 0usize;
+        for b in data.get(2..2 + n_bytes)? {
+            len = (len << 8) | *b as usize;
+        }
+
+        (len, 2 + n_bytes)
+    };
+
+    let value = data.get(header_len..header_len + len)?;
+    Some((Tlv { tag, value }, &data[header_len + len..]))
+}
+
+fn read_all_tlv(mut data: &[u8]) -> Vec<Tlv<'_>> {
+    let mut items = Vec::new();
+    while let Some((tlv, rest)) = read_tlv(data) {
+        items.push(tlv);
+        data = rest;
+    }
+
+    items
+}
+
+fn common_name(name: &[u8]) -> String {
+    for rdn in read_all_tlv(name) {
+        for attribute in read_all_tlv(rdn.value) {
+            let fields = read_all_tlv(attribute.value);
+            if fields.len() == 2 && fields[0].value == OID_COMMON_NAME {
+                return String::from_utf8_lossy(fields[1].value).into_owned();
+            }
+        }
+    }
+
+    "".to_owned()
+}
+
+fn format_time(time: &Tlv) -> Option<String> {
+    let (year, rest) = match time.tag {
+        // UTCTime uses a two digit year
+        0x17 => (time.value.get(..2)?, time.value.get(2..12)?),
+        0x18 => (time.value.get(..4)?, time.value.get(4..14)?),
+        _ => return None,
+    };
+    if !year.iter().chain(rest).all(u8::is_ascii_digit) {
+        return None;
+    }
+
+    // only ASCII digits are left, slicing on any byte is safe
+    let year = std::str::from_utf8(year).ok()?;
+    let rest = std::str::from_utf8(rest).ok()?;
+    let year = match year.len() {
+        2 => {
+            let yy = year.parse::<u32>().ok()?;
+            (if yy >= 50 { 1900 + yy } else { 2000 + yy }).to_string()
+        }
+        _ => year.to_owned(),
+    };
+
+    Some(format!(
+        "{}-{}-{} {}:{}:{} UTC",
+        year,
+        &rest[0..2],
+        &rest[2..4],
+        &rest[4..6],
+        &rest[6..8],
+        &rest[8..10]
+    ))
+}
+
+fn subject_alt_names(extensions: &[u8]) -> Vec<String> {
+    let mut names = Vec::new();
+    let extensions = match read_tlv(extensions) {
+        Some((v, _)) => v,
+        None => return names,
+    };
+
+    for extension in read_all_tlv(extensions.value) {
+        let fields = read_all_tlv(extension.value);
+        if fields.is_empty() || fields[0].value != OID_SUBJECT_ALT_NAME {
+            continue;
+        }
+
+        // the critical flag is optional, the value is always the last field
+        let general_names = match fields.last().and_then(|v| read_tlv(v.value)) {
+            Some((v, _)) => v,
+            None => continue,
+        };
+
+        for general_name in read_all_tlv(general_names.value) {
+            match general_name.tag {
+                0x82 => names.push(String::from_utf8_lossy(general_name.value).into_owned()),
+                0x87 if general_name.value.len() == 4 => {
+                    let v = general_name.value;
+                    names.push(format!("{}.{}.{}.{}", v[0], v[1], v[2], v[3]));
+                }
+                _ => (),
+            }
+        }
+    }
+
+    names
+}
+
+pub fn parse_certificate(host: &str, der: &[u8]) -> Option<CertificateInfo> {
+    let (certificate, _) = read_tlv(der)?;
+    let (tbs_certificate, _) = read_tlv(certificate.value)?;
+    let mut fields = read_all_tlv(tbs_certificate.value);
+
+    // skip the optional explicit version
+    if !fields.is_empty() && fields[0].tag == 0xa0 {
+        fields.remove(0);
+    }
+
+    // serial, signature, issuer, validity, subject, public key, [optional extensions]
+    if fields.len() < 6 {
+        return None;
+    }
+
+    let validity = read_all_tlv(fields[3].value);
+    let not_after = validity.get(1).and_then(format_time).unwrap_or_default();
+    let subject_alt_names = fields
+        .iter()
+        .skip(6)
+        .find(|f| f.tag == 0xa3)
+        .map(|f| subject_alt_names(f.value))
+        .unwrap_or_default();
+
+    Some(CertificateInfo {
+        host: host.to_owned(),
+        common_name: common_name(fields[4].value),
+        issuer: common_name(fields[2].value),
+        subject_alt_names,
+        not_after,
+    })
+}
+
+/// Handshakes with the target through the configured proxy and TLS versions, the certificate is
+/// recorded whether it is valid or not
+pub fn fetch_certificate(
+    url: &str,
+    tls_min_version: Option<native_tls::Protocol>,
+    tls_max_version: Option<native_tls::Protocol>,
+) -> Option<CertificateInfo> {
+    let uri = url.parse::<Uri>().ok()?;
+    if uri.scheme_str() != Some("https") {
+        return None;
+    }
+
+    let host = uri.host()?.to_owned();
+    let dst = Destination::try_from_uri(uri).ok()?;
+    let mut tls_connector_builder = native_tls::TlsConnector::builder();
+    tls_connector_builder.danger_accept_invalid_certs(true);
+    tls_connector_builder.danger_accept_invalid_hostnames(true);
+    tls_connector_builder.min_protocol_version(tls_min_version);
+    tls_connector_builder.max_protocol_version(tls_max_version);
+    let tls_connector = tls_connector_builder.build().ok()?;
+    let connector = HttpsConnector::from((ProxyConnector::new(1), tls_connector));
+
+    let (tx, rx) = channel::<CertificateInfo>();
+    let connecting = connector.connect(dst).map(move |(stream, _)| {
+        if let MaybeHttpsStream::Https(stream) = stream {
+            let der = stream
+                .get_ref()
+                .peer_certificate()
+                .ok()
+                .and_then(|v| v?.to_der().ok());
+            if let Some(info) = der.and_then(|der| parse_certificate(&host, &der)) {
+                tx.send(info).unwrap_or(());
+            }
+        }
+    });
+    rt::run(
+        Timeout::new(connecting, Duration::from_secs(10))
+            .map_err(|e| debug!("Unable to retrieve the certificate: {:?}", e)),
+    );
+
+    rx.try_recv().ok()
+}
+
+/// Hands the certificate of each host to the first result coming from it, so it is reported once
+/// per host instead of being repeated in every result
+#[derive(Default)]
+pub struct CertificateReport {
+    pending: HashMap<String, CertificateInfo>,
+}
+
+impl CertificateReport {
+    pub fn new(certificates: impl IntoIterator<Item = CertificateInfo>) -> Self {
+        CertificateReport {
+            pending: certificates
+                .into_iter()
+                .map(|info| (info.host.to_lowercase(), info))
+                .collect(),
+        }
+    }
+
+    pub fn take(&mut self, url: &str) -> Option<CertificateInfo> {
+        if self.pending.is_empty() {
+            return None;
+        }
+
+        let uri = url.parse::<Uri>().ok()?;
+        self.pending.remove(&uri.host()?.to_lowercase())
+    }
+}
+
+/// The SAN names of the certificate, a wildcard name without its `*` label, each followed by its
+/// left-most label under the target domain, so `api.example.net` also seeds `api.<domain>`
+pub fn seed_hosts(info: &CertificateInfo, domain: &str) -> Vec<String> {
+    let domain = domain.trim_matches('.').to_lowercase();
+    let mut hosts: Vec<String> = Vec::new();
+    for san in info.subject_alt_names.iter() {
+        if san.parse::<std::net::IpAddr>().is_ok() {
+            continue;
+        }
+
+        let name = san.trim_start_matches("*.").to_lowercase();
+        if name.is_empty() || name.contains('*') {
+            continue;
+        }
+
+        // the labels left of the target domain, or of the SAN's own base for another domain
+        let labels: Vec<&str> = match name.strip_suffix(&format!(".{}", domain)) {
+            Some(sub) => sub.split('.').collect(),
+            None if name == domain => Vec::new(),
+            None => {
+                let labels: Vec<&str> = name.split('.').collect();
+                labels[..labels.len().saturating_sub(2)].to_vec()
+            }
+        };
+        let label = labels
+            .first()
+            .filter(|label| !label.is_empty() && !domain.is_empty())
+            .map(|label| format!("{}.{}", label, domain));
+
+        for host in std::iter::once(name.clone()).chain(label) {
+            if !hosts.contains(&host) {
+                hosts.push(host);
+            }
+        }
+    }
+
+    hosts
+}
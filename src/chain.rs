@@ -0,0 +1,208 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    fs,
+    io::Write,
+    path::Path,
+    sync::{
+        mpsc::{channel, IntoIter, Sender},
+        Arc,
+    },
+    thread,
+};
+
+use crate::{
+    dirbuster::{
+        self,
+        result_processor::{ResultProcessorConfig, SingleDirScanResult},
+        soft404::{self, probe_url, HostFilters},
+        utils::{build_urls, UrlConfig},
+        DirConfig,
+    },
+    dnsbuster::{self, result_processor::SingleDnsScanResult, DnsConfig},
+    output::{format_finding, Journal},
+    ratelimit::Pacer,
+    severity::Classifier,
+    sink::ResultSink,
+    vhostbuster::{self, result_processor::SingleVhostScanResult, VhostConfig},
+};
+
+#[derive(Debug, Clone)]
+pub struct ChainConfig {
+    pub domains: Vec<String>,
+    pub dir_wordlist: String,
+    pub url: String,
+    pub dir: DirConfig,
+    pub vhost: VhostConfig,
+    pub processor: ResultProcessorConfig,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ChainReport {
+    pub dns: Vec<SingleDnsScanResult>,
+    pub vhost: Vec<SingleVhostScanResult>,
+    pub dir: Vec<SingleDirScanResult>,
+}
+
+impl ChainReport {
+    pub fn summary(&self) -> String {
+        format!(
+            "[+] Chain summary\t: {} hosts resolved, {} vhosts matched, {} paths found",
+            self.dns.len(),
+            self.vhost.len(),
+            self.dir.len()
+        )
+    }
+
+    pub fn findings(&self) -> Vec<Value> {
+        let dns = self.dns.iter().filter_map(|r| serde_json::to_value(r).ok());
+        let vhost = self
+            .vhost
+            .iter()
+            .filter_map(|r| serde_json::to_value(r).ok());
+        let dir = self.dir.iter().filter_map(|r| serde_json::to_value(r).ok());
+
+        dns.chain(vhost).chain(dir).collect()
+    }
+}
+
+fn stage<T, F>(run: F) -> IntoIter<T>
+where
+    T: Send + 'static,
+    F: FnOnce(Sender<T>) + Send + 'static,
+{
+    let (tx, rx) = channel::<T>();
+    thread::spawn(move || run(tx));
+    rx.into_iter()
+}
+
+pub fn hostname(domain: &str) -> &str {
+    match domain.rfind(':') {
+        Some(i) => &domain[..i],
+        None => domain,
+    }
+}
+
+pub fn base_url(url: &str, host: &str) -> String {
+    let uri = match url.parse::<hyper::Uri>() {
+        Ok(v) => v,
+        Err(_) => return format!("http://{}/", host),
+    };
+    let scheme = uri.scheme_part().map(|s| s.as_str()).unwrap_or("http");
+
+    match uri.port_part() {
+        Some(port) => format!("{}://{}:{}/", scheme, host, port),
+        None => format!("{}://{}/", scheme, host),
+    }
+}
+
+pub fn run(config: ChainConfig, journal: &mut Journal) -> ChainReport {
+    let mut report = ChainReport::default();
+    let pacer = Arc::new(Pacer::default());
+
+    crate::say!("[+] Resolving {} candidates", config.domains.len());
+    let domains = config.domains.clone();
+    let dns_config = DnsConfig {
+        n_threads: config.dir.n_threads,
+        timeout: dnsbuster::DEFAULT_TIMEOUT,
+        retries: dnsbuster::DEFAULT_RETRIES,
+    };
+    for msg in stage(move |tx| dnsbuster::run(tx, domains, dns_config)) {
+        if msg.status {
+            println!("OK\t{}", hostname(&msg.domain));
+            journal.record(&msg);
+            report.dns.push(msg);
+        }
+    }
+
+    let hosts: Vec<String> = report
+        .dns
+        .iter()
+        .map(|r| hostname(&r.domain).to_owned())
+        .collect();
+    if hosts.is_empty() {
+        return report;
+    }
+
+    crate::say!("[+] Testing {} vhosts against {}", hosts.len(), config.url);
+    let vhosts: Vec<hyper::Uri> = hosts.iter().filter_map(|h| h.parse().ok()).collect();
+    let vhost_config = config.vhost.clone();
+    let vhost_pacer = pacer.clone();
+    for msg in stage(move |tx| vhostbuster::run_with_pacer(tx, vhosts, vhost_config, vhost_pacer)) {
+        if msg.error.is_none() && !msg.ignored {
+            println!(
+                "{}{}",
+                format_finding(&msg.method, &msg.status, &msg.vhost, None),
+                vhostbuster::utils::format_details(&msg)
+            );
+            journal.record(&msg);
+            report.vhost.push(msg);
+        }
+    }
+
+    let url_config = UrlConfig {
+        extensions: Vec::new(),
+        append_slash: false,
+        raw_path: false,
+        mutations: false,
+        backslash: false,
+        force_extensions: false,
+        wraps: Vec::new(),
+    };
+    let mut urls: Vec<hyper::Uri> = Vec::new();
+    for host in hosts.iter() {
+        urls.extend(build_urls(
+            &config.dir_wordlist,
+            &base_url(&config.url, host),
+            &url_config,
+        ));
+    }
+
+    let probes: Vec<hyper::Uri> = hosts
+        .iter()
+        .filter_map(|host| probe_url(&base_url(&config.url, host)))
+        .collect();
+    let soft404s = soft404::probe(probes, config.dir.clone(), pacer.clone());
+    for banner in soft404s.banners() {
+        crate::say!("{}", banner);
+    }
+    let mut host_filters = HostFilters::new(config.processor.clone()).with_soft404s(soft404s);
+
+    crate::say!("[+] Scanning {} paths on {} hosts", urls.len(), hosts.len());
+    let classifier = Classifier::new(Vec::new());
+    let dir_config = config.dir.clone();
+    for mut msg in stage(move |tx| dirbuster::run_with_pacer(tx, urls, dir_config, pacer)) {
+        if msg.error.is_some() {
+            continue;
+        }
+
+        msg.severity = Some(classifier.classify(&msg.url, &msg.status, &msg.body));
+        if host_filters.maybe_add_result(msg.clone()) {
+            journal.record(&msg);
+            println!(
+                "{}",
+                format_finding(&msg.method, &msg.status, &msg.url, msg.severity)
+            );
+        }
+    }
+    report.dir = host_filters.into_results();
+
+    report
+}
+
+pub fn save_chain_report(path: &str, report: &ChainReport) {
+    let json_string = serde_json::to_string(report).unwrap();
+
+    let mut file = match fs::File::create(Path::new(path)) {
+        Ok(f) => f,
+        Err(e) => {
+            error!("Error while creating file: {}\n{}", path, e);
+            return;
+        }
+    };
+
+    match file.write_all(json_string.as_bytes()) {
+        Ok(_) => debug!("Results saved to: {}", path),
+        Err(e) => error!("Error while writing results to file: {}\n{}", path, e),
+    };
+}
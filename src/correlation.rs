@@ -0,0 +1,37 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::template::expand;
+
+pub const HEADER: &str = "X-Rustbuster-Id";
+
+/// The identifier sent with the requests of a scan, so that the server logs can be matched with
+/// its results
+#[derive(Debug)]
+pub struct RequestTag {
+    pub scan_id: String,
+    sequence: AtomicUsize,
+}
+
+impl RequestTag {
+    pub fn new() -> Self {
+        RequestTag {
+            scan_id: expand("{{rand(12)}}", ""),
+            sequence: AtomicUsize::new(0),
+        }
+    }
+
+    /// The identifier of the next scan entry, its follow-up requests reuse it
+    pub fn next(&self) -> String {
+        format!(
+            "{}-{}",
+            self.scan_id,
+            self.sequence.fetch_add(1, Ordering::SeqCst) + 1
+        )
+    }
+}
+
+impl Default for RequestTag {
+    fn default() -> Self {
+        RequestTag::new()
+    }
+}
@@ -0,0 +1,149 @@
+use futures::Stream;
+use hyper::{
+    rt::{self, Future},
+    Body, Client, Request, Uri,
+};
+use hyper_tls::HttpsConnector;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+use crate::proxy::ProxyConnector;
+
+const ATTACKER_DOMAIN: &str = "rustbuster-cors.example";
+
+#[derive(Debug, Clone)]
+pub struct CorsConfig {
+    pub n_threads: usize,
+    pub ignore_certificate: bool,
+    pub user_agent: String,
+    pub http_headers: Vec<(String, String)>,
+    pub tls_min_version: Option<native_tls::Protocol>,
+    pub tls_max_version: Option<native_tls::Protocol>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CorsFinding {
+    pub url: String,
+    pub origin: String,
+    pub allow_origin: String,
+}
+
+pub fn attacker_origins(url: &Uri) -> Vec<String> {
+    let mut origins = vec![format!("https://{}", ATTACKER_DOMAIN), "null".to_owned()];
+    if let Some(host) = url.host() {
+        origins.push(format!("https://{}.{}", host, ATTACKER_DOMAIN));
+    }
+
+    origins
+}
+
+pub fn is_vulnerable(origin: &str, headers: &hyper::HeaderMap) -> Option<String> {
+    let allow_origin = headers
+        .get("Access-Control-Allow-Origin")
+        .and_then(|v| v.to_str().ok())?
+        .trim();
+    let allow_credentials = headers
+        .get("Access-Control-Allow-Credentials")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.trim().eq_ignore_ascii_case("true"))
+        .unwrap_or(false);
+
+    if allow_credentials && allow_origin == origin {
+        Some(allow_origin.to_owned())
+    } else {
+        None
+    }
+}
+
+fn make_request_future(
+    client: &Client<HttpsConnector<ProxyConnector>>,
+    url: Uri,
+    origin: String,
+    config: &CorsConfig,
+) -> impl Future<Item = Option<CorsFinding>, Error = ()> {
+    let mut request_builder = Request::builder();
+    for header_tuple in &config.http_headers {
+        request_builder.header(header_tuple.0.as_str(), header_tuple.1.as_str());
+    }
+
+    let request = request_builder
+        .header("User-Agent", &config.user_agent[..])
+        .header("Origin", origin.as_str())
+        .uri(&url)
+        .body(Body::empty())
+        .expect("Request builder");
+
+    client
+        .request(request)
+        .map(move |res| {
+            is_vulnerable(&origin, res.headers()).map(|allow_origin| CorsFinding {
+                url: url.to_string(),
+                origin,
+                allow_origin,
+            })
+        })
+        .or_else(|e| {
+            debug!("CORS check failed: {}", e);
+            Ok(None)
+        })
+}
+
+pub fn check(urls: &[String], config: &CorsConfig) -> Vec<CorsFinding> {
+    let mut tls_connector_builder = native_tls::TlsConnector::builder();
+    tls_connector_builder.danger_accept_invalid_certs(config.ignore_certificate);
+    tls_connector_builder.min_protocol_version(config.tls_min_version);
+    tls_connector_builder.max_protocol_version(config.tls_max_version);
+    let tls_connector = tls_connector_builder
+        .build()
+        .expect("TLS initialization failed");
+    let http_connector = ProxyConnector::new(config.n_threads);
+    let https_connector = HttpsConnector::from((http_connector, tls_connector));
+    let client = Client::builder().build(https_connector);
+
+    let mut probes: Vec<(Uri, String)> = Vec::new();
+    for url in urls.iter() {
+        let uri = match url.parse::<Uri>() {
+            Ok(v) => v,
+            Err(e) => {
+                debug!("URI: {}", e);
+                continue;
+            }
+        };
+
+        for origin in attacker_origins(&uri) {
+            probes.push((uri.clone(), origin));
+        }
+    }
+
+    let findings = Arc::new(Mutex::new(Vec::new()));
+    let findings_writer = findings.clone();
+    let config = config.clone();
+    let n_threads = config.n_threads;
+    let stream = futures::stream::iter_ok(probes)
+        .map(move |(url, origin)| make_request_future(&client, url, origin, &config))
+        .buffer_unordered(n_threads)
+        .for_each(move |finding| {
+            if let Some(finding) = finding {
+                findings_writer.lock().unwrap().push(finding);
+            }
+
+            Ok(())
+        });
+
+    rt::run(stream);
+
+    let findings = findings.lock().unwrap().clone();
+    findings
+}
+
+pub fn report(findings: &[CorsFinding]) -> String {
+    let mut lines = vec![format!("[!] CORS misconfigurations\t: {}", findings.len())];
+    for finding in findings.iter() {
+        lines.push(format!(
+            "\t{} reflects Origin: {} with credentials allowed",
+            finding.url, finding.origin
+        ));
+    }
+
+    lines.join("\n")
+}
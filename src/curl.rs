@@ -0,0 +1,97 @@
+use crate::{
+    har::HarRequest,
+    proxy::{Proxy, ProxySettings},
+};
+
+/// Single quotes for a POSIX shell, a quote inside is closed, escaped and reopened
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// A printf format writing the bytes back, octal escapes keep NUL and invalid UTF-8 intact
+fn printf_format(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|&b| match b {
+            b'%' => "%%".to_owned(),
+            b'\\' => "\\\\".to_owned(),
+            0x20..=0x7e => char::from(b).to_string(),
+            _ => format!("\\{:03o}", b),
+        })
+        .collect()
+}
+
+/// A curl command line sending the same request through the same proxy
+pub fn command(request: &HarRequest, proxy: Option<&Proxy>, ignore_certificate: bool) -> String {
+    let mut args = vec!["curl".to_owned()];
+    match request.method.as_str() {
+        "GET" => (),
+        "HEAD" => args.push("-I".to_owned()),
+        method => args.push(format!("-X {}", quote(method))),
+    }
+    args.push(quote(&request.url));
+    // curl would resolve the dot segments the scan sent as they are
+    if request.url.contains("/.") {
+        args.push("--path-as-is".to_owned());
+    }
+
+    for header in request.headers.iter() {
+        args.push(format!(
+            "-H {}",
+            quote(&format!("{}: {}", header.name, header.value))
+        ));
+    }
+    // A shell argument can't hold every byte, binary bodies are piped from printf
+    let mut stdin = None;
+    if let Some(post_data) = &request.post_data {
+        let body = post_data.bytes();
+        match String::from_utf8(body) {
+            Ok(text) if !text.contains('\0') => {
+                args.push(format!("--data-binary {}", quote(&text)))
+            }
+            Ok(text) => stdin = Some(printf_format(text.as_bytes())),
+            Err(e) => stdin = Some(printf_format(e.as_bytes())),
+        }
+    }
+
+    if let Some(proxy) = proxy {
+        let host = if proxy.host.contains(':') {
+            format!("[{}]", proxy.host)
+        } else {
+            proxy.host.to_owned()
+        };
+        args.push(format!(
+            "-x {}",
+            quote(&format!("http://{}:{}", host, proxy.port))
+        ));
+        if let Some(authorization) = &proxy.authorization {
+            args.push(format!(
+                "--proxy-header {}",
+                quote(&format!("Proxy-Authorization: Basic {}", authorization))
+            ));
+        }
+    }
+    if ignore_certificate {
+        args.push("-k".to_owned());
+    }
+
+    match stdin {
+        Some(format) => {
+            args.push("--data-binary @-".to_owned());
+            format!("printf {} | {}", quote(&format), args.join(" "))
+        }
+        None => args.join(" "),
+    }
+}
+
+/// The curl command line of the request, going through the proxy of the environment
+pub fn command_from_env(request: &HarRequest, ignore_certificate: bool) -> String {
+    let settings = ProxySettings::from_env();
+    let proxy = request.url.parse::<hyper::Uri>().ok().and_then(|uri| {
+        settings
+            .proxy_for(uri.scheme_str().unwrap_or("http"), uri.host().unwrap_or(""))
+            .cloned()
+    });
+
+    command(request, proxy.as_ref(), ignore_certificate)
+}
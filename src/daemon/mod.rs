@@ -0,0 +1,667 @@
+use chrono::Local;
+use futures::{Future, Stream};
+use hyper::{
+    header::{AUTHORIZATION, CONTENT_TYPE},
+    rt,
+    service::service_fn,
+    Body, HeaderMap, Method, Request, Response, Server, StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File},
+    io::Read,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+pub mod schedule;
+
+use schedule::Schedule;
+
+const TICK: Duration = Duration::from_secs(1);
+
+/// The modes a job can run
+const JOB_MODES: &[&str] = &["dir", "dns", "vhost", "tilde", "fuzz", "methods"];
+
+/// How the value of a job flag is read
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum JobValue {
+    None,
+    Value,
+    /// Inline words or the files of the wordlists directory
+    Wordlist,
+}
+
+/// The flags a job can use, with their short name. The other flags read or write the files of
+/// the caller's choice (--body-file, --session, --known, -o, --har...), the daemon picks where
+/// the results go and only reads the wordlists of its own directory
+const JOB_FLAGS: &[(&str, &str, JobValue)] = &[
+    ("verbose", "v", JobValue::None),
+    ("no-banner", "", JobValue::None),
+    ("no-color", "", JobValue::None),
+    ("quiet", "q", JobValue::None),
+    ("threads", "t", JobValue::Value),
+    ("exit-on-error", "K", JobValue::None),
+    ("format", "", JobValue::Value),
+    ("no-progress-bar", "", JobValue::None),
+    ("prefer-family", "", JobValue::Value),
+    ("max-results", "", JobValue::Value),
+    ("include-status-codes", "s", JobValue::Value),
+    ("ignore-status-codes", "S", JobValue::Value),
+    ("user-agent", "a", JobValue::Value),
+    ("ignore-certificate", "k", JobValue::None),
+    ("no-env-proxy", "", JobValue::None),
+    ("no-favicon", "", JobValue::None),
+    ("tag-requests", "", JobValue::None),
+    ("probe-target", "", JobValue::None),
+    ("http-method", "X", JobValue::Value),
+    ("http-body", "b", JobValue::Value),
+    ("http-header", "H", JobValue::Value),
+    ("tls-min", "", JobValue::Value),
+    ("tls-max", "", JobValue::Value),
+    ("digest", "", JobValue::Value),
+    ("capture-header", "", JobValue::Value),
+    ("audit-headers", "", JobValue::None),
+    ("url", "u", JobValue::Value),
+    ("slow-on", "", JobValue::Value),
+    ("max-rate-limit-wait", "", JobValue::Value),
+    ("show-curl", "", JobValue::None),
+    ("sort", "", JobValue::Value),
+    ("group-by", "", JobValue::Value),
+    ("ignore-string", "x", JobValue::Value),
+    ("include-string", "i", JobValue::Value),
+    ("extensions", "e", JobValue::Value),
+    ("check-cors", "", JobValue::None),
+    ("check-head", "", JobValue::None),
+    ("probe-http10", "", JobValue::None),
+    ("severity-rule", "", JobValue::Value),
+    ("force-extensions", "", JobValue::None),
+    ("wrap", "", JobValue::Value),
+    ("skip-dir", "", JobValue::Value),
+    ("both-schemes", "", JobValue::None),
+    ("tag-each-request", "", JobValue::None),
+    ("ports", "", JobValue::Value),
+    ("range-threshold", "", JobValue::Value),
+    ("range-size", "", JobValue::Value),
+    ("rule", "", JobValue::Value),
+    ("append-slash", "f", JobValue::None),
+    ("mutations", "", JobValue::None),
+    ("backslash", "", JobValue::None),
+    ("pipeline", "", JobValue::Value),
+    ("adaptive", "", JobValue::None),
+    ("raw-path", "", JobValue::None),
+    ("wordlist", "w", JobValue::Wordlist),
+    ("dedupe", "", JobValue::None),
+    ("trim", "", JobValue::None),
+    ("skip-comments", "", JobValue::None),
+    ("min-length", "", JobValue::Value),
+    ("max-length", "", JobValue::Value),
+    ("extension", "", JobValue::Value),
+    ("domain", "d", JobValue::Value),
+    ("seed-from-cert", "", JobValue::None),
+    ("brute-tlds", "", JobValue::None),
+    ("zone-walk", "", JobValue::None),
+    ("dns-timeout", "", JobValue::Value),
+    ("dns-retries", "", JobValue::Value),
+    ("asn-lookup", "", JobValue::None),
+    ("output-format", "", JobValue::Value),
+    ("resolve-vhosts", "", JobValue::None),
+    ("absolute-uri", "", JobValue::None),
+    ("csrf-url", "", JobValue::Value),
+    ("csrf-regex", "", JobValue::Value),
+    ("csrf-header", "", JobValue::Value),
+    ("jwt-claims", "", JobValue::Value),
+    ("extract", "", JobValue::Value),
+    ("next-url", "", JobValue::Value),
+    ("next-body", "", JobValue::Value),
+    ("mutate", "", JobValue::None),
+    ("auto-calibrate", "", JobValue::None),
+    ("generate", "g", JobValue::Value),
+    ("wordlist-encoding", "", JobValue::Value),
+    ("jwt-none", "", JobValue::None),
+];
+
+#[derive(Debug, Clone)]
+pub struct DaemonConfig {
+    pub queue: String,
+    pub listen: SocketAddr,
+    pub max_jobs: usize,
+    pub results_dir: String,
+    pub wordlists_dir: String,
+    pub token: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct DaemonJob {
+    pub name: String,
+    pub arguments: Vec<String>,
+    #[serde(default)]
+    pub schedule: String,
+    #[serde(default)]
+    pub runs: usize,
+    #[serde(default)]
+    pub last_started: Option<String>,
+    #[serde(default)]
+    pub last_exit_code: Option<i32>,
+    #[serde(default)]
+    pub last_output: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct JobQueue {
+    pub jobs: Vec<DaemonJob>,
+    #[serde(default)]
+    pub pending: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+struct DaemonStatus<'a> {
+    jobs: &'a [DaemonJob],
+    pending: &'a [String],
+    running: Vec<&'a str>,
+}
+
+impl JobQueue {
+    pub fn load(path: &str) -> Option<Self> {
+        let content = match fs::read_to_string(path) {
+            Ok(v) => v,
+            Err(_) => return Some(JobQueue::default()),
+        };
+
+        match serde_json::from_str(&content) {
+            Ok(v) => Some(v),
+            Err(e) => {
+                error!("Error while parsing job queue: {}\n{}", path, e);
+                None
+            }
+        }
+    }
+
+    pub fn save(&self, path: &str) {
+        if path.is_empty() {
+            return;
+        }
+
+        let json_string = serde_json::to_string_pretty(self).unwrap();
+        if let Err(e) = fs::write(path, json_string) {
+            error!("Error while writing job queue to file: {}\n{}", path, e);
+        }
+    }
+}
+
+fn job_flag(argument: &str) -> Option<(&'static str, JobValue)> {
+    let flag = match argument.strip_prefix("--") {
+        Some(long) => JOB_FLAGS
+            .iter()
+            .find(|(name, _, _)| *name == long.split('=').next().unwrap_or("")),
+        // the short flags are given one by one, -kw list would hide the value of -w
+        None if argument.len() == 2 && argument.starts_with('-') => JOB_FLAGS
+            .iter()
+            .find(|(_, short, _)| !short.is_empty() && *short == &argument[1..]),
+        None => None,
+    };
+
+    flag.map(|(name, _, value)| (*name, *value))
+}
+
+/// Resolves the wordlists of a -w value in the wordlists directory, the words following
+/// inline: up to the next value are kept as they are
+fn resolve_wordlists(value: &str, wordlists_dir: &str) -> Result<String, String> {
+    let mut resolved = Vec::new();
+    let mut inline = false;
+    for part in value.split(',') {
+        inline = inline || part.starts_with(crate::wordlist::INLINE_PREFIX);
+        if inline {
+            resolved.push(part.to_owned());
+            continue;
+        }
+
+        let path = Path::new(part);
+        let relative = !part.is_empty()
+            && path
+                .components()
+                .all(|c| matches!(c, std::path::Component::Normal(_)));
+        if !relative {
+            return Err(format!(
+                "{} is refused, the wordlists are named relative to the wordlists directory",
+                part
+            ));
+        }
+        if wordlists_dir.is_empty() {
+            return Err(format!(
+                "{} is refused, only inline wordlists are allowed without --wordlists-dir",
+                part
+            ));
+        }
+
+        let dir = Path::new(wordlists_dir);
+        let path = dir.join(path);
+        // a link in the directory must not lead out of it
+        if let (Ok(dir), Ok(target)) = (dir.canonicalize(), path.canonicalize()) {
+            if !target.starts_with(dir) {
+                return Err(format!("{} is outside of the wordlists directory", part));
+            }
+        }
+        resolved.push(path.to_string_lossy().into_owned());
+    }
+
+    Ok(resolved.join(","))
+}
+
+/// The arguments the job runs with, only the allowed flags are accepted
+pub fn job_arguments(job: &DaemonJob, wordlists_dir: &str) -> Result<Vec<String>, String> {
+    let mut arguments = job.arguments.iter();
+    let mut resolved = match arguments.next().map(|a| a.as_str()) {
+        None => return Err("the arguments must start with the scan mode".to_owned()),
+        Some(mode) if JOB_MODES.contains(&mode) => vec![mode.to_owned()],
+        Some(mode) => return Err(format!("the {} mode cannot be scheduled", mode)),
+    };
+
+    while let Some(argument) = arguments.next() {
+        let (name, kind) = match job_flag(argument) {
+            Some(v) => v,
+            None => return Err(format!("{} is refused, it is not a scan flag", argument)),
+        };
+        let attached = argument.starts_with("--") && argument.contains('=');
+        let value = match (kind, attached) {
+            (JobValue::None, true) => return Err(format!("--{} takes no value", name)),
+            (JobValue::None, false) => {
+                resolved.push(format!("--{}", name));
+                continue;
+            }
+            (_, true) => argument.splitn(2, '=').nth(1).unwrap_or(""),
+            (_, false) => match arguments.next() {
+                Some(v) if !v.starts_with('-') => v.as_str(),
+                _ => return Err(format!("--{} needs a value", name)),
+            },
+        };
+        let value = match kind {
+            JobValue::Wordlist => resolve_wordlists(value, wordlists_dir)?,
+            _ => value.to_owned(),
+        };
+        resolved.push(format!("--{}", name));
+        resolved.push(value);
+    }
+
+    Ok(resolved)
+}
+
+pub fn validate_job(job: &DaemonJob, wordlists_dir: &str) -> Result<(), String> {
+    let valid_name = !job.name.is_empty()
+        && !job.name.starts_with('.')
+        && job
+            .name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+    if !valid_name {
+        return Err("the name can only contain letters, digits, '-', '_' and '.'".to_owned());
+    }
+
+    job_arguments(job, wordlists_dir)?;
+
+    if !job.schedule.is_empty() && Schedule::parse(&job.schedule).is_none() {
+        return Err(format!("invalid schedule: {}", job.schedule));
+    }
+
+    Ok(())
+}
+
+pub struct Daemon {
+    pub config: DaemonConfig,
+    pub queue: JobQueue,
+    running: Vec<(String, Child)>,
+}
+
+impl Daemon {
+    /// The invalid jobs of the queue are marked as failed and their pending runs dropped
+    pub fn new(config: DaemonConfig, mut queue: JobQueue) -> Self {
+        for job in queue.jobs.iter_mut() {
+            if let Err(e) = validate_job(job, &config.wordlists_dir) {
+                error!("{}: {}", job.name, e);
+                job.last_exit_code = Some(crate::exit_code::INVALID_CONFIG);
+                queue.pending.retain(|p| p != &job.name);
+            }
+        }
+
+        Daemon {
+            config,
+            queue,
+            running: Vec::new(),
+        }
+    }
+
+    fn is_running(&self, name: &str) -> bool {
+        self.running.iter().any(|(n, _)| n == name)
+    }
+
+    pub fn enqueue(&mut self, name: &str) -> bool {
+        if !self.queue.jobs.iter().any(|j| j.name == name) {
+            return false;
+        }
+
+        if !self.is_running(name) && !self.queue.pending.iter().any(|p| p == name) {
+            self.queue.pending.push(name.to_owned());
+            self.queue.save(&self.config.queue);
+        }
+
+        true
+    }
+
+    pub fn schedule(&mut self, time: &chrono::DateTime<Local>) {
+        let due: Vec<String> = self
+            .queue
+            .jobs
+            .iter()
+            .filter(|j| Schedule::parse(&j.schedule).is_some_and(|s| s.matches(time)))
+            .map(|j| j.name.to_owned())
+            .collect();
+        for name in due {
+            self.enqueue(&name);
+        }
+    }
+
+    fn reap(&mut self) {
+        let mut finished = Vec::new();
+        self.running
+            .retain_mut(|(name, child)| match child.try_wait() {
+                Ok(Some(status)) => {
+                    finished.push((name.to_owned(), status.code()));
+                    false
+                }
+                Ok(None) => true,
+                Err(e) => {
+                    error!("{} - {}", name, e);
+                    finished.push((name.to_owned(), None));
+                    false
+                }
+            });
+
+        for (name, code) in finished.iter() {
+            match code {
+                Some(code) => crate::say!("[+] {}\tFinished with exit code {}", name, code),
+                None => crate::say!("[+] {}\tTerminated by a signal", name),
+            }
+            if let Some(job) = self.queue.jobs.iter_mut().find(|j| &j.name == name) {
+                job.last_exit_code = *code;
+            }
+        }
+
+        if !finished.is_empty() {
+            self.queue.save(&self.config.queue);
+        }
+    }
+
+    fn start(&mut self, name: &str) {
+        let job = match self.queue.jobs.iter_mut().find(|j| j.name == name) {
+            Some(v) => v,
+            None => return,
+        };
+        let wordlists_dir = &self.config.wordlists_dir;
+        let arguments = match validate_job(job, wordlists_dir)
+            .and_then(|_| job_arguments(job, wordlists_dir))
+        {
+            Ok(v) => v,
+            Err(e) => {
+                error!("{} - {}", job.name, e);
+                job.last_exit_code = Some(crate::exit_code::INVALID_CONFIG);
+                return;
+            }
+        };
+
+        let dir = Path::new(&self.config.results_dir).join(&job.name);
+        if let Err(e) = fs::create_dir_all(&dir) {
+            error!("Unable to create {}: {}", dir.display(), e);
+            return;
+        }
+
+        let started = Local::now();
+        let stem = started.format("%Y%m%d-%H%M%S").to_string();
+        let output: PathBuf = dir.join(format!("{}.json", stem));
+        let log = match File::create(dir.join(format!("{}.log", stem))) {
+            Ok(v) => v,
+            Err(e) => {
+                error!("Unable to create the log of {}: {}", job.name, e);
+                return;
+            }
+        };
+        let program = match std::env::current_exe() {
+            Ok(v) => v,
+            Err(e) => {
+                error!("Unable to find the rustbuster executable: {}", e);
+                return;
+            }
+        };
+
+        let mut command = Command::new(program);
+        command.args(&arguments);
+        if !arguments.iter().any(|a| a == "--no-progress-bar") {
+            command.arg("--no-progress-bar");
+        }
+        let child = command
+            .arg("-o")
+            .arg(&output)
+            .stdin(Stdio::null())
+            .stdout(
+                log.try_clone()
+                    .map(Stdio::from)
+                    .unwrap_or_else(|_| Stdio::null()),
+            )
+            .stderr(log)
+            .spawn();
+        match child {
+            Ok(child) => {
+                crate::say!("[+] {}\tStarted: {}", job.name, job.arguments.join(" "));
+                job.runs += 1;
+                job.last_started = Some(started.format("%Y-%m-%d %H:%M:%S").to_string());
+                job.last_exit_code = None;
+                job.last_output = Some(output.to_string_lossy().into_owned());
+                self.running.push((job.name.to_owned(), child));
+            }
+            Err(e) => error!("Unable to start {}: {}", job.name, e),
+        }
+    }
+
+    fn start_pending(&mut self) {
+        let mut started = false;
+        while self.running.len() < self.config.max_jobs && !self.queue.pending.is_empty() {
+            let name = self.queue.pending.remove(0);
+            self.start(&name);
+            started = true;
+        }
+
+        if started {
+            self.queue.save(&self.config.queue);
+        }
+    }
+
+    pub fn status(&self) -> String {
+        serde_json::to_string(&DaemonStatus {
+            jobs: &self.queue.jobs,
+            pending: &self.queue.pending,
+            running: self.running.iter().map(|(n, _)| n.as_str()).collect(),
+        })
+        .unwrap()
+    }
+
+    /// Requests need the bearer token, and POST requests a JSON body so that a web page cannot
+    /// send them without a CORS preflight
+    pub fn authorize(
+        &self,
+        method: &Method,
+        headers: &HeaderMap,
+    ) -> Result<(), (StatusCode, String)> {
+        let token = headers
+            .get(AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .unwrap_or("");
+        if !same_token(token, &self.config.token) {
+            return Err((StatusCode::UNAUTHORIZED, error_body("invalid token")));
+        }
+
+        let json = headers
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.split(';').next())
+            .is_some_and(|v| v.trim().eq_ignore_ascii_case("application/json"));
+        if method == Method::POST && !json {
+            return Err((
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                error_body("the content type must be application/json"),
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn handle(&mut self, method: &Method, path: &str, body: &[u8]) -> (StatusCode, String) {
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        match (method, segments.as_slice()) {
+            (&Method::GET, ["jobs"]) => (StatusCode::OK, self.status()),
+            (&Method::POST, ["jobs"]) => {
+                let job: DaemonJob = match serde_json::from_slice(body) {
+                    Ok(v) => v,
+                    Err(e) => return (StatusCode::BAD_REQUEST, error_body(&e.to_string())),
+                };
+                if let Err(e) = validate_job(&job, &self.config.wordlists_dir) {
+                    return (StatusCode::BAD_REQUEST, error_body(&e));
+                }
+
+                let job = DaemonJob {
+                    runs: 0,
+                    last_started: None,
+                    last_exit_code: None,
+                    last_output: None,
+                    ..job
+                };
+                match self.queue.jobs.iter_mut().find(|j| j.name == job.name) {
+                    Some(existing) => {
+                        existing.arguments = job.arguments;
+                        existing.schedule = job.schedule;
+                    }
+                    None => self.queue.jobs.push(job),
+                }
+                self.queue.save(&self.config.queue);
+                (StatusCode::OK, self.status())
+            }
+            (&Method::DELETE, ["jobs", name]) => {
+                let before = self.queue.jobs.len();
+                self.queue.jobs.retain(|j| &j.name != name);
+                self.queue.pending.retain(|p| p != name);
+                if self.queue.jobs.len() == before {
+                    return (StatusCode::NOT_FOUND, error_body("unknown job"));
+                }
+
+                self.queue.save(&self.config.queue);
+                (StatusCode::OK, self.status())
+            }
+            (&Method::POST, ["jobs", name, "run"]) => {
+                if !self.enqueue(name) {
+                    return (StatusCode::NOT_FOUND, error_body("unknown job"));
+                }
+
+                (StatusCode::ACCEPTED, self.status())
+            }
+            _ => (StatusCode::NOT_FOUND, error_body("unknown endpoint")),
+        }
+    }
+}
+
+fn error_body(message: &str) -> String {
+    serde_json::json!({ "error": message }).to_string()
+}
+
+/// Compares in constant time, the time taken tells nothing about the expected token
+fn same_token(token: &str, expected: &str) -> bool {
+    token.len() == expected.len()
+        && !expected.is_empty()
+        && token
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+/// A token from the OS random generator, None where there is no /dev/urandom
+pub fn generate_token() -> Option<String> {
+    let mut bytes = [0u8; 24];
+    File::open("/dev/urandom")
+        .and_then(|mut f| f.read_exact(&mut bytes))
+        .ok()?;
+
+    Some(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+fn serve(daemon: Arc<Mutex<Daemon>>, listen: SocketAddr) {
+    let new_service = move || {
+        let daemon = daemon.clone();
+        service_fn(move |req: Request<Body>| {
+            let daemon = daemon.clone();
+            let (parts, body) = req.into_parts();
+            let refused = daemon
+                .lock()
+                .unwrap()
+                .authorize(&parts.method, &parts.headers)
+                .err();
+            let path = parts.uri.path().to_owned();
+            body.concat2().map(move |body| {
+                let (status, body) = match refused {
+                    Some(refused) => refused,
+                    None => daemon
+                        .lock()
+                        .unwrap()
+                        .handle(&parts.method, &path, &body[..]),
+                };
+                Response::builder()
+                    .status(status)
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(body))
+                    .unwrap()
+            })
+        })
+    };
+
+    let server = Server::bind(&listen)
+        .serve(new_service)
+        .map_err(|e| error!("Control API: {}", e));
+    rt::run(server);
+}
+
+pub fn run(config: DaemonConfig) {
+    let queue = match JobQueue::load(&config.queue) {
+        Some(v) => v,
+        None => return,
+    };
+    let listen = config.listen;
+    let daemon = Daemon::new(config, queue);
+    crate::say!(
+        "[+] Daemon\t: {} jobs, {} pending, control API on http://{}/jobs",
+        daemon.queue.jobs.len(),
+        daemon.queue.pending.len(),
+        listen
+    );
+    let daemon = Arc::new(Mutex::new(daemon));
+    let api = daemon.clone();
+    thread::spawn(move || serve(api, listen));
+
+    let mut last_minute = String::new();
+    loop {
+        let now = Local::now();
+        let minute = now.format("%Y%m%d%H%M").to_string();
+        {
+            let mut daemon = daemon.lock().unwrap();
+            if minute != last_minute {
+                daemon.schedule(&now);
+                last_minute = minute;
+            }
+            daemon.reap();
+            daemon.start_pending();
+        }
+
+        thread::sleep(TICK);
+    }
+}
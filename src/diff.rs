@@ -0,0 +1,180 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::{collections::HashSet, fs};
+
+#[derive(Debug, Clone, Default)]
+pub struct ResultsDiff {
+    pub added: Vec<Value>,
+    pub removed: Vec<Value>,
+    pub changed: Vec<(Value, Value)>,
+}
+
+fn field(entry: &Value, name: &str) -> String {
+    match entry.get(name) {
+        Some(Value::String(v)) => v.to_owned(),
+        Some(Value::Null) | None => "".to_owned(),
+        Some(v) => v.to_string(),
+    }
+}
+
+pub fn result_key(entry: &Value) -> String {
+    match entry.get("request") {
+        // tilde results don't have a single url, the request identifies them
+        Some(request) => format!(
+            "{}{}~{}.{}",
+            field(request, "url"),
+            field(request, "filename"),
+            field(request, "duplicate_index"),
+            field(request, "extension")
+        ),
+        None => format!(
+            "{} {}{}{} {}",
+            field(entry, "method"),
+            field(entry, "url"),
+            field(entry, "vhost"),
+            field(entry, "domain"),
+            field(entry, "payload")
+        ),
+    }
+}
+
+fn result_value(entry: &Value) -> String {
+    format!(
+        "{} {} {}",
+        field(entry, "status"),
+        field(entry, "extra"),
+        field(entry, "kind")
+    )
+}
+
+pub fn describe(entry: &Value) -> String {
+    match entry.get("request") {
+        Some(request) => format!(
+            "{}\t{}~{}.{}",
+            field(entry, "kind"),
+            field(request, "filename"),
+            field(request, "duplicate_index"),
+            field(request, "extension")
+        ),
+        None => format!(
+            "{}\t{}\t{}{}{} {}",
+            field(entry, "method"),
+            field(entry, "status"),
+            field(entry, "url"),
+            field(entry, "vhost"),
+            field(entry, "domain"),
+            field(entry, "payload")
+        )
+        .trim()
+        .to_owned(),
+    }
+}
+
+pub fn diff_results(old: &[Value], new: &[Value]) -> ResultsDiff {
+    let mut diff = ResultsDiff::default();
+    for entry in new.iter() {
+        let key = result_key(entry);
+        match old.iter().find(|e| result_key(e) == key) {
+            None => diff.added.push(entry.clone()),
+            Some(previous) => {
+                if result_value(previous) != result_value(entry) {
+                    diff.changed.push((previous.clone(), entry.clone()));
+                }
+            }
+        }
+    }
+
+    for entry in old.iter() {
+        let key = result_key(entry);
+        if !new.iter().any(|e| result_key(e) == key) {
+            diff.removed.push(entry.clone());
+        }
+    }
+
+    diff
+}
+
+pub fn load_results(path: &str) -> Option<Vec<Value>> {
+    let content = match fs::read_to_string(path) {
+        Ok(v) => v,
+        Err(e) => {
+            error!("Error while reading file: {}\n{}", path, e);
+            return None;
+        }
+    };
+
+    match serde_json::from_str::<Vec<Value>>(&content) {
+        Ok(v) => Some(v),
+        Err(e) => {
+            error!("Error while parsing results file: {}\n{}", path, e);
+            None
+        }
+    }
+}
+
+pub fn run(old_path: &str, new_path: &str) {
+    let (old, new) = match (load_results(old_path), load_results(new_path)) {
+        (Some(old), Some(new)) => (old, new),
+        _ => return,
+    };
+
+    let diff = diff_results(&old, &new);
+    for entry in diff.added.iter() {
+        println!("+\t{}", describe(entry));
+    }
+
+    for entry in diff.removed.iter() {
+        println!("-\t{}", describe(entry));
+    }
+
+    for (previous, current) in diff.changed.iter() {
+        println!("~\t{}\n\t\t=> {}", describe(previous), describe(current));
+    }
+
+    println!(
+        "\n[+] Added: {} | Removed: {} | Changed: {}",
+        diff.added.len(),
+        diff.removed.len(),
+        diff.changed.len()
+    );
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct KnownResults {
+    keys: HashSet<String>,
+    pub skipped: usize,
+}
+
+impl KnownResults {
+    pub fn load(path: &str) -> Self {
+        if path.is_empty() {
+            return KnownResults::default();
+        }
+
+        KnownResults {
+            keys: load_results(path)
+                .unwrap_or_default()
+                .iter()
+                .map(result_key)
+                .collect(),
+            skipped: 0,
+        }
+    }
+
+    pub fn is_known<T: Serialize>(&mut self, res: &T) -> bool {
+        if self.keys.is_empty() {
+            return false;
+        }
+
+        let known = match serde_json::to_value(res) {
+            Ok(v) => self.keys.contains(&result_key(&v)),
+            Err(_) => false,
+        };
+
+        if known {
+            self.skipped += 1;
+        }
+
+        known
+    }
+}
@@ -0,0 +1,202 @@
+use futures::{future, Future};
+use hyper::{rt, Client};
+use hyper_tls::HttpsConnector;
+use md5::Md5;
+use sha2::{Digest, Sha256};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::proxy::ProxyConnector;
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DigestChallenge {
+    pub realm: String,
+    pub nonce: String,
+    pub opaque: Option<String>,
+    pub qop: Option<String>,
+    pub algorithm: String,
+}
+
+pub fn parse_challenge(header: &str) -> Option<DigestChallenge> {
+    let header = header.trim();
+    if header.len() < 7 || !header[..7].eq_ignore_ascii_case("digest ") {
+        return None;
+    }
+
+    let mut params: Vec<(String, String)> = Vec::new();
+    let mut rest = &header[7..];
+    while let Some(eq) = rest.find('=') {
+        let name = rest[..eq]
+            .trim()
+            .trim_start_matches(',')
+            .trim()
+            .to_lowercase();
+        rest = rest[eq + 1..].trim_start();
+        let value = if rest.starts_with('"') {
+            let end = rest[1..].find('"').map(|i| i + 1).unwrap_or(rest.len());
+            let value = rest[1..end].to_owned();
+            rest = rest.get(end + 1..).unwrap_or("");
+            value
+        } else {
+            let end = rest.find(',').unwrap_or(rest.len());
+            let value = rest[..end].trim().to_owned();
+            rest = &rest[end..];
+            value
+        };
+        params.push((name, value));
+    }
+
+    let param = |name: &str| {
+        params
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.to_owned())
+    };
+    let qop = param("qop").and_then(|qop| {
+        if qop.split(',').any(|q| q.trim() == "auth") {
+            Some("auth".to_owned())
+        } else {
+            None
+        }
+    });
+
+    Some(DigestChallenge {
+        realm: param("realm").unwrap_or_default(),
+        nonce: param("nonce")?,
+        opaque: param("opaque"),
+        qop,
+        algorithm: param("algorithm").unwrap_or_else(|| "MD5".to_owned()),
+    })
+}
+
+#[derive(Debug)]
+pub struct DigestAuth {
+    username: String,
+    password: String,
+    challenge: Mutex<Option<DigestChallenge>>,
+    nc: AtomicUsize,
+}
+
+impl DigestAuth {
+    pub fn new(username: &str, password: &str) -> Self {
+        DigestAuth {
+            username: username.to_owned(),
+            password: password.to_owned(),
+            challenge: Mutex::new(None),
+            nc: AtomicUsize::new(0),
+        }
+    }
+
+    fn hash(algorithm: &str, data: &str) -> String {
+        if algorithm.to_uppercase().starts_with("SHA-256") {
+            to_hex(&Sha256::digest(data.as_bytes()))
+        } else {
+            to_hex(&Md5::digest(data.as_bytes()))
+        }
+    }
+
+    pub fn observe(&self, headers: &hyper::HeaderMap) {
+        let challenge = headers
+            .get_all("WWW-Authenticate")
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .find_map(parse_challenge);
+        if let Some(challenge) = challenge {
+            let mut current = self.challenge.lock().unwrap();
+            // the nonce count restarts with every nonce, stale ones included
+            if current.as_ref().map(|c| &c.nonce) != Some(&challenge.nonce) {
+                self.nc.store(0, Ordering::SeqCst);
+            }
+            *current = Some(challenge);
+        }
+    }
+
+    pub fn authorization_with_cnonce(
+        &self,
+        method: &str,
+        uri: &str,
+        cnonce: &str,
+    ) -> Option<String> {
+        let challenge = self.challenge.lock().unwrap().clone()?;
+        let algorithm = &challenge.algorithm;
+        let mut ha1 = Self::hash(
+            algorithm,
+            &format!("{}:{}:{}", self.username, challenge.realm, self.password),
+        );
+        if algorithm.to_lowercase().ends_with("-sess") {
+            ha1 = Self::hash(
+                algorithm,
+                &format!("{}:{}:{}", ha1, challenge.nonce, cnonce),
+            );
+        }
+        let ha2 = Self::hash(algorithm, &format!("{}:{}", method, uri));
+
+        let mut header = format!(
+            "Digest username=\"{}\", realm=\"{}\", nonce=\"{}\", uri=\"{}\", algorithm={}",
+            self.username, challenge.realm, challenge.nonce, uri, algorithm
+        );
+        let response = match &challenge.qop {
+            Some(qop) => {
+                let nc = format!("{:08x}", self.nc.fetch_add(1, Ordering::SeqCst) + 1);
+                header.push_str(&format!(", qop={}, nc={}, cnonce=\"{}\"", qop, nc, cnonce));
+                Self::hash(
+                    algorithm,
+                    &format!(
+                        "{}:{}:{}:{}:{}:{}",
+                        ha1, challenge.nonce, nc, cnonce, qop, ha2
+                    ),
+                )
+            }
+            None => Self::hash(algorithm, &format!("{}:{}:{}", ha1, challenge.nonce, ha2)),
+        };
+        header.push_str(&format!(", response=\"{}\"", response));
+        if let Some(opaque) = &challenge.opaque {
+            header.push_str(&format!(", opaque=\"{}\"", opaque));
+        }
+
+        Some(header)
+    }
+
+    pub fn authorization(&self, method: &str, uri: &hyper::Uri) -> Option<String> {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let cnonce = to_hex(&Md5::digest(format!("{}", nanos).as_bytes()));
+        let uri = uri.path_and_query().map(|p| p.as_str()).unwrap_or("/");
+        self.authorization_with_cnonce(method, uri, &cnonce[..16])
+    }
+
+    /// Takes a dedicated client, it's dropped with the request so its idle connection doesn't
+    /// keep the runtime running
+    pub fn handshake(&self, client: Client<HttpsConnector<ProxyConnector>>, url: &str) {
+        let uri = match url.parse::<hyper::Uri>() {
+            Ok(v) => v,
+            Err(_) => return,
+        };
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        rt::run(
+            future::lazy(move || client.get(uri))
+                .map(move |res| tx.send(res.headers().clone()).unwrap_or(()))
+                .map_err(|e| warn!("Digest handshake failed: {}", e)),
+        );
+
+        if let Ok(headers) = rx.try_recv() {
+            self.observe(&headers);
+        }
+
+        if self.challenge.lock().unwrap().is_none() {
+            warn!("The target didn't send a Digest challenge, requests won't be authenticated");
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use regex::Regex;
+use std::collections::HashSet;
+
+const MAX_WORDS: usize = 2000;
+
+fn is_word(word: &str) -> bool {
+    (2..=64).contains(&word.len())
+        && !word.chars().all(|c| c.is_ascii_digit())
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_' || c == '-' || c == '~')
+}
+
+/// The name, its stem without the extension, and the parts of a snake or kebab case stem
+fn push_name(words: &mut Vec<String>, name: &str) {
+    words.push(name.to_owned());
+    let stem = match name.rfind('.') {
+        Some(i) if i > 0 => {
+            words.push(name[..i].to_owned());
+            &name[..i]
+        }
+        _ => name,
+    };
+
+    let parts: Vec<&str> = stem.split(&['-', '_'][..]).collect();
+    if parts.len() > 1 {
+        words.extend(parts.iter().map(|part| (*part).to_owned()));
+    }
+}
+
+fn link_path<'a>(link: &'a str, host: &str) -> Option<&'a str> {
+    let link = link.trim().split(&['#', '?'][..]).next()?;
+    let rest = match link.find("//") {
+        Some(i) if i == 0 || link[..i].ends_with(':') => &link[i + 2..],
+        _ => return Some(link),
+    };
+    if !link.starts_with("//") && !link.starts_with("http") {
+        return None;
+    }
+
+    // links to other hosts tell nothing about the target
+    let (authority, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, ""),
+    };
+    let authority_host = authority.rsplit('@').next()?.split(':').next()?;
+    if authority_host.eq_ignore_ascii_case(host) {
+        Some(path)
+    } else {
+        None
+    }
+}
+
+/// The words of a response: the path segments of its links and script strings, and its form fields
+pub fn tokens(body: &str, host: &str) -> Vec<String> {
+    let link_re = Regex::new(r#"(?i)(?:href|src|action)\s*=\s*["']?([^"'\s>]+)"#).unwrap();
+    let path_re = Regex::new(r#"["'`](/[\w.~%/-]+)["'`]"#).unwrap();
+    let field_re = Regex::new(
+        r#"(?i)<(?:input|select|textarea|button|form)\b[^>]*?\b(?:name|id)\s*=\s*["']?([^"'\s>]+)"#,
+    )
+    .unwrap();
+
+    let mut words = Vec::new();
+    let links = link_re
+        .captures_iter(body)
+        .chain(path_re.captures_iter(body))
+        .filter_map(|caps| caps.get(1))
+        .filter(|link| !link.as_str().contains(':') || link.as_str().starts_with("http"));
+    for link in links {
+        let path = match link_path(link.as_str(), host) {
+            Some(v) => v,
+            None => continue,
+        };
+        for segment in path.split('/').filter(|s| *s != "." && *s != "..") {
+            push_name(&mut words, segment);
+        }
+    }
+
+    for caps in field_re.captures_iter(body) {
+        for name in caps[1].split(|c: char| !c.is_ascii_alphanumeric() && c != '_' && c != '-') {
+            push_name(&mut words, name);
+        }
+    }
+
+    words.retain(|word| is_word(word));
+    words
+}
+
+/// Remembers the words already scanned, only the new ones are fed back to the scan
+pub struct Learner {
+    host: String,
+    known: HashSet<String>,
+    learned: usize,
+}
+
+impl Learner {
+    pub fn new(wordlist: &str, host: &str) -> Self {
+        Learner {
+            host: host.to_owned(),
+            known: wordlist.lines().map(|w| w.to_owned()).collect(),
+            learned: 0,
+        }
+    }
+
+    pub fn learn(&mut self, body: &str) -> Vec<String> {
+        let mut words = Vec::new();
+        for word in tokens(body, &self.host) {
+            if self.learned >= MAX_WORDS {
+                debug!("{} words learned, the next ones are ignored", MAX_WORDS);
+                break;
+            }
+            if self.known.insert(word.to_owned()) {
+                self.learned += 1;
+                words.push(word);
+            }
+        }
+
+        words
+    }
+
+    pub fn learned(&self) -> usize {
+        self.learned
+    }
+}
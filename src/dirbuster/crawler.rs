@@ -0,0 +1,214 @@
+use futures::Stream;
+use hyper::{
+    rt::{self, Future},
+    Body, Client, Request, Uri,
+};
+use hyper_tls::HttpsConnector;
+use regex::Regex;
+use std::sync::{Arc, Mutex};
+
+use super::{build_client, utils::is_skipped, DirConfig};
+
+use crate::proxy::ProxyConnector;
+
+const MAX_DEPTH: usize = 3;
+const MAX_PAGES: usize = 200;
+const STATIC_EXTENSIONS: &[&str] = &[
+    "css", "js", "png", "jpg", "jpeg", "gif", "svg", "ico", "woff", "woff2", "ttf", "eot", "pdf",
+    "zip", "mp3", "mp4",
+];
+
+fn normalize_path(path: &str) -> String {
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => (),
+            ".." => {
+                segments.pop();
+            }
+            s => segments.push(s),
+        }
+    }
+
+    let mut normalized = format!("/{}", segments.join("/"));
+    if path.ends_with('/') && !segments.is_empty() {
+        normalized.push('/');
+    }
+
+    normalized
+}
+
+pub fn resolve_link(page: &Uri, link: &str) -> Option<Uri> {
+    let link = link.trim().split(&['#', '?'][..]).next()?;
+    let scheme = page.scheme_part()?.as_str();
+    let authority = page.authority_part()?.as_str();
+    let (link_authority, path) = if link.starts_with("//") {
+        let uri = format!("{}:{}", scheme, link).parse::<Uri>().ok()?;
+        (
+            uri.authority_part()?.as_str().to_owned(),
+            uri.path().to_owned(),
+        )
+    } else if link.starts_with('/') {
+        (authority.to_owned(), link.to_owned())
+    } else if link.contains(':') {
+        let uri = link.parse::<Uri>().ok()?;
+        match uri.scheme_part().map(|s| s.as_str()) {
+            Some("http") | Some("https") => (),
+            _ => return None,
+        }
+
+        (
+            uri.authority_part()?.as_str().to_owned(),
+            uri.path().to_owned(),
+        )
+    } else if link.is_empty() {
+        return None;
+    } else {
+        let base = page.path();
+        let dir = &base[..base.rfind('/').map(|i| i + 1).unwrap_or(0)];
+        (authority.to_owned(), format!("{}{}", dir, link))
+    };
+
+    if link_authority != authority {
+        return None;
+    }
+
+    format!("{}://{}{}", scheme, authority, normalize_path(&path))
+        .parse::<Uri>()
+        .ok()
+}
+
+pub fn parent_directories(path: &str) -> Vec<String> {
+    path.match_indices('/')
+        .skip(1)
+        .map(|(i, _)| path[..=i].to_owned())
+        .collect()
+}
+
+fn is_static(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or("");
+    match name.rfind('.') {
+        Some(i) => STATIC_EXTENSIONS.contains(&name[i + 1..].to_lowercase().as_str()),
+        None => false,
+    }
+}
+
+fn fetch_page(
+    client: &Client<HttpsConnector<ProxyConnector>>,
+    url: Uri,
+    config: &DirConfig,
+) -> impl Future<Item = Option<(Uri, String)>, Error = ()> {
+    let mut request_builder = Request::builder();
+
+    for header_tuple in &config.http_headers {
+        request_builder.header(header_tuple.0.as_str(), header_tuple.1.as_str());
+    }
+
+    let request = request_builder
+        .header("User-Agent", &config.user_agent[..])
+        .uri(&url)
+        .header("Host", url.host().unwrap())
+        .body(Body::empty())
+        .expect("Request builder");
+
+    client
+        .request(request)
+        .and_then(|res| res.into_body().concat2())
+        .map(move |body| Some((url, String::from_utf8_lossy(&body).into_owned())))
+        .or_else(|e| {
+            debug!("Crawler: {}", e);
+            Ok(None)
+        })
+}
+
+pub fn crawl(url: &str, config: &DirConfig, skip_dirs: &[Regex]) -> Vec<String> {
+    let start = match url.parse::<Uri>() {
+        Ok(v) => v,
+        Err(e) => {
+            debug!("Crawler: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let scheme = start.scheme_part().map(|s| s.as_str()).unwrap_or("http");
+    let authority = start
+        .authority_part()
+        .map(|a| a.as_str())
+        .unwrap_or("")
+        .to_owned();
+    let base_path = start.path();
+    let base = format!(
+        "{}://{}{}",
+        scheme,
+        authority,
+        &base_path[..=base_path.rfind('/').unwrap_or(0)]
+    );
+    let link_regex = Regex::new(r#"(?i)(?:href|src|action)\s*=\s*["']?([^"'\s>]+)"#).unwrap();
+    let mut visited: Vec<String> = vec![start.to_string()];
+    let mut directories: Vec<String> = Vec::new();
+    let mut queue = vec![start.clone()];
+
+    for depth in 0..MAX_DEPTH {
+        if queue.is_empty() {
+            break;
+        }
+
+        debug!("Crawling {} pages at depth {}", queue.len(), depth);
+        let pages = Arc::new(Mutex::new(Vec::<(Uri, String)>::new()));
+        let pages_writer = pages.clone();
+        // a client per depth, the runtime only stops once its idle connections are dropped
+        let client = build_client(config);
+        let config = config.clone();
+        let n_threads = config.n_threads;
+        let stream = futures::stream::iter_ok(queue)
+            .map(move |url| fetch_page(&client, url, &config))
+            .buffer_unordered(n_threads)
+            .for_each(move |page| {
+                if let Some(page) = page {
+                    pages_writer.lock().unwrap().push(page);
+                }
+
+                Ok(())
+            });
+
+        rt::run(stream);
+
+        let mut next = Vec::new();
+        for (page, body) in pages.lock().unwrap().iter() {
+            for capture in link_regex.captures_iter(body) {
+                let link = match resolve_link(page, &capture[1]) {
+                    Some(v) => v,
+                    None => continue,
+                };
+
+                for directory in parent_directories(link.path()) {
+                    if is_skipped(&directory, skip_dirs) {
+                        continue;
+                    }
+
+                    let directory = format!("{}://{}{}", scheme, authority, directory);
+                    if directory.starts_with(&base)
+                        && directory != base
+                        && !directories.contains(&directory)
+                    {
+                        directories.push(directory);
+                    }
+                }
+
+                let link_string = link.to_string();
+                if visited.len() < MAX_PAGES
+                    && !is_static(link.path())
+                    && !is_skipped(link.path(), skip_dirs)
+                    && !visited.contains(&link_string)
+                {
+                    visited.push(link_string);
+                    next.push(link);
+                }
+            }
+        }
+
+        queue = next;
+    }
+
+    directories
+}
@@ -1,33 +1,162 @@
-use futures::Stream;
+use chrono::Local;
+use futures::{
+    future::{self, Either, Loop},
+    Stream,
+};
 use hyper::{
-    client::HttpConnector,
     rt::{self, Future},
     Body, Client, Method, Request, StatusCode, Uri,
 };
 use hyper_tls::{self, HttpsConnector};
 use native_tls;
-use std::sync::mpsc::Sender;
+use std::sync::{mpsc::Sender, Arc};
 
+pub mod adaptive;
+pub mod crawler;
+pub mod ports;
 pub mod result_processor;
+pub mod schemes;
+pub mod soft404;
 pub mod utils;
 
+use crate::{
+    correlation::{self, RequestTag},
+    digest::DigestAuth,
+    geoip::remote_ip,
+    har::{HarEntry, HarRequest, HarResponse},
+    negotiate::{self, Negotiate, SecurityContext},
+    output::capture_headers,
+    proxy::ProxyConnector,
+    ratelimit::Pacer,
+    scheduler::JobQueue,
+    session::{send, Session},
+    sigv4::{self, SigV4},
+    summary::HeaderAudit,
+    template::{expand, expand_bytes, word_from_uri},
+};
 use result_processor::SingleDirScanResult;
+use utils::{needs_range, slash_redirect};
 
 #[derive(Debug, Clone)]
 pub struct DirConfig {
     pub n_threads: usize,
     pub ignore_certificate: bool,
     pub http_method: String,
-    pub http_body: String,
+    pub http_body: Vec<u8>,
     pub user_agent: String,
     pub http_headers: Vec<(String, String)>,
+    pub save_har: bool,
+    /// Every body is buffered, otherwise only the successful ones are and the others are just
+    /// counted
+    pub read_body: bool,
+    pub tls_min_version: Option<native_tls::Protocol>,
+    pub tls_max_version: Option<native_tls::Protocol>,
+    pub digest: Option<(String, String)>,
+    pub negotiate: Option<Arc<Negotiate>>,
+    pub aws_sigv4: Option<Arc<SigV4>>,
+    pub capture_headers: Vec<String>,
+    pub header_audit: Option<Arc<HeaderAudit>>,
+    pub session: Option<Arc<Session>>,
+    pub range_threshold: u64,
+    pub range_size: usize,
+    pub request_tag: Option<Arc<RequestTag>>,
+}
+
+fn build_request(
+    url: &Uri,
+    config: &DirConfig,
+    digest: &Option<Arc<DigestAuth>>,
+    request_id: &Option<String>,
+) -> (Request<Body>, Vec<u8>, Option<SecurityContext>) {
+    let mut request_builder = Request::builder();
+    let word = word_from_uri(url);
+    let http_body = expand_bytes(&config.http_body, &word);
+
+    if let Some(request_id) = request_id {
+        request_builder.header(correlation::HEADER, request_id.as_str());
+    }
+
+    for header_tuple in &config.http_headers {
+        request_builder.header(
+            expand(&header_tuple.0, &word).as_str(),
+            expand(&header_tuple.1, &word).as_str(),
+        );
+    }
+
+    if let Some(authorization) = digest
+        .as_ref()
+        .and_then(|d| d.authorization(&config.http_method, url))
+    {
+        request_builder.header("Authorization", authorization.as_str());
+    }
+
+    let context = config
+        .negotiate
+        .as_ref()
+        .and_then(|n| n.authorization(url.host().unwrap()));
+    if let Some(context) = &context {
+        request_builder.header("Authorization", context.authorization().as_str());
+    }
+
+    let host = sigv4::host_header(url).unwrap();
+    if let Some(sigv4) = &config.aws_sigv4 {
+        for (name, value) in sigv4.sign(&config.http_method, &host, url, &http_body) {
+            request_builder.header(name.as_str(), value.as_str());
+        }
+    }
+
+    let request = request_builder
+        .header("User-Agent", &config.user_agent[..])
+        .method(&config.http_method[..])
+        .uri(url)
+        .header("Host", host.as_str())
+        .body(Body::from(http_body.clone()))
+        .expect("Request builder");
+
+    (request, http_body, context)
+}
+
+/// Buffers the body when something looks at it, otherwise drains it so the connection can be
+/// reused and only counts its bytes
+fn read_body(body: Body, keep: bool) -> impl Future<Item = (Vec<u8>, usize), Error = hyper::Error> {
+    if keep {
+        Either::A(body.concat2().map(|body| (body.to_vec(), body.len())))
+    } else {
+        Either::B(
+            body.fold(0, |length, chunk| {
+                Ok::<_, hyper::Error>(length + chunk.len())
+            })
+            .map(|length| (Vec::new(), length)),
+        )
+    }
+}
+
+fn read_prefix(body: Body, limit: usize) -> impl Future<Item = Vec<u8>, Error = hyper::Error> {
+    future::loop_fn((body, Vec::new()), move |(body, mut prefix)| {
+        body.into_future()
+            .map_err(|(e, _)| e)
+            .map(move |(chunk, body)| match chunk {
+                Some(chunk) => {
+                    prefix.extend_from_slice(&chunk);
+                    if prefix.len() >= limit {
+                        prefix.truncate(limit);
+                        Loop::Break(prefix)
+                    } else {
+                        Loop::Continue((body, prefix))
+                    }
+                }
+                None => Loop::Break(prefix),
+            })
+    })
 }
 
 fn make_request_future(
     tx: Sender<SingleDirScanResult>,
-    client: &Client<HttpsConnector<HttpConnector>>,
+    client: &Client<HttpsConnector<ProxyConnector>>,
     url: Uri,
-    config: &DirConfig,
+    config: &Arc<DirConfig>,
+    pacer: Arc<Pacer>,
+    digest: Option<Arc<DigestAuth>>,
 ) -> impl Future<Item = (), Error = ()> {
     let tx_err = tx.clone();
     let mut target = SingleDirScanResult {
@@ -36,27 +165,90 @@ fn make_request_future(
         status: StatusCode::default().to_string(),
         error: None,
         extra: None,
+        certificate: None,
+        captured_headers: Vec::new(),
+        severity: None,
+        range_supported: None,
+        request_id: config.request_tag.as_ref().map(|tag| tag.next()),
+        geo: None,
+        server_ip: None,
+        curl: None,
+        har_entry: None,
+        body: "".to_owned(),
+        length: 0,
     };
     let mut target_err = target.clone();
-    let mut request_builder = Request::builder();
-
-    for header_tuple in &config.http_headers {
-        request_builder.header(header_tuple.0.as_str(), header_tuple.1.as_str());
-    }
+    let request_id = target.request_id.clone();
+    let (request, http_body, context) = build_request(&url, config, &digest, &request_id);
+    let capture_names = config.capture_headers.clone();
+    let header_audit = config.header_audit.clone();
+    let mut har_request = if config.save_har {
+        Some(HarRequest::new(&request, &http_body))
+    } else {
+        None
+    };
+    let started = Local::now();
 
-    let request = request_builder
-        .header("User-Agent", &config.user_agent[..])
-        .method(&config.http_method[..])
-        .uri(&url)
-        .header("Host", url.host().unwrap())
-        .body(Body::from(config.http_body.clone()))
-        .expect("Request builder");
+    let client = client.clone();
+    let session = config.session.clone();
+    let config = config.clone();
+    let follow_digest = digest.clone();
+    let follow_pacer = pacer.clone();
+    let range_client = client.clone();
+    let range_session = session.clone();
+    let range_config = config.clone();
+    let range_digest = digest.clone();
+    let range_request_id = request_id.clone();
 
-    client
-        .request(request)
+    pacer
+        .wait()
+        .then({
+            let client = client.clone();
+            let session = session.clone();
+            move |_| send(&client, session, request, http_body)
+        })
         .and_then(move |res| {
+            let slashed = res
+                .headers()
+                .get("Location")
+                .and_then(|v| v.to_str().ok())
+                .filter(|_| res.status().is_redirection())
+                .and_then(|location| slash_redirect(&url, location));
+            let slashed = match slashed {
+                Some(v) => v,
+                None => return Either::A(futures::future::ok((url, res, har_request, context))),
+            };
+
+            debug!("Following the trailing slash redirect to {}", slashed);
+            negotiate::verify(context, res.headers());
+            let (request, http_body, context) =
+                build_request(&slashed, &config, &follow_digest, &request_id);
+            if har_request.is_some() {
+                har_request = Some(HarRequest::new(&request, &http_body));
+            }
+
+            Either::B(
+                follow_pacer
+                    .wait()
+                    .then(move |_| send(&client, session, request, http_body))
+                    .map(move |res| (slashed, res, har_request, context)),
+            )
+        })
+        .and_then(move |(url, res, har_request, context)| {
+            negotiate::verify(context, res.headers());
+            pacer.observe(res.headers());
+            pacer.observe_status(res.status());
+            if let Some(digest) = digest {
+                digest.observe(res.headers());
+            }
+            if let Some(header_audit) = header_audit {
+                header_audit.observe(&url, res.headers());
+            }
             let status = res.status();
+            target.url = url.to_string();
             target.status = status.to_string();
+            target.captured_headers = capture_headers(res.headers(), &capture_names);
+            target.server_ip = remote_ip(&res);
             if status.is_redirection() {
                 target.extra = Some(
                     res.headers()
@@ -66,8 +258,70 @@ fn make_request_future(
                         .unwrap()
                         .to_owned(),
                 );
+            } else if status == StatusCode::UNAUTHORIZED {
+                target.extra = res
+                    .headers()
+                    .get("WWW-Authenticate")
+                    .and_then(|v| v.to_str().ok())
+                    .map(|v| v.to_owned());
+            }
+
+            let (parts, body) = res.into_parts();
+            let length = match needs_range(&parts.headers, range_config.range_threshold) {
+                Some(v) if range_config.http_method == "GET" && status.is_success() => v,
+                _ => {
+                    // the severity rules look for directory listings in the successful bodies
+                    let keep = range_config.read_body || status.is_success();
+                    return Either::A(
+                        futures::future::ok((target, parts, har_request))
+                            .join(read_body(body, keep)),
+                    );
+                }
+            };
+
+            // only the beginning of big files is fetched, the rest of the body is never read
+            drop(body);
+            debug!(
+                "Requesting the first {} bytes of {}",
+                range_config.range_size, url
+            );
+            target.length = length as usize;
+            let (mut request, http_body, context) =
+                build_request(&url, &range_config, &range_digest, &range_request_id);
+            request.headers_mut().insert(
+                "Range",
+                format!("bytes=0-{}", range_config.range_size.saturating_sub(1))
+                    .parse()
+                    .unwrap(),
+            );
+            // the HAR entry records the ranged exchange, not the response dropped above
+            let har_request = har_request.map(|_| HarRequest::new(&request, &http_body));
+            let range_size = range_config.range_size;
+            Either::B(
+                send(&range_client, range_session, request, http_body).and_then(move |res| {
+                    negotiate::verify(context, res.headers());
+                    target.range_supported = Some(res.status() == StatusCode::PARTIAL_CONTENT);
+                    let (parts, body) = res.into_parts();
+                    read_prefix(body, range_size).map(move |body| {
+                        let length = body.len();
+                        ((target, parts, har_request), (body, length))
+                    })
+                }),
+            )
+        })
+        .and_then(move |((mut target, parts, har_request), (body, length))| {
+            if let Some(har_request) = har_request {
+                target.har_entry = Some(HarEntry::new(
+                    started,
+                    har_request,
+                    HarResponse::new(&parts, &body),
+                ));
             }
 
+            target.body = String::from_utf8_lossy(&body).into_owned();
+            if target.range_supported.is_none() {
+                target.length = length;
+            }
             tx.send(target).unwrap();
             Ok(())
         })
@@ -78,20 +332,76 @@ fn make_request_future(
         })
 }
 
-pub fn run(tx: Sender<SingleDirScanResult>, urls: Vec<hyper::Uri>, config: DirConfig) {
+fn build_client(config: &DirConfig) -> Client<HttpsConnector<ProxyConnector>> {
     let mut tls_connector_builder = native_tls::TlsConnector::builder();
     tls_connector_builder.danger_accept_invalid_certs(config.ignore_certificate);
+    tls_connector_builder.min_protocol_version(config.tls_min_version);
+    tls_connector_builder.max_protocol_version(config.tls_max_version);
     let tls_connector = tls_connector_builder
         .build()
         .expect("TLS initialization failed");
-    let mut http_connector = HttpConnector::new(config.n_threads);
-    http_connector.enforce_http(false);
+    let http_connector = ProxyConnector::new(config.n_threads);
     let https_connector = HttpsConnector::from((http_connector, tls_connector));
-    let client = Client::builder().build(https_connector);
+    Client::builder().build(https_connector)
+}
+
+pub fn run_with_pacer(
+    tx: Sender<SingleDirScanResult>,
+    urls: Vec<hyper::Uri>,
+    config: DirConfig,
+    pacer: Arc<Pacer>,
+) {
+    let first = urls.first().cloned();
+    run_stream(tx, first, futures::stream::iter_ok(urls), config, pacer);
+}
+
+/// Scans the jobs of the queue in priority order until it is closed and drained
+pub fn run_queue(
+    tx: Sender<SingleDirScanResult>,
+    queue: JobQueue<hyper::Uri>,
+    config: DirConfig,
+    pacer: Arc<Pacer>,
+) {
+    let first = queue.peek();
+    run_stream(tx, first, queue, config, pacer);
+}
+
+fn run_stream<S>(
+    tx: Sender<SingleDirScanResult>,
+    first: Option<hyper::Uri>,
+    urls: S,
+    config: DirConfig,
+    pacer: Arc<Pacer>,
+) where
+    S: Stream<Item = hyper::Uri, Error = ()> + Send + 'static,
+{
+    let client = build_client(&config);
     let n_threads = config.n_threads;
+    let config = Arc::new(config);
+
+    let digest = config
+        .digest
+        .as_ref()
+        .map(|(username, password)| Arc::new(DigestAuth::new(username, password)));
+    if let (Some(digest), Some(url)) = (&digest, first) {
+        digest.handshake(build_client(&config), &url.to_string());
+    }
+
+    if let Some(session) = config.session.as_ref().filter(|s| s.generation() == 0) {
+        session.clone().handshake(build_client(&config));
+    }
 
-    let stream = futures::stream::iter_ok(urls)
-        .map(move |url| make_request_future(tx.clone(), &client, url, &config))
+    let stream = urls
+        .map(move |url| {
+            make_request_future(
+                tx.clone(),
+                &client,
+                url,
+                &config,
+                pacer.clone(),
+                digest.clone(),
+            )
+        })
         .buffer_unordered(n_threads)
         .for_each(Ok)
         .map_err(|err| eprintln!("Err {:?}", err));
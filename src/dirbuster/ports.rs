@@ -0,0 +1,69 @@
+use hyper::Uri;
+use std::collections::BTreeMap;
+
+use super::result_processor::SingleDirScanResult;
+
+/// The ports of a list like 80,443,8000-8100, in the given order without duplicates
+pub fn parse_ports(list: &str) -> Result<Vec<u16>, String> {
+    let mut ports = Vec::new();
+    for item in list
+        .split(',')
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+    {
+        let parse = |port: &str| match port.trim().parse::<u16>() {
+            Ok(0) | Err(_) => Err(format!("Invalid port: {}", port)),
+            Ok(v) => Ok(v),
+        };
+        let (first, last) = match item.find('-') {
+            Some(i) => (parse(&item[..i])?, parse(&item[i + 1..])?),
+            None => (parse(item)?, parse(item)?),
+        };
+        if first > last {
+            return Err(format!("Invalid port range: {}", item));
+        }
+
+        for port in first..=last {
+            if !ports.contains(&port) {
+                ports.push(port);
+            }
+        }
+    }
+
+    Ok(ports)
+}
+
+/// The URL on another port, 80 is always plain HTTP and 443 always HTTPS
+pub fn with_port(url: &str, port: u16) -> Option<String> {
+    let uri = url.parse::<Uri>().ok()?;
+    let scheme = match port {
+        80 => "http",
+        443 => "https",
+        _ => uri.scheme_str()?,
+    };
+    let path = uri.path_and_query().map(|p| p.as_str()).unwrap_or("/");
+
+    Some(format!("{}://{}:{}{}", scheme, uri.host()?, port, path))
+}
+
+fn port_of(url: &str) -> Option<u16> {
+    let uri = url.parse::<Uri>().ok()?;
+    uri.port_u16().or(match uri.scheme_str()? {
+        "https" => Some(443),
+        _ => Some(80),
+    })
+}
+
+pub fn report(results: &[SingleDirScanResult]) -> String {
+    let mut ports: BTreeMap<u16, usize> = BTreeMap::new();
+    for port in results.iter().filter_map(|result| port_of(&result.url)) {
+        *ports.entry(port).or_default() += 1;
+    }
+
+    let mut report = "\n[+] Results per port".to_owned();
+    for (port, count) in ports.iter() {
+        report.push_str(&format!("\n\t{}\t{}", port, count));
+    }
+
+    report
+}
@@ -1,4 +1,10 @@
 use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+
+use crate::{
+    certificate::CertificateInfo, geoip::GeoInfo, har::HarEntry, severity::Severity,
+    sink::ResultSink,
+};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SingleDirScanResult {
@@ -7,12 +13,38 @@ pub struct SingleDirScanResult {
     pub status: String,
     pub error: Option<String>,
     pub extra: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub certificate: Option<CertificateInfo>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub captured_headers: Vec<(String, String)>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub severity: Option<Severity>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub range_supported: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub request_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub geo: Option<GeoInfo>,
+    #[serde(skip)]
+    pub server_ip: Option<IpAddr>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub curl: Option<String>,
+    #[serde(skip)]
+    pub har_entry: Option<HarEntry>,
+    /// Only read when the result arrives, the stored results drop it
+    #[serde(skip)]
+    pub body: String,
+    #[serde(skip)]
+    pub length: usize,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ResultProcessorConfig {
     pub include: Vec<String>,
     pub ignore: Vec<String>,
+    pub include_body: Vec<String>,
+    pub ignore_body: Vec<String>,
+    pub rules: Vec<(String, Vec<String>)>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -28,9 +60,34 @@ impl ScanResult {
             config,
         }
     }
+}
 
-    pub fn maybe_add_result(&mut self, res: SingleDirScanResult) -> bool {
+impl ResultSink<SingleDirScanResult> for ScanResult {
+    fn accept(&self, res: &SingleDirScanResult) -> bool {
         trace!("{:?}", res);
+        for ignore in self.config.ignore_body.iter() {
+            if res.body.contains(ignore) {
+                return false;
+            }
+        }
+
+        if !self.config.include_body.is_empty()
+            && !self
+                .config
+                .include_body
+                .iter()
+                .any(|include| res.body.contains(include))
+        {
+            return false;
+        }
+
+        let path = res.url.split(&['?', '#'][..]).next().unwrap_or("");
+        for (extension, codes) in self.config.rules.iter() {
+            if path.ends_with(&format!(".{}", extension)) {
+                return codes.iter().any(|code| res.status.starts_with(code));
+            }
+        }
+
         let mut ignore = false;
         let mut include = false;
         for code in self.config.ignore.iter() {
@@ -47,11 +104,11 @@ impl ScanResult {
             }
         }
 
-        if !ignore && (self.config.include.is_empty() || include) {
-            self.results.push(res);
-            return true;
-        }
+        !ignore && (self.config.include.is_empty() || include)
+    }
 
-        false
+    fn store(&mut self, mut res: SingleDirScanResult) {
+        res.body = String::new();
+        self.results.push(res);
     }
 }
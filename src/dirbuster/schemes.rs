@@ -0,0 +1,117 @@
+use hyper::Uri;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The URL over the other scheme, the default port of a scheme is swapped for the other one
+pub fn other_scheme(url: &Uri) -> Option<Uri> {
+    let (scheme, default_port, other_port) = match url.scheme_str()? {
+        "http" => ("https", 80, 443),
+        "https" => ("http", 443, 80),
+        _ => return None,
+    };
+    let host = url.host()?;
+    let authority = match url.port_u16() {
+        Some(port) if port != default_port && port != other_port => format!("{}:{}", host, port),
+        _ => host.to_owned(),
+    };
+    let path = url.path_and_query().map(|p| p.as_str()).unwrap_or("/");
+
+    format!("{}://{}{}", scheme, authority, path).parse().ok()
+}
+
+/// Every URL followed by its twin over the other scheme
+pub fn with_both_schemes(urls: Vec<Uri>) -> Vec<Uri> {
+    let mut both = Vec::with_capacity(urls.len() * 2);
+    for url in urls {
+        let other = other_scheme(&url);
+        both.push(url);
+        both.extend(other);
+    }
+
+    both
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemeResponse {
+    pub status: String,
+    pub length: usize,
+    pub location: Option<String>,
+}
+
+/// The responses of both schemes for every path, to report the paths served differently
+#[derive(Debug, Default)]
+pub struct SchemeDiff {
+    responses: BTreeMap<String, (Option<SchemeResponse>, Option<SchemeResponse>)>,
+    kept: BTreeSet<String>,
+}
+
+fn split_url(url: &str) -> Option<(bool, String)> {
+    let uri = url.parse::<Uri>().ok()?;
+    let https = uri.scheme_str()? == "https";
+    let path = uri.path_and_query().map(|p| p.as_str()).unwrap_or("/");
+
+    Some((https, format!("{}{}", uri.host()?, path)))
+}
+
+impl SchemeDiff {
+    pub fn observe(&mut self, url: &str, response: SchemeResponse, kept: bool) {
+        let (https, key) = match split_url(url) {
+            Some(v) => v,
+            None => return,
+        };
+        if kept {
+            self.kept.insert(key.to_owned());
+        }
+
+        let entry = self.responses.entry(key).or_default();
+        if https {
+            entry.1 = Some(response);
+        } else {
+            entry.0 = Some(response);
+        }
+    }
+
+    /// The kept paths whose status differs between the schemes, the HTTP redirects to the same
+    /// path over HTTPS are expected
+    pub fn differences(&self) -> Vec<(&str, Option<&SchemeResponse>, Option<&SchemeResponse>)> {
+        let mut differences = Vec::new();
+        for key in self.kept.iter() {
+            let (http, https) = match self.responses.get(key) {
+                Some(v) => v,
+                None => continue,
+            };
+            let upgraded = format!("https://{}", key);
+            let same = match (http, https) {
+                (Some(http), Some(https)) => {
+                    http.status == https.status
+                        || (http.status.starts_with('3')
+                            && http.location.as_deref() == Some(upgraded.as_str()))
+                }
+                _ => false,
+            };
+            if !same {
+                differences.push((key.as_str(), http.as_ref(), https.as_ref()));
+            }
+        }
+
+        differences
+    }
+
+    pub fn report(&self) -> String {
+        let describe = |response: Option<&SchemeResponse>| match response {
+            Some(response) => format!("{} ({} bytes)", response.status, response.length),
+            None => "no response".to_owned(),
+        };
+
+        let mut report = "\n[+] Scheme differences".to_owned();
+        for (path, http, https) in self.differences() {
+            report.push_str(&format!(
+                "\n\t{}\thttp: {}\thttps: {}",
+                path,
+                describe(http),
+                describe(https)
+            ));
+        }
+
+        report
+    }
+}
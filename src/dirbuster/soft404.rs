@@ -0,0 +1,166 @@
+use std::{
+    collections::BTreeMap,
+    sync::{mpsc::channel, Arc},
+    thread,
+};
+
+use super::{
+    result_processor::{ResultProcessorConfig, ScanResult, SingleDirScanResult},
+    DirConfig,
+};
+use crate::{
+    ratelimit::Pacer,
+    sink::ResultSink,
+    template::{expand, word_from_uri},
+};
+
+/// Dynamic parts like dates or tokens keep the words but move the length a little, 2% of it or
+/// this many bytes
+const LENGTH_TOLERANCE: usize = 16;
+
+/// What a host answers for a path that does not exist, the path is removed from the body and the
+/// location since servers often reflect it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Soft404 {
+    pub status: String,
+    pub length: usize,
+    pub words: usize,
+    pub location: Option<String>,
+}
+
+fn host_of(url: &str) -> String {
+    url.parse::<hyper::Uri>()
+        .ok()
+        .and_then(|uri| uri.authority_part().map(|a| a.as_str().to_lowercase()))
+        .unwrap_or_default()
+}
+
+fn strip_word(value: &str, word: &str) -> String {
+    if word.is_empty() {
+        value.to_owned()
+    } else {
+        value.replace(word, "")
+    }
+}
+
+impl Soft404 {
+    pub fn new(result: &SingleDirScanResult) -> Self {
+        let word = result
+            .url
+            .parse::<hyper::Uri>()
+            .map(|uri| word_from_uri(&uri))
+            .unwrap_or_default();
+        let body = strip_word(&result.body, &word);
+        Soft404 {
+            status: result.status.to_owned(),
+            length: body.len(),
+            words: body.split_whitespace().count(),
+            location: result.extra.as_ref().map(|l| strip_word(l, &word)),
+        }
+    }
+
+    /// Same status, location and amount of words, and about the same length
+    pub fn matches(&self, result: &SingleDirScanResult) -> bool {
+        let other = Soft404::new(result);
+        let tolerance = LENGTH_TOLERANCE.max(self.length / 50);
+        self.status == other.status
+            && self.location == other.location
+            && self.words == other.words
+            && self.length.abs_diff(other.length) <= tolerance
+    }
+}
+
+/// The soft-404 of every host, so that what is learned on one host never hides the results of
+/// another
+#[derive(Debug, Clone, Default)]
+pub struct Soft404s {
+    by_host: BTreeMap<String, Soft404>,
+}
+
+impl Soft404s {
+    pub fn learn(&mut self, probe: &SingleDirScanResult) {
+        self.by_host
+            .insert(host_of(&probe.url), Soft404::new(probe));
+    }
+
+    pub fn matches(&self, result: &SingleDirScanResult) -> bool {
+        self.by_host
+            .get(&host_of(&result.url))
+            .is_some_and(|soft404| soft404.matches(result))
+    }
+
+    /// The banners of the hosts whose missing paths do not answer 404
+    pub fn banners(&self) -> Vec<String> {
+        self.by_host
+            .iter()
+            .filter(|(_, soft404)| !soft404.status.starts_with("404"))
+            .map(|(host, soft404)| crate::banner::soft404(host, &soft404.status, soft404.length))
+            .collect()
+    }
+}
+
+/// Requests the probe URLs and learns the soft-404 of their hosts
+pub fn probe(probes: Vec<hyper::Uri>, config: DirConfig, pacer: Arc<Pacer>) -> Soft404s {
+    let (tx, rx) = channel::<SingleDirScanResult>();
+    thread::spawn(move || super::run_with_pacer(tx, probes, config, pacer));
+    let mut soft404s = Soft404s::default();
+    for msg in rx.into_iter().filter(|msg| msg.error.is_none()) {
+        soft404s.learn(&msg);
+    }
+
+    soft404s
+}
+
+/// A random path under the base URL, its response is the soft-404 of the host
+pub fn probe_url(base_url: &str) -> Option<hyper::Uri> {
+    format!("{}{}", base_url, expand("{{rand(16)}}", ""))
+        .parse()
+        .ok()
+}
+
+/// The soft-404 and the results of every host
+#[derive(Debug, Clone)]
+pub struct HostFilters {
+    filter: ScanResult,
+    soft404s: Soft404s,
+    results: BTreeMap<String, Vec<SingleDirScanResult>>,
+}
+
+impl HostFilters {
+    pub fn new(config: ResultProcessorConfig) -> Self {
+        HostFilters {
+            filter: ScanResult::new(config),
+            soft404s: Soft404s::default(),
+            results: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_soft404s(mut self, soft404s: Soft404s) -> Self {
+        self.soft404s = soft404s;
+        self
+    }
+
+    /// The results of every host, grouped by host
+    pub fn into_results(self) -> Vec<SingleDirScanResult> {
+        self.results.into_values().flatten().collect()
+    }
+}
+
+impl ResultSink<SingleDirScanResult> for HostFilters {
+    fn accept(&self, result: &SingleDirScanResult) -> bool {
+        if self.soft404s.matches(result) {
+            trace!("Soft-404: {}", result.url);
+            return false;
+        }
+
+        self.filter.accept(result)
+    }
+
+    fn store(&mut self, mut result: SingleDirScanResult) {
+        result.body = String::new();
+        self.results
+            .entry(host_of(&result.url))
+            .or_default()
+            .push(result);
+    }
+}
@@ -1,43 +1,64 @@
-use std::{fs, fs::File, io::Write, path::Path, str};
+use std::{fs::File, io::Write, path::Path, str};
 
-use super::result_processor::SingleDirScanResult;
+use regex::Regex;
 
-pub fn build_urls(
-    wordlist_path: &str,
-    url: &str,
-    extensions: Vec<String>,
-    append_slash: bool,
-) -> Vec<hyper::Uri> {
-    debug!("building urls");
-    let mut urls: Vec<hyper::Uri> = Vec::new();
-    let wordlist =
-        fs::read_to_string(wordlist_path).expect("Something went wrong reading the wordlist file");
-    let urls_iter = wordlist
-        .lines()
-        .filter(|word| !word.starts_with('#') && !word.starts_with(' '))
-        .map(|word| {
-            if url.ends_with("/") {
-                format!("{}{}", url, word)
-            } else {
-                format!("{}/{}", url, word)
+use super::{
+    crawler::{parent_directories, resolve_link},
+    result_processor::SingleDirScanResult,
+};
+use crate::{output::merge_results, rawpath::escape_raw_path, template::expand, wordlist};
+
+const MUTATIONS: &[&str] = &["%00", ";.js", ".", "::$DATA", "%20"];
+
+fn push_mutations(urls: &mut Vec<hyper::Uri>, url: &str) {
+    for mutation in MUTATIONS.iter() {
+        match format!("{}{}", url, mutation).parse::<hyper::Uri>() {
+            Ok(v) => {
+                urls.push(v);
             }
-        });
-
-    for url in urls_iter {
-        if append_slash {
-            if !url.ends_with("/") {
-                match format!("{}/", url).parse::<hyper::Uri>() {
-                    Ok(v) => {
-                        urls.push(v);
-                    }
-                    Err(e) => {
-                        trace!("URI: {}", e);
-                    }
-                }
+            Err(e) => {
+                trace!("URI: {}", e);
             }
         }
+    }
+}
+
+fn push_backslash(urls: &mut Vec<hyper::Uri>, url: &str) {
+    let authority_start = url.find("://").map(|i| i + 3).unwrap_or(0);
+    let path_start = match url[authority_start..].find('/') {
+        Some(i) => authority_start + i + 1,
+        None => return,
+    };
+
+    if !url[path_start..].contains('/') {
+        return;
+    }
 
-        match url.parse::<hyper::Uri>() {
+    let (head, path) = url.split_at(path_start);
+    match format!("{}{}", head, path.replace('/', "%5c")).parse::<hyper::Uri>() {
+        Ok(v) => {
+            urls.push(v);
+        }
+        Err(e) => {
+            trace!("URI: {}", e);
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UrlConfig {
+    pub extensions: Vec<String>,
+    pub append_slash: bool,
+    pub raw_path: bool,
+    pub mutations: bool,
+    pub backslash: bool,
+    pub force_extensions: bool,
+    pub wraps: Vec<(String, String)>,
+}
+
+fn push_candidate(urls: &mut Vec<hyper::Uri>, url: &str, config: &UrlConfig) {
+    if config.append_slash && !url.ends_with('/') {
+        match format!("{}/", url).parse::<hyper::Uri>() {
             Ok(v) => {
                 urls.push(v);
             }
@@ -45,26 +66,80 @@ pub fn build_urls(
                 trace!("URI: {}", e);
             }
         }
+    }
 
-        for extension in extensions.iter() {
-            if append_slash {
-                match format!("{}.{}/", url, extension).parse::<hyper::Uri>() {
-                    Ok(v) => {
-                        urls.push(v);
-                    }
-                    Err(e) => {
-                        trace!("URI: {}", e);
-                    }
-                }
-            }
+    match url.parse::<hyper::Uri>() {
+        Ok(v) => {
+            urls.push(v);
+        }
+        Err(e) => {
+            trace!("URI: {}", e);
+        }
+    }
+
+    if config.mutations {
+        push_mutations(urls, url);
+    }
 
-            match format!("{}.{}", url, extension).parse::<hyper::Uri>() {
-                Ok(v) => {
-                    urls.push(v);
-                }
-                Err(e) => {
-                    trace!("URI: {}", e);
-                }
+    if config.backslash {
+        push_backslash(urls, url);
+    }
+}
+
+fn push_word(urls: &mut Vec<hyper::Uri>, base: &str, word: &str, name: &str, config: &UrlConfig) {
+    let url = if base.ends_with('/') {
+        format!("{}{}", base, name)
+    } else {
+        format!("{}/{}", base, name)
+    };
+    let url = expand(&url, word);
+    if config.raw_path {
+        push_candidate(urls, &escape_raw_path(&url), config);
+    } else {
+        push_candidate(urls, &url, config);
+    }
+}
+
+pub fn build_urls(wordlist_path: &str, url: &str, config: &UrlConfig) -> Vec<hyper::Uri> {
+    debug!("building urls");
+    let wordlist = wordlist::read_filtered(wordlist_path)
+        .expect("Something went wrong reading the wordlist file");
+    build_word_urls(&wordlist, url, config)
+}
+
+pub fn build_word_urls(wordlist: &str, url: &str, config: &UrlConfig) -> Vec<hyper::Uri> {
+    let mut urls: Vec<hyper::Uri> = Vec::new();
+    // dirsearch-style wordlists only get the extensions where %EXT% is
+    let extend_all = config.force_extensions || !wordlist.contains("%EXT%");
+    let words = wordlist
+        .lines()
+        .filter(|word| !word.starts_with('#') && (config.raw_path || !word.starts_with(' ')));
+
+    for word in words {
+        let names: Vec<String> = if word.contains("%EXT%") {
+            config
+                .extensions
+                .iter()
+                .map(|extension| word.replace("%EXT%", extension))
+                .collect()
+        } else if extend_all {
+            std::iter::once(word.to_owned())
+                .chain(
+                    config
+                        .extensions
+                        .iter()
+                        .map(|extension| format!("{}.{}", word, extension)),
+                )
+                .collect()
+        } else {
+            vec![word.to_owned()]
+        };
+
+        for name in names.iter() {
+            push_word(&mut urls, url, word, name, config);
+            for (prefix, suffix) in config.wraps.iter() {
+                let wrapped = format!("{}{}{}", prefix, name, suffix);
+                push_word(&mut urls, url, word, &wrapped, config);
             }
         }
     }
@@ -72,8 +147,63 @@ pub fn build_urls(
     urls
 }
 
-pub fn save_dir_results(path: &str, results: &Vec<SingleDirScanResult>) {
-    let json_string = serde_json::to_string(&results).unwrap();
+/// True when the path, taken as a directory, or one of its parents matches a --skip-dir pattern
+pub fn is_skipped(path: &str, skip_dirs: &[Regex]) -> bool {
+    if skip_dirs.is_empty() {
+        return false;
+    }
+
+    let mut directories = parent_directories(path);
+    if !path.ends_with('/') {
+        directories.push(format!("{}/", path));
+    }
+    directories
+        .iter()
+        .any(|directory| skip_dirs.iter().any(|re| re.is_match(directory)))
+}
+
+pub fn slash_redirect(url: &hyper::Uri, location: &str) -> Option<hyper::Uri> {
+    let scheme = location
+        .parse::<hyper::Uri>()
+        .ok()
+        .and_then(|uri| uri.scheme_str().map(|s| s.to_owned()));
+    if scheme.is_some() && scheme.as_deref() != url.scheme_str() {
+        return None;
+    }
+
+    let resolved = resolve_link(url, location)?;
+    if url.path().ends_with('/') || resolved.path() != format!("{}/", url.path()) {
+        return None;
+    }
+
+    match url.query() {
+        Some(query) => format!("{}?{}", resolved, query).parse().ok(),
+        None => Some(resolved),
+    }
+}
+
+pub fn needs_range(headers: &hyper::HeaderMap, threshold: u64) -> Option<u64> {
+    let length = headers
+        .get("Content-Length")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())?;
+
+    if threshold > 0 && length > threshold {
+        Some(length)
+    } else {
+        None
+    }
+}
+
+pub fn save_dir_results(path: &str, results: &Vec<SingleDirScanResult>, append: bool) {
+    let json_string = if append {
+        serde_json::to_string(&merge_results(path, results, |r| {
+            (r.method.clone(), r.url.clone())
+        }))
+        .unwrap()
+    } else {
+        serde_json::to_string(&results).unwrap()
+    };
 
     let mut file = match File::create(Path::new(path)) {
         Ok(f) => f,
@@ -0,0 +1,198 @@
+use futures::{future, Future, Stream};
+use hyper::rt;
+use serde::{Deserialize, Serialize};
+use trust_dns_resolver::{
+    config::{ResolverConfig, ResolverOpts},
+    system_conf::read_system_conf,
+    AsyncResolver,
+};
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::IpAddr,
+    sync::mpsc::channel,
+};
+
+use super::{result_processor::SingleDnsScanResult, DnsConfig};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AsnInfo {
+    pub ip: IpAddr,
+    pub asn: u32,
+    pub prefix: String,
+    pub country: String,
+    pub owner: String,
+}
+
+/// The Team Cymru IP to ASN name of an address
+pub fn origin_name(ip: &IpAddr) -> String {
+    match ip {
+        IpAddr::V4(ip) => {
+            let octets: Vec<String> = ip.octets().iter().rev().map(|o| o.to_string()).collect();
+            format!("{}.origin.asn.cymru.com.", octets.join("."))
+        }
+        IpAddr::V6(ip) => {
+            let nibbles: Vec<String> = ip
+                .octets()
+                .iter()
+                .rev()
+                .flat_map(|o| vec![o & 0xf, o >> 4])
+                .map(|n| format!("{:x}", n))
+                .collect();
+            format!("{}.origin6.asn.cymru.com.", nibbles.join("."))
+        }
+    }
+}
+
+/// `15169 | 8.8.8.0/24 | US | arin | 2000-03-30`, the first ASN wins when several announce the prefix
+pub fn parse_origin(txt: &str) -> Option<(u32, String, String)> {
+    let fields: Vec<&str> = txt.split('|').map(str::trim).collect();
+    let asn = fields.first()?.split_whitespace().next()?.parse().ok()?;
+    let prefix = fields.get(1)?.to_string();
+    let country = fields.get(2).cloned().unwrap_or("").to_owned();
+
+    Some((asn, prefix, country))
+}
+
+/// `15169 | US | arin | 2000-03-30 | GOOGLE, US`
+pub fn parse_owner(txt: &str) -> Option<String> {
+    let owner = txt.splitn(5, '|').nth(4)?.trim();
+    if owner.is_empty() {
+        None
+    } else {
+        Some(owner.to_owned())
+    }
+}
+
+fn lookup_txt(
+    resolver: &AsyncResolver,
+    name: String,
+) -> impl Future<Item = Option<String>, Error = ()> {
+    resolver.txt_lookup(name.as_str()).then(move |res| {
+        let txt = match res {
+            Ok(lookup) => lookup.iter().next().map(|txt| {
+                txt.txt_data()
+                    .iter()
+                    .map(|data| String::from_utf8_lossy(data).into_owned())
+                    .collect::<String>()
+            }),
+            Err(e) => {
+                trace!("{}: {}", name, e);
+                None
+            }
+        };
+
+        Ok(txt)
+    })
+}
+
+pub fn lookup(ips: &[IpAddr], config: &DnsConfig) -> HashMap<IpAddr, AsnInfo> {
+    let (resolver_config, mut opts) =
+        read_system_conf().unwrap_or_else(|_| (ResolverConfig::default(), ResolverOpts::default()));
+    opts.timeout = config.timeout;
+    opts.attempts = config.retries + 1;
+    let (resolver, background) = AsyncResolver::new(resolver_config, opts);
+
+    let mut ips = ips.to_vec();
+    ips.sort();
+    ips.dedup();
+
+    let (tx, rx) = channel::<HashMap<IpAddr, AsnInfo>>();
+    let n_threads = config.n_threads;
+    let owner_resolver = resolver.clone();
+    let origins = futures::stream::iter_ok(ips)
+        .map(move |ip| {
+            lookup_txt(&resolver, origin_name(&ip))
+                .map(move |txt| (ip, txt.as_ref().and_then(|t| parse_origin(t))))
+        })
+        .buffer_unordered(n_threads)
+        .filter_map(|(ip, origin)| origin.map(|origin| (ip, origin)))
+        .collect();
+    let task = origins.and_then(move |origins| {
+        let mut asns: Vec<u32> = origins.iter().map(|(_, (asn, _, _))| *asn).collect();
+        asns.sort();
+        asns.dedup();
+
+        futures::stream::iter_ok(asns)
+            .map(move |asn| {
+                lookup_txt(&owner_resolver, format!("AS{}.asn.cymru.com.", asn))
+                    .map(move |txt| (asn, txt.as_ref().and_then(|t| parse_owner(t))))
+            })
+            .buffer_unordered(n_threads)
+            .collect()
+            .map(move |owners| {
+                let owners: HashMap<u32, String> = owners
+                    .into_iter()
+                    .filter_map(|(asn, owner)| owner.map(|owner| (asn, owner)))
+                    .collect();
+                let infos = origins
+                    .into_iter()
+                    .map(|(ip, (asn, prefix, country))| {
+                        let owner = owners.get(&asn).cloned().unwrap_or_default();
+                        (
+                            ip,
+                            AsnInfo {
+                                ip,
+                                asn,
+                                prefix,
+                                country,
+                                owner,
+                            },
+                        )
+                    })
+                    .collect();
+                tx.send(infos).unwrap_or(());
+            })
+    });
+
+    rt::run(future::lazy(move || {
+        rt::spawn(background);
+        task
+    }));
+
+    rx.try_recv().unwrap_or_default()
+}
+
+pub fn enrich(results: &mut [SingleDnsScanResult], config: &DnsConfig) {
+    let ips: Vec<IpAddr> = results
+        .iter()
+        .filter(|r| r.status)
+        .flat_map(|r| r.extra.iter().flatten().map(|addr| addr.ip()))
+        .collect();
+    if ips.is_empty() {
+        return;
+    }
+
+    let infos = lookup(&ips, config);
+    for result in results.iter_mut() {
+        result.asn = result
+            .extra
+            .iter()
+            .flatten()
+            .filter_map(|addr| infos.get(&addr.ip()).cloned())
+            .collect();
+    }
+}
+
+pub fn report(results: &[SingleDnsScanResult]) -> String {
+    let mut owners: BTreeMap<(u32, String), Vec<&str>> = BTreeMap::new();
+    for result in results.iter() {
+        let host = match result.domain.rfind(':') {
+            Some(i) => &result.domain[..i],
+            None => &result.domain,
+        };
+        for info in result.asn.iter() {
+            let hosts = owners.entry((info.asn, info.owner.to_owned())).or_default();
+            if !hosts.contains(&host) {
+                hosts.push(host);
+            }
+        }
+    }
+
+    let mut lines = vec![format!("[+] Autonomous systems\t: {}", owners.len())];
+    for ((asn, owner), hosts) in owners.iter() {
+        lines.push(format!("\tAS{} {}\t: {}", asn, owner, hosts.join(", ")));
+    }
+
+    lines.join("\n")
+}
@@ -1,59 +1,169 @@
-use futures::{future, Future, Stream};
+use futures::{
+    future::{self, Loop},
+    Future, Stream,
+};
 use hyper::rt;
+use trust_dns_resolver::{
+    config::{LookupIpStrategy, NameServerConfig, ResolverConfig, ResolverOpts},
+    error::{ResolveError, ResolveErrorKind},
+    system_conf::read_system_conf,
+    AsyncResolver,
+};
 
-use std::{net::ToSocketAddrs, sync::mpsc::Sender};
+use std::{
+    net::SocketAddr,
+    sync::{mpsc::Sender, Arc},
+    time::Duration,
+};
 
+pub mod asn;
 pub mod result_processor;
 pub mod utils;
+pub mod zone_walk;
 
+use crate::family::{order_by_family, prefer_family};
 use result_processor::SingleDnsScanResult;
 
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_millis(2000);
+pub const DEFAULT_RETRIES: usize = 2;
+
 #[derive(Debug, Clone)]
 pub struct DnsConfig {
     pub n_threads: usize,
+    pub timeout: Duration,
+    pub retries: usize,
+}
+
+fn build_resolvers(
+    config: &DnsConfig,
+) -> (Vec<AsyncResolver>, Vec<impl Future<Item = (), Error = ()>>) {
+    let (resolver_config, mut opts) = match read_system_conf() {
+        Ok((resolver_config, _)) if resolver_config.name_servers().is_empty() => {
+            (ResolverConfig::default(), ResolverOpts::default())
+        }
+        Ok(v) => v,
+        Err(e) => {
+            warn!(
+                "Unable to read the system DNS configuration, using the defaults: {}",
+                e
+            );
+            (ResolverConfig::default(), ResolverOpts::default())
+        }
+    };
+    opts.timeout = config.timeout;
+    opts.attempts = 1;
+    opts.ip_strategy = LookupIpStrategy::Ipv4AndIpv6;
+    opts.distrust_nx_responses = true;
+
+    let mut groups: Vec<Vec<NameServerConfig>> = Vec::new();
+    for name_server in resolver_config.name_servers() {
+        match groups
+            .iter_mut()
+            .find(|group| group[0].socket_addr == name_server.socket_addr)
+        {
+            Some(group) => group.push(name_server.clone()),
+            None => groups.push(vec![name_server.clone()]),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|group| {
+            AsyncResolver::new(
+                ResolverConfig::from_parts(
+                    resolver_config.domain().cloned(),
+                    resolver_config.search().to_vec(),
+                    group,
+                ),
+                opts,
+            )
+        })
+        .unzip()
 }
 
 fn make_request_future(
     tx: Sender<SingleDnsScanResult>,
+    resolvers: Arc<Vec<AsyncResolver>>,
     domain: String,
+    index: usize,
+    retries: usize,
 ) -> impl Future<Item = (), Error = ()> {
-    future::lazy(move || {
-        match domain.to_socket_addrs() {
-            Ok(v) => {
-                debug!("{:?}", v);
-                let mut addrs: Vec<std::net::SocketAddr> = Vec::new();
-                for addr in v {
-                    addrs.push(addr);
-                }
+    let (host, port) = match domain.rfind(':') {
+        Some(i) => (
+            domain[..i].to_owned(),
+            domain[i + 1..].parse::<u16>().unwrap_or(80),
+        ),
+        None => (domain.to_owned(), 80),
+    };
+    let name = format!("{}.", host);
 
-                let result = SingleDnsScanResult {
+    future::loop_fn(0, move |attempt| {
+        let resolver = &resolvers[(index + attempt) % resolvers.len()];
+        let host = host.clone();
+        resolver
+            .lookup_ip(name.as_str())
+            .then(move |res| match res {
+                Ok(v) => Ok(Loop::Break(Some(v))),
+                Err(ref e) if is_definitive(e) => Ok(Loop::Break(None)),
+                Err(e) => {
+                    if attempt < retries {
+                        debug!("Retrying {} on another resolver: {}", host, e);
+                        Ok(Loop::Continue(attempt + 1))
+                    } else {
+                        warn!("Giving up on {} after {} retries: {}", host, retries, e);
+                        Ok(Loop::Break(None))
+                    }
+                }
+            })
+    })
+    .map(move |lookup| {
+        let result = match lookup {
+            Some(v) => {
+                debug!("{:?}", v);
+                let mut addrs: Vec<SocketAddr> =
+                    v.iter().map(|ip| SocketAddr::new(ip, port)).collect();
+                order_by_family(&mut addrs, prefer_family(), SocketAddr::ip);
+                SingleDnsScanResult {
                     domain,
                     status: true,
                     extra: Some(addrs),
-                };
-                tx.send(result).unwrap();
-            }
-            Err(_e) => {
-                let result = SingleDnsScanResult {
-                    domain,
-                    status: false,
-                    extra: None,
-                };
-
-                tx.send(result).unwrap();
+                    asn: Vec::new(),
+                    geo: Vec::new(),
+                }
             }
+            None => SingleDnsScanResult {
+                domain,
+                status: false,
+                extra: None,
+                asn: Vec::new(),
+                geo: Vec::new(),
+            },
         };
 
-        Ok(())
+        tx.send(result).unwrap();
     })
 }
 
+fn is_definitive(error: &ResolveError) -> bool {
+    matches!(error.kind(), ResolveErrorKind::NoRecordsFound { .. })
+}
+
 pub fn run(tx: Sender<SingleDnsScanResult>, domains: Vec<String>, config: DnsConfig) {
-    let stream = futures::stream::iter_ok(domains)
-        .map(move |url| make_request_future(tx.clone(), url))
+    let (resolvers, backgrounds) = build_resolvers(&config);
+    let resolvers = Arc::new(resolvers);
+    let retries = config.retries;
+    let stream = futures::stream::iter_ok(domains.into_iter().enumerate())
+        .map(move |(index, domain)| {
+            make_request_future(tx.clone(), resolvers.clone(), domain, index, retries)
+        })
         .buffer_unordered(config.n_threads)
         .for_each(Ok)
         .map_err(|err| eprintln!("Err {:?}", err));
 
-    rt::run(stream);
+    rt::run(future::lazy(move || {
+        for background in backgrounds {
+            rt::spawn(background);
+        }
+        stream
+    }));
 }
@@ -1,10 +1,17 @@
 use serde::{Deserialize, Serialize};
 
+use super::asn::AsnInfo;
+use crate::{geoip::GeoInfo, sink::ResultSink};
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SingleDnsScanResult {
     pub domain: String,
     pub status: bool,
     pub extra: Option<Vec<std::net::SocketAddr>>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub asn: Vec<AsnInfo>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub geo: Vec<GeoInfo>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -18,10 +25,11 @@ impl DnsScanResult {
             results: Vec::<SingleDnsScanResult>::new(),
         }
     }
+}
 
-    pub fn maybe_add_result(&mut self, res: SingleDnsScanResult) -> bool {
+impl ResultSink<SingleDnsScanResult> for DnsScanResult {
+    fn store(&mut self, res: SingleDnsScanResult) {
         trace!("{:?}", res);
         self.results.push(res);
-        true
     }
 }
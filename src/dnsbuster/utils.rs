@@ -1,10 +1,11 @@
 use std::{fs, io::Write, path};
 
 use super::result_processor::SingleDnsScanResult;
+use crate::{output::merge_results, wordlist};
 
 pub fn build_domains(wordlist_path: &str, url: &str) -> Vec<String> {
     debug!("building urls");
-    fs::read_to_string(wordlist_path)
+    wordlist::read_filtered(wordlist_path)
         .expect("Something went wrong reading the wordlist file")
         .lines()
         .filter(|word| !word.starts_with('#') && !word.starts_with(' '))
@@ -12,8 +13,75 @@ pub fn build_domains(wordlist_path: &str, url: &str) -> Vec<String> {
         .collect()
 }
 
-pub fn save_dns_results(path: &str, results: &Vec<SingleDnsScanResult>) {
-    let json_string = serde_json::to_string(&results).unwrap();
+pub const TLDS: &[&str] = &[
+    "com", "net", "org", "io", "co", "info", "biz", "app", "dev", "ai", "me", "xyz", "online",
+    "site", "tech", "cloud", "us", "uk", "co.uk", "de", "fr", "it", "es", "nl", "eu", "ch", "ru",
+    "cn", "jp", "in", "br", "ca", "au",
+];
+
+pub fn base_name(domain: &str) -> &str {
+    domain
+        .split('.')
+        .find(|label| !label.is_empty())
+        .unwrap_or("")
+}
+
+pub fn tld_domains<'a>(tlds: impl Iterator<Item = &'a str>, domain: &str) -> Vec<String> {
+    let base = base_name(domain);
+    tlds.map(|tld| tld.trim().trim_start_matches('.'))
+        .filter(|tld| !tld.is_empty() && !tld.starts_with('#'))
+        .map(|tld| format!("{}.{}:80", base, tld))
+        .collect()
+}
+
+pub fn build_tld_domains(wordlist_path: Option<&String>, domain: &str) -> Vec<String> {
+    debug!("building tld domains");
+    match wordlist_path {
+        Some(path) => tld_domains(
+            wordlist::read_filtered(path)
+                .expect("Something went wrong reading the wordlist file")
+                .lines(),
+            domain,
+        ),
+        None => tld_domains(TLDS.iter().cloned(), domain),
+    }
+}
+
+pub fn seed_domains(domains: Vec<String>, seeds: Vec<String>) -> Vec<String> {
+    let mut seeded: Vec<String> = Vec::new();
+    for seed in seeds {
+        let domain = format!("{}:80", seed);
+        if !domains.contains(&domain) && !seeded.contains(&domain) {
+            seeded.push(domain);
+        }
+    }
+
+    seeded.extend(domains);
+    seeded
+}
+
+/// One `<ip> <fqdn>` line per resolved address, as in /etc/hosts
+pub fn hosts_lines(results: &[SingleDnsScanResult]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for result in results.iter().filter(|r| r.status) {
+        let host = match result.domain.rfind(':') {
+            Some(i) => &result.domain[..i],
+            None => &result.domain,
+        };
+        for addr in result.extra.iter().flatten() {
+            lines.push(format!("{} {}", addr.ip(), host));
+        }
+    }
+
+    lines
+}
+
+pub fn save_dns_results(path: &str, results: &Vec<SingleDnsScanResult>, append: bool) {
+    let json_string = if append {
+        serde_json::to_string(&merge_results(path, results, |r| r.domain.clone())).unwrap()
+    } else {
+        serde_json::to_string(&results).unwrap()
+    };
 
     let mut file = match fs::File::create(path::Path::new(path)) {
         Ok(f) => f,
@@ -0,0 +1,145 @@
+use futures::{
+    future::{self, Loop},
+    Future,
+};
+use hyper::rt;
+use trust_dns_resolver::{
+    config::{ResolverConfig, ResolverOpts},
+    proto::rr::{RData, RecordType},
+    system_conf::read_system_conf,
+    AsyncResolver,
+};
+
+use std::sync::mpsc::channel;
+
+use super::DnsConfig;
+
+const NSEC: u16 = 47;
+const NSEC3PARAM: u16 = 51;
+const MAX_NAMES: usize = 100_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ZoneWalk {
+    Walked(Vec<String>),
+    Nsec3 { iterations: u16 },
+    Unsigned,
+}
+
+pub fn parse_name(rdata: &[u8]) -> Option<String> {
+    let mut labels: Vec<String> = Vec::new();
+    let mut i = 0;
+    loop {
+        let len = *rdata.get(i)? as usize;
+        if len == 0 {
+            break;
+        }
+        if len > 63 {
+            return None;
+        }
+
+        let label = rdata.get(i + 1..i + 1 + len)?;
+        labels.push(String::from_utf8_lossy(label).to_lowercase());
+        i += 1 + len;
+    }
+
+    Some(labels.join("."))
+}
+
+pub fn parse_nsec3param(rdata: &[u8]) -> Option<u16> {
+    let iterations = rdata.get(2..4)?;
+    Some(u16::from(iterations[0]) << 8 | u16::from(iterations[1]))
+}
+
+fn in_zone(name: &str, zone: &str) -> bool {
+    name == zone || name.ends_with(&format!(".{}", zone))
+}
+
+fn lookup_rdata(
+    resolver: &AsyncResolver,
+    name: &str,
+    code: u16,
+) -> impl Future<Item = Vec<Vec<u8>>, Error = ()> {
+    resolver
+        .lookup(format!("{}.", name).as_str(), RecordType::Unknown(code))
+        .then(move |res| {
+            let rdata = match res {
+                Ok(lookup) => lookup
+                    .iter()
+                    .filter_map(|rdata| match rdata {
+                        RData::Unknown { code: c, rdata } if *c == code => {
+                            rdata.anything().cloned()
+                        }
+                        _ => None,
+                    })
+                    .collect(),
+                Err(e) => {
+                    trace!("{}", e);
+                    Vec::new()
+                }
+            };
+
+            Ok(rdata)
+        })
+}
+
+pub fn walk(domain: &str, config: &DnsConfig) -> ZoneWalk {
+    let (resolver_config, mut opts) =
+        read_system_conf().unwrap_or_else(|_| (ResolverConfig::default(), ResolverOpts::default()));
+    opts.timeout = config.timeout;
+    opts.attempts = config.retries + 1;
+    let (resolver, background) = AsyncResolver::new(resolver_config, opts);
+
+    let zone = domain.trim_end_matches('.').to_lowercase();
+    let (tx, rx) = channel::<ZoneWalk>();
+
+    let walker = resolver.clone();
+    let start = zone.to_owned();
+    let apex = lookup_rdata(&resolver, &zone, NSEC).and_then(move |rdata| {
+        let next = match rdata.first().and_then(|rdata| parse_name(rdata)) {
+            Some(next) => next,
+            None => return future::Either::A(future::ok(None)),
+        };
+
+        future::Either::B(future::loop_fn(
+            (vec![start.clone()], next),
+            move |(mut names, next)| {
+                let done = next == start
+                    || !in_zone(&next, &start)
+                    || names.contains(&next)
+                    || names.len() >= MAX_NAMES;
+                if done {
+                    return future::Either::A(future::ok(Loop::Break(Some(names))));
+                }
+
+                debug!("NSEC: {}", next);
+                names.push(next.to_owned());
+                future::Either::B(lookup_rdata(&walker, &next, NSEC).map(move |rdata| {
+                    match rdata.first().and_then(|rdata| parse_name(rdata)) {
+                        Some(next) => Loop::Continue((names, next)),
+                        None => Loop::Break(Some(names)),
+                    }
+                }))
+            },
+        ))
+    });
+
+    let nsec3 = lookup_rdata(&resolver, &zone, NSEC3PARAM);
+    drop(resolver);
+    let task = apex.and_then(move |names| {
+        nsec3.map(move |params| {
+            let result = match (names, params.first().and_then(|p| parse_nsec3param(p))) {
+                (Some(names), _) => ZoneWalk::Walked(names),
+                (None, Some(iterations)) => ZoneWalk::Nsec3 { iterations },
+                (None, None) => ZoneWalk::Unsigned,
+            };
+            tx.send(result).unwrap_or(());
+        })
+    });
+
+    rt::run(future::lazy(move || {
+        rt::spawn(background);
+        task
+    }));
+
+    rx.try_recv().unwrap_or(ZoneWalk::Unsigned)
+}
@@ -0,0 +1,179 @@
+use futures::Stream;
+use hyper::{
+    rt::{self, Future},
+    Body, Client, Request, Uri, Version,
+};
+use hyper_tls::HttpsConnector;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+use crate::proxy::ProxyConnector;
+
+pub const VARIANTS: &[(&str, bool)] = &[("HTTP/1.0", true), ("HTTP/1.0 without Host", false)];
+/// Error pages often echo the request, a few bytes more or less are not a different response
+const LENGTH_TOLERANCE: usize = 32;
+
+#[derive(Debug, Clone)]
+pub struct DowngradeConfig {
+    pub n_threads: usize,
+    pub ignore_certificate: bool,
+    pub user_agent: String,
+    pub http_headers: Vec<(String, String)>,
+    pub tls_min_version: Option<native_tls::Protocol>,
+    pub tls_max_version: Option<native_tls::Protocol>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DowngradeFinding {
+    pub url: String,
+    pub variant: String,
+    pub status: String,
+    pub length: usize,
+    pub downgraded_status: String,
+    pub downgraded_length: usize,
+}
+
+pub fn is_candidate(status: &str) -> bool {
+    status.starts_with("403") || status.starts_with("404")
+}
+
+pub fn compare(
+    url: &str,
+    variant: &str,
+    original: (&str, usize),
+    downgraded: (&str, usize),
+) -> Option<DowngradeFinding> {
+    // 5% of the original length, at least LENGTH_TOLERANCE bytes
+    let tolerance = LENGTH_TOLERANCE.max(original.1 / 20);
+    if original.0 == downgraded.0 && original.1.abs_diff(downgraded.1) <= tolerance {
+        return None;
+    }
+
+    Some(DowngradeFinding {
+        url: url.to_owned(),
+        variant: variant.to_owned(),
+        status: original.0.to_owned(),
+        length: original.1,
+        downgraded_status: downgraded.0.to_owned(),
+        downgraded_length: downgraded.1,
+    })
+}
+
+fn make_request_future(
+    client: &Client<HttpsConnector<ProxyConnector>>,
+    url: Uri,
+    original: (String, usize),
+    variant: (&'static str, bool),
+    config: &DowngradeConfig,
+) -> impl Future<Item = Option<DowngradeFinding>, Error = ()> {
+    let mut request_builder = Request::builder();
+    for header_tuple in &config.http_headers {
+        request_builder.header(header_tuple.0.as_str(), header_tuple.1.as_str());
+    }
+    if variant.1 {
+        if let Some(authority) = url.authority_part() {
+            request_builder.header("Host", authority.as_str());
+        }
+    }
+
+    let request = request_builder
+        .header("User-Agent", &config.user_agent[..])
+        .version(Version::HTTP_10)
+        .uri(&url)
+        .body(Body::empty())
+        .expect("Request builder");
+
+    client
+        .request(request)
+        .and_then(|res| {
+            let status = res.status().to_string();
+            res.into_body()
+                .concat2()
+                .map(move |body| (status, body.len()))
+        })
+        .map(move |(status, length)| {
+            compare(
+                &url.to_string(),
+                variant.0,
+                (&original.0, original.1),
+                (&status, length),
+            )
+        })
+        .or_else(|e| {
+            debug!("Downgrade probe failed: {}", e);
+            Ok(None)
+        })
+}
+
+pub fn check(
+    results: &[(String, String, usize)],
+    config: &DowngradeConfig,
+) -> Vec<DowngradeFinding> {
+    let mut tls_connector_builder = native_tls::TlsConnector::builder();
+    tls_connector_builder.danger_accept_invalid_certs(config.ignore_certificate);
+    tls_connector_builder.min_protocol_version(config.tls_min_version);
+    tls_connector_builder.max_protocol_version(config.tls_max_version);
+    let tls_connector = tls_connector_builder
+        .build()
+        .expect("TLS initialization failed");
+    let http_connector = ProxyConnector::new(config.n_threads);
+    let https_connector = HttpsConnector::from((http_connector, tls_connector));
+    let client = Client::builder().set_host(false).build(https_connector);
+
+    let mut probes = Vec::new();
+    for (url, status, length) in results.iter().filter(|(_, s, _)| is_candidate(s)) {
+        let uri = match url.parse::<Uri>() {
+            Ok(v) => v,
+            Err(e) => {
+                debug!("URI: {}", e);
+                continue;
+            }
+        };
+
+        for variant in VARIANTS.iter() {
+            probes.push((uri.clone(), (status.to_owned(), *length), *variant));
+        }
+    }
+
+    let findings = Arc::new(Mutex::new(Vec::new()));
+    let findings_writer = findings.clone();
+    let config = config.clone();
+    let n_threads = config.n_threads;
+    let stream = futures::stream::iter_ok(probes)
+        .map(move |(url, original, variant)| {
+            make_request_future(&client, url, original, variant, &config)
+        })
+        .buffer_unordered(n_threads)
+        .for_each(move |finding| {
+            if let Some(finding) = finding {
+                findings_writer.lock().unwrap().push(finding);
+            }
+
+            Ok(())
+        });
+
+    rt::run(stream);
+
+    let findings = findings.lock().unwrap().clone();
+    findings
+}
+
+pub fn report(findings: &[DowngradeFinding]) -> String {
+    let mut lines = vec![format!(
+        "[!] Protocol downgrade differences\t: {}",
+        findings.len()
+    )];
+    for finding in findings.iter() {
+        lines.push(format!(
+            "\t{} returned {} ({} bytes) with {}, {} ({} bytes) otherwise",
+            finding.url,
+            finding.downgraded_status,
+            finding.downgraded_length,
+            finding.variant,
+            finding.status,
+            finding.length
+        ));
+    }
+
+    lines.join("\n")
+}
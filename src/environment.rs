@@ -0,0 +1,111 @@
+pub const ENV_PREFIX: &str = "RUSTBUSTER_";
+
+/// An option that can be set from the environment, the tables are next to the arguments in args
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EnvArg {
+    pub long: &'static str,
+    pub short: Option<char>,
+    pub aliases: &'static [&'static str],
+    pub takes_value: bool,
+    pub multiple: bool,
+}
+
+impl EnvArg {
+    /// A flag set by true or false
+    pub const fn flag(long: &'static str, short: Option<char>) -> Self {
+        EnvArg {
+            long,
+            short,
+            aliases: &[],
+            takes_value: false,
+            multiple: false,
+        }
+    }
+
+    pub const fn value(long: &'static str, short: Option<char>) -> Self {
+        EnvArg {
+            takes_value: true,
+            ..EnvArg::flag(long, short)
+        }
+    }
+
+    /// An option that can be repeated, one value per line
+    pub const fn values(long: &'static str, short: Option<char>) -> Self {
+        EnvArg {
+            takes_value: true,
+            multiple: true,
+            ..EnvArg::flag(long, short)
+        }
+    }
+
+    pub const fn aliases(self, aliases: &'static [&'static str]) -> Self {
+        EnvArg { aliases, ..self }
+    }
+}
+
+pub fn env_name(long: &str) -> String {
+    format!("{}{}", ENV_PREFIX, long.to_uppercase().replace('-', "_"))
+}
+
+fn is_given(arg: &EnvArg, specs: &[EnvArg], argv: &[String]) -> bool {
+    for token in argv.iter() {
+        if token == "--" {
+            break;
+        }
+
+        if let Some(name) = token.strip_prefix("--") {
+            let name = name.split('=').next().unwrap_or("");
+            if name == arg.long || arg.aliases.iter().any(|a| *a == name) {
+                return true;
+            }
+        } else if let Some(shorts) = token.strip_prefix('-') {
+            // -kq bundles flags, -t4 carries the value of an option
+            for c in shorts.chars() {
+                if Some(c) == arg.short {
+                    return true;
+                }
+                match specs.iter().find(|s| s.short == Some(c)) {
+                    Some(spec) if !spec.takes_value => continue,
+                    _ => break,
+                }
+            }
+        }
+    }
+
+    false
+}
+
+pub fn arguments<F: Fn(&str) -> Option<String>>(
+    specs: &[EnvArg],
+    argv: &[String],
+    lookup: F,
+) -> Vec<String> {
+    let mut arguments = Vec::new();
+    for arg in specs.iter() {
+        let name = env_name(arg.long);
+        let value = match lookup(&name) {
+            Some(v) => v,
+            None => continue,
+        };
+        if is_given(arg, specs, argv) {
+            debug!("{} is overridden by the command line", name);
+            continue;
+        }
+
+        if !arg.takes_value {
+            match value.to_lowercase().as_str() {
+                "1" | "true" | "yes" => arguments.push(format!("--{}", arg.long)),
+                "0" | "false" | "no" | "" => (),
+                _ => warn!("Ignoring {}, it must be true or false", name),
+            }
+        } else if arg.multiple {
+            for line in value.lines().filter(|l| !l.is_empty()) {
+                arguments.push(format!("--{}={}", arg.long, line));
+            }
+        } else {
+            arguments.push(format!("--{}={}", arg.long, value));
+        }
+    }
+
+    arguments
+}
@@ -0,0 +1,22 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub const NO_FINDINGS: i32 = 0;
+pub const FINDINGS: i32 = 1;
+pub const ABORTED: i32 = 2;
+pub const INVALID_CONFIG: i32 = 3;
+
+static SCAN_ABORTED: AtomicBool = AtomicBool::new(false);
+
+pub fn abort() {
+    SCAN_ABORTED.store(true, Ordering::SeqCst);
+}
+
+pub fn from_findings(findings: usize) -> i32 {
+    if SCAN_ABORTED.load(Ordering::SeqCst) {
+        ABORTED
+    } else if findings > 0 {
+        FINDINGS
+    } else {
+        NO_FINDINGS
+    }
+}
@@ -0,0 +1,242 @@
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+use crate::{
+    diff::{describe, result_key},
+    severity::Severity,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub mode: String,
+    pub title: String,
+    pub description: String,
+    pub severity: Severity,
+    pub protocol: Option<String>,
+    pub host: String,
+    pub port: Option<u16>,
+    pub path: String,
+    pub method: String,
+    pub status: String,
+    pub addresses: Vec<String>,
+    pub unique_id: String,
+}
+
+const DESCRIBED_FIELDS: &[&str] = &[
+    "url", "vhost", "domain", "method", "status", "extra", "location", "title", "kind", "payload",
+];
+
+fn text(entry: &Value, name: &str) -> String {
+    match entry.get(name) {
+        Some(Value::String(v)) => v.to_owned(),
+        Some(Value::Null) | None => "".to_owned(),
+        Some(v) => v.to_string(),
+    }
+}
+
+fn finding(mode: &str, entry: &Value) -> Option<Finding> {
+    if !entry["error"].is_null() {
+        return None;
+    }
+
+    let target = entry["request"]["url"]
+        .as_str()
+        .or_else(|| entry["url"].as_str())
+        .or_else(|| entry["vhost"].as_str());
+    let (protocol, host, port, path) = match target {
+        Some(target) => {
+            let uri = target.parse::<hyper::Uri>().ok()?;
+            let protocol = uri.scheme_str().unwrap_or("http").to_owned();
+            let port = uri.port_u16().unwrap_or(match protocol.as_str() {
+                "https" | "wss" => 443,
+                _ => 80,
+            });
+            let path = uri
+                .path_and_query()
+                .map(|p| p.as_str().to_owned())
+                .unwrap_or_else(|| "/".to_owned());
+            (Some(protocol), uri.host()?.to_owned(), Some(port), path)
+        }
+        None => {
+            let domain = entry["domain"].as_str()?;
+            let host = match domain.rfind(':') {
+                Some(i) => &domain[..i],
+                None => domain,
+            };
+            (None, host.to_owned(), None, "".to_owned())
+        }
+    };
+
+    let mut description = format!("Found by the rustbuster {} mode.\n", mode);
+    for name in DESCRIBED_FIELDS.iter() {
+        let value = text(entry, name);
+        if !value.is_empty() && value != "[]" {
+            description.push_str(&format!("\n{}: {}", name, value));
+        }
+    }
+
+    Some(Finding {
+        mode: mode.to_owned(),
+        title: format!(
+            "rustbuster {}: {}",
+            mode,
+            describe(entry).replace('\t', " ")
+        ),
+        description,
+        severity: serde_json::from_value(entry["severity"].clone()).unwrap_or(Severity::Info),
+        protocol,
+        host,
+        port,
+        path,
+        method: text(entry, "method"),
+        status: text(entry, "status"),
+        addresses: entry["extra"]
+            .as_array()
+            .map(|addrs| {
+                addrs
+                    .iter()
+                    .filter_map(|addr| addr.as_str())
+                    .filter_map(|addr| addr.parse::<std::net::SocketAddr>().ok())
+                    .map(|addr| addr.ip().to_string())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        unique_id: format!("{} {}", mode, result_key(entry).trim()),
+    })
+}
+
+pub fn findings(results: &BTreeMap<String, Vec<Value>>) -> Vec<Finding> {
+    results
+        .iter()
+        .flat_map(|(mode, entries)| entries.iter().filter_map(move |e| finding(mode, e)))
+        .collect()
+}
+
+/// DefectDojo's "Generic Findings Import" JSON
+pub fn defectdojo(findings: &[Finding], date: &str) -> Value {
+    let findings: Vec<Value> = findings
+        .iter()
+        .map(|finding| {
+            let mut endpoint = Map::new();
+            endpoint.insert("host".to_owned(), json!(finding.host));
+            if let Some(protocol) = &finding.protocol {
+                endpoint.insert("protocol".to_owned(), json!(protocol));
+                endpoint.insert("port".to_owned(), json!(finding.port));
+                endpoint.insert(
+                    "path".to_owned(),
+                    json!(finding.path.trim_start_matches('/')),
+                );
+            }
+            let severity = match finding.severity {
+                Severity::Info => "Info",
+                Severity::Low => "Low",
+                Severity::Medium => "Medium",
+                Severity::High => "High",
+            };
+
+            json!({
+                "title": finding.title,
+                "description": finding.description,
+                "severity": severity,
+                "date": date,
+                "active": true,
+                "verified": false,
+                "static_finding": false,
+                "dynamic_finding": true,
+                "unique_id_from_tool": finding.unique_id,
+                "vuln_id_from_tool": format!("rustbuster-{}", finding.mode),
+                "endpoints": [endpoint],
+            })
+        })
+        .collect();
+
+    json!({ "findings": findings })
+}
+
+// port => (service name, vulnerabilities)
+type Services = BTreeMap<u16, (String, Vec<Value>)>;
+
+/// Faraday's bulk create JSON, also read by its report importer
+pub fn faraday(findings: &[Finding]) -> Value {
+    let mut hosts: BTreeMap<String, (Vec<String>, Services)> = BTreeMap::new();
+    for finding in findings.iter() {
+        let (protocol, port) = match (&finding.protocol, finding.port) {
+            (Some(protocol), Some(port)) => (protocol, port),
+            _ => {
+                // dns findings are assets, one host per resolved address
+                let ips = if finding.addresses.is_empty() {
+                    vec![finding.host.to_owned()]
+                } else {
+                    finding.addresses.clone()
+                };
+                for ip in ips {
+                    let host = hosts.entry(ip).or_default();
+                    if !host.0.contains(&finding.host) {
+                        host.0.push(finding.host.to_owned());
+                    }
+                }
+                continue;
+            }
+        };
+
+        let host = hosts.entry(finding.host.to_owned()).or_default();
+        if !host.0.contains(&finding.host) {
+            host.0.push(finding.host.to_owned());
+        }
+        let severity = match finding.severity {
+            Severity::Info => "info",
+            Severity::Low => "low",
+            Severity::Medium => "med",
+            Severity::High => "high",
+        };
+        let status_code = finding
+            .status
+            .split_whitespace()
+            .next()
+            .and_then(|code| code.parse::<u16>().ok());
+        let service = host
+            .1
+            .entry(port)
+            .or_insert_with(|| (protocol.to_owned(), Vec::new()));
+        service.1.push(json!({
+            "name": finding.title,
+            "desc": finding.description,
+            "severity": severity,
+            "type": "VulnerabilityWeb",
+            "website": finding.host,
+            "path": finding.path,
+            "method": finding.method,
+            "status_code": status_code,
+            "external_id": finding.unique_id,
+            "tool": "rustbuster",
+        }));
+    }
+
+    let hosts: Vec<Value> = hosts
+        .into_iter()
+        .map(|(ip, (hostnames, services))| {
+            let services: Vec<Value> = services
+                .into_iter()
+                .map(|(port, (name, vulnerabilities))| {
+                    json!({
+                        "name": name,
+                        "port": port,
+                        "protocol": "tcp",
+                        "status": "open",
+                        "vulnerabilities": vulnerabilities,
+                    })
+                })
+                .collect();
+
+            json!({
+                "ip": ip,
+                "description": "",
+                "hostnames": hostnames,
+                "services": services,
+                "vulnerabilities": [],
+            })
+        })
+        .collect();
+
+    json!({ "hosts": hosts })
+}
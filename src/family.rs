@@ -0,0 +1,85 @@
+use futures::Future;
+use hyper::client::connect::dns::{GaiResolver, Name, Resolve};
+use std::{
+    io,
+    net::IpAddr,
+    sync::atomic::{AtomicU8, Ordering},
+    vec,
+};
+
+static PREFER_FAMILY: AtomicU8 = AtomicU8::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Family {
+    V4,
+    V6,
+}
+
+impl Family {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "4" => Some(Family::V4),
+            "6" => Some(Family::V6),
+            _ => None,
+        }
+    }
+
+    pub fn matches(self, ip: &IpAddr) -> bool {
+        match self {
+            Family::V4 => ip.is_ipv4(),
+            Family::V6 => ip.is_ipv6(),
+        }
+    }
+}
+
+pub fn set_prefer_family(family: Option<Family>) {
+    let value = match family {
+        Some(Family::V4) => 4,
+        Some(Family::V6) => 6,
+        None => 0,
+    };
+    PREFER_FAMILY.store(value, Ordering::SeqCst);
+}
+
+pub fn prefer_family() -> Option<Family> {
+    match PREFER_FAMILY.load(Ordering::SeqCst) {
+        4 => Some(Family::V4),
+        6 => Some(Family::V6),
+        _ => None,
+    }
+}
+
+/// Moves the addresses of the preferred family first, keeping the resolver order otherwise
+pub fn order_by_family<T, F: Fn(&T) -> IpAddr>(addrs: &mut [T], family: Option<Family>, ip: F) {
+    if let Some(family) = family {
+        addrs.sort_by_key(|addr| !family.matches(&ip(addr)));
+    }
+}
+
+// hyper's happy eyeballs tries the family of the first address, the other one is the fallback
+#[derive(Clone)]
+pub struct FamilyResolver {
+    inner: GaiResolver,
+}
+
+impl FamilyResolver {
+    pub fn new(threads: usize) -> Self {
+        FamilyResolver {
+            inner: GaiResolver::new(threads),
+        }
+    }
+}
+
+impl Resolve for FamilyResolver {
+    type Addrs = vec::IntoIter<IpAddr>;
+    type Future = Box<dyn Future<Item = Self::Addrs, Error = io::Error> + Send>;
+
+    fn resolve(&self, name: Name) -> Self::Future {
+        let family = prefer_family();
+        Box::new(self.inner.resolve(name).map(move |addrs| {
+            let mut addrs: Vec<IpAddr> = addrs.collect();
+            order_by_family(&mut addrs, family, |ip| *ip);
+            addrs.into_iter()
+        }))
+    }
+}
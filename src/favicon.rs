@@ -0,0 +1,115 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use futures::{Future, Stream};
+use hyper::{rt, Client, StatusCode, Uri};
+use hyper_tls::HttpsConnector;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio_timer::Timeout;
+
+use crate::proxy::ProxyConnector;
+
+pub const FINGERPRINTS: &[(i32, &str)] = &[
+    (116323821, "Spring Boot"),
+    (81586312, "Jenkins"),
+    (1278323681, "GitLab"),
+    (1485257654, "SonarQube"),
+    (-305179312, "Atlassian Confluence"),
+    (442749392, "Microsoft Outlook Web App"),
+    (-335242539, "F5 BIG-IP"),
+    (945408572, "Fortinet FortiGate"),
+    (-297069493, "Apache Tomcat"),
+];
+
+pub fn murmur3_32(data: &[u8], seed: u32) -> u32 {
+    const C1: u32 = 0xcc9e_2d51;
+    const C2: u32 = 0x1b87_3593;
+    let mut h = seed;
+    let chunks = data.chunks_exact(4);
+    let tail = chunks.remainder();
+    for chunk in chunks {
+        let mut k = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        k = k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+        h ^= k;
+        h = h.rotate_left(13).wrapping_mul(5).wrapping_add(0xe654_6b64);
+    }
+
+    if !tail.is_empty() {
+        let mut k = 0u32;
+        for (i, b) in tail.iter().enumerate() {
+            k ^= u32::from(*b) << (8 * i);
+        }
+        h ^= k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+    }
+
+    h ^= data.len() as u32;
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x85eb_ca6b);
+    h ^= h >> 13;
+    h = h.wrapping_mul(0xc2b2_ae35);
+    h ^ (h >> 16)
+}
+
+pub fn favicon_hash(data: &[u8]) -> i32 {
+    // same as python's base64.encodebytes, which is what shodan hashes
+    let encoded = STANDARD.encode(data);
+    let mut lines = String::new();
+    for line in encoded.as_bytes().chunks(76) {
+        lines.push_str(&String::from_utf8_lossy(line));
+        lines.push('\n');
+    }
+
+    murmur3_32(lines.as_bytes(), 0) as i32
+}
+
+pub fn identify(hash: i32) -> Option<&'static str> {
+    FINGERPRINTS
+        .iter()
+        .find(|(h, _)| *h == hash)
+        .map(|(_, product)| *product)
+}
+
+pub fn fingerprint(url: &str, ignore_certificate: bool) -> Option<(i32, Option<&'static str>)> {
+    let hash = favicon_hash(&fetch_favicon(url, ignore_certificate)?);
+    Some((hash, identify(hash)))
+}
+
+pub fn fetch_favicon(url: &str, ignore_certificate: bool) -> Option<Vec<u8>> {
+    let uri = url.parse::<Uri>().ok()?;
+    let favicon = format!(
+        "{}://{}/favicon.ico",
+        uri.scheme_part()?.as_str(),
+        uri.authority_part()?.as_str()
+    )
+    .parse::<Uri>()
+    .ok()?;
+
+    let mut tls_connector_builder = native_tls::TlsConnector::builder();
+    tls_connector_builder.danger_accept_invalid_certs(ignore_certificate);
+    let tls_connector = tls_connector_builder.build().ok()?;
+    let http_connector = ProxyConnector::new(1);
+    let client: Client<HttpsConnector<ProxyConnector>> =
+        Client::builder().build(HttpsConnector::from((http_connector, tls_connector)));
+
+    let body = Arc::new(Mutex::new(None));
+    let body_res = body.clone();
+    let request = client
+        .get(favicon)
+        .and_then(|res| {
+            let status = res.status();
+            res.into_body().concat2().map(move |body| (status, body))
+        })
+        .map(move |(status, data)| {
+            if status == StatusCode::OK && !data.is_empty() {
+                *body_res.lock().unwrap() = Some(data.to_vec());
+            }
+        });
+    rt::run(
+        Timeout::new(request, Duration::from_secs(10))
+            .map_err(|e| debug!("Unable to fetch the favicon: {:?}", e)),
+    );
+
+    let data = body.lock().unwrap().take();
+    data
+}
@@ -0,0 +1,251 @@
+use futures::{future, Future, Stream};
+use hyper::{rt, Body, Client, Request, Uri};
+use hyper_tls::HttpsConnector;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::Write,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    favicon::murmur3_32,
+    proxy::{PinnedConnector, ProxyConnector},
+};
+
+#[derive(Debug, Clone)]
+pub struct FrontingConfig {
+    pub n_threads: usize,
+    pub ignore_certificate: bool,
+    pub http_method: String,
+    pub http_body: Vec<u8>,
+    pub user_agent: String,
+    pub http_headers: Vec<(String, String)>,
+    pub tls_min_version: Option<native_tls::Protocol>,
+    pub tls_max_version: Option<native_tls::Protocol>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FrontingResult {
+    pub sni: Option<String>,
+    pub host: Option<String>,
+    pub status: String,
+    pub length: usize,
+    pub hash: u32,
+    pub distinct: bool,
+    pub error: Option<String>,
+}
+
+/// The first combination, the target name as both SNI and Host, is the baseline
+pub fn combinations(
+    target: &str,
+    domains: &[String],
+    tls: bool,
+) -> Vec<(Option<String>, Option<String>)> {
+    let mut names = vec![target.to_lowercase()];
+    for domain in domains.iter() {
+        let domain = domain.trim().to_lowercase();
+        if !domain.is_empty() && !names.contains(&domain) {
+            names.push(domain);
+        }
+    }
+
+    let mut values: Vec<Option<String>> = names.into_iter().map(Some).collect();
+    values.push(None);
+    let snis = if tls { values.clone() } else { vec![None] };
+
+    let mut combinations = Vec::new();
+    for sni in snis.iter() {
+        for host in values.iter() {
+            combinations.push((sni.clone(), host.clone()));
+        }
+    }
+
+    combinations
+}
+
+pub fn mark_distinct(results: &mut [FrontingResult]) {
+    let baseline = match results.first() {
+        Some(v) if v.error.is_none() => Some((v.status.to_owned(), v.hash)),
+        _ => None,
+    };
+
+    for result in results.iter_mut().skip(1) {
+        result.distinct = result.error.is_none()
+            && baseline
+                .as_ref()
+                .is_none_or(|(status, hash)| &result.status != status || result.hash != *hash);
+    }
+}
+
+fn build_client(
+    config: &FrontingConfig,
+    host: &str,
+    use_sni: bool,
+) -> Client<HttpsConnector<PinnedConnector>> {
+    let mut tls_connector_builder = native_tls::TlsConnector::builder();
+    tls_connector_builder.danger_accept_invalid_certs(config.ignore_certificate);
+    // the certificate is checked against the SNI, which is expected to differ from the target
+    tls_connector_builder.danger_accept_invalid_hostnames(true);
+    tls_connector_builder.use_sni(use_sni);
+    tls_connector_builder.min_protocol_version(config.tls_min_version);
+    tls_connector_builder.max_protocol_version(config.tls_max_version);
+    let tls_connector = tls_connector_builder
+        .build()
+        .expect("TLS initialization failed");
+    let http_connector = PinnedConnector::new(ProxyConnector::new(config.n_threads), host);
+
+    Client::builder().build(HttpsConnector::from((http_connector, tls_connector)))
+}
+
+pub fn run(url: &str, domains: &[String], config: &FrontingConfig) -> Vec<FrontingResult> {
+    let uri = match url.parse::<Uri>() {
+        Ok(v) => v,
+        Err(e) => {
+            error!("URI: {}", e);
+            return Vec::new();
+        }
+    };
+    let target = uri.host().unwrap_or("").to_owned();
+    let scheme = uri.scheme_str().unwrap_or("http").to_owned();
+    let port = uri
+        .port_part()
+        .map(|p| format!(":{}", p))
+        .unwrap_or_default();
+    let path = uri
+        .path_and_query()
+        .map(|p| p.as_str().to_owned())
+        .unwrap_or_else(|| "/".to_owned());
+
+    let sni_client = build_client(config, &target, true);
+    let no_sni_client = build_client(config, &target, false);
+    let probes = combinations(&target, domains, scheme == "https");
+
+    let results = Arc::new(Mutex::new(Vec::new()));
+    let results_writer = results.clone();
+    let config = config.clone();
+    let n_threads = config.n_threads;
+    let stream = futures::stream::iter_ok(probes.into_iter().enumerate())
+        .map(move |(index, (sni, host))| {
+            let client = if sni.is_some() {
+                &sni_client
+            } else {
+                &no_sni_client
+            };
+            let request_uri = format!(
+                "{}://{}{}{}",
+                scheme,
+                sni.as_deref().unwrap_or(&target),
+                port,
+                path
+            );
+            let mut request_builder = Request::builder();
+            for header_tuple in &config.http_headers {
+                request_builder.header(header_tuple.0.as_str(), header_tuple.1.as_str());
+            }
+            let request = request_builder
+                .method(config.http_method.as_str())
+                .header("User-Agent", &config.user_agent[..])
+                .header("Host", host.as_deref().unwrap_or(""))
+                .uri(request_uri)
+                .body(Body::from(config.http_body.clone()));
+
+            let mut result = FrontingResult {
+                sni,
+                host,
+                status: String::new(),
+                length: 0,
+                hash: 0,
+                distinct: false,
+                error: None,
+            };
+            let request = match request {
+                Ok(v) => v,
+                Err(e) => {
+                    result.error = Some(e.to_string());
+                    return future::Either::A(future::ok((index, result)));
+                }
+            };
+
+            future::Either::B(
+                client
+                    .request(request)
+                    .and_then(|res| {
+                        let status = res.status().to_string();
+                        res.into_body().concat2().map(move |body| (status, body))
+                    })
+                    .then(move |response| {
+                        match response {
+                            Ok((status, body)) => {
+                                result.status = status;
+                                result.length = body.len();
+                                result.hash = murmur3_32(&body, 0);
+                            }
+                            Err(e) => result.error = Some(e.to_string()),
+                        }
+
+                        Ok((index, result))
+                    }),
+            )
+        })
+        .buffer_unordered(n_threads)
+        .for_each(move |result| {
+            results_writer.lock().unwrap().push(result);
+            Ok(())
+        });
+
+    rt::run(stream);
+
+    let mut results = results.lock().unwrap().clone();
+    results.sort_by_key(|(index, _)| *index);
+    let mut results: Vec<FrontingResult> = results.into_iter().map(|(_, r)| r).collect();
+    mark_distinct(&mut results);
+    results
+}
+
+fn label(value: &Option<String>) -> &str {
+    value.as_deref().unwrap_or("(none)")
+}
+
+pub fn report(results: &[FrontingResult]) -> String {
+    let distinct = results.iter().filter(|r| r.distinct).count();
+    let mut lines = vec![format!(
+        "[+] SNI / Host combinations\t: {}, {} with distinct content",
+        results.len(),
+        distinct
+    )];
+    for result in results.iter() {
+        let marker = if result.distinct { "[!]" } else { "   " };
+        let outcome = match &result.error {
+            Some(e) => format!("error: {}", e),
+            None => format!("{}\t{} bytes", result.status, result.length),
+        };
+        lines.push(format!(
+            "\t{} SNI: {:<30} Host: {:<30} {}",
+            marker,
+            label(&result.sni),
+            label(&result.host),
+            outcome
+        ));
+    }
+
+    lines.join("\n")
+}
+
+pub fn save_fronting_results(path: &str, results: &[FrontingResult]) {
+    let json_string = serde_json::to_string(results).unwrap();
+
+    let mut file = match File::create(Path::new(path)) {
+        Ok(f) => f,
+        Err(e) => {
+            error!("Error while creating file: {}\n{}", path, e);
+            return;
+        }
+    };
+
+    match file.write_all(json_string.as_bytes()) {
+        Ok(_) => debug!("Results saved to: {}", path),
+        Err(e) => error!("Error while writing results to file: {}\n{}", path, e),
+    };
+}
@@ -0,0 +1,108 @@
+/// The number of random payloads sent before the scan to learn the usual response
+pub const SAMPLES: usize = 5;
+
+const THRESHOLD: f64 = 3.0;
+
+/// What is compared between responses, the latency is in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    pub status: u16,
+    pub length: usize,
+    pub words: usize,
+    pub latency: u64,
+}
+
+impl Sample {
+    pub fn new(status: &str, body: &str, latency: u64) -> Self {
+        Sample {
+            status: status
+                .split_whitespace()
+                .next()
+                .and_then(|code| code.parse().ok())
+                .unwrap_or(0),
+            length: body.len(),
+            words: body.split_whitespace().count(),
+            latency,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stat {
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+impl Stat {
+    fn new(values: &[f64]) -> Self {
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+        Stat {
+            mean,
+            stddev: variance.sqrt(),
+        }
+    }
+
+    /// The stddev of identical baseline responses is 0, the floor keeps small variations quiet
+    fn deviates(&self, value: f64, floor: f64) -> bool {
+        (value - self.mean).abs() > THRESHOLD * self.stddev.max(floor)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Baseline {
+    pub samples: usize,
+    pub statuses: Vec<u16>,
+    pub length: Stat,
+    pub words: Stat,
+    pub latency: Stat,
+}
+
+impl Baseline {
+    pub fn new(samples: &[Sample]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut statuses: Vec<u16> = samples.iter().map(|s| s.status).collect();
+        statuses.sort();
+        statuses.dedup();
+        let stat = |f: fn(&Sample) -> f64| Stat::new(&samples.iter().map(f).collect::<Vec<_>>());
+
+        Some(Baseline {
+            samples: samples.len(),
+            statuses,
+            length: stat(|s| s.length as f64),
+            words: stat(|s| s.words as f64),
+            latency: stat(|s| s.latency as f64),
+        })
+    }
+
+    /// What makes the response stand out, empty when it answers like the baseline
+    pub fn deviations(&self, sample: &Sample) -> Vec<&'static str> {
+        let mut deviations = Vec::new();
+        if !self.statuses.contains(&sample.status) {
+            deviations.push("status");
+        }
+        if self
+            .length
+            .deviates(sample.length as f64, (self.length.mean * 0.05).max(16.0))
+        {
+            deviations.push("length");
+        }
+        if self
+            .words
+            .deviates(sample.words as f64, (self.words.mean * 0.05).max(2.0))
+        {
+            deviations.push("words");
+        }
+        if self
+            .latency
+            .deviates(sample.latency as f64, (self.latency.mean * 0.5).max(250.0))
+        {
+            deviations.push("latency");
+        }
+
+        deviations
+    }
+}
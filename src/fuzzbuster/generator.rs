@@ -0,0 +1,118 @@
+/// Every word of `min` to `max` chars of the charset, shortest first, as hydra's -x: a, A and 1
+/// stand for the lowercase letters, the uppercase letters and the digits, other chars for themselves
+#[derive(Debug, Clone, PartialEq)]
+pub struct Generator {
+    pub min: usize,
+    pub max: usize,
+    pub charset: Vec<char>,
+}
+
+impl Generator {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut parts = spec.splitn(3, ':');
+        let (min, max, charset) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(min), Some(max), Some(charset)) => (min, max, charset),
+            _ => {
+                return Err(format!(
+                    "Invalid generator: {}, expected min:max:charset",
+                    spec
+                ))
+            }
+        };
+        let min: usize = min
+            .parse()
+            .map_err(|_| format!("Invalid minimum length: {}", min))?;
+        let max: usize = max
+            .parse()
+            .map_err(|_| format!("Invalid maximum length: {}", max))?;
+        if min == 0 || min > max {
+            return Err(format!(
+                "Invalid lengths: {}:{}, expected 0 < min <= max",
+                min, max
+            ));
+        }
+
+        let mut chars: Vec<char> = Vec::new();
+        for c in charset.chars() {
+            let expanded: Vec<char> = match c {
+                'a' => ('a'..='z').collect(),
+                'A' => ('A'..='Z').collect(),
+                '1' => ('0'..='9').collect(),
+                c => vec![c],
+            };
+            for c in expanded {
+                if !chars.contains(&c) {
+                    chars.push(c);
+                }
+            }
+        }
+
+        if chars.is_empty() {
+            return Err(format!("Empty charset: {}", spec));
+        }
+
+        Ok(Generator {
+            min,
+            max,
+            charset: chars,
+        })
+    }
+
+    /// The number of words, saturating when it does not fit
+    pub fn count(&self) -> u64 {
+        (self.min..=self.max).fold(0u64, |acc, len| {
+            acc.saturating_add((self.charset.len() as u64).saturating_pow(len as u32))
+        })
+    }
+
+    pub fn words(&self) -> Words {
+        Words {
+            charset: self.charset.clone(),
+            max: self.max,
+            indices: vec![0; self.min],
+            done: false,
+        }
+    }
+}
+
+/// The words of a generator, built one at a time
+#[derive(Debug, Clone)]
+pub struct Words {
+    charset: Vec<char>,
+    max: usize,
+    indices: Vec<usize>,
+    done: bool,
+}
+
+impl Iterator for Words {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        if self.done {
+            return None;
+        }
+
+        let word = self.indices.iter().map(|&i| self.charset[i]).collect();
+        // the last char moves fastest, the word grows once every combination is sent
+        let mut position = self.indices.len();
+        loop {
+            if position == 0 {
+                if self.indices.len() == self.max {
+                    self.done = true;
+                } else {
+                    self.indices = vec![0; self.indices.len() + 1];
+                }
+                break;
+            }
+
+            position -= 1;
+            self.indices[position] += 1;
+            if self.indices[position] < self.charset.len() {
+                break;
+            }
+            self.indices[position] = 0;
+        }
+
+        Some(word)
+    }
+}
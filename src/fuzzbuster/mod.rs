@@ -1,6 +1,6 @@
+use chrono::Local;
 use futures::Stream;
 use hyper::{
-    client::HttpConnector,
     rt::{self, Future},
     Body, Client, Request, StatusCode,
 };
@@ -9,15 +9,44 @@ use itertools::Itertools;
 use native_tls;
 use std::sync::mpsc::channel;
 use std::sync::mpsc::Sender;
+use std::sync::Arc;
 use std::thread;
 
+pub mod calibrate;
+pub mod generator;
 pub mod result_processor;
 mod spec;
 pub mod utils;
 
+use crate::{
+    cors::CorsConfig,
+    diff::KnownResults,
+    digest::DigestAuth,
+    geoip::{remote_ip, GeoIp},
+    har::{HarEntry, HarRequest, HarResponse},
+    interactive::{Console, Seen},
+    negotiate::{self, Negotiate},
+    output::{
+        capture_headers, format_captured_headers, format_finding, format_result, save_formatted,
+        Journal,
+    },
+    progress::ProgressEvents,
+    proxy::ProxyConnector,
+    ratelimit::{Pacer, SlowRule, DEFAULT_MAX_DELAY},
+    rawpath::escape_raw_path,
+    report::{self, GroupBy, SortKey},
+    session::{send, Session},
+    severity::{Classifier, SeverityRule},
+    sigv4::{self, SigV4},
+    sink::{Pipeline, ResultSink},
+    summary::{AuthSurface, ErrorSummary, HeaderAudit, RedirectGroups},
+    template::{expand, expand_bytes},
+};
+use calibrate::{Baseline, Sample};
+use generator::Generator;
 use result_processor::{FuzzScanProcessor, FuzzScanProcessorConfig, SingleFuzzScanResult};
 
-use std::{fs, time::SystemTime};
+use std::time::{Duration, SystemTime};
 
 use indicatif::{ProgressBar, ProgressStyle};
 
@@ -28,21 +57,135 @@ pub struct FuzzBuster {
     pub n_threads: usize,
     pub ignore_certificate: bool,
     pub http_method: String,
-    pub http_body: String,
+    pub http_body: Vec<u8>,
     pub user_agent: String,
     pub http_headers: Vec<(String, String)>,
     pub wordlist_paths: Vec<String>,
+    pub payloads: Vec<(String, String)>,
+    pub mutate: bool,
+    pub auto_calibrate: bool,
+    pub hex_wordlists: bool,
+    pub generator: Option<Generator>,
+    pub slow_rules: Vec<SlowRule>,
+    pub max_rate_limit_wait: Duration,
+    pub favicon: bool,
+    pub certificate: bool,
+    pub sort: Option<SortKey>,
+    pub group_by: Option<GroupBy>,
+    pub interactive: bool,
     pub url: String,
     pub include_status_codes: Vec<String>,
     pub ignore_status_codes: Vec<String>,
     pub include_body: Vec<String>,
     pub ignore_body: Vec<String>,
     pub no_progress_bar: bool,
+    pub progress_json: String,
     pub exit_on_connection_errors: bool,
     pub output: String,
+    pub output_append: bool,
+    pub known: String,
+    pub tls_min_version: Option<native_tls::Protocol>,
+    pub tls_max_version: Option<native_tls::Protocol>,
+    pub har: String,
+    pub save_requests: String,
+    pub show_curl: bool,
+    pub burp: String,
+    pub raw_path: bool,
+    pub jwt_tokens: Vec<String>,
+    pub digest: Option<(String, String)>,
+    pub negotiate: Option<Arc<Negotiate>>,
+    pub aws_sigv4: Option<Arc<SigV4>>,
+    pub capture_headers: Vec<String>,
+    pub check_cors: bool,
+    pub audit_headers: bool,
+    pub max_results: usize,
+    pub severity_rules: Vec<SeverityRule>,
+    pub format: String,
     pub csrf_url: Option<String>,
     pub csrf_regex: Option<String>,
     pub csrf_headers: Option<Vec<(String, String)>>,
+    pub follow_up: Option<FollowUp>,
+    pub session: Option<Arc<Session>>,
+    pub geoip: Arc<GeoIp>,
+}
+
+/// A single threaded GET scan without output, the fields a scan needs are set on top of it
+impl Default for FuzzBuster {
+    fn default() -> Self {
+        FuzzBuster {
+            n_threads: 1,
+            ignore_certificate: false,
+            http_method: "GET".to_owned(),
+            http_body: Vec::new(),
+            user_agent: "rustbuster".to_owned(),
+            http_headers: Vec::new(),
+            wordlist_paths: Vec::new(),
+            payloads: Vec::new(),
+            mutate: false,
+            auto_calibrate: false,
+            hex_wordlists: false,
+            generator: None,
+            slow_rules: Vec::new(),
+            max_rate_limit_wait: DEFAULT_MAX_DELAY,
+            favicon: false,
+            certificate: true,
+            sort: None,
+            group_by: None,
+            interactive: false,
+            url: "".to_owned(),
+            include_status_codes: Vec::new(),
+            ignore_status_codes: Vec::new(),
+            include_body: Vec::new(),
+            ignore_body: Vec::new(),
+            no_progress_bar: false,
+            progress_json: "".to_owned(),
+            exit_on_connection_errors: false,
+            output: "".to_owned(),
+            output_append: false,
+            known: "".to_owned(),
+            tls_min_version: None,
+            tls_max_version: None,
+            har: "".to_owned(),
+            save_requests: "".to_owned(),
+            show_curl: false,
+            burp: "".to_owned(),
+            raw_path: false,
+            jwt_tokens: Vec::new(),
+            digest: None,
+            negotiate: None,
+            aws_sigv4: None,
+            capture_headers: Vec::new(),
+            check_cors: false,
+            audit_headers: false,
+            max_results: 0,
+            severity_rules: Vec::new(),
+            format: "".to_owned(),
+            csrf_url: None,
+            csrf_regex: None,
+            csrf_headers: None,
+            follow_up: None,
+            session: None,
+            geoip: Default::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FollowUp {
+    pub extract: Regex,
+    pub url: String,
+    pub body: String,
+}
+
+#[derive(Debug, Clone)]
+struct RequestOptions {
+    digest: Option<Arc<DigestAuth>>,
+    negotiate: Option<Arc<Negotiate>>,
+    aws_sigv4: Option<Arc<SigV4>>,
+    capture_headers: Vec<String>,
+    header_audit: Option<Arc<HeaderAudit>>,
+    follow_up: Option<FollowUp>,
+    session: Option<Arc<Session>>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -50,7 +193,7 @@ pub struct FuzzRequest {
     pub uri: hyper::Uri,
     pub http_method: String,
     pub http_headers: Vec<(String, String)>,
-    pub http_body: String,
+    pub http_body: Vec<u8>,
     pub user_agent: String,
     pub payload: Vec<String>,
     pub csrf_uri: Option<hyper::Uri>,
@@ -58,34 +201,157 @@ pub struct FuzzRequest {
     pub csrf_headers: Option<Vec<(String, String)>>,
 }
 
+fn response_extra(status: StatusCode, headers: &hyper::HeaderMap) -> Option<String> {
+    if status.is_redirection() {
+        headers
+            .get("Location")
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_owned())
+    } else if status == StatusCode::UNAUTHORIZED {
+        headers
+            .get("WWW-Authenticate")
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_owned())
+    } else {
+        None
+    }
+}
+
 impl FuzzBuster {
-    pub fn run(self) {
+    pub fn run(self) -> Vec<SingleFuzzScanResult> {
+        self.run_with_sinks(Vec::new())
+    }
+
+    /// Runs the scan, the custom sinks are handed the results the processor kept as they come
+    pub fn run_with_sinks<'a>(
+        self,
+        sinks: Vec<Box<dyn ResultSink<SingleFuzzScanResult> + Send + 'a>>,
+    ) -> Vec<SingleFuzzScanResult> {
         let (tx, rx) = channel::<SingleFuzzScanResult>();
         let mut tls_connector_builder = native_tls::TlsConnector::builder();
         tls_connector_builder.danger_accept_invalid_certs(self.ignore_certificate);
+        tls_connector_builder.min_protocol_version(self.tls_min_version);
+        tls_connector_builder.max_protocol_version(self.tls_max_version);
         let tls_connector = tls_connector_builder
             .build()
             .expect("TLS initialization failed");
-        let mut http_connector = HttpConnector::new(self.n_threads);
-        http_connector.enforce_http(false);
+        let http_connector = ProxyConnector::new(self.n_threads);
         let https_connector = HttpsConnector::from((http_connector, tls_connector));
-        let client = Client::builder().build(https_connector);
+        let client = Client::builder().build(https_connector.clone());
         let n_threads = self.n_threads;
+        let save_har = !self.har.is_empty()
+            || !self.save_requests.is_empty()
+            || !self.burp.is_empty()
+            || self.show_curl;
+        let pacer =
+            Arc::new(Pacer::new(self.slow_rules.clone()).with_max_delay(self.max_rate_limit_wait));
+        let console = if self.interactive {
+            crate::say!("{}", crate::banner::interactive());
+            Some(Console::start(pacer.clone()))
+        } else {
+            None
+        };
         let rp_config = FuzzScanProcessorConfig {
             include: self.include_status_codes.clone(),
             ignore: self.ignore_status_codes.clone(),
             include_body: self.include_body.clone(),
             ignore_body: self.ignore_body.clone(),
         };
-        let requests = self.build_requests();
+        let certificate = if self.certificate {
+            crate::certificate::fetch_certificate(
+                &self.url,
+                self.tls_min_version,
+                self.tls_max_version,
+            )
+        } else {
+            None
+        };
+        if let Some(info) = &certificate {
+            crate::say!("{}", crate::banner::certificate(info));
+        }
+        let mut certificates = crate::certificate::CertificateReport::new(certificate);
+
+        if self.favicon && !self.fuzzes_host() {
+            if let Some((hash, product)) =
+                crate::favicon::fingerprint(&self.url, self.ignore_certificate)
+            {
+                crate::say!("{}", crate::banner::favicon(hash, product));
+            }
+        }
+
+        let (requests, total_numbers_of_request, exact): (
+            Box<dyn Iterator<Item = FuzzRequest> + Send>,
+            usize,
+            bool,
+        ) = match &self.generator {
+            Some(generator) => {
+                let (requests, count) = self.generated_requests(generator);
+                (requests, count, false)
+            }
+            None => {
+                let requests = self.build_requests();
+                let count = requests.len();
+                (Box::new(requests.into_iter()), count, true)
+            }
+        };
+        let mut requests = requests.peekable();
+        let options = RequestOptions {
+            digest: self
+                .digest
+                .as_ref()
+                .map(|(username, password)| Arc::new(DigestAuth::new(username, password))),
+            negotiate: self.negotiate.clone(),
+            aws_sigv4: self.aws_sigv4.clone(),
+            capture_headers: self.capture_headers.clone(),
+            header_audit: if self.audit_headers {
+                Some(Arc::new(HeaderAudit::default()))
+            } else {
+                None
+            },
+            follow_up: self.follow_up.clone(),
+            session: self.session.clone(),
+        };
+        if let (Some(digest), Some(request)) = (&options.digest, requests.peek()) {
+            digest.handshake(
+                Client::builder().build(https_connector.clone()),
+                &request.uri.to_string(),
+            );
+        }
+
+        if let Some(session) = &self.session {
+            session
+                .clone()
+                .handshake(Client::builder().build(https_connector.clone()));
+        }
+
+        let baseline = if self.auto_calibrate {
+            self.calibrate(
+                Client::builder().build(https_connector.clone()),
+                &pacer,
+                &options,
+            )
+        } else {
+            None
+        };
+        let header_audit = options.header_audit.clone();
         let mut current_numbers_of_request = 0;
-        let total_numbers_of_request = requests.len();
         let start_time = SystemTime::now();
-        let mut result_processor = FuzzScanProcessor::new(rp_config);
+        let mut journal = Journal::open(&self.output);
+        let mut pipeline = Pipeline::new(FuzzScanProcessor::new(rp_config));
+        pipeline.register(Box::new(&mut journal));
+        for sink in sinks {
+            pipeline.register(sink);
+        }
+        let mut errors = ErrorSummary::default();
+        let mut progress = ProgressEvents::open(&self.progress_json);
+        let mut auth_surface = AuthSurface::default();
+        let mut redirect_groups = RedirectGroups::default();
+        let mut known_results = KnownResults::load(&self.known);
+        let classifier = Classifier::new(self.severity_rules.clone());
         let bar = if self.no_progress_bar {
             ProgressBar::hidden()
         } else {
-            ProgressBar::new(requests.len() as u64)
+            ProgressBar::new(total_numbers_of_request as u64)
         };
         bar.set_draw_delta(100);
         bar.set_style(ProgressStyle::default_bar()
@@ -94,7 +360,14 @@ impl FuzzBuster {
 
         let stream = futures::stream::iter_ok(requests)
             .map(move |request| {
-                FuzzBuster::make_request_future(tx.clone(), client.clone(), request)
+                FuzzBuster::make_request_future(
+                    tx.clone(),
+                    client.clone(),
+                    request,
+                    save_har,
+                    pacer.clone(),
+                    options.clone(),
+                )
             })
             .buffer_unordered(n_threads)
             .for_each(Ok)
@@ -103,8 +376,22 @@ impl FuzzBuster {
         let _ = thread::spawn(move || rt::run(stream));
 
         while current_numbers_of_request != total_numbers_of_request {
+            if self.max_results > 0 && pipeline.processor.results.len() >= self.max_results {
+                if self.no_progress_bar {
+                    crate::say!("{}", crate::banner::max_results(self.max_results));
+                } else {
+                    bar.println(crate::banner::max_results(self.max_results));
+                }
+                break;
+            }
+
             current_numbers_of_request = current_numbers_of_request + 1;
             bar.inc(1);
+            progress.update(
+                current_numbers_of_request,
+                total_numbers_of_request,
+                pipeline.processor.results.len(),
+            );
             let seconds_from_start = start_time.elapsed().unwrap().as_millis() / 1000;
             if seconds_from_start != 0 {
                 bar.set_message(
@@ -114,18 +401,23 @@ impl FuzzBuster {
                 bar.set_message("warming up...")
             }
 
-            let msg = match rx.recv() {
+            let mut msg = match rx.recv() {
                 Ok(msg) => msg,
+                // the generated requests that are not valid are never sent
+                Err(_) if !exact => break,
                 Err(_err) => {
                     error!("{:?}", _err);
                     break;
                 }
             };
 
+            errors.add_status(&msg.status);
             match &msg.error {
                 Some(e) => {
                     error!("{} - {:?}", msg.url, e);
+                    errors.add_error(e);
                     if current_numbers_of_request == 1 || self.exit_on_connection_errors {
+                        crate::exit_code::abort();
                         warn!("Check connectivity to the target");
                         break;
                     }
@@ -135,58 +427,265 @@ impl FuzzBuster {
                 None => (),
             }
 
-            let was_added = result_processor.maybe_add_result(msg.clone());
+            msg.certificate = certificates.take(&msg.url);
+            msg.severity = Some(classifier.classify(&msg.url, &msg.status, &msg.body));
+            let deviations = match &baseline {
+                Some(baseline) => {
+                    let sample = Sample::new(&msg.status, &msg.body, msg.latency);
+                    let deviations = baseline.deviations(&sample);
+                    if deviations.is_empty() {
+                        continue;
+                    }
+                    deviations
+                }
+                None => Vec::new(),
+            };
+            if let (true, Some(entry)) = (self.show_curl, &msg.har_entry) {
+                msg.curl = Some(crate::curl::command_from_env(
+                    &entry.request,
+                    self.ignore_certificate,
+                ));
+            }
+            let was_added = pipeline.maybe_add_result(msg.clone());
             if was_added {
-                let mut extra = msg.extra.unwrap_or("".to_owned());
+                // the live filters only hide the result from the output, they can still change
+                let live_hidden = console.as_ref().is_some_and(|console| {
+                    let seen = Seen {
+                        method: msg.method.to_owned(),
+                        status: msg.status.to_owned(),
+                        url: msg.url.to_owned(),
+                        length: msg.body.len(),
+                    };
+                    console.record(seen, &msg.body)
+                });
+                auth_surface.maybe_add(&msg.url, &msg.status, msg.extra.as_deref());
+                if known_results.is_known(&msg) {
+                    continue;
+                }
 
-                if !extra.is_empty() {
-                    extra = format!("\n\t\t\t\t\t\t=> {}", extra)
+                if let (true, Some(location)) = (msg.status.starts_with('3'), &msg.extra) {
+                    if !redirect_groups.add(&msg.url, location) {
+                        continue;
+                    }
                 }
 
-                let n_tabs = match msg.status.len() / 8 {
-                    3 => 1,
-                    2 => 2,
-                    1 => 3,
-                    0 => 4,
-                    _ => 0,
-                };
+                if live_hidden {
+                    continue;
+                }
 
-                if self.no_progress_bar {
+                if !self.format.is_empty() {
+                    let line = format_result(&self.format, &msg, msg.body.len());
+                    if self.no_progress_bar {
+                        println!("{}", line);
+                    } else {
+                        bar.println(line);
+                    }
+                    continue;
+                }
+
+                if crate::output::is_quiet() {
                     println!(
-                        "{}\t{}{}{} ({:?}){}",
+                        "{}\t{}\t{}\t{}",
                         msg.method,
                         msg.status,
-                        "\t".repeat(n_tabs),
                         msg.url,
-                        msg.payload,
-                        extra,
+                        msg.payload.join(",")
                     );
+                    continue;
+                }
+
+                let mut extra = msg.extra.unwrap_or("".to_owned());
+
+                if !extra.is_empty() {
+                    extra = format!("\n\t\t\t\t\t\t=> {}", extra)
+                }
+
+                extra.push_str(&format_captured_headers(&msg.captured_headers));
+                if let Some(curl) = &msg.curl {
+                    extra.push_str(&format!("\n\t\t\t\t\t\t=> {}", curl));
+                }
+                if !deviations.is_empty() {
+                    extra.push_str(&format!(
+                        "\n\t\t\t\t\t\t=> Deviates: {}",
+                        deviations.join(", ")
+                    ));
+                }
+
+                let line = format_finding(&msg.method, &msg.status, &msg.url, msg.severity);
+                if self.no_progress_bar {
+                    println!("{} ({:?}){}", line, msg.payload, extra);
                 } else {
                     bar.println(format!(
-                        "{}\t{}{}{}\n\t\t\t\t\t\t=> PAYLOAD: {:?}{}",
-                        msg.method,
-                        msg.status,
-                        "\t".repeat(n_tabs),
-                        msg.url,
-                        msg.payload,
-                        extra,
+                        "{}\n\t\t\t\t\t\t=> PAYLOAD: {:?}{}",
+                        line, msg.payload, extra,
                     ));
                 }
             }
         }
 
+        let mut result_processor = pipeline.into_processor();
+        progress.finish(
+            current_numbers_of_request,
+            total_numbers_of_request,
+            result_processor.results.len(),
+        );
         bar.finish();
-        println!("{}", crate::banner::ending_time());
+        if let Some(console) = &console {
+            result_processor
+                .results
+                .retain(|r| !console.hides(&r.url, &r.status, r.body.len()));
+        }
+
+        if self.sort.is_some() || self.group_by.is_some() {
+            report::order(&mut result_processor.results, self.sort, self.group_by);
+            crate::say!(
+                "{}",
+                report::summary(&result_processor.results, self.group_by)
+            );
+        }
+
+        if !auth_surface.is_empty() {
+            crate::say!("{}", auth_surface.report());
+        }
+
+        if let Some(header_audit) = &header_audit {
+            crate::say!("{}", header_audit.report());
+        }
+
+        if redirect_groups.collapsed() > 0 {
+            crate::say!("{}", redirect_groups.report());
+        }
+
+        if self.check_cors {
+            let urls: Vec<String> = result_processor
+                .results
+                .iter()
+                .map(|r| r.url.to_owned())
+                .collect();
+            let cors_config = CorsConfig {
+                n_threads: self.n_threads,
+                ignore_certificate: self.ignore_certificate,
+                user_agent: self.user_agent.to_owned(),
+                http_headers: self.http_headers.clone(),
+                tls_min_version: self.tls_min_version,
+                tls_max_version: self.tls_max_version,
+            };
+            crate::say!(
+                "{}",
+                crate::cors::report(&crate::cors::check(&urls, &cors_config))
+            );
+        }
+
+        if !errors.is_empty() {
+            crate::say!("{}", errors.report());
+        }
 
-        if !self.output.is_empty() {
-            result_processor.save_fuzz_results(&self.output);
+        if known_results.skipped > 0 {
+            crate::say!("{}", crate::banner::known_results(known_results.skipped));
         }
+
+        crate::say!("{}", crate::banner::ending_time());
+
+        if self.geoip.is_enabled() {
+            for result in result_processor.results.iter_mut() {
+                result.geo = self.geoip.annotate(result.server_ip);
+            }
+        }
+
+        if !self.har.is_empty() {
+            crate::har::save_har(
+                &self.har,
+                result_processor
+                    .results
+                    .iter()
+                    .filter_map(|r| r.har_entry.clone())
+                    .collect(),
+            );
+        }
+
+        if !self.save_requests.is_empty() {
+            crate::har::save_requests(
+                &self.save_requests,
+                result_processor
+                    .results
+                    .iter()
+                    .filter_map(|r| r.har_entry.clone())
+                    .collect(),
+            );
+        }
+
+        if !self.burp.is_empty() {
+            crate::burp::save_items(
+                &self.burp,
+                &result_processor
+                    .results
+                    .iter()
+                    .filter_map(|r| r.har_entry.clone())
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        if !self.output.is_empty() && !self.format.is_empty() {
+            let lines: Vec<String> = result_processor
+                .results
+                .iter()
+                .map(|r| format_result(&self.format, r, r.body.len()))
+                .collect();
+            save_formatted(&self.output, &lines, self.output_append);
+        } else if !self.output.is_empty() {
+            result_processor.save_fuzz_results(&self.output, self.output_append);
+        }
+        journal.finish();
+
+        result_processor.results
+    }
+
+    /// The client is dedicated to the calibration, the runtime only stops once it's dropped
+    fn calibrate(
+        &self,
+        client: Client<HttpsConnector<ProxyConnector>>,
+        pacer: &Arc<Pacer>,
+        options: &RequestOptions,
+    ) -> Option<Baseline> {
+        let (tx, rx) = channel::<SingleFuzzScanResult>();
+        let pacer = pacer.clone();
+        let options = RequestOptions {
+            header_audit: None,
+            ..options.clone()
+        };
+        let requests = self.calibration_requests();
+        rt::run(futures::stream::iter_ok(requests).for_each(move |request| {
+            FuzzBuster::make_request_future(
+                tx.clone(),
+                client.clone(),
+                request,
+                false,
+                pacer.clone(),
+                options.clone(),
+            )
+        }));
+
+        let samples: Vec<Sample> = rx
+            .try_iter()
+            .filter(|r| r.error.is_none())
+            .map(|r| Sample::new(&r.status, &r.body, r.latency))
+            .collect();
+        let baseline = Baseline::new(&samples);
+        match &baseline {
+            Some(baseline) => crate::say!("{}", crate::banner::fuzz_baseline(baseline)),
+            None => warn!("No baseline response, every result is shown"),
+        }
+
+        baseline
     }
 
     fn make_request_future(
         tx: Sender<SingleFuzzScanResult>,
-        client: Client<HttpsConnector<HttpConnector>>,
+        client: Client<HttpsConnector<ProxyConnector>>,
         request: FuzzRequest,
+        save_har: bool,
+        pacer: Arc<Pacer>,
+        options: RequestOptions,
     ) -> impl Future<Item = (), Error = ()> {
         let tx_err = tx.clone();
         let tx_err2 = tx.clone();
@@ -195,16 +694,30 @@ impl FuzzBuster {
             method: request.http_method.clone(),
             status: StatusCode::default().to_string(),
             payload: request.payload.clone(),
-            body: request.http_body.clone(),
+            body: String::from_utf8_lossy(&request.http_body).into_owned(),
             error: None,
             extra: None,
+            certificate: None,
+            captured_headers: Vec::new(),
+            severity: None,
+            extracted: None,
+            geo: None,
+            server_ip: None,
+            curl: None,
+            har_entry: None,
+            latency: 0,
         };
         let mut target_err = target.clone();
         let mut target_err2 = target.clone();
         let mut request_builder = Request::builder();
+        let word = request.payload.first().cloned().unwrap_or_default();
+        let next_client = client.clone();
 
         for header_tuple in &request.http_headers {
-            request_builder.header(header_tuple.0.as_str(), header_tuple.1.as_str());
+            request_builder.header(
+                expand(&header_tuple.0, &word).as_str(),
+                expand(&header_tuple.1, &word).as_str(),
+            );
         }
 
         let csrf_fut = match &request.csrf_uri {
@@ -240,8 +753,7 @@ impl FuzzBuster {
                             .and_then(|res| res.into_body().concat2())
                             .join3(futures::future::ok(re), futures::future::ok(request))
                             .and_then(|(body, re, request)| {
-                                let vec = body.iter().cloned().collect();
-                                let body = String::from_utf8(vec).unwrap();
+                                let body = String::from_utf8_lossy(&body).into_owned();
                                 match re.captures_iter(&body).take(1).next() {
                                     Some(v) => Ok((Some(v[1].to_owned()), request)),
                                     None => {
@@ -259,7 +771,9 @@ impl FuzzBuster {
             }
         };
 
-        csrf_fut
+        pacer
+            .wait()
+            .then(move |_| csrf_fut)
             .and_then(move |(csrf, request)| {
                 let request = match csrf {
                     Some(v) => {
@@ -268,39 +782,142 @@ impl FuzzBuster {
                     }
                     _ => request,
                 };
+                let http_body = expand_bytes(&request.http_body, &word);
+                let next_headers: Vec<(String, String)> = request
+                    .http_headers
+                    .iter()
+                    .map(|(header, value)| (expand(header, &word), expand(value, &word)))
+                    .collect();
+                let user_agent = request.user_agent.clone();
+                if let Some(authorization) = options
+                    .digest
+                    .as_ref()
+                    .and_then(|d| d.authorization(&request.http_method, &request.uri))
+                {
+                    request_builder.header("Authorization", authorization.as_str());
+                }
+
+                let context = options
+                    .negotiate
+                    .as_ref()
+                    .and_then(|n| request.uri.host().and_then(|h| n.authorization(h)));
+                if let Some(context) = &context {
+                    request_builder.header("Authorization", context.authorization().as_str());
+                }
+
+                if let (Some(sigv4), Some(host)) =
+                    (&options.aws_sigv4, sigv4::host_header(&request.uri))
+                {
+                    for (name, value) in
+                        sigv4.sign(&request.http_method, &host, &request.uri, &http_body)
+                    {
+                        request_builder.header(name.as_str(), value.as_str());
+                    }
+                }
 
                 let request = request_builder
                     .header("User-Agent", &request.user_agent[..])
                     .method(&request.http_method[..])
                     .uri(&request.uri)
-                    .body(Body::from(request.http_body.clone()))
+                    .body(Body::from(http_body.clone()))
                     .expect("Request builder");
+                let har_request = if save_har {
+                    Some(HarRequest::new(&request, &http_body))
+                } else {
+                    None
+                };
+                let started = Local::now();
+                let digest = options.digest;
+                let capture_names = options.capture_headers;
+                let next_capture_names = capture_names.clone();
+                let header_audit = options.header_audit;
+                let follow_up = options.follow_up;
+                let session = options.session;
+                let next_session = session.clone();
+                let uri = request.uri().clone();
 
-                client
-                    .request(request)
+                send(&client, session, request, http_body)
                     .and_then(move |res| {
-                        let status = res.status();
-                        target.status = status.to_string();
-                        if status.is_redirection() {
-                            target.extra = Some(
-                                res.headers()
-                                    .get("Location")
-                                    .unwrap()
-                                    .to_str()
-                                    .unwrap()
-                                    .to_owned(),
-                            );
+                        negotiate::verify(context, res.headers());
+                        pacer.observe(res.headers());
+                        pacer.observe_status(res.status());
+                        target.latency = (Local::now() - started).num_milliseconds().max(0) as u64;
+                        if let Some(digest) = digest {
+                            digest.observe(res.headers());
+                        }
+                        if let Some(header_audit) = header_audit {
+                            header_audit.observe(&uri, res.headers());
                         }
+                        target.status = res.status().to_string();
+                        target.captured_headers = capture_headers(res.headers(), &capture_names);
+                        target.server_ip = remote_ip(&res);
+                        target.extra = response_extra(res.status(), res.headers());
 
-                        futures::future::ok(target).join(res.into_body().concat2())
+                        let (parts, body) = res.into_parts();
+                        futures::future::ok((target, parts)).join(body.concat2())
                     })
-                    .and_then(move |(target, body)| {
+                    .and_then(move |((target, parts), body)| {
                         let mut target = target;
-                        let vec = body.iter().cloned().collect();
-                        let body = String::from_utf8(vec).unwrap();
+                        if let Some(har_request) = har_request {
+                            target.har_entry = Some(HarEntry::new(
+                                started,
+                                har_request,
+                                HarResponse::new(&parts, &body),
+                            ));
+                        }
+
+                        let body = String::from_utf8_lossy(&body).into_owned();
+                        let extracted = follow_up
+                            .as_ref()
+                            .and_then(|f| utils::extract_value(&f.extract, &body));
                         target.body = body;
-                        tx.send(target.clone()).unwrap();
-                        Ok(())
+
+                        let (follow_up, extracted) = match (follow_up, extracted) {
+                            (Some(f), Some(v)) => (f, v),
+                            _ => {
+                                tx.send(target).unwrap();
+                                return futures::future::Either::A(futures::future::ok(()));
+                            }
+                        };
+
+                        let next_url = utils::fill_follow_up(&follow_up.url, &extracted, &word);
+                        let next_body = utils::fill_follow_up(&follow_up.body, &extracted, &word);
+                        let next_method = if next_body.is_empty() { "GET" } else { "POST" };
+                        let mut next_builder = Request::builder();
+                        for (header, value) in next_headers.iter() {
+                            next_builder.header(header.as_str(), value.as_str());
+                        }
+
+                        if let Some(cookies) = utils::session_cookies(&parts.headers) {
+                            next_builder.header("Cookie", cookies.as_str());
+                        }
+
+                        target.url = next_url.clone();
+                        target.method = next_method.to_owned();
+                        target.extracted = Some(extracted);
+                        let next_request = next_builder
+                            .header("User-Agent", &user_agent[..])
+                            .method(next_method)
+                            .uri(&next_url[..])
+                            .body(Body::from(next_body.clone()))
+                            .expect("Request builder");
+                        let next_body_bytes = next_body.into_bytes();
+
+                        futures::future::Either::B(
+                            send(&next_client, next_session, next_request, next_body_bytes)
+                                .and_then(move |res| {
+                                    target.status = res.status().to_string();
+                                    target.captured_headers =
+                                        capture_headers(res.headers(), &next_capture_names);
+                                    target.server_ip = remote_ip(&res);
+                                    target.extra = response_extra(res.status(), res.headers());
+                                    futures::future::ok(target).join(res.into_body().concat2())
+                                })
+                                .map(move |(mut target, body)| {
+                                    target.body = String::from_utf8_lossy(&body).into_owned();
+                                    tx.send(target).unwrap_or(());
+                                }),
+                        )
                     })
                     .or_else(move |e| {
                         target_err.error = Some(e.to_string());
@@ -315,89 +932,238 @@ impl FuzzBuster {
             })
     }
 
+    /// Whether a marker is in the scheme, host or port of the URL, the favicon of the target is
+    /// then unknown before the scan
+    fn fuzzes_host(&self) -> bool {
+        let (scheme, rest) = self.url.split_once("://").unwrap_or(("", &self.url));
+        let host = format!("{}{}", scheme, rest.split('/').next().unwrap_or_default());
+        host.contains("FUZZ")
+            || self
+                .payloads
+                .iter()
+                .any(|(marker, _)| host.contains(marker.as_str()))
+    }
+
+    fn wordlists(&self) -> Vec<Vec<String>> {
+        let paths = if self.payloads.is_empty() {
+            self.wordlist_paths.clone()
+        } else {
+            self.payloads
+                .iter()
+                .map(|(_, path)| path.to_owned())
+                .collect()
+        };
+        let mut wordlists = if self.mutate {
+            vec![crate::mutate::mutations(&String::from_utf8_lossy(
+                &self.http_body,
+            ))]
+        } else {
+            paths
+                .iter()
+                .map(|wordlist| {
+                    crate::wordlist::read_filtered(wordlist)
+                        .expect("Something went wrong reading the wordlist file")
+                        .lines()
+                        .filter(|word| {
+                            !word.starts_with('#') && (self.raw_path || !word.starts_with(' '))
+                        })
+                        .filter(|word| {
+                            let valid = !self.hex_wordlists || utils::decode_hex(word).is_some();
+                            if !valid {
+                                debug!("Invalid hex entry: {}", word);
+                            }
+                            valid
+                        })
+                        .map(|x| x.to_owned())
+                        .collect::<Vec<String>>()
+                })
+                .collect::<Vec<Vec<String>>>()
+        };
+        if self.payloads.is_empty() && !self.jwt_tokens.is_empty() {
+            wordlists.push(self.jwt_tokens.clone());
+        }
+
+        wordlists
+    }
+
     pub fn build_requests(&self) -> Vec<FuzzRequest> {
         debug!("building requests");
-        let mut requests: Vec<FuzzRequest> = Vec::new();
-        let wordlists_iter = self
-            .wordlist_paths
-            .iter()
-            .map(|wordlist| {
-                fs::read_to_string(wordlist)
-                    .expect("Something went wrong reading the wordlist file")
-                    .lines()
-                    .filter(|word| !word.starts_with('#') && !word.starts_with(' '))
-                    .map(|x| x.to_owned())
-                    .collect::<Vec<String>>()
+        match &self.generator {
+            Some(generator) => self.generated_requests(generator).0.collect(),
+            None => self
+                .wordlists()
+                .into_iter()
+                .multi_cartesian_product()
+                .filter_map(|words| self.request_for(words))
+                .collect(),
+        }
+    }
+
+    /// The generated words come last, the requests are built one at a time as they are sent and
+    /// their count is an upper bound
+    fn generated_requests(
+        &self,
+        generator: &Generator,
+    ) -> (Box<dyn Iterator<Item = FuzzRequest> + Send>, usize) {
+        let wordlists = self.wordlists();
+        let count = wordlists.iter().fold(generator.count(), |acc, w| {
+            acc.saturating_mul(w.len() as u64)
+        });
+        let combinations: Box<dyn Iterator<Item = Vec<String>> + Send> = if wordlists.is_empty() {
+            Box::new(std::iter::once(Vec::new()))
+        } else {
+            Box::new(wordlists.into_iter().multi_cartesian_product())
+        };
+        let generator = generator.clone();
+        let this = self.clone();
+        let requests = combinations
+            .flat_map(move |words| {
+                generator.words().map(move |word| {
+                    let mut words = words.clone();
+                    words.push(word);
+                    words
+                })
             })
-            .multi_cartesian_product();
+            .filter_map(move |words| this.request_for(words));
+
+        (Box::new(requests), count.min(usize::MAX as u64) as usize)
+    }
 
-        for words in wordlists_iter {
-            let mut url = self.url.clone();
-            let mut http_body = self.http_body.clone();
-            let mut http_headers = self.http_headers.clone();
-            let payload = words.clone();
+    /// Requests with random payloads, their responses are the usual answer of the target
+    pub fn calibration_requests(&self) -> Vec<FuzzRequest> {
+        let markers = if self.mutate {
+            1
+        } else if !self.payloads.is_empty() {
+            self.payloads.len()
+        } else {
+            self.wordlist_paths.len()
+                + usize::from(!self.jwt_tokens.is_empty())
+                + usize::from(self.generator.is_some())
+        };
 
-            for word in words {
+        (0..calibrate::SAMPLES)
+            .filter_map(|_| self.request_for((0..markers).map(|_| self.random_word()).collect()))
+            .collect()
+    }
+
+    fn random_word(&self) -> String {
+        if self.hex_wordlists {
+            crate::digest::to_hex(expand("{{rand(6)}}", "").as_bytes())
+        } else {
+            expand("{{rand(12)}}", "")
+        }
+    }
+
+    fn request_for(&self, words: Vec<String>) -> Option<FuzzRequest> {
+        let mut url = self.url.clone();
+        let mut http_body = self.http_body.clone();
+        let mut http_headers = self.http_headers.clone();
+        let mut http_method = self.http_method.clone();
+        let payload = words.clone();
+        let (body_words, words) = if self.hex_wordlists {
+            let body_words: Vec<Vec<u8>> = words
+                .iter()
+                .map(|word| utils::decode_hex(word).unwrap_or_default())
+                .collect();
+            let words = body_words.iter().map(|w| utils::escape_binary(w)).collect();
+            (body_words, words)
+        } else {
+            (words.iter().map(|w| w.as_bytes().to_vec()).collect(), words)
+        };
+
+        if self.mutate {
+            http_body = body_words[0].to_owned();
+        } else if !self.payloads.is_empty() {
+            let mut bindings = self
+                .payloads
+                .iter()
+                .map(|(marker, _)| marker)
+                .zip(words.iter().zip(body_words.iter()))
+                .collect::<Vec<_>>();
+            bindings.sort_by_key(|(marker, _)| std::cmp::Reverse(marker.len()));
+
+            for (marker, (word, body_word)) in bindings {
+                http_method = http_method.replace(marker.as_str(), word);
+                url = url.replace(marker.as_str(), word);
+                for (header, value) in http_headers.iter_mut() {
+                    *header = header.replace(marker.as_str(), word);
+                    *value = value.replace(marker.as_str(), word);
+                }
+                http_body = utils::replace_bytes(&http_body, marker, body_word, usize::MAX);
+            }
+
+            if http_method != self.http_method
+                && hyper::Method::from_bytes(http_method.as_bytes()).is_err()
+            {
+                debug!("Method: {}", http_method);
+                return None;
+            }
+        } else {
+            let mut words = words.iter().zip(body_words.iter());
+            if http_method.contains("FUZZ") {
+                if let Some((word, _)) = words.next() {
+                    http_method = http_method.replacen("FUZZ", word, 1);
+                }
+
+                if hyper::Method::from_bytes(http_method.as_bytes()).is_err() {
+                    debug!("Method: {}", http_method);
+                    return None;
+                }
+            }
+
+            for (word, body_word) in words {
                 if url.contains("FUZZ") {
-                    url = url.replacen("FUZZ", &word, 1);
+                    url = url.replacen("FUZZ", word, 1);
                 }
 
                 for (header, value) in http_headers.iter_mut() {
                     if header.contains("FUZZ") {
-                        *header = header.replacen("FUZZ", &word, 1);
+                        *header = header.replacen("FUZZ", word, 1);
                         break;
                     } else if value.contains("FUZZ") {
-                        *value = value.replacen("FUZZ", &word, 1);
+                        *value = value.replacen("FUZZ", word, 1);
                         break;
                     }
                 }
 
-                if http_body.contains("FUZZ") {
-                    http_body = http_body.replacen("FUZZ", &word, 1);
-                }
+                http_body = utils::replace_bytes(&http_body, "FUZZ", body_word, 1);
             }
+        }
 
-            match url.parse::<hyper::Uri>() {
-                Ok(uri) => match &self.csrf_url {
-                    Some(csrf_url) => match csrf_url.parse::<hyper::Uri>() {
-                        Ok(csrf_uri) => {
-                            requests.push(FuzzRequest {
-                                http_body,
-                                uri,
-                                http_headers,
-                                payload,
-                                user_agent: self.user_agent.clone(),
-                                http_method: self.http_method.clone(),
-                                csrf_uri: Some(csrf_uri),
-                                csrf_regex: self.csrf_regex.to_owned(),
-                                csrf_headers: self.csrf_headers.clone(),
-                            });
-                        }
-                        Err(e) => {
-                            debug!("CSRF URI: {}", e);
-                        }
-                    },
-                    None => {
-                        requests.push(FuzzRequest {
-                            http_body,
-                            uri,
-                            http_headers,
-                            payload,
-                            user_agent: self.user_agent.clone(),
-                            http_method: self.http_method.clone(),
-                            csrf_uri: None,
-                            csrf_regex: None,
-                            csrf_headers: None,
-                        });
-                    }
-                },
+        url = expand(&url, payload.first().map(|w| w.as_str()).unwrap_or(""));
+        if self.raw_path {
+            url = escape_raw_path(&url);
+        }
+
+        let uri = match url.parse::<hyper::Uri>() {
+            Ok(v) => v,
+            Err(e) => {
+                debug!("URI: {}", e);
+                return None;
+            }
+        };
+        let csrf_uri = match &self.csrf_url {
+            Some(csrf_url) => match csrf_url.parse::<hyper::Uri>() {
+                Ok(v) => Some(v),
                 Err(e) => {
-                    debug!("URI: {}", e);
+                    debug!("CSRF URI: {}", e);
+                    return None;
                 }
-            }
-        }
+            },
+            None => None,
+        };
 
-        requests
+        Some(FuzzRequest {
+            http_body,
+            uri,
+            http_headers,
+            payload,
+            user_agent: self.user_agent.clone(),
+            http_method,
+            csrf_regex: csrf_uri.as_ref().and(self.csrf_regex.clone()),
+            csrf_headers: csrf_uri.as_ref().and(self.csrf_headers.clone()),
+            csrf_uri,
+        })
     }
 
     pub fn replace_csrf(request: FuzzRequest, csrf: String) -> FuzzRequest {
@@ -413,7 +1179,7 @@ impl FuzzBuster {
             *value = value.replace("CSRFCSRF", &csrf);
         }
 
-        p.http_body = p.http_body.replace("CSRFCSRF", &csrf);
+        p.http_body = utils::replace_bytes(&p.http_body, "CSRFCSRF", csrf.as_bytes(), usize::MAX);
         p
     }
 }
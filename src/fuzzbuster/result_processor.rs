@@ -1,5 +1,10 @@
 use serde::{Deserialize, Serialize};
-use std::{fs::File, io::Write, path::Path, str};
+use std::{fs::File, io::Write, net::IpAddr, path::Path, str};
+
+use crate::{
+    certificate::CertificateInfo, geoip::GeoInfo, har::HarEntry, output::merge_results,
+    severity::Severity, sink::ResultSink,
+};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SingleFuzzScanResult {
@@ -10,6 +15,24 @@ pub struct SingleFuzzScanResult {
     pub extra: Option<String>,
     pub payload: Vec<String>,
     pub body: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub certificate: Option<CertificateInfo>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub captured_headers: Vec<(String, String)>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub severity: Option<Severity>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub extracted: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub geo: Option<GeoInfo>,
+    #[serde(skip)]
+    pub server_ip: Option<IpAddr>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub curl: Option<String>,
+    #[serde(skip)]
+    pub har_entry: Option<HarEntry>,
+    #[serde(skip)]
+    pub latency: u64,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -34,7 +57,33 @@ impl FuzzScanProcessor {
         }
     }
 
-    pub fn maybe_add_result(&mut self, res: SingleFuzzScanResult) -> bool {
+    pub fn save_fuzz_results(&self, path: &str, append: bool) {
+        let json_string = if append {
+            serde_json::to_string(&merge_results(path, &self.results, |r| {
+                (r.method.clone(), r.url.clone(), r.payload.clone())
+            }))
+            .unwrap()
+        } else {
+            serde_json::to_string(&self.results).unwrap()
+        };
+
+        let mut file = match File::create(Path::new(path)) {
+            Ok(f) => f,
+            Err(e) => {
+                error!("Error while creating file: {}\n{}", path, e);
+                return;
+            }
+        };
+
+        match file.write_all(json_string.as_bytes()) {
+            Ok(_) => debug!("Results saved to: {}", path),
+            Err(e) => error!("Error while writing results to file: {}\n{}", path, e),
+        };
+    }
+}
+
+impl ResultSink<SingleFuzzScanResult> for FuzzScanProcessor {
+    fn accept(&self, res: &SingleFuzzScanResult) -> bool {
         trace!("{:?}", res);
 
         if self.config.ignore_body.len() != 0 {
@@ -48,7 +97,6 @@ impl FuzzScanProcessor {
         if self.config.include_body.len() != 0 {
             for include in &self.config.include_body {
                 if res.body.contains(include) {
-                    self.results.push(res);
                     return true;
                 }
             }
@@ -65,34 +113,15 @@ impl FuzzScanProcessor {
         if self.config.include.len() != 0 {
             for code in &self.config.include {
                 if res.status.starts_with(code) {
-                    self.results.push(res);
                     return true;
                 }
             }
         }
 
-        if self.config.include.len() == 0 && self.config.include_body.len() == 0 {
-            self.results.push(res);
-            return true;
-        }
-
-        false
+        self.config.include.len() == 0 && self.config.include_body.len() == 0
     }
 
-    pub fn save_fuzz_results(self, path: &str) {
-        let json_string = serde_json::to_string(&self.results).unwrap();
-
-        let mut file = match File::create(Path::new(path)) {
-            Ok(f) => f,
-            Err(e) => {
-                error!("Error while creating file: {}\n{}", path, e);
-                return;
-            }
-        };
-
-        match file.write_all(json_string.as_bytes()) {
-            Ok(_) => debug!("Results saved to: {}", path),
-            Err(e) => error!("Error while writing results to file: {}\n{}", path, e),
-        };
+    fn store(&mut self, res: SingleFuzzScanResult) {
+        self.results.push(res);
     }
 }
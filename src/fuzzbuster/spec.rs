@@ -6,24 +6,16 @@ test_suite! {
     fixture fuzzbuster_url_single() -> crate::fuzzbuster::FuzzBuster {
         setup(&mut self) {
             crate::fuzzbuster::FuzzBuster {
-                n_threads: 1,
                 ignore_certificate: true,
-                http_method: "GET".to_owned(),
-                http_body: "body".to_owned(),
+                http_body: b"body".to_vec(),
                 user_agent: "ua".to_owned(),
                 http_headers: vec![("Header".to_owned(), "Value".to_owned())],
                 wordlist_paths: vec!["./examples/wordlist_short".to_owned()],
                 url: "http://localhost/FUZZ".to_owned(),
-                include_status_codes: vec![],
                 ignore_status_codes: vec!["404".to_owned()],
-                include_body: vec![],
-                ignore_body: vec![],
                 no_progress_bar: true,
-                exit_on_connection_errors: false,
-                output: "".to_owned(),
-                csrf_url: None,
-                csrf_regex: None,
-                csrf_headers: None,
+                certificate: false,
+                ..Default::default()
             }
         }
     }
@@ -31,24 +23,34 @@ test_suite! {
     fixture fuzzbuster_url_multiple() -> crate::fuzzbuster::FuzzBuster {
         setup(&mut self) {
             crate::fuzzbuster::FuzzBuster {
-                n_threads: 1,
                 ignore_certificate: true,
-                http_method: "GET".to_owned(),
-                http_body: "body".to_owned(),
+                http_body: b"body".to_vec(),
                 user_agent: "ua".to_owned(),
                 http_headers: vec![("Header".to_owned(), "Value".to_owned())],
                 wordlist_paths: vec!["./examples/wordlist_short".to_owned(), "./examples/wordlist_short".to_owned()],
                 url: "http://localhost/FUZZ/FUZZ".to_owned(),
-                include_status_codes: vec![],
                 ignore_status_codes: vec!["404".to_owned()],
-                include_body: vec![],
-                ignore_body: vec![],
                 no_progress_bar: true,
-                exit_on_connection_errors: false,
-                output: "".to_owned(),
-                csrf_url: None,
-                csrf_regex: None,
-                csrf_headers: None,
+                certificate: false,
+                ..Default::default()
+            }
+        }
+    }
+
+    fixture fuzzbuster_method() -> crate::fuzzbuster::FuzzBuster {
+        setup(&mut self) {
+            crate::fuzzbuster::FuzzBuster {
+                ignore_certificate: true,
+                http_method: "FUZZ".to_owned(),
+                http_body: b"body".to_vec(),
+                user_agent: "ua".to_owned(),
+                http_headers: vec![("Header".to_owned(), "Value".to_owned())],
+                wordlist_paths: vec!["./examples/wordlist_short".to_owned(), "./examples/wordlist_short".to_owned()],
+                url: "http://localhost/FUZZ".to_owned(),
+                ignore_status_codes: vec!["404".to_owned()],
+                no_progress_bar: true,
+                certificate: false,
+                ..Default::default()
             }
         }
     }
@@ -56,24 +58,16 @@ test_suite! {
     fixture fuzzbuster_header_single() -> crate::fuzzbuster::FuzzBuster {
         setup(&mut self) {
             crate::fuzzbuster::FuzzBuster {
-                n_threads: 1,
                 ignore_certificate: true,
-                http_method: "GET".to_owned(),
-                http_body: "body".to_owned(),
+                http_body: b"body".to_vec(),
                 user_agent: "ua".to_owned(),
                 http_headers: vec![("Header".to_owned(), "FUZZ".to_owned())],
                 wordlist_paths: vec!["./examples/wordlist_short".to_owned()],
                 url: "http://localhost/".to_owned(),
-                include_status_codes: vec![],
                 ignore_status_codes: vec!["404".to_owned()],
-                include_body: vec![],
-                ignore_body: vec![],
                 no_progress_bar: true,
-                exit_on_connection_errors: false,
-                output: "".to_owned(),
-                csrf_url: None,
-                csrf_regex: None,
-                csrf_headers: None,
+                certificate: false,
+                ..Default::default()
             }
         }
     }
@@ -81,24 +75,16 @@ test_suite! {
     fixture fuzzbuster_header_multiple() -> crate::fuzzbuster::FuzzBuster {
         setup(&mut self) {
             crate::fuzzbuster::FuzzBuster {
-                n_threads: 1,
                 ignore_certificate: true,
-                http_method: "GET".to_owned(),
-                http_body: "body".to_owned(),
+                http_body: b"body".to_vec(),
                 user_agent: "ua".to_owned(),
                 http_headers: vec![("FUZZ".to_owned(), "FUZZ".to_owned())],
                 wordlist_paths: vec!["./examples/wordlist_short".to_owned(), "./examples/wordlist_short".to_owned()],
                 url: "http://localhost/".to_owned(),
-                include_status_codes: vec![],
                 ignore_status_codes: vec!["404".to_owned()],
-                include_body: vec![],
-                ignore_body: vec![],
                 no_progress_bar: true,
-                exit_on_connection_errors: false,
-                output: "".to_owned(),
-                csrf_url: None,
-                csrf_regex: None,
-                csrf_headers: None,
+                certificate: false,
+                ..Default::default()
             }
         }
     }
@@ -106,24 +92,16 @@ test_suite! {
     fixture fuzzbuster_body_single() -> crate::fuzzbuster::FuzzBuster {
         setup(&mut self) {
             crate::fuzzbuster::FuzzBuster {
-                n_threads: 1,
                 ignore_certificate: true,
-                http_method: "GET".to_owned(),
-                http_body: "FUZZ".to_owned(),
+                http_body: b"FUZZ".to_vec(),
                 user_agent: "ua".to_owned(),
                 http_headers: vec![("Header".to_owned(), "Value".to_owned())],
                 wordlist_paths: vec!["./examples/wordlist_short".to_owned()],
                 url: "http://localhost/".to_owned(),
-                include_status_codes: vec![],
                 ignore_status_codes: vec!["404".to_owned()],
-                include_body: vec![],
-                ignore_body: vec![],
                 no_progress_bar: true,
-                exit_on_connection_errors: false,
-                output: "".to_owned(),
-                csrf_url: None,
-                csrf_regex: None,
-                csrf_headers: None,
+                certificate: false,
+                ..Default::default()
             }
         }
     }
@@ -131,24 +109,16 @@ test_suite! {
     fixture fuzzbuster_body_multiple() -> crate::fuzzbuster::FuzzBuster {
         setup(&mut self) {
             crate::fuzzbuster::FuzzBuster {
-                n_threads: 1,
                 ignore_certificate: true,
-                http_method: "GET".to_owned(),
-                http_body: "FUZZ:FUZZ".to_owned(),
+                http_body: b"FUZZ:FUZZ".to_vec(),
                 user_agent: "ua".to_owned(),
                 http_headers: vec![("Header".to_owned(), "Value".to_owned())],
                 wordlist_paths: vec!["./examples/wordlist_short".to_owned(), "./examples/wordlist_short".to_owned()],
                 url: "http://localhost/".to_owned(),
-                include_status_codes: vec![],
                 ignore_status_codes: vec!["404".to_owned()],
-                include_body: vec![],
-                ignore_body: vec![],
                 no_progress_bar: true,
-                exit_on_connection_errors: false,
-                output: "".to_owned(),
-                csrf_url: None,
-                csrf_regex: None,
-                csrf_headers: None,
+                certificate: false,
+                ..Default::default()
             }
         }
     }
@@ -159,7 +129,7 @@ test_suite! {
                 uri: "http://localhost/".parse::<hyper::Uri>().unwrap(),
                 http_method: "GET".to_owned(),
                 http_headers: vec![],
-                http_body: "CSRFCSRF".to_owned(),
+                http_body: b"CSRFCSRF".to_vec(),
                 user_agent: "ua".to_owned(),
                 payload: vec!["1".to_owned()],
                 csrf_uri: Some("http://localhost/".parse::<hyper::Uri>().unwrap()),
@@ -175,7 +145,7 @@ test_suite! {
                 uri: "http://localhost/".parse::<hyper::Uri>().unwrap(),
                 http_method: "GET".to_owned(),
                 http_headers: vec![("X-CSRF-Token".to_owned(), "CSRFCSRF".to_owned())],
-                http_body: "body".to_owned(),
+                http_body: b"body".to_vec(),
                 user_agent: "ua".to_owned(),
                 payload: vec!["1".to_owned()],
                 csrf_uri: Some("http://localhost/".parse::<hyper::Uri>().unwrap()),
@@ -191,7 +161,7 @@ test_suite! {
                 uri: "http://localhost/CSRFCSRF".parse::<hyper::Uri>().unwrap(),
                 http_method: "GET".to_owned(),
                 http_headers: vec![],
-                http_body: "body".to_owned(),
+                http_body: b"body".to_vec(),
                 user_agent: "ua".to_owned(),
                 payload: vec!["1".to_owned()],
                 csrf_uri: Some("http://localhost/".parse::<hyper::Uri>().unwrap()),
@@ -213,8 +183,8 @@ test_suite! {
     test build_requests_fuzz_url_single(fuzzbuster_url_single()) {
         let requests = fuzzbuster_url_single.val.build_requests();
         let expected: Vec<crate::fuzzbuster::FuzzRequest> = vec![
-            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/1".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: "body".to_owned(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
-            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/2".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: "body".to_owned(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/1".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/2".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
         ];
         assert_eq!(expected, requests);
     }
@@ -222,10 +192,10 @@ test_suite! {
     test build_requests_fuzz_url_multiple(fuzzbuster_url_multiple()) {
         let requests = fuzzbuster_url_multiple.val.build_requests();
         let expected: Vec<crate::fuzzbuster::FuzzRequest> = vec![
-            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/1/1".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: "body".to_owned(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
-            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/1/2".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: "body".to_owned(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
-            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/2/1".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: "body".to_owned(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
-            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/2/2".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: "body".to_owned(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None }
+            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/1/1".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/1/2".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/2/1".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/2/2".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None }
         ];
         assert_eq!(expected, requests);
     }
@@ -233,8 +203,8 @@ test_suite! {
     test build_requests_fuzz_header_single(fuzzbuster_header_single()) {
         let requests = fuzzbuster_header_single.val.build_requests();
         let expected: Vec<crate::fuzzbuster::FuzzRequest> = vec![
-            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "1".to_owned())], http_body: "body".to_owned(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
-            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "2".to_owned())], http_body: "body".to_owned(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "1".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "2".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
         ];
         assert_eq!(expected, requests);
     }
@@ -242,10 +212,10 @@ test_suite! {
     test build_requests_fuzz_header_multiple(fuzzbuster_header_multiple()) {
         let requests = fuzzbuster_header_multiple.val.build_requests();
         let expected: Vec<crate::fuzzbuster::FuzzRequest> = vec![
-            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("1".to_owned(), "1".to_owned())], http_body: "body".to_owned(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
-            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("1".to_owned(), "2".to_owned())], http_body: "body".to_owned(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
-            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("2".to_owned(), "1".to_owned())], http_body: "body".to_owned(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
-            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("2".to_owned(), "2".to_owned())], http_body: "body".to_owned(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("1".to_owned(), "1".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("1".to_owned(), "2".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("2".to_owned(), "1".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("2".to_owned(), "2".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
         ];
         assert_eq!(expected, requests);
     }
@@ -253,8 +223,8 @@ test_suite! {
     test build_requests_fuzz_body_single(fuzzbuster_body_single()) {
         let requests = fuzzbuster_body_single.val.build_requests();
         let expected: Vec<crate::fuzzbuster::FuzzRequest> = vec![
-            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: "1".to_owned(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
-            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: "2".to_owned(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"1".to_vec(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"2".to_vec(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
         ];
         assert_eq!(expected, requests);
     }
@@ -262,24 +232,101 @@ test_suite! {
     test build_requests_fuzz_body_multiple(fuzzbuster_body_multiple()) {
         let requests = fuzzbuster_body_multiple.val.build_requests();
         let expected: Vec<crate::fuzzbuster::FuzzRequest> = vec![
-            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: "1:1".to_owned(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
-            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: "1:2".to_owned(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
-            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: "2:1".to_owned(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
-            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: "2:2".to_owned(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"1:1".to_vec(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"1:2".to_vec(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"2:1".to_vec(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"2:2".to_vec(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+        ];
+        assert_eq!(expected, requests);
+    }
+
+    test build_requests_fuzz_method(fuzzbuster_method()) {
+        let requests = fuzzbuster_method.val.build_requests();
+        let expected: Vec<crate::fuzzbuster::FuzzRequest> = vec![
+            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/1".parse::<hyper::Uri>().unwrap(), http_method: "1".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/2".parse::<hyper::Uri>().unwrap(), http_method: "1".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/1".parse::<hyper::Uri>().unwrap(), http_method: "2".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/2".parse::<hyper::Uri>().unwrap(), http_method: "2".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+        ];
+        assert_eq!(expected, requests);
+    }
+
+    test build_requests_named_payloads(fuzzbuster_url_single()) {
+        let mut fuzzbuster = fuzzbuster_url_single.val;
+        fuzzbuster.url = "http://localhost/FUZZ2/FUZZ1".to_owned();
+        fuzzbuster.http_body = b"FUZZ1".to_vec();
+        fuzzbuster.payloads = vec![
+            ("FUZZ1".to_owned(), "inline:a".to_owned()),
+            ("FUZZ2".to_owned(), "inline:b,c".to_owned()),
+        ];
+        let requests = fuzzbuster.build_requests();
+        let expected: Vec<crate::fuzzbuster::FuzzRequest> = vec![
+            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/b/a".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"a".to_vec(), user_agent: "ua".to_owned(), payload: vec!["a".to_owned(), "b".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            crate::fuzzbuster::FuzzRequest { uri: "http://localhost/c/a".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"a".to_vec(), user_agent: "ua".to_owned(), payload: vec!["a".to_owned(), "c".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
         ];
         assert_eq!(expected, requests);
     }
 
+    test build_requests_mutate(fuzzbuster_url_single()) {
+        let mut fuzzbuster = fuzzbuster_url_single.val;
+        fuzzbuster.url = "http://localhost/".to_owned();
+        fuzzbuster.http_body = b"id=5".to_vec();
+        fuzzbuster.mutate = true;
+        let requests = fuzzbuster.build_requests();
+        let bodies: Vec<Vec<u8>> = requests.into_iter().map(|r| r.http_body).collect();
+        let expected: Vec<Vec<u8>> = crate::mutate::mutations("id=5").into_iter().map(String::into_bytes).collect();
+        assert_eq!(expected, bodies);
+    }
+
+    test fuzzes_host(fuzzbuster_url_single()) {
+        let mut fuzzbuster = fuzzbuster_url_single.val;
+        fuzzbuster.url = "http://localhost/FUZZ".to_owned();
+        assert!(!fuzzbuster.fuzzes_host());
+        fuzzbuster.url = "http://FUZZ.localhost/".to_owned();
+        assert!(fuzzbuster.fuzzes_host());
+        fuzzbuster.url = "http://localhost:PORT/user".to_owned();
+        fuzzbuster.payloads = vec![("PORT".to_owned(), "inline:80,8080".to_owned())];
+        assert!(fuzzbuster.fuzzes_host());
+    }
+
+    test check_payload_markers() {
+        let check = crate::fuzzbuster::utils::check_payload_markers;
+        assert_eq!(Ok(()), check(&["FUZZ1", "FUZZ2"], &["http://localhost/FUZZ1", "FUZZ2"]));
+        assert!(check(&["FUZZ1"], &["http://localhost/FUZZ1/FUZZ2"]).is_err());
+        assert!(check(&["FUZZ1", "FUZZ2"], &["http://localhost/FUZZ1"]).is_err());
+    }
+
+    test follow_up_values() {
+        let re = regex::Regex::new(r#"token="(\w+)""#).unwrap();
+        assert_eq!(Some("abc".to_owned()), crate::fuzzbuster::utils::extract_value(&re, r#"<input token="abc">"#));
+        assert_eq!(None, crate::fuzzbuster::utils::extract_value(&re, "<input>"));
+        assert_eq!("t=abc&p=pass", crate::fuzzbuster::utils::fill_follow_up("t=EXTRACT&p={{word}}", "abc", "pass"));
+    }
+
+    test session_cookies() {
+        let mut headers = hyper::HeaderMap::new();
+        headers.append("Set-Cookie", "sid=1; Path=/".parse().unwrap());
+        headers.append("Set-Cookie", "csrf=2".parse().unwrap());
+        assert_eq!(Some("sid=1; csrf=2".to_owned()), crate::fuzzbuster::utils::session_cookies(&headers));
+        assert_eq!(None, crate::fuzzbuster::utils::session_cookies(&hyper::HeaderMap::new()));
+    }
+
     test split_http_headers() {
         let header = "Header: Value";
         let expected = ("Header".to_owned(), "Value".to_owned());
         assert_eq!(expected, crate::fuzzbuster::utils::split_http_headers(header));
     }
 
+    test build_multipart_body() {
+        let forms = vec!["user=FUZZ".to_owned()];
+        let expected = "--b\r\nContent-Disposition: form-data; name=\"user\"\r\n\r\nFUZZ\r\n--b--\r\n";
+        assert_eq!(Ok(expected.as_bytes().to_vec()), crate::fuzzbuster::utils::build_multipart_body(&forms, "b"));
+    }
+
     test replace_csrf_body(fuzzrequest_csrf_body) {
         let request = fuzzrequest_csrf_body.val;
         let actual = crate::fuzzbuster::FuzzBuster::replace_csrf(request, "VALUE".to_owned());
-        assert_eq!("VALUE", actual.http_body);
+        assert_eq!(b"VALUE".to_vec(), actual.http_body);
     }
 
     test replace_csrf_header(fuzzrequest_csrf_header) {
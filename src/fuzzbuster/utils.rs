@@ -1,6 +1,177 @@
+use regex::Regex;
+
 pub fn split_http_headers(header: &str) -> (String, String) {
     let index = header.find(':').unwrap_or(0);
     let header_name = header[..index].to_owned();
     let header_value = header[index + 2..].to_owned();
     (header_name, header_value)
 }
+
+pub fn multipart_boundary() -> String {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    format!("----rustbuster{:x}", nanos)
+}
+
+pub fn build_multipart_body(forms: &[String], boundary: &str) -> Result<Vec<u8>, String> {
+    let mut body = Vec::new();
+    for form in forms.iter() {
+        let index = match form.find('=') {
+            Some(v) => v,
+            None => return Err(format!("Invalid form field: {}, expected name=value", form)),
+        };
+        let name = &form[..index];
+        let value = &form[index + 1..];
+
+        body.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
+        if !value.starts_with('@') {
+            body.extend_from_slice(
+                format!(
+                    "Content-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                    name, value
+                )
+                .as_bytes(),
+            );
+            continue;
+        }
+
+        let mut options = value[1..].split(';');
+        let path = options.next().unwrap_or("");
+        let mut filename = std::path::Path::new(path)
+            .file_name()
+            .map(|f| f.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut content_type = "application/octet-stream".to_owned();
+        for option in options {
+            if let Some(v) = option.strip_prefix("type=") {
+                content_type = v.to_owned();
+            } else if let Some(v) = option.strip_prefix("filename=") {
+                filename = v.to_owned();
+            }
+        }
+
+        let content = match std::fs::read(path) {
+            Ok(v) => v,
+            Err(e) => return Err(format!("Unable to read the form file: {}\n{}", path, e)),
+        };
+
+        body.extend_from_slice(
+            format!(
+                "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+                name, filename, content_type
+            )
+            .as_bytes(),
+        );
+        body.extend_from_slice(&content);
+        body.extend_from_slice(b"\r\n");
+    }
+
+    body.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
+    Ok(body)
+}
+
+pub fn check_payload_markers(markers: &[&str], templates: &[&str]) -> Result<(), String> {
+    let marker_re = Regex::new(r"FUZZ\d*").unwrap();
+    for template in templates.iter() {
+        for found in marker_re.find_iter(template) {
+            if !markers.contains(&found.as_str()) {
+                return Err(format!("No payload is bound to {}", found.as_str()));
+            }
+        }
+    }
+
+    for marker in markers.iter() {
+        if !templates.iter().any(|template| template.contains(marker)) {
+            return Err(format!("Payload {} is not used in the request", marker));
+        }
+    }
+
+    Ok(())
+}
+
+/// The bytes of a hex wordlist entry
+pub fn decode_hex(word: &str) -> Option<Vec<u8>> {
+    let hex: String = word
+        .replace("\\x", "")
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    let hex = hex.strip_prefix("0x").unwrap_or(&hex);
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| match pair {
+            [high, low] => {
+                u8::from_str_radix(&format!("{}{}", *high as char, *low as char), 16).ok()
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// The body with the first `count` occurrences of the marker replaced, bodies may not be UTF-8
+pub fn replace_bytes(body: &[u8], marker: &str, with: &[u8], count: usize) -> Vec<u8> {
+    let marker = marker.as_bytes();
+    let mut replaced = Vec::with_capacity(body.len());
+    let mut rest = body;
+    let mut n = 0;
+    while n < count && !marker.is_empty() {
+        match rest.windows(marker.len()).position(|w| w == marker) {
+            Some(i) => {
+                replaced.extend_from_slice(&rest[..i]);
+                replaced.extend_from_slice(with);
+                rest = &rest[i + marker.len()..];
+                n += 1;
+            }
+            None => break,
+        }
+    }
+    replaced.extend_from_slice(rest);
+
+    replaced
+}
+
+/// Decoded entries in URLs and headers, only printable ASCII is kept as-is
+pub fn escape_binary(word: &[u8]) -> String {
+    word.iter()
+        .map(|&b| {
+            if b.is_ascii_graphic() && b != b'%' {
+                char::from(b).to_string()
+            } else {
+                format!("%{:02X}", b)
+            }
+        })
+        .collect()
+}
+
+pub fn extract_value(re: &Regex, body: &str) -> Option<String> {
+    re.captures(body)
+        .and_then(|caps| caps.get(1).or_else(|| caps.get(0)))
+        .map(|value| value.as_str().to_owned())
+}
+
+pub fn fill_follow_up(template: &str, extracted: &str, word: &str) -> String {
+    crate::template::expand(&template.replace("EXTRACT", extracted), word)
+}
+
+pub fn session_cookies(headers: &hyper::HeaderMap) -> Option<String> {
+    let cookies: Vec<&str> = headers
+        .get_all("Set-Cookie")
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .filter_map(|v| v.split(';').next())
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .collect();
+
+    if cookies.is_empty() {
+        None
+    } else {
+        Some(cookies.join("; "))
+    }
+}
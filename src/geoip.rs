@@ -0,0 +1,98 @@
+use hyper::{client::connect::HttpInfo, Response};
+use maxminddb::{geoip2, Reader};
+use serde::{Deserialize, Serialize};
+use std::{fmt, net::IpAddr};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GeoInfo {
+    pub ip: IpAddr,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub country: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub country_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub city: Option<String>,
+}
+
+impl GeoInfo {
+    pub fn from_city(ip: IpAddr, city: &geoip2::City) -> Option<Self> {
+        let english = |names: &Option<std::collections::BTreeMap<&str, &str>>| {
+            names
+                .as_ref()
+                .and_then(|names| names.get("en"))
+                .map(|name| (*name).to_owned())
+        };
+        let country = city.country.as_ref().or(city.registered_country.as_ref());
+        let info = GeoInfo {
+            ip,
+            country: country.and_then(|c| c.iso_code).map(|c| c.to_owned()),
+            country_name: country.and_then(|c| english(&c.names)),
+            city: city.city.as_ref().and_then(|c| english(&c.names)),
+        };
+
+        if info.country.is_none() && info.city.is_none() {
+            None
+        } else {
+            Some(info)
+        }
+    }
+}
+
+/// A GeoLite2 or GeoIP2 Country or City database, lookups are no-ops without one
+#[derive(Default)]
+pub struct GeoIp {
+    reader: Option<Reader<Vec<u8>>>,
+}
+
+impl GeoIp {
+    pub fn open(path: &str) -> Option<Self> {
+        if path.is_empty() {
+            return Some(GeoIp::default());
+        }
+
+        match Reader::open_readfile(path) {
+            Ok(reader) => Some(GeoIp {
+                reader: Some(reader),
+            }),
+            Err(e) => {
+                error!("Unable to open the GeoIP database {}: {}", path, e);
+                None
+            }
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.reader.is_some()
+    }
+
+    pub fn annotate(&self, ip: Option<IpAddr>) -> Option<GeoInfo> {
+        ip.and_then(|ip| self.lookup(ip))
+    }
+
+    pub fn lookup(&self, ip: IpAddr) -> Option<GeoInfo> {
+        let reader = self.reader.as_ref()?;
+        match reader.lookup::<geoip2::City>(ip) {
+            Ok(city) => GeoInfo::from_city(ip, &city),
+            Err(e) => {
+                trace!("{}: {}", ip, e);
+                None
+            }
+        }
+    }
+}
+
+// the reader holds the whole database
+impl fmt::Debug for GeoIp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GeoIp")
+            .field("enabled", &self.is_enabled())
+            .finish()
+    }
+}
+
+/// The address hyper connected to, the proxy's one when the request went through a proxy
+pub fn remote_ip<T>(res: &Response<T>) -> Option<IpAddr> {
+    res.extensions()
+        .get::<HttpInfo>()
+        .map(|info| info.remote_addr().ip())
+}
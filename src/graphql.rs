@@ -0,0 +1,129 @@
+use futures::{future, Stream};
+use hyper::{
+    rt::{self, Future},
+    Body, Client, Request,
+};
+use hyper_tls::HttpsConnector;
+use serde::{Deserialize, Serialize};
+use std::{fs::File, io::Write, path::Path, sync::mpsc::channel};
+
+use crate::proxy::ProxyConnector;
+
+const INTROSPECTION_QUERY: &str = "{\"query\":\"query { __schema { queryType { name } \
+                                   mutationType { name } types { name fields { name } } } }\"}";
+
+pub const BRUTE_FORCE_IGNORE: &[&str] = &["Cannot query field", "Schema is not configured"];
+
+#[derive(Debug, Clone)]
+pub struct GraphqlConfig {
+    pub ignore_certificate: bool,
+    pub user_agent: String,
+    pub http_headers: Vec<(String, String)>,
+    pub tls_min_version: Option<native_tls::Protocol>,
+    pub tls_max_version: Option<native_tls::Protocol>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct GraphqlSchema {
+    pub queries: Vec<String>,
+    pub mutations: Vec<String>,
+}
+
+pub fn brute_force_body(operation: &str) -> String {
+    format!("{{\"query\":\"{} {{ FUZZ }}\"}}", operation)
+}
+
+pub fn parse_introspection(body: &str) -> Option<GraphqlSchema> {
+    let value: serde_json::Value = serde_json::from_str(body).ok()?;
+    let schema = value.get("data")?.get("__schema")?;
+    let types = schema.get("types")?.as_array()?;
+    let fields_of = |root: &str| -> Vec<String> {
+        let name = match schema
+            .get(root)
+            .and_then(|t| t.get("name"))
+            .and_then(|n| n.as_str())
+        {
+            Some(v) => v,
+            None => return Vec::new(),
+        };
+
+        types
+            .iter()
+            .filter(|t| t.get("name").and_then(|n| n.as_str()) == Some(name))
+            .filter_map(|t| t.get("fields").and_then(|f| f.as_array()))
+            .flatten()
+            .filter_map(|f| f.get("name").and_then(|n| n.as_str()))
+            .map(|n| n.to_owned())
+            .collect()
+    };
+
+    Some(GraphqlSchema {
+        queries: fields_of("queryType"),
+        mutations: fields_of("mutationType"),
+    })
+}
+
+pub fn introspect(url: &str, config: &GraphqlConfig) -> Option<GraphqlSchema> {
+    let mut tls_connector_builder = native_tls::TlsConnector::builder();
+    tls_connector_builder.danger_accept_invalid_certs(config.ignore_certificate);
+    tls_connector_builder.min_protocol_version(config.tls_min_version);
+    tls_connector_builder.max_protocol_version(config.tls_max_version);
+    let tls_connector = tls_connector_builder
+        .build()
+        .expect("TLS initialization failed");
+    let http_connector = ProxyConnector::new(1);
+    let https_connector = HttpsConnector::from((http_connector, tls_connector));
+    let client = Client::builder().build::<_, Body>(https_connector);
+
+    let mut request_builder = Request::builder();
+    for header_tuple in &config.http_headers {
+        request_builder.header(header_tuple.0.as_str(), header_tuple.1.as_str());
+    }
+
+    let request = request_builder
+        .header("User-Agent", &config.user_agent[..])
+        .header("Content-Type", "application/json")
+        .method("POST")
+        .uri(url)
+        .body(Body::from(INTROSPECTION_QUERY))
+        .expect("Request builder");
+
+    let (tx, rx) = channel::<String>();
+    rt::run(
+        // the client goes with the request, its idle connection would keep the runtime running
+        future::lazy(move || client.request(request))
+            .and_then(|res| res.into_body().concat2())
+            .map(move |body| {
+                tx.send(String::from_utf8_lossy(&body).into_owned())
+                    .unwrap_or(());
+            })
+            .map_err(|e| debug!("Introspection request failed: {}", e)),
+    );
+
+    parse_introspection(&rx.try_recv().ok()?)
+}
+
+pub fn report(schema: &GraphqlSchema) -> String {
+    format!(
+        "[+] Queries\t: {}\n[+] Mutations\t: {}",
+        schema.queries.join(", "),
+        schema.mutations.join(", ")
+    )
+}
+
+pub fn save_schema(path: &str, schema: &GraphqlSchema) {
+    let json_string = serde_json::to_string(schema).unwrap();
+
+    let mut file = match File::create(Path::new(path)) {
+        Ok(f) => f,
+        Err(e) => {
+            error!("Error while creating file: {}\n{}", path, e);
+            return;
+        }
+    };
+
+    match file.write_all(json_string.as_bytes()) {
+        Ok(_) => debug!("Results saved to: {}", path),
+        Err(e) => error!("Error while writing results to file: {}\n{}", path, e),
+    };
+}
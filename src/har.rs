@@ -0,0 +1,356 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::Path,
+};
+
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Har {
+    pub log: HarLog,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HarLog {
+    pub version: String,
+    pub creator: HarCreator,
+    pub entries: Vec<HarEntry>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HarCreator {
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HarHeader {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HarPostData {
+    pub mime_type: String,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+}
+
+impl HarPostData {
+    /// The body exactly as it was sent
+    pub fn bytes(&self) -> Vec<u8> {
+        decode_text(&self.text, &self.encoding)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HarRequest {
+    pub method: String,
+    pub url: String,
+    pub http_version: String,
+    pub cookies: Vec<HarHeader>,
+    pub headers: Vec<HarHeader>,
+    pub query_string: Vec<HarHeader>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_data: Option<HarPostData>,
+    pub headers_size: i64,
+    pub body_size: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HarContent {
+    pub size: i64,
+    pub mime_type: String,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+}
+
+impl HarContent {
+    /// The body exactly as it was received
+    pub fn bytes(&self) -> Vec<u8> {
+        decode_text(&self.text, &self.encoding)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HarResponse {
+    pub status: u16,
+    pub status_text: String,
+    pub http_version: String,
+    pub cookies: Vec<HarHeader>,
+    pub headers: Vec<HarHeader>,
+    pub content: HarContent,
+    #[serde(rename = "redirectURL")]
+    pub redirect_url: String,
+    pub headers_size: i64,
+    pub body_size: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HarTimings {
+    pub send: i64,
+    pub wait: i64,
+    pub receive: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HarCache {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HarEntry {
+    pub started_date_time: String,
+    pub time: i64,
+    pub request: HarRequest,
+    pub response: HarResponse,
+    pub cache: HarCache,
+    pub timings: HarTimings,
+}
+
+/// HAR text is JSON, a body which is not UTF-8 is kept as base64 instead of being mangled
+fn encode_text(body: &[u8]) -> (String, Option<String>) {
+    match std::str::from_utf8(body) {
+        Ok(text) => (text.to_owned(), None),
+        Err(_) => (STANDARD.encode(body), Some("base64".to_owned())),
+    }
+}
+
+fn decode_text(text: &str, encoding: &Option<String>) -> Vec<u8> {
+    match encoding.as_ref().map(|e| e.as_str()) {
+        Some("base64") => STANDARD
+            .decode(text)
+            .unwrap_or_else(|_| text.as_bytes().to_vec()),
+        _ => text.as_bytes().to_vec(),
+    }
+}
+
+fn map_headers(headers: &hyper::HeaderMap) -> Vec<HarHeader> {
+    headers
+        .iter()
+        .map(|(name, value)| HarHeader {
+            name: name.as_str().to_owned(),
+            value: String::from_utf8_lossy(value.as_bytes()).into_owned(),
+        })
+        .collect()
+}
+
+fn map_query_string(uri: &hyper::Uri) -> Vec<HarHeader> {
+    match uri.query() {
+        None => Vec::new(),
+        Some(query) => query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let mut parts = pair.splitn(2, '=');
+                HarHeader {
+                    name: parts.next().unwrap_or("").to_owned(),
+                    value: parts.next().unwrap_or("").to_owned(),
+                }
+            })
+            .collect(),
+    }
+}
+
+impl HarRequest {
+    pub fn new<T>(request: &hyper::Request<T>, body: &[u8]) -> Self {
+        let headers = map_headers(request.headers());
+        let post_data = if body.is_empty() {
+            None
+        } else {
+            let (text, encoding) = encode_text(body);
+            Some(HarPostData {
+                mime_type: request
+                    .headers()
+                    .get("Content-Type")
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or("")
+                    .to_owned(),
+                text,
+                encoding,
+            })
+        };
+
+        HarRequest {
+            method: request.method().to_string(),
+            url: request.uri().to_string(),
+            http_version: format!("{:?}", request.version()),
+            cookies: Vec::new(),
+            headers,
+            query_string: map_query_string(request.uri()),
+            post_data,
+            headers_size: -1,
+            body_size: body.len() as i64,
+        }
+    }
+}
+
+impl HarResponse {
+    pub fn new(parts: &hyper::http::response::Parts, body: &[u8]) -> Self {
+        let (text, encoding) = encode_text(body);
+        HarResponse {
+            status: parts.status.as_u16(),
+            status_text: parts.status.canonical_reason().unwrap_or("").to_owned(),
+            http_version: format!("{:?}", parts.version),
+            cookies: Vec::new(),
+            headers: map_headers(&parts.headers),
+            content: HarContent {
+                size: body.len() as i64,
+                mime_type: parts
+                    .headers
+                    .get("Content-Type")
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or("")
+                    .to_owned(),
+                text,
+                encoding,
+            },
+            redirect_url: parts
+                .headers
+                .get("Location")
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or("")
+                .to_owned(),
+            headers_size: -1,
+            body_size: body.len() as i64,
+        }
+    }
+}
+
+impl HarEntry {
+    pub fn new(started: DateTime<Local>, request: HarRequest, response: HarResponse) -> Self {
+        let time = Local::now()
+            .signed_duration_since(started)
+            .num_milliseconds();
+
+        HarEntry {
+            started_date_time: started.to_rfc3339(),
+            time,
+            request,
+            response,
+            cache: HarCache {},
+            timings: HarTimings {
+                send: 0,
+                wait: time,
+                receive: 0,
+            },
+        }
+    }
+}
+
+pub fn save_har(path: &str, entries: Vec<HarEntry>) {
+    let har = Har {
+        log: HarLog {
+            version: "1.2".to_owned(),
+            creator: HarCreator {
+                name: "rustbuster".to_owned(),
+                version: VERSION.to_owned(),
+            },
+            entries,
+        },
+    };
+    let json_string = serde_json::to_string(&har).unwrap();
+
+    let mut file = match File::create(Path::new(path)) {
+        Ok(f) => f,
+        Err(e) => {
+            error!("Error while creating file: {}\n{}", path, e);
+            return;
+        }
+    };
+
+    match file.write_all(json_string.as_bytes()) {
+        Ok(_) => debug!("HAR saved to: {}", path),
+        Err(e) => error!("Error while writing HAR to file: {}\n{}", path, e),
+    };
+}
+
+/// The request as sent on the wire, ready to be replayed with ncat or pasted in Burp Repeater
+pub fn raw_request(request: &HarRequest) -> Vec<u8> {
+    let uri = request.url.parse::<hyper::Uri>().ok();
+    let target = uri
+        .as_ref()
+        .and_then(|uri| uri.path_and_query())
+        .map(|pq| pq.as_str())
+        .unwrap_or("/");
+    let has_header = |name: &str| {
+        request
+            .headers
+            .iter()
+            .any(|h| h.name.eq_ignore_ascii_case(name))
+    };
+
+    let mut raw = format!("{} {} {}\r\n", request.method, target, request.http_version);
+    // hyper adds the Host and Content-Length headers when sending, they are not in the request
+    if !has_header("Host") {
+        if let Some(authority) = uri.as_ref().and_then(|uri| uri.authority_part()) {
+            raw.push_str(&format!("Host: {}\r\n", authority));
+        }
+    }
+    for header in request.headers.iter() {
+        raw.push_str(&format!("{}: {}\r\n", header.name, header.value));
+    }
+    let body = request
+        .post_data
+        .as_ref()
+        .map(|p| p.bytes())
+        .unwrap_or_default();
+    if !body.is_empty() && !has_header("Content-Length") {
+        raw.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    }
+    raw.push_str("\r\n");
+
+    let mut raw = raw.into_bytes();
+    raw.extend_from_slice(&body);
+    raw
+}
+
+/// The file name of the nth request, the URL keeps the files recognizable
+pub fn request_file_name(index: usize, request: &HarRequest) -> String {
+    let url = request
+        .url
+        .splitn(2, "://")
+        .last()
+        .unwrap_or("")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(100)
+        .collect::<String>();
+
+    format!("{:04}-{}-{}.txt", index + 1, request.method, url)
+}
+
+pub fn save_requests(dir: &str, entries: Vec<HarEntry>) {
+    if let Err(e) = fs::create_dir_all(dir) {
+        error!("Error while creating directory: {}\n{}", dir, e);
+        return;
+    }
+
+    for (index, entry) in entries.iter().enumerate() {
+        let path = Path::new(dir).join(request_file_name(index, &entry.request));
+        if let Err(e) = fs::write(&path, raw_request(&entry.request)) {
+            error!(
+                "Error while writing request to file: {}\n{}",
+                path.display(),
+                e
+            );
+            return;
+        }
+    }
+    debug!("{} requests saved to: {}", entries.len(), dir);
+}
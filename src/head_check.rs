@@ -0,0 +1,134 @@
+use futures::Stream;
+use hyper::{
+    rt::{self, Future},
+    Body, Client, Request, Uri,
+};
+use hyper_tls::HttpsConnector;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+use crate::proxy::ProxyConnector;
+
+#[derive(Debug, Clone)]
+pub struct HeadCheckConfig {
+    pub n_threads: usize,
+    pub ignore_certificate: bool,
+    pub user_agent: String,
+    pub http_headers: Vec<(String, String)>,
+    pub tls_min_version: Option<native_tls::Protocol>,
+    pub tls_max_version: Option<native_tls::Protocol>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LengthDiscrepancy {
+    pub url: String,
+    pub head_length: u64,
+    pub get_length: usize,
+}
+
+pub fn discrepancy(
+    url: &str,
+    headers: &hyper::HeaderMap,
+    get_length: usize,
+) -> Option<LengthDiscrepancy> {
+    let head_length = headers
+        .get("Content-Length")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())?;
+
+    if head_length == get_length as u64 {
+        None
+    } else {
+        Some(LengthDiscrepancy {
+            url: url.to_owned(),
+            head_length,
+            get_length,
+        })
+    }
+}
+
+fn make_request_future(
+    client: &Client<HttpsConnector<ProxyConnector>>,
+    url: Uri,
+    get_length: usize,
+    config: &HeadCheckConfig,
+) -> impl Future<Item = Option<LengthDiscrepancy>, Error = ()> {
+    let mut request_builder = Request::builder();
+    for header_tuple in &config.http_headers {
+        request_builder.header(header_tuple.0.as_str(), header_tuple.1.as_str());
+    }
+
+    let request = request_builder
+        .header("User-Agent", &config.user_agent[..])
+        .method("HEAD")
+        .uri(&url)
+        .body(Body::empty())
+        .expect("Request builder");
+
+    client
+        .request(request)
+        .map(move |res| discrepancy(&url.to_string(), res.headers(), get_length))
+        .or_else(|e| {
+            debug!("HEAD check failed: {}", e);
+            Ok(None)
+        })
+}
+
+pub fn check(results: &[(String, usize)], config: &HeadCheckConfig) -> Vec<LengthDiscrepancy> {
+    let mut tls_connector_builder = native_tls::TlsConnector::builder();
+    tls_connector_builder.danger_accept_invalid_certs(config.ignore_certificate);
+    tls_connector_builder.min_protocol_version(config.tls_min_version);
+    tls_connector_builder.max_protocol_version(config.tls_max_version);
+    let tls_connector = tls_connector_builder
+        .build()
+        .expect("TLS initialization failed");
+    let http_connector = ProxyConnector::new(config.n_threads);
+    let https_connector = HttpsConnector::from((http_connector, tls_connector));
+    let client = Client::builder().build(https_connector);
+
+    let probes: Vec<(Uri, usize)> = results
+        .iter()
+        .filter_map(|(url, length)| match url.parse::<Uri>() {
+            Ok(v) => Some((v, *length)),
+            Err(e) => {
+                debug!("URI: {}", e);
+                None
+            }
+        })
+        .collect();
+
+    let discrepancies = Arc::new(Mutex::new(Vec::new()));
+    let discrepancies_writer = discrepancies.clone();
+    let config = config.clone();
+    let n_threads = config.n_threads;
+    let stream = futures::stream::iter_ok(probes)
+        .map(move |(url, length)| make_request_future(&client, url, length, &config))
+        .buffer_unordered(n_threads)
+        .for_each(move |discrepancy| {
+            if let Some(discrepancy) = discrepancy {
+                discrepancies_writer.lock().unwrap().push(discrepancy);
+            }
+
+            Ok(())
+        });
+
+    rt::run(stream);
+
+    let discrepancies = discrepancies.lock().unwrap().clone();
+    discrepancies
+}
+
+pub fn report(discrepancies: &[LengthDiscrepancy]) -> String {
+    let mut lines = vec![format!(
+        "[!] HEAD/GET length discrepancies\t: {}",
+        discrepancies.len()
+    )];
+    for discrepancy in discrepancies.iter() {
+        lines.push(format!(
+            "\t{} advertises {} bytes on HEAD but sent {} bytes on GET",
+            discrepancy.url, discrepancy.head_length, discrepancy.get_length
+        ));
+    }
+
+    lines.join("\n")
+}
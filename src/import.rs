@@ -0,0 +1,295 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use regex::Regex;
+use serde_json::Value;
+use std::{
+    collections::HashSet,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+};
+
+use crate::{exit_code, sigv4::uri_decode};
+
+/// One request seen by the proxy, with the parameter names the export reports apart
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Exchange {
+    pub url: String,
+    pub body: String,
+    pub params: Vec<String>,
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn element(item: &str, name: &str) -> Option<(String, String)> {
+    let re = Regex::new(&format!(
+        r"(?s)<{0}(\s[^>]*)?>(?:<!\[CDATA\[(.*?)\]\]>|([^<]*))</{0}>",
+        name
+    ))
+    .unwrap();
+    let caps = re.captures(item)?;
+    let attrs = caps.get(1).map(|m| m.as_str()).unwrap_or("").to_owned();
+    let value = match (caps.get(2), caps.get(3)) {
+        (Some(cdata), _) => cdata.as_str().to_owned(),
+        (None, Some(text)) => unescape(text.as_str()),
+        _ => "".to_owned(),
+    };
+
+    Some((attrs, value))
+}
+
+fn request_body(request: &str) -> String {
+    match request.find("\r\n\r\n").or_else(|| request.find("\n\n")) {
+        Some(i) => request[i..].trim().to_owned(),
+        None => "".to_owned(),
+    }
+}
+
+/// Burp's "Save items" XML, the requests may be base64 encoded
+pub fn burp(xml: &str) -> Vec<Exchange> {
+    let item_re = Regex::new(r"(?s)<item>(.*?)</item>").unwrap();
+    item_re
+        .captures_iter(xml)
+        .filter_map(|caps| {
+            let item = &caps[1];
+            let (_, url) = element(item, "url")?;
+            let body = match element(item, "request") {
+                Some((attrs, request)) if attrs.contains("base64=\"true\"") => STANDARD
+                    .decode(request.split_whitespace().collect::<String>())
+                    .map(|r| request_body(&String::from_utf8_lossy(&r)))
+                    .unwrap_or_default(),
+                Some((_, request)) => request_body(&request),
+                None => "".to_owned(),
+            };
+
+            Some(Exchange {
+                url,
+                body,
+                params: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+/// The instances of ZAP's XML report
+pub fn zap_xml(xml: &str) -> Vec<Exchange> {
+    let instance_re = Regex::new(r"(?s)<instance>(.*?)</instance>").unwrap();
+    instance_re
+        .captures_iter(xml)
+        .filter_map(|caps| {
+            let (_, url) = element(&caps[1], "uri")?;
+            let params = element(&caps[1], "param")
+                .map(|(_, param)| vec![param])
+                .unwrap_or_default();
+
+            Some(Exchange {
+                url,
+                body: "".to_owned(),
+                params,
+            })
+        })
+        .collect()
+}
+
+/// ZAP's JSON report instances and the messages of its API
+pub fn zap_json(value: &Value, exchanges: &mut Vec<Exchange>) {
+    match value {
+        Value::Object(map) => {
+            let header = map.get("requestHeader").and_then(Value::as_str);
+            let url = match (map.get("uri").and_then(Value::as_str), header) {
+                (Some(uri), _) => Some(uri.to_owned()),
+                (None, Some(header)) => header.split_whitespace().nth(1).map(|u| u.to_owned()),
+                _ => None,
+            };
+            if let Some(url) = url {
+                exchanges.push(Exchange {
+                    url,
+                    body: map
+                        .get("requestBody")
+                        .and_then(Value::as_str)
+                        .unwrap_or("")
+                        .to_owned(),
+                    params: map
+                        .get("param")
+                        .and_then(Value::as_str)
+                        .map(|param| vec![param.to_owned()])
+                        .unwrap_or_default(),
+                });
+            }
+            for value in map.values() {
+                zap_json(value, exchanges);
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                zap_json(value, exchanges);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Tells the exports apart, a list of URLs as saved by ZAP's "Export All URLs" otherwise
+pub fn exchanges(content: &str) -> Vec<Exchange> {
+    let trimmed = content.trim_start();
+    if trimmed.starts_with('{') || trimmed.starts_with('[') {
+        let mut exchanges = Vec::new();
+        if let Ok(value) = serde_json::from_str::<Value>(trimmed) {
+            zap_json(&value, &mut exchanges);
+        }
+        exchanges
+    } else if content.contains("<items") {
+        burp(content)
+    } else if content.contains("<OWASPZAPReport") {
+        zap_xml(content)
+    } else {
+        content
+            .lines()
+            .map(str::trim)
+            .filter(|line| line.starts_with("http://") || line.starts_with("https://"))
+            .map(|url| Exchange {
+                url: url.to_owned(),
+                ..Exchange::default()
+            })
+            .collect()
+    }
+}
+
+fn json_keys(value: &Value, keys: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter() {
+                keys.push(key.to_owned());
+                json_keys(value, keys);
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                json_keys(value, keys);
+            }
+        }
+        _ => (),
+    }
+}
+
+fn form_names(query: &str) -> Vec<String> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split('=').next())
+        .map(|name| uri_decode(&name.replace('+', " ")))
+        .collect()
+}
+
+fn push_unique(words: &mut Vec<String>, seen: &mut HashSet<String>, word: String) {
+    if !word.is_empty() && seen.insert(word.to_owned()) {
+        words.push(word);
+    }
+}
+
+/// The paths, with their parent directories, and the parameter names of the host's requests
+pub fn wordlists(exchanges: &[Exchange], host: &str) -> (Vec<String>, Vec<String>) {
+    let (mut paths, mut params) = (Vec::new(), Vec::new());
+    let (mut seen_paths, mut seen_params) = (HashSet::new(), HashSet::new());
+    for exchange in exchanges.iter() {
+        let uri = match exchange.url.parse::<hyper::Uri>() {
+            Ok(v) => v,
+            Err(e) => {
+                trace!("{}: {}", exchange.url, e);
+                continue;
+            }
+        };
+        if !host.is_empty() && !uri.host().unwrap_or("").eq_ignore_ascii_case(host) {
+            continue;
+        }
+
+        let segments: Vec<&str> = uri.path().split('/').filter(|s| !s.is_empty()).collect();
+        for i in 0..segments.len() {
+            push_unique(&mut paths, &mut seen_paths, segments[..=i].join("/"));
+        }
+
+        let mut names = form_names(uri.query().unwrap_or(""));
+        let body = exchange.body.trim();
+        if body.starts_with('{') || body.starts_with('[') {
+            if let Ok(value) = serde_json::from_str::<Value>(body) {
+                json_keys(&value, &mut names);
+            }
+        } else if body.contains('=') && !body.contains('\n') {
+            names.extend(form_names(body));
+        }
+        names.extend(exchange.params.iter().cloned());
+        for name in names {
+            push_unique(&mut params, &mut seen_params, name);
+        }
+    }
+
+    (paths, params)
+}
+
+/// Appends the words missing from the wordlist, creating it when needed
+pub fn merge(path: &str, words: &[String]) -> io::Result<usize> {
+    let existing = match fs::read_to_string(path) {
+        Ok(v) => v,
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => "".to_owned(),
+        Err(e) => return Err(e),
+    };
+    let known: HashSet<&str> = existing.lines().collect();
+    let missing: Vec<&String> = words
+        .iter()
+        .filter(|word| !known.contains(word.as_str()))
+        .collect();
+    if missing.is_empty() {
+        return Ok(0);
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if !existing.is_empty() && !existing.ends_with('\n') {
+        writeln!(file)?;
+    }
+    for word in missing.iter() {
+        writeln!(file, "{}", word)?;
+    }
+
+    Ok(missing.len())
+}
+
+pub fn run(export: &str, host: &str, paths_wordlist: &str, params_wordlist: &str) -> i32 {
+    let content = match fs::read_to_string(export) {
+        Ok(v) => v,
+        Err(e) => {
+            error!("Unable to read the proxy export {}: {}", export, e);
+            return exit_code::INVALID_CONFIG;
+        }
+    };
+    let exchanges = exchanges(&content);
+    if exchanges.is_empty() {
+        warn!("No request in {}", export);
+        return exit_code::NO_FINDINGS;
+    }
+
+    let (paths, params) = wordlists(&exchanges, host);
+    crate::say!(
+        "[+] Requests\t: {}, {} paths and {} parameters observed",
+        exchanges.len(),
+        paths.len(),
+        params.len()
+    );
+    for (wordlist, words) in [(paths_wordlist, &paths), (params_wordlist, &params)].iter() {
+        if wordlist.is_empty() {
+            continue;
+        }
+        match merge(wordlist, words) {
+            Ok(added) => crate::say!("[+] {}\t: {} words added", wordlist, added),
+            Err(e) => {
+                error!("Unable to update the wordlist {}: {}", wordlist, e);
+                return exit_code::INVALID_CONFIG;
+            }
+        }
+    }
+
+    exit_code::NO_FINDINGS
+}
@@ -0,0 +1,231 @@
+use regex::Regex;
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, Write},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use crate::ratelimit::Pacer;
+
+const COMMANDS: &[(&str, &str)] = &[
+    (
+        "fc [status,...]",
+        "Hides the results whose status starts with one of the values, clears the filter without value",
+    ),
+    (
+        "fs [size,...]",
+        "Hides the results of these sizes, clears the filter without value",
+    ),
+    (
+        "fr [regex]",
+        "Hides the next results whose body matches the regex, clears the filter without value",
+    ),
+    ("show", "Lists the results the filters keep"),
+    ("resume", "Resumes the scan, as an empty line does"),
+];
+
+fn help() -> String {
+    COMMANDS
+        .iter()
+        .map(|(command, description)| format!("\t{:<16}{}", command, description))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A result as the console re-evaluates it against the filters, the body is only matched once
+#[derive(Debug, Clone, PartialEq)]
+pub struct Seen {
+    pub method: String,
+    pub status: String,
+    pub url: String,
+    pub length: usize,
+}
+
+/// The filters changed during the scan, on top of the ones given on the command line
+#[derive(Debug, Default)]
+pub struct LiveFilters {
+    statuses: Vec<String>,
+    sizes: Vec<usize>,
+    regex: Option<Regex>,
+}
+
+fn values(value: &str) -> Vec<&str> {
+    value
+        .split(',')
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
+impl LiveFilters {
+    /// The status and size filters, the ones that still apply to the results seen earlier
+    pub fn hides_seen(&self, status: &str, length: usize) -> bool {
+        self.statuses.iter().any(|code| status.starts_with(code)) || self.sizes.contains(&length)
+    }
+
+    pub fn hides_body(&self, body: &str) -> bool {
+        self.regex.as_ref().is_some_and(|re| re.is_match(body))
+    }
+
+    /// Whether the regex is still the one that matched the body when the result arrived
+    pub fn hides_match(&self, matched: Option<&str>) -> bool {
+        match (&self.regex, matched) {
+            (Some(re), Some(matched)) => re.as_str() == matched,
+            _ => false,
+        }
+    }
+
+    /// Applies a filter command, the error tells what is wrong with it
+    pub fn apply(&mut self, command: &str) -> Result<(), String> {
+        let command = command.trim();
+        let (name, value) = match command.find(char::is_whitespace) {
+            Some(i) => (&command[..i], command[i..].trim()),
+            None => (command, ""),
+        };
+        match name {
+            "fc" => self.statuses = values(value).iter().map(|v| (*v).to_owned()).collect(),
+            "fs" => {
+                self.sizes = values(value)
+                    .iter()
+                    .map(|v| v.parse().map_err(|_| format!("Invalid size: {}", v)))
+                    .collect::<Result<_, _>>()?
+            }
+            "fr" if value.is_empty() => self.regex = None,
+            "fr" => {
+                self.regex = Some(Regex::new(value).map_err(|e| format!("Invalid regex: {}", e))?)
+            }
+            _ => return Err(format!("Unknown command: {}", name)),
+        }
+
+        Ok(())
+    }
+
+    pub fn describe(&self) -> String {
+        format!(
+            "fc: {}\tfs: {}\tfr: {}",
+            self.statuses.join(","),
+            self.sizes
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+            self.regex.as_ref().map(|re| re.as_str()).unwrap_or("")
+        )
+    }
+}
+
+/// Pauses the scan when Enter is pressed and reads filter commands until resumed
+pub struct Console {
+    filters: Mutex<LiveFilters>,
+    seen: Mutex<Vec<Seen>>,
+    /// The regex that matched the body of each result, the bodies are not kept
+    matched: Mutex<HashMap<String, String>>,
+    pacer: Arc<Pacer>,
+}
+
+impl Console {
+    pub fn new(pacer: Arc<Pacer>) -> Arc<Self> {
+        Arc::new(Console {
+            filters: Mutex::new(LiveFilters::default()),
+            seen: Mutex::new(Vec::new()),
+            matched: Mutex::new(HashMap::new()),
+            pacer,
+        })
+    }
+
+    pub fn start(pacer: Arc<Pacer>) -> Arc<Self> {
+        let console = Console::new(pacer);
+
+        let reader = console.clone();
+        thread::spawn(move || {
+            let stdin = io::stdin();
+            let mut paused = false;
+            for line in stdin.lock().lines() {
+                let line = match line {
+                    Ok(v) => v,
+                    Err(_) => break,
+                };
+                paused = reader.handle(paused, line.trim());
+            }
+        });
+
+        console
+    }
+
+    /// Handles a line typed on the console, true while the scan is paused
+    fn handle(&self, paused: bool, line: &str) -> bool {
+        if !paused {
+            self.pacer.pause();
+            println!(
+                "\n[?] Paused, the requests in flight still complete. Commands:\n{}",
+                help()
+            );
+            println!("{}", self.filters.lock().unwrap().describe());
+            prompt();
+            return true;
+        }
+
+        match line {
+            "" | "resume" => {
+                self.pacer.resume();
+                println!("[?] Resumed");
+                return false;
+            }
+            "show" => {
+                for seen in self.seen.lock().unwrap().iter() {
+                    if !self.hides(&seen.url, &seen.status, seen.length) {
+                        println!(
+                            "{}\t{}\t{}\t{}",
+                            seen.method, seen.status, seen.length, seen.url
+                        );
+                    }
+                }
+            }
+            "help" => println!("{}", help()),
+            command => match self.apply(command) {
+                Ok(_) => println!("{}", self.filters.lock().unwrap().describe()),
+                Err(e) => println!("{}", e),
+            },
+        }
+        prompt();
+
+        true
+    }
+
+    pub fn apply(&self, command: &str) -> Result<(), String> {
+        self.filters.lock().unwrap().apply(command)
+    }
+
+    /// Keeps the result for the next evaluations, true when the filters hide it from the output.
+    /// The body is only matched against the regex set when the result arrives
+    pub fn record(&self, seen: Seen, body: &str) -> bool {
+        // the filters are released first, show holds the results while it reads them
+        let hidden = {
+            let filters = self.filters.lock().unwrap();
+            let matched = filters.hides_body(body);
+            if matched {
+                let regex = filters.regex.as_ref().unwrap().as_str().to_owned();
+                self.matched
+                    .lock()
+                    .unwrap()
+                    .insert(seen.url.to_owned(), regex);
+            }
+            matched || filters.hides_seen(&seen.status, seen.length)
+        };
+        self.seen.lock().unwrap().push(seen);
+        hidden
+    }
+
+    /// Whether the filters set now hide the result, for the output and the saved results
+    pub fn hides(&self, url: &str, status: &str, length: usize) -> bool {
+        let filters = self.filters.lock().unwrap();
+        filters.hides_seen(status, length)
+            || filters.hides_match(self.matched.lock().unwrap().get(url).map(|m| m.as_str()))
+    }
+}
+
+fn prompt() {
+    print!("> ");
+    io::stdout().flush().ok();
+}
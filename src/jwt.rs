@@ -0,0 +1,40 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+const NONE_ALGORITHMS: &[&str] = &["none", "None", "NONE", "nOnE"];
+
+fn unsigned_token(alg: &str, claims: &str) -> String {
+    format!(
+        "{}.{}",
+        URL_SAFE_NO_PAD.encode(format!("{{\"alg\":\"{}\",\"typ\":\"JWT\"}}", alg)),
+        URL_SAFE_NO_PAD.encode(claims)
+    )
+}
+
+pub fn sign_hs256(claims: &str, secret: &str) -> String {
+    let token = unsigned_token("HS256", claims);
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(token.as_bytes());
+    format!(
+        "{}.{}",
+        token,
+        URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes())
+    )
+}
+
+pub fn build_tokens(claims: &str, secrets: &[String], alg_none: bool) -> Vec<String> {
+    let mut tokens: Vec<String> = secrets
+        .iter()
+        .map(|secret| sign_hs256(claims, secret))
+        .collect();
+
+    if alg_none {
+        for alg in NONE_ALGORITHMS.iter() {
+            tokens.push(format!("{}.", unsigned_token(alg, claims)));
+        }
+    }
+
+    tokens
+}
@@ -1,8 +1,59 @@
 #[macro_use]
 extern crate log;
 
+pub mod args;
+pub mod authbuster;
 pub mod banner;
+pub mod burp;
+pub mod certificate;
+pub mod chain;
+pub mod correlation;
+pub mod cors;
+pub mod curl;
+pub mod daemon;
+pub mod diff;
+pub mod digest;
 pub mod dirbuster;
 pub mod dnsbuster;
+pub mod downgrade;
+pub mod environment;
+pub mod exit_code;
+pub mod export;
+pub mod family;
+pub mod favicon;
+pub mod fronting;
 pub mod fuzzbuster;
+pub mod geoip;
+pub mod graphql;
+pub mod har;
+pub mod head_check;
+pub mod import;
+pub mod interactive;
+pub mod jwt;
+pub mod logging;
+pub mod methodbuster;
+pub mod mutate;
+pub mod negotiate;
+pub mod nmap;
+pub mod output;
+pub mod progress;
+pub mod project;
+pub mod proxy;
+pub mod ratelimit;
+pub mod rawpath;
+pub mod reachability;
+pub mod report;
+pub mod scheduler;
+pub mod session;
+pub mod severity;
+pub mod sigv4;
+pub mod sink;
+pub mod soap;
+pub mod subdomains;
+pub mod summary;
+pub mod template;
+pub mod tls;
 pub mod vhostbuster;
+pub mod wordlist;
+pub mod wsbuster;
+pub mod xml;
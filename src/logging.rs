@@ -0,0 +1,27 @@
+use chrono::Local;
+use log::Record;
+use std::io::Write;
+
+pub fn json_record(record: &Record, timestamp: &str) -> String {
+    serde_json::json!({
+        "timestamp": timestamp,
+        "level": record.level().to_string(),
+        "target": record.target(),
+        "message": record.args().to_string(),
+    })
+    .to_string()
+}
+
+pub fn init(format: &str) {
+    let mut builder = pretty_env_logger::formatted_builder();
+    if format == "json" {
+        builder
+            .format(|f, record| writeln!(f, "{}", json_record(record, &Local::now().to_rfc3339())));
+    }
+
+    if let Ok(s) = std::env::var("RUST_LOG") {
+        builder.parse_filters(&s);
+    }
+
+    builder.init();
+}
@@ -5,37 +5,127 @@ extern crate clap;
 
 use clap::{App, SubCommand};
 use indicatif::{ProgressBar, ProgressStyle};
-use std::{sync::mpsc::channel, thread, time::SystemTime};
+use std::{
+    sync::{mpsc::channel, Arc},
+    thread,
+    time::{Duration, SystemTime},
+};
 
 mod args;
+mod authbuster;
 mod banner;
+mod burp;
+mod certificate;
+mod chain;
+mod correlation;
+mod cors;
+mod curl;
+mod daemon;
+mod diff;
+mod digest;
 mod dirbuster;
 mod dnsbuster;
+mod downgrade;
+mod environment;
+mod exit_code;
+mod export;
+mod family;
+mod favicon;
+mod fronting;
 mod fuzzbuster;
+mod geoip;
+mod graphql;
+mod har;
+mod head_check;
+mod import;
+mod interactive;
+mod jwt;
+mod logging;
+mod methodbuster;
+mod mutate;
+mod negotiate;
+mod nmap;
+mod output;
+mod progress;
+mod project;
+mod proxy;
+mod ratelimit;
+mod rawpath;
+mod reachability;
+mod report;
+mod scheduler;
+mod session;
+mod severity;
+mod sigv4;
+mod sink;
+mod soap;
+mod subdomains;
+mod summary;
+mod template;
 mod tildebuster;
+mod tls;
 mod vhostbuster;
+mod wordlist;
+mod wsbuster;
+mod xml;
 
 use args::*;
+use authbuster::AuthBuster;
 use dirbuster::{
+    adaptive::Learner,
     result_processor::{ResultProcessorConfig, ScanResult, SingleDirScanResult},
+    schemes::{with_both_schemes, SchemeDiff, SchemeResponse},
+    soft404,
     utils::*,
     DirConfig,
 };
 use dnsbuster::{
+    asn,
     result_processor::{DnsScanResult, SingleDnsScanResult},
     utils::*,
+    zone_walk::{self, ZoneWalk},
     DnsConfig,
 };
+use interactive::{Console, Seen};
+use methodbuster::{
+    result_processor::{MethodScanResult, SingleMethodScanResult},
+    utils::save_method_results,
+    MethodConfig,
+};
 use tildebuster::TildeBuster;
 use vhostbuster::{
     result_processor::{SingleVhostScanResult, VhostScanResult},
     utils::*,
     VhostConfig,
 };
+use wsbuster::{
+    result_processor::{SingleWsScanResult, WsScanResult},
+    utils::save_ws_results,
+    WsConfig,
+};
 
+use cors::CorsConfig;
+use diff::KnownResults;
+use downgrade::DowngradeConfig;
+use fronting::FrontingConfig;
 use fuzzbuster::FuzzBuster;
+use geoip::GeoIp;
+use graphql::GraphqlConfig;
+use head_check::HeadCheckConfig;
+use progress::ProgressEvents;
+use project::Project;
+use ratelimit::Pacer;
+use scheduler::{JobKind, JobQueue};
+use severity::{Classifier, Matcher};
+use sink::{Pipeline, ResultSink};
+use soap::SoapConfig;
+use summary::{AuthSurface, ErrorSummary, HeaderAudit, RedirectGroups};
 
 fn main() {
+    std::process::exit(run());
+}
+
+fn run() -> i32 {
     if std::env::vars()
         .filter(|(name, _value)| name == "RUST_LOG")
         .collect::<Vec<(String, String)>>()
@@ -45,8 +135,7 @@ fn main() {
         std::env::set_var("RUST_LOG", "rustbuster=warn");
     }
 
-    pretty_env_logger::init();
-    let matches = App::new("rustbuster")
+    let app = App::new("rustbuster")
         .version(crate_version!())
         .author("by phra & ps1dr3x")
         .about("DirBuster for rust")
@@ -69,24 +158,40 @@ fn main() {
             --csrf-regex '\\{\"csrf\":\"(\\w+)\"\\}'
     5. Tilde mode:
         rustbuster tilde -u http://localhost:3000/ -e aspx -X OPTIONS
+    6. Methods mode:
+        rustbuster methods -u http://localhost:3000/ -w examples/wordlist
+    7. GraphQL mode:
+        rustbuster graphql -u http://localhost:3000/graphql -w examples/wordlist
+    8. Auth mode:
+        rustbuster auth -u http://localhost:3000/login -X POST -b 'user=^USER^&pass=^PASS^' \\
+            -U examples/wordlist -P examples/wordlist
+    9. Chain mode:
+        rustbuster chain -u http://10.0.0.1/ -d example.com -w examples/wordlist \\
+            --dir-wordlist examples/wordlist -x \"Default page\"
+    10. WebSocket mode:
+        rustbuster ws -u ws://localhost:3000/ -w examples/wordlist --messages examples/wordlist
+    11. SOAP mode:
+        rustbuster soap -u http://localhost:3000/Service.asmx?wsdl -w examples/wordlist -x faultstring
+    12. Fronting mode:
+        rustbuster fronting -u https://203.0.113.10/ -d cdn.example.com -d origin.example.com
 ")
-        .subcommand(set_wordlist_args(set_dir_args(set_http_args(set_common_args(SubCommand::with_name("dir")))))
+        .subcommand(set_mode_args("dir", SubCommand::with_name("dir"))
             .about("Directories and files enumeration mode")
             .after_help("EXAMPLE:
     rustbuster dir -u http://localhost:3000/ -w examples/wordlist -e php"))
-        .subcommand(set_wordlist_args(set_dns_args(set_common_args(SubCommand::with_name("dns"))))
+        .subcommand(set_mode_args("dns", SubCommand::with_name("dns"))
             .about("A/AAAA entries enumeration mode")
             .after_help("EXAMPLE:
     rustbuster dns -d google.com -w examples/wordlist"))
-        .subcommand(set_wordlist_args(set_vhost_args(set_http_args(set_common_args(SubCommand::with_name("vhost")))))
+        .subcommand(set_mode_args("vhost", SubCommand::with_name("vhost"))
             .about("Virtual hosts enumeration mode")
             .after_help("EXAMPLE:
     rustbuster vhost -u http://localhost:3000/ -w examples/wordlist -d test.local -x \"Hello\""))
-        .subcommand(set_tilde_args(set_http_args(set_common_args(SubCommand::with_name("tilde"))))
+        .subcommand(set_mode_args("tilde", SubCommand::with_name("tilde"))
             .about("IIS 8.3 shortname enumeration mode")
             .after_help("EXAMPLE:
     rustbuster tilde -u http://localhost:3000/ -e aspx -X OPTIONS"))
-        .subcommand(set_wordlist_args(set_fuzz_args(set_body_args(set_http_args(set_common_args(SubCommand::with_name("fuzz"))))))
+        .subcommand(set_mode_args("fuzz", SubCommand::with_name("fuzz"))
             .about("Custom fuzzing enumeration mode")
             .after_help("EXAMPLE:
     rustbuster fuzz -u http://localhost:3000/login \\
@@ -98,18 +203,147 @@ fn main() {
         -s 200 \\
         --csrf-url \"http://localhost:3000/csrf\" \\
         --csrf-regex '\\{\"csrf\":\"(\\w+)\"\\}'"))
-        .get_matches();
+        .subcommand(set_mode_args("methods", SubCommand::with_name("methods"))
+            .about("HTTP methods enumeration mode")
+            .after_help("EXAMPLE:
+    rustbuster methods -u http://localhost:3000/ -w examples/wordlist"))
+        .subcommand(set_mode_args("ws", SubCommand::with_name("ws"))
+            .about("WebSocket endpoints enumeration mode")
+            .after_help("EXAMPLE:
+    rustbuster ws -u ws://localhost:3000/ -w examples/wordlist --messages examples/wordlist"))
+        .subcommand(set_mode_args("graphql", SubCommand::with_name("graphql"))
+            .about("GraphQL queries and mutations enumeration mode")
+            .after_help("EXAMPLE:
+    rustbuster graphql -u http://localhost:3000/graphql -w examples/wordlist"))
+        .subcommand(set_mode_args("soap", SubCommand::with_name("soap"))
+            .about("WSDL operations enumeration and SOAP fuzzing mode")
+            .after_help("EXAMPLE:
+    rustbuster soap -u http://localhost:3000/Service.asmx?wsdl -w examples/wordlist -x faultstring"))
+        .subcommand(set_mode_args("auth", SubCommand::with_name("auth"))
+            .about("Login credentials bruteforce mode")
+            .after_help("EXAMPLE:
+    rustbuster auth -u http://localhost:3000/login -X POST -b 'user=^USER^&pass=^PASS^' \\
+        -U examples/wordlist -P examples/wordlist"))
+        .subcommand(set_mode_args("chain", SubCommand::with_name("chain"))
+            .about("Chains the dns, vhost and dir modes in a single run")
+            .after_help("EXAMPLE:
+    rustbuster chain -u http://10.0.0.1/ -d example.com -w examples/wordlist \\
+        --dir-wordlist examples/wordlist -x \"Default page\""))
+        .subcommand(set_mode_args("fronting", SubCommand::with_name("fronting"))
+            .about("Compares the content returned for each SNI and Host combination")
+            .after_help("EXAMPLE:
+    rustbuster fronting -u https://203.0.113.10/ -d cdn.example.com -d origin.example.com"))
+        .subcommand(set_diff_args(SubCommand::with_name("diff"))
+            .about("Compares two saved result files")
+            .after_help("EXAMPLE:
+    rustbuster diff old.json new.json"))
+        .subcommand(set_import_args(SubCommand::with_name("import"))
+            .about("Merges the paths and parameter names of a Burp or ZAP export into wordlists")
+            .after_help("EXAMPLE:
+    rustbuster import burp.xml --host app.example.com --paths paths.txt --params params.txt"))
+        .subcommand(set_project_args(SubCommand::with_name("project"))
+            .about("Shows, exports or resumes the scans recorded in a project file")
+            .after_help("EXAMPLE:
+    rustbuster project client.rbp --export findings.json"))
+        .subcommand(set_daemon_args(SubCommand::with_name("daemon"))
+            .about("Runs the scheduled scan jobs of a queue file, controlled through a REST API")
+            .after_help("EXAMPLE:
+    rustbuster daemon jobs.json --listen 127.0.0.1:7070 --max-jobs 2"));
+    let argv = apply_env_args(std::env::args().collect());
+    let matches = match app.get_matches_from_safe(argv) {
+        Ok(v) => v,
+        Err(e) if e.use_stderr() => {
+            eprintln!("{}", e.message);
+            return exit_code::INVALID_CONFIG;
+        }
+        Err(e) => e.exit(),
+    };
 
     let mode = matches.subcommand_name().unwrap_or("dir");
     let submatches = match matches.subcommand_matches(mode) {
         Some(v) => v,
         None => {
             println!("{}", matches.usage());
-            return;
+            return exit_code::INVALID_CONFIG;
         }
     };
+    logging::init(submatches.value_of("log-format").unwrap_or("text"));
+
+    if let Some(path) = submatches.value_of("input-nmap") {
+        return nmap::run(
+            path,
+            !submatches.value_of("output").unwrap_or("").is_empty(),
+        );
+    }
+
+    if mode == "diff" {
+        let diff_args = extract_diff_args(submatches);
+        diff::run(&diff_args.old, &diff_args.new);
+        return exit_code::NO_FINDINGS;
+    }
+
+    if mode == "import" {
+        let import_args = extract_import_args(submatches);
+        return import::run(
+            &import_args.export,
+            &import_args.host,
+            &import_args.paths,
+            &import_args.params,
+        );
+    }
 
-    let common_args = extract_common_args(submatches);
+    if mode == "daemon" {
+        match extract_daemon_args(submatches) {
+            Ok(v) => daemon::run(v),
+            Err(e) => {
+                error!("{}", e);
+                return exit_code::INVALID_CONFIG;
+            }
+        }
+        return exit_code::NO_FINDINGS;
+    }
+
+    if mode == "project" {
+        let project_args = extract_project_args(submatches);
+        project::run(
+            &project_args.project,
+            &project_args.export,
+            &project_args.export_format,
+            project_args.resume,
+        );
+        return exit_code::NO_FINDINGS;
+    }
+
+    let common_args = match extract_common_args(submatches) {
+        Ok(v) => v,
+        Err(e) => {
+            error!("{}", e);
+            return exit_code::INVALID_CONFIG;
+        }
+    };
+    output::set_quiet(common_args.quiet);
+    output::set_color(common_args.color);
+    proxy::set_env_proxy(!submatches.is_present("no-env-proxy"));
+    family::set_prefer_family(
+        submatches
+            .value_of("prefer-family")
+            .and_then(family::Family::parse),
+    );
+    match extract_wordlist_filter(submatches) {
+        Ok(v) => wordlist::set_filter(v),
+        Err(e) => {
+            error!("{}", e);
+            return exit_code::INVALID_CONFIG;
+        }
+    }
+    let mut project = match Project::open(&common_args.project, mode) {
+        Some(v) => v,
+        None => return exit_code::INVALID_CONFIG,
+    };
+    let geoip = match GeoIp::open(&common_args.geoip) {
+        Some(v) => Arc::new(v),
+        None => return exit_code::INVALID_CONFIG,
+    };
 
     match submatches.occurrences_of("verbose") {
         0 => trace!("No verbose info"),
@@ -118,38 +352,119 @@ fn main() {
         3 | _ => trace!("Don't be crazy"),
     }
 
-    println!("{}", banner::copyright());
+    crate::say!("{}", banner::copyright());
 
     if !common_args.no_banner {
-        println!("{}", banner::generate());
+        crate::say!("{}", banner::generate());
     }
 
-    println!("{}", banner::starting_time());
+    crate::say!("{}", banner::starting_time());
+
+    if let (true, Some(url)) = (
+        submatches.is_present("probe-target"),
+        submatches.value_of("url"),
+    ) {
+        match reachability::probe(url, submatches.is_present("ignore-certificate")) {
+            Ok(status) => crate::say!("{}", banner::reachable(&status)),
+            Err(e) => {
+                error!("{} is unreachable, {}", url, e);
+                return exit_code::ABORTED;
+            }
+        }
+    }
 
     let mut current_numbers_of_request = 0;
     let start_time = SystemTime::now();
 
-    match mode {
+    let findings = match mode {
         "dir" => {
             let wordlist_args = match extract_wordlist_args(submatches) {
-                Err(_) => return,
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+
+            let har_args = extract_har_args(submatches);
+            let http_args = match extract_http_args(submatches)
+                .and_then(|v| prepare_http_args(submatches, v))
+            {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
                 Ok(v) => v,
             };
 
-            let http_args = extract_http_args(submatches);
             if !url_is_valid(&http_args.url) {
-                return;
+                return exit_code::INVALID_CONFIG;
             }
 
-            let dir_args = extract_dir_args(submatches);
-            let urls = build_urls(
-                &wordlist_args.wordlist_paths[0],
+            let certificate = certificate::fetch_certificate(
                 &http_args.url,
-                dir_args.extensions,
-                dir_args.append_slash,
+                http_args.tls_min_version,
+                http_args.tls_max_version,
             );
-            let total_numbers_of_request = urls.len();
-            let (tx, rx) = channel::<SingleDirScanResult>();
+            if let Some(info) = &certificate {
+                crate::say!("{}", banner::certificate(info));
+            }
+
+            if http_args.favicon {
+                if let Some((hash, product)) =
+                    favicon::fingerprint(&http_args.url, http_args.ignore_certificate)
+                {
+                    crate::say!("{}", banner::favicon(hash, product));
+                }
+            }
+
+            let dir_args = match extract_dir_args(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+            if dir_args.check_head && http_args.http_method != "GET" {
+                error!("The HEAD check compares against GET responses, -X must be GET");
+                return exit_code::INVALID_CONFIG;
+            }
+            let severity_rules = match extract_severity_rules(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+            let slow_rules = match extract_slow_rules(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+            let max_rate_limit_wait = match extract_max_rate_limit_wait(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+            let (sort, group_by) = extract_report_order(submatches);
+            let interactive = submatches.is_present("interactive");
+            let body_args = extract_body_args(submatches);
+            let session = match extract_session_args(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+            let header_audit = if http_args.audit_headers {
+                Some(Arc::new(HeaderAudit::default()))
+            } else {
+                None
+            };
             let config = DirConfig {
                 n_threads: common_args.n_threads,
                 ignore_certificate: http_args.ignore_certificate,
@@ -157,12 +472,120 @@ fn main() {
                 http_body: http_args.http_body.to_owned(),
                 user_agent: http_args.user_agent.to_owned(),
                 http_headers: http_args.http_headers.clone(),
+                save_har: har_args.save_har(),
+                read_body: !body_args.include_strings.is_empty()
+                    || !body_args.ignore_strings.is_empty()
+                    || har_args.save_har()
+                    || dir_args.adaptive
+                    || interactive
+                    || severity_rules
+                        .iter()
+                        .any(|rule| matches!(rule.matcher, Matcher::Body(_))),
+                tls_min_version: http_args.tls_min_version,
+                tls_max_version: http_args.tls_max_version,
+                digest: http_args.digest.clone(),
+                negotiate: http_args.negotiate.clone(),
+                aws_sigv4: http_args.aws_sigv4.clone(),
+                capture_headers: http_args.capture_headers.clone(),
+                header_audit: header_audit.clone(),
+                session,
+                range_threshold: dir_args.range_threshold,
+                range_size: dir_args.range_size,
+                request_tag: http_args
+                    .request_tag
+                    .clone()
+                    .filter(|_| dir_args.tag_each_request),
+            };
+            let mut base_urls = if dir_args.ports.is_empty() {
+                vec![http_args.url.to_owned()]
+            } else {
+                dir_args
+                    .ports
+                    .iter()
+                    .filter_map(|port| dirbuster::ports::with_port(&http_args.url, *port))
+                    .collect()
+            };
+            if dir_args.crawl {
+                let directories =
+                    dirbuster::crawler::crawl(&http_args.url, &config, &dir_args.skip_dirs);
+                crate::say!("{}", banner::crawled_directories(&directories));
+                if !dir_args.brute_force {
+                    return exit_code::from_findings(directories.len());
+                }
+                if directories.is_empty() {
+                    warn!("The crawl found no directory to brute force");
+                    return exit_code::NO_FINDINGS;
+                }
+
+                // the wordlist only runs under the discovered directories, not the root
+                base_urls = if dir_args.ports.is_empty() {
+                    directories
+                } else {
+                    directories
+                        .iter()
+                        .flat_map(|directory| {
+                            dir_args.ports.iter().filter_map(move |port| {
+                                dirbuster::ports::with_port(directory, *port)
+                            })
+                        })
+                        .collect()
+                };
+            }
+
+            let url_config = UrlConfig {
+                extensions: dir_args.extensions,
+                append_slash: dir_args.append_slash,
+                raw_path: dir_args.raw_path,
+                mutations: dir_args.mutations,
+                backslash: dir_args.backslash,
+                force_extensions: dir_args.force_extensions,
+                wraps: dir_args.wraps,
+            };
+            let mut urls: Vec<hyper::Uri> = Vec::new();
+            for base_url in base_urls.iter() {
+                urls.extend(build_urls(
+                    &wordlist_args.wordlist_paths[0],
+                    base_url,
+                    &url_config,
+                ));
+            }
+            let skip_dirs = dir_args.skip_dirs;
+            let candidates = urls.len();
+            urls.retain(|url| !is_skipped(url.path(), &skip_dirs));
+            if urls.len() < candidates {
+                crate::say!("{}", banner::skipped_dirs(candidates - urls.len()));
+            }
+            let both_schemes = dir_args.both_schemes;
+            let many_ports = dir_args.ports.len() > 1;
+            let mut scheme_diff = if both_schemes {
+                urls = with_both_schemes(urls);
+                Some(SchemeDiff::default())
+            } else {
+                None
             };
+            let queue = JobQueue::default();
+            queue.extend_weighted(urls, 0, &project.hit_rates(), scheduler::word_of);
+
+            let mut total_numbers_of_request = queue.pending();
+            let (tx, rx) = channel::<SingleDirScanResult>();
             let rp_config = ResultProcessorConfig {
                 include: http_args.include_status_codes,
                 ignore: http_args.ignore_status_codes,
+                include_body: body_args.include_strings,
+                ignore_body: body_args.ignore_strings,
+                rules: dir_args.rules,
             };
-            let mut result_processor = ScanResult::new(rp_config);
+            let mut journal = output::Journal::open(&common_args.output);
+            let mut pipeline = Pipeline::new(ScanResult::new(rp_config));
+            pipeline.register(Box::new(&mut journal));
+            let mut certificates = certificate::CertificateReport::new(certificate);
+            let mut progress = ProgressEvents::open(&common_args.progress_json);
+            let mut errors = ErrorSummary::default();
+            let mut auth_surface = AuthSurface::default();
+            let mut redirect_groups = RedirectGroups::default();
+            let mut known_results = KnownResults::load(&common_args.known);
+            let mut downgrade_candidates: Vec<(String, String, usize)> = Vec::new();
+            let classifier = Classifier::new(severity_rules);
             let bar = if common_args.no_progress_bar {
                 ProgressBar::hidden()
             } else {
@@ -173,22 +596,80 @@ fn main() {
                 .template("{spinner} [{elapsed_precise}] {bar:40.red/white} {pos:>7}/{len:7} ETA: {eta_precise} req/s: {msg}")
                 .progress_chars("#>-"));
 
-            thread::spawn(move || dirbuster::run(tx, urls, config));
+            let pacer = Arc::new(Pacer::new(slow_rules).with_max_delay(max_rate_limit_wait));
+            let console = if interactive {
+                crate::say!("{}", banner::interactive());
+                Some(Console::start(pacer.clone()))
+            } else {
+                None
+            };
+            let run_pacer = pacer.clone();
+            let mut learner = if dir_args.adaptive {
+                let wordlist =
+                    wordlist::read_filtered(&wordlist_args.wordlist_paths[0]).unwrap_or_default();
+                let host = http_args
+                    .url
+                    .parse::<hyper::Uri>()
+                    .ok()
+                    .and_then(|uri| uri.host().map(|h| h.to_owned()))
+                    .unwrap_or_default();
+                Some(Learner::new(&wordlist, &host))
+            } else {
+                // nothing is discovered while the scan runs
+                queue.close();
+                None
+            };
+            // the hosts of a run answer missing paths their own way, each gets its soft-404
+            let soft404s = if both_schemes || many_ports {
+                let mut probes: Vec<hyper::Uri> = base_urls
+                    .iter()
+                    .filter_map(|url| soft404::probe_url(url))
+                    .collect();
+                if both_schemes {
+                    probes = with_both_schemes(probes);
+                }
+                let soft404s = soft404::probe(probes, config.clone(), pacer.clone());
+                for banner in soft404s.banners() {
+                    crate::say!("{}", banner);
+                }
+                Some(soft404s)
+            } else {
+                None
+            };
+            let run_queue = queue.clone();
+            thread::spawn(move || dirbuster::run_queue(tx, run_queue, config, run_pacer));
 
             while current_numbers_of_request != total_numbers_of_request {
+                if common_args.max_results > 0
+                    && pipeline.processor.results.len() >= common_args.max_results
+                {
+                    if common_args.no_progress_bar {
+                        crate::say!("{}", banner::max_results(common_args.max_results));
+                    } else {
+                        bar.println(banner::max_results(common_args.max_results));
+                    }
+                    break;
+                }
+
                 current_numbers_of_request = current_numbers_of_request + 1;
                 bar.inc(1);
+                progress.update(
+                    current_numbers_of_request,
+                    total_numbers_of_request,
+                    pipeline.processor.results.len(),
+                );
                 let seconds_from_start = start_time.elapsed().unwrap().as_millis() / 1000;
-                if seconds_from_start != 0 {
-                    bar.set_message(
-                        &(current_numbers_of_request as u64 / seconds_from_start as u64)
-                            .to_string(),
-                    );
+                let mut message = if seconds_from_start != 0 {
+                    (current_numbers_of_request as u64 / seconds_from_start as u64).to_string()
                 } else {
-                    bar.set_message("warming up...")
+                    "warming up...".to_owned()
+                };
+                if let Some(budget) = pacer.budget() {
+                    message.push_str(&format!(" budget: {}", budget));
                 }
+                bar.set_message(&message);
 
-                let msg = match rx.recv() {
+                let mut msg = match rx.recv() {
                     Ok(msg) => msg,
                     Err(_err) => {
                         error!("{:?}", _err);
@@ -196,11 +677,16 @@ fn main() {
                     }
                 };
 
+                errors.add_status(&msg.status);
                 match &msg.error {
                     Some(e) => {
                         error!("{} - {:?}", msg.url, e);
-                        if current_numbers_of_request == 1 || common_args.exit_on_connection_errors
+                        errors.add_error(e);
+                        // one of the schemes or ports may not be served at all
+                        if (current_numbers_of_request == 1 && !both_schemes && !many_ports)
+                            || common_args.exit_on_connection_errors
                         {
+                            exit_code::abort();
                             warn!("Check connectivity to the target");
                             break;
                         }
@@ -210,65 +696,375 @@ fn main() {
                     None => (),
                 }
 
-                let was_added = result_processor.maybe_add_result(msg.clone());
+                msg.certificate = certificates.take(&msg.url);
+                msg.severity = Some(classifier.classify(&msg.url, &msg.status, &msg.body));
+                if let (true, Some(entry)) = (har_args.show_curl, &msg.har_entry) {
+                    msg.curl = Some(curl::command_from_env(
+                        &entry.request,
+                        http_args.ignore_certificate,
+                    ));
+                }
+                // the 404 are usually ignored, the probe needs the responses as they came
+                if dir_args.probe_http10 && downgrade::is_candidate(&msg.status) {
+                    downgrade_candidates.push((
+                        msg.url.to_owned(),
+                        msg.status.to_owned(),
+                        msg.length,
+                    ));
+                }
+                // the soft-404 go first, a hidden result reaches none of the sinks
+                let hidden = soft404s.as_ref().is_some_and(|s| s.matches(&msg));
+                let was_added = !hidden && pipeline.maybe_add_result(msg.clone());
+                // the live filters only hide the result from the output, they can still change
+                let live_hidden = was_added
+                    && console.as_ref().is_some_and(|console| {
+                        let seen = Seen {
+                            method: msg.method.to_owned(),
+                            status: msg.status.to_owned(),
+                            url: msg.url.to_owned(),
+                            length: msg.length,
+                        };
+                        console.record(seen, &msg.body)
+                    });
+                if let Some(scheme_diff) = scheme_diff.as_mut() {
+                    let response = SchemeResponse {
+                        status: msg.status.to_owned(),
+                        length: msg.length,
+                        location: msg.extra.clone(),
+                    };
+                    scheme_diff.observe(&msg.url, response, was_added);
+                }
+                if let (true, Some(learner)) = (was_added, learner.as_mut()) {
+                    let words = learner.learn(&msg.body);
+                    if !words.is_empty() {
+                        debug!("Learned from {}: {}", msg.url, words.join(", "));
+                        let words = words.join("\n");
+                        for base_url in base_urls.iter() {
+                            let mut urls = build_word_urls(&words, base_url, &url_config);
+                            if both_schemes {
+                                urls = with_both_schemes(urls);
+                            }
+                            for url in urls {
+                                if is_skipped(url.path(), &skip_dirs) {
+                                    continue;
+                                }
+                                // the learned words go before the rest of the wordlist
+                                queue.push(JobKind::FollowUp, 0, url);
+                                total_numbers_of_request += 1;
+                            }
+                        }
+                        bar.set_length(total_numbers_of_request as u64);
+                    }
+                }
                 if was_added {
-                    let mut extra = msg.extra.unwrap_or("".to_owned());
+                    auth_surface.maybe_add(&msg.url, &msg.status, msg.extra.as_deref());
+                    if known_results.is_known(&msg) {
+                        continue;
+                    }
 
-                    if !extra.is_empty() {
-                        extra = format!("\n\t\t\t\t\t\t=> {}", extra)
+                    if let (true, Some(location)) = (msg.status.starts_with('3'), &msg.extra) {
+                        if !redirect_groups.add(&msg.url, location) {
+                            continue;
+                        }
                     }
 
-                    let n_tabs = match msg.status.len() / 8 {
-                        3 => 1,
-                        2 => 2,
-                        1 => 3,
-                        0 => 4,
-                        _ => 0,
-                    };
+                    if live_hidden {
+                        continue;
+                    }
 
-                    if common_args.no_progress_bar {
+                    if !common_args.format.is_empty() {
+                        let line = output::format_result(&common_args.format, &msg, msg.length);
+                        if common_args.no_progress_bar {
+                            println!("{}", line);
+                        } else {
+                            bar.println(line);
+                        }
+                        continue;
+                    }
+
+                    if common_args.quiet {
                         println!(
-                            "{}\t{}{}{}{}",
+                            "{}\t{}\t{}\t{}",
                             msg.method,
                             msg.status,
-                            "\t".repeat(n_tabs),
                             msg.url,
-                            extra
+                            msg.extra.as_deref().unwrap_or("")
                         );
+                        continue;
+                    }
+
+                    let mut extra = msg.extra.unwrap_or("".to_owned());
+
+                    if !extra.is_empty() {
+                        extra = format!("\n\t\t\t\t\t\t=> {}", extra)
+                    }
+
+                    extra.push_str(&output::format_captured_headers(&msg.captured_headers));
+                    if let Some(curl) = &msg.curl {
+                        extra.push_str(&format!("\n\t\t\t\t\t\t=> {}", curl));
+                    }
+
+                    let line = format!(
+                        "{}{}",
+                        output::format_finding(&msg.method, &msg.status, &msg.url, msg.severity),
+                        extra
+                    );
+                    if common_args.no_progress_bar {
+                        println!("{}", line);
                     } else {
-                        bar.println(format!(
-                            "{}\t{}{}{}{}",
-                            msg.method,
-                            msg.status,
-                            "\t".repeat(n_tabs),
-                            msg.url,
-                            extra
-                        ));
+                        bar.println(line);
                     }
                 }
             }
+            queue.close();
 
+            let mut result_processor = pipeline.into_processor();
+            progress.finish(
+                current_numbers_of_request,
+                total_numbers_of_request,
+                result_processor.results.len(),
+            );
             bar.finish();
-            println!("{}", banner::ending_time());
+            if let Some(console) = &console {
+                result_processor
+                    .results
+                    .retain(|r| !console.hides(&r.url, &r.status, r.length));
+            }
 
-            if !common_args.output.is_empty() {
-                save_dir_results(&common_args.output, &result_processor.results);
+            if sort.is_some() || group_by.is_some() {
+                report::order(&mut result_processor.results, sort, group_by);
+                crate::say!("{}", report::summary(&result_processor.results, group_by));
+            }
+
+            if !auth_surface.is_empty() {
+                crate::say!("{}", auth_surface.report());
+            }
+
+            if let Some(header_audit) = &header_audit {
+                crate::say!("{}", header_audit.report());
+            }
+
+            if redirect_groups.collapsed() > 0 {
+                crate::say!("{}", redirect_groups.report());
+            }
+
+            if let Some(scheme_diff) = &scheme_diff {
+                crate::say!("{}", scheme_diff.report());
+            }
+
+            if many_ports {
+                crate::say!("{}", dirbuster::ports::report(&result_processor.results));
+            }
+
+            if dir_args.check_cors {
+                let urls: Vec<String> = result_processor
+                    .results
+                    .iter()
+                    .map(|r| r.url.to_owned())
+                    .collect();
+                let cors_config = CorsConfig {
+                    n_threads: common_args.n_threads,
+                    ignore_certificate: http_args.ignore_certificate,
+                    user_agent: http_args.user_agent.to_owned(),
+                    http_headers: http_args.http_headers.clone(),
+                    tls_min_version: http_args.tls_min_version,
+                    tls_max_version: http_args.tls_max_version,
+                };
+                crate::say!("{}", cors::report(&cors::check(&urls, &cors_config)));
+            }
+
+            if dir_args.check_head {
+                let results: Vec<(String, usize)> = result_processor
+                    .results
+                    .iter()
+                    .map(|r| (r.url.to_owned(), r.length))
+                    .collect();
+                let head_check_config = HeadCheckConfig {
+                    n_threads: common_args.n_threads,
+                    ignore_certificate: http_args.ignore_certificate,
+                    user_agent: http_args.user_agent.to_owned(),
+                    http_headers: http_args.http_headers.clone(),
+                    tls_min_version: http_args.tls_min_version,
+                    tls_max_version: http_args.tls_max_version,
+                };
+                crate::say!(
+                    "{}",
+                    head_check::report(&head_check::check(&results, &head_check_config))
+                );
+            }
+
+            if dir_args.probe_http10 {
+                let downgrade_config = DowngradeConfig {
+                    n_threads: common_args.n_threads,
+                    ignore_certificate: http_args.ignore_certificate,
+                    user_agent: http_args.user_agent.to_owned(),
+                    http_headers: http_args.http_headers.clone(),
+                    tls_min_version: http_args.tls_min_version,
+                    tls_max_version: http_args.tls_max_version,
+                };
+                crate::say!(
+                    "{}",
+                    downgrade::report(&downgrade::check(&downgrade_candidates, &downgrade_config))
+                );
+            }
+
+            if !errors.is_empty() {
+                crate::say!("{}", errors.report());
+            }
+
+            if let Some(budget) = pacer.budget() {
+                crate::say!("{}", banner::rate_budget(&budget));
+            }
+
+            if known_results.skipped > 0 {
+                crate::say!("{}", banner::known_results(known_results.skipped));
+            }
+
+            if let Some(learner) = &learner {
+                crate::say!("{}", banner::learned_words(learner.learned()));
+            }
+
+            crate::say!("{}", banner::ending_time());
+
+            if geoip.is_enabled() {
+                for result in result_processor.results.iter_mut() {
+                    result.geo = geoip.annotate(result.server_ip);
+                }
+            }
+
+            if !har_args.har.is_empty() {
+                har::save_har(
+                    &har_args.har,
+                    result_processor
+                        .results
+                        .iter()
+                        .filter_map(|r| r.har_entry.clone())
+                        .collect(),
+                );
+            }
+
+            if !har_args.save_requests.is_empty() {
+                har::save_requests(
+                    &har_args.save_requests,
+                    result_processor
+                        .results
+                        .iter()
+                        .filter_map(|r| r.har_entry.clone())
+                        .collect(),
+                );
             }
+
+            if !har_args.burp.is_empty() {
+                burp::save_items(
+                    &har_args.burp,
+                    &result_processor
+                        .results
+                        .iter()
+                        .filter_map(|r| r.har_entry.clone())
+                        .collect::<Vec<_>>(),
+                );
+            }
+
+            if !common_args.output.is_empty() && !common_args.format.is_empty() {
+                let lines: Vec<String> = result_processor
+                    .results
+                    .iter()
+                    .map(|r| output::format_result(&common_args.format, r, r.length))
+                    .collect();
+                output::save_formatted(&common_args.output, &lines, common_args.output_append);
+            } else if !common_args.output.is_empty() {
+                save_dir_results(
+                    &common_args.output,
+                    &result_processor.results,
+                    common_args.output_append,
+                );
+            }
+
+            journal.finish();
+            project.record(&result_processor.results);
+            result_processor.results.len()
         }
         "dns" => {
             let wordlist_args = match extract_wordlist_args(submatches) {
-                Err(_) => return,
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
                 Ok(v) => v,
             };
 
-            let dns_args = extract_dns_args(submatches);
-            let domains = build_domains(&wordlist_args.wordlist_paths[0], &dns_args.domain);
-            let total_numbers_of_request = domains.len();
-            let (tx, rx) = channel::<SingleDnsScanResult>();
+            let subdomains = match extract_subdomains(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+
+            let dns_args = match extract_dns_args(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
             let config = DnsConfig {
                 n_threads: common_args.n_threads,
+                timeout: Duration::from_millis(dns_args.timeout),
+                retries: dns_args.retries,
+            };
+            let walked = if dns_args.zone_walk {
+                match zone_walk::walk(&dns_args.domain, &config) {
+                    ZoneWalk::Walked(names) => {
+                        crate::say!("{}", banner::zone_walked(names.len()));
+                        Some(names.iter().map(|name| format!("{}:80", name)).collect())
+                    }
+                    ZoneWalk::Nsec3 { iterations } => {
+                        crate::say!("{}", banner::zone_walk_nsec3(iterations));
+                        None
+                    }
+                    ZoneWalk::Unsigned => {
+                        crate::say!("{}", banner::zone_walk_unsigned());
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+            let mut domains = if let Some(walked) = walked {
+                walked
+            } else if let Some(subdomains) = subdomains {
+                subdomains
+                    .iter()
+                    .map(|name| format!("{}:80", name))
+                    .collect()
+            } else if dns_args.brute_tlds {
+                build_tld_domains(wordlist_args.wordlist_paths.first(), &dns_args.domain)
+            } else {
+                build_domains(&wordlist_args.wordlist_paths[0], &dns_args.domain)
             };
-            let mut result_processor = DnsScanResult::new();
+            if dns_args.seed_from_cert {
+                match certificate::fetch_certificate(
+                    &format!("https://{}/", dns_args.domain),
+                    None,
+                    None,
+                ) {
+                    Some(info) => {
+                        crate::say!("{}", banner::certificate(&info));
+                        domains =
+                            seed_domains(domains, certificate::seed_hosts(&info, &dns_args.domain));
+                    }
+                    None => warn!("Unable to retrieve the certificate, SAN seeding skipped"),
+                }
+            }
+
+            let total_numbers_of_request = domains.len();
+            let (tx, rx) = channel::<SingleDnsScanResult>();
+            let mut journal = output::Journal::open(&common_args.output);
+            let mut pipeline = Pipeline::new(DnsScanResult::new());
+            pipeline.register(Box::new(&mut journal));
+            let mut progress = ProgressEvents::open(&common_args.progress_json);
+            let mut known_results = KnownResults::load(&common_args.known);
 
             let bar = if common_args.no_progress_bar {
                 ProgressBar::hidden()
@@ -280,11 +1076,24 @@ fn main() {
                 .template("{spinner} [{elapsed_precise}] {bar:40.red/white} {pos:>7}/{len:7} ETA: {eta_precise} req/s: {msg}")
                 .progress_chars("#>-"));
 
+            let asn_config = config.clone();
             thread::spawn(move || dnsbuster::run(tx, domains, config));
 
+            let mut found = 0;
+
             while current_numbers_of_request != total_numbers_of_request {
+                if common_args.max_results > 0 && found >= common_args.max_results {
+                    if common_args.no_progress_bar {
+                        crate::say!("{}", banner::max_results(common_args.max_results));
+                    } else {
+                        bar.println(banner::max_results(common_args.max_results));
+                    }
+                    break;
+                }
+
                 current_numbers_of_request = current_numbers_of_request + 1;
                 bar.inc(1);
+                progress.update(current_numbers_of_request, total_numbers_of_request, found);
 
                 let seconds_from_start = start_time.elapsed().unwrap().as_millis() / 1000;
                 if seconds_from_start != 0 {
@@ -304,8 +1113,26 @@ fn main() {
                     }
                 };
 
-                result_processor.maybe_add_result(msg.clone());
+                pipeline.maybe_add_result(msg.clone());
+                if msg.status {
+                    found += 1;
+                }
+
                 match msg.status {
+                    true if known_results.is_known(&msg) => (),
+                    true if common_args.quiet => {
+                        let addrs: Vec<String> = msg
+                            .extra
+                            .unwrap_or_default()
+                            .iter()
+                            .map(|addr| addr.ip().to_string())
+                            .collect();
+                        println!(
+                            "{}\t{}",
+                            &msg.domain[..msg.domain.len() - 3],
+                            addrs.join(",")
+                        );
+                    }
                     true => {
                         if common_args.no_progress_bar {
                             println!("OK\t{}", &msg.domain[..msg.domain.len() - 3]);
@@ -342,38 +1169,180 @@ fn main() {
                 }
             }
 
+            let mut result_processor = pipeline.into_processor();
+            progress.finish(current_numbers_of_request, total_numbers_of_request, found);
             bar.finish();
-            println!("{}", banner::ending_time());
+            if known_results.skipped > 0 {
+                crate::say!("{}", banner::known_results(known_results.skipped));
+            }
+            if dns_args.asn_lookup {
+                asn::enrich(&mut result_processor.results, &asn_config);
+                crate::say!("{}", asn::report(&result_processor.results));
+            }
 
-            if !common_args.output.is_empty() {
-                save_dns_results(&common_args.output, &result_processor.results);
+            crate::say!("{}", banner::ending_time());
+
+            if geoip.is_enabled() {
+                for result in result_processor.results.iter_mut() {
+                    result.geo = result
+                        .extra
+                        .iter()
+                        .flatten()
+                        .filter_map(|addr| geoip.lookup(addr.ip()))
+                        .collect();
+                }
+            }
+
+            if dns_args.output_format == "hosts" && !common_args.output.is_empty() {
+                output::save_formatted(
+                    &common_args.output,
+                    &hosts_lines(&result_processor.results),
+                    common_args.output_append,
+                );
+            } else if !common_args.output.is_empty() {
+                save_dns_results(
+                    &common_args.output,
+                    &result_processor.results,
+                    common_args.output_append,
+                );
             }
+            journal.finish();
+
+            let resolved: Vec<&SingleDnsScanResult> = result_processor
+                .results
+                .iter()
+                .filter(|r| r.status)
+                .collect();
+            project.record(&resolved);
+            resolved.len()
         }
         "vhost" => {
-            let dns_args = extract_dns_args(submatches);
+            let dns_args = match extract_dns_args(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
             let body_args = extract_body_args(submatches);
-            let http_args = extract_http_args(submatches);
+            let har_args = extract_har_args(submatches);
+            let http_args = match extract_http_args(submatches)
+                .and_then(|v| prepare_http_args(submatches, v))
+            {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+
             if !url_is_valid(&http_args.url) {
-                return;
+                return exit_code::INVALID_CONFIG;
             }
 
             let wordlist_args = match extract_wordlist_args(submatches) {
-                Err(_) => return,
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
                 Ok(v) => v,
             };
 
-            let vhosts = build_vhosts(&wordlist_args.wordlist_paths[0], &dns_args.domain);
-            let total_numbers_of_request = vhosts.len();
-            let (tx, rx) = channel::<SingleVhostScanResult>();
-            let config = VhostConfig {
-                n_threads: common_args.n_threads,
-                ignore_certificate: http_args.ignore_certificate,
-                http_method: http_args.http_method.to_owned(),
-                user_agent: http_args.user_agent.to_owned(),
-                ignore_strings: body_args.ignore_strings,
-                original_url: http_args.url.to_owned(),
+            let subdomains = match extract_subdomains(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+
+            let slow_rules = match extract_slow_rules(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
             };
-            let mut result_processor = VhostScanResult::new();
+            let max_rate_limit_wait = match extract_max_rate_limit_wait(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+
+            let certificate = certificate::fetch_certificate(
+                &http_args.url,
+                http_args.tls_min_version,
+                http_args.tls_max_version,
+            );
+            if let Some(info) = &certificate {
+                crate::say!("{}", banner::certificate(info));
+            }
+
+            if http_args.favicon {
+                if let Some((hash, product)) =
+                    favicon::fingerprint(&http_args.url, http_args.ignore_certificate)
+                {
+                    crate::say!("{}", banner::favicon(hash, product));
+                }
+            }
+
+            let mut vhosts = match subdomains {
+                Some(subdomains) => subdomains
+                    .iter()
+                    .filter_map(|name| name.parse::<hyper::Uri>().ok())
+                    .collect(),
+                None => build_vhosts(&wordlist_args.wordlist_paths[0], &dns_args.domain),
+            };
+            let words = vhosts.len();
+            if dns_args.seed_from_cert {
+                match &certificate {
+                    Some(info) => {
+                        vhosts =
+                            seed_vhosts(vhosts, certificate::seed_hosts(info, &dns_args.domain));
+                    }
+                    None => warn!("Unable to retrieve the certificate, SAN seeding skipped"),
+                }
+            }
+            let seeds = vhosts.len() - words;
+            let vhosts = scheduler::prioritize(vhosts, seeds, &project.hit_rates());
+
+            let total_numbers_of_request = if dns_args.absolute_uri {
+                vhosts.len() * 2
+            } else {
+                vhosts.len()
+            };
+            let (tx, rx) = channel::<SingleVhostScanResult>();
+            let header_audit = if http_args.audit_headers {
+                Some(Arc::new(HeaderAudit::default()))
+            } else {
+                None
+            };
+            let config = VhostConfig {
+                n_threads: common_args.n_threads,
+                ignore_certificate: http_args.ignore_certificate,
+                http_method: http_args.http_method.to_owned(),
+                http_body: http_args.http_body.to_owned(),
+                user_agent: http_args.user_agent.to_owned(),
+                ignore_strings: body_args.ignore_strings,
+                original_url: http_args.url.to_owned(),
+                save_har: har_args.save_har(),
+                tls_min_version: http_args.tls_min_version,
+                tls_max_version: http_args.tls_max_version,
+                digest: http_args.digest.clone(),
+                negotiate: http_args.negotiate.clone(),
+                capture_headers: http_args.capture_headers.clone(),
+                header_audit: header_audit.clone(),
+                absolute_uri: dns_args.absolute_uri,
+            };
+            let mut journal = output::Journal::open(&common_args.output);
+            let mut pipeline = Pipeline::new(VhostScanResult::new());
+            pipeline.register(Box::new(&mut journal));
+            let mut certificates = certificate::CertificateReport::new(certificate);
+            let mut progress = ProgressEvents::open(&common_args.progress_json);
+            let mut errors = ErrorSummary::default();
+            let mut known_results = KnownResults::load(&common_args.known);
             let bar = if common_args.no_progress_bar {
                 ProgressBar::hidden()
             } else {
@@ -384,22 +1353,41 @@ fn main() {
                 .template("{spinner} [{elapsed_precise}] {bar:40.red/white} {pos:>7}/{len:7} ETA: {eta_precise} req/s: {msg}")
                 .progress_chars("#>-"));
 
-            thread::spawn(move || vhostbuster::run(tx, vhosts, config));
+            let pacer = Arc::new(Pacer::new(slow_rules).with_max_delay(max_rate_limit_wait));
+            let run_pacer = pacer.clone();
+            thread::spawn(move || vhostbuster::run_with_pacer(tx, vhosts, config, run_pacer));
 
             while current_numbers_of_request != total_numbers_of_request {
+                if common_args.max_results > 0
+                    && pipeline.processor.results.len() >= common_args.max_results
+                {
+                    if common_args.no_progress_bar {
+                        crate::say!("{}", banner::max_results(common_args.max_results));
+                    } else {
+                        bar.println(banner::max_results(common_args.max_results));
+                    }
+                    break;
+                }
+
                 current_numbers_of_request = current_numbers_of_request + 1;
                 bar.inc(1);
+                progress.update(
+                    current_numbers_of_request,
+                    total_numbers_of_request,
+                    pipeline.processor.results.len(),
+                );
                 let seconds_from_start = start_time.elapsed().unwrap().as_millis() / 1000;
-                if seconds_from_start != 0 {
-                    bar.set_message(
-                        &(current_numbers_of_request as u64 / seconds_from_start as u64)
-                            .to_string(),
-                    );
+                let mut message = if seconds_from_start != 0 {
+                    (current_numbers_of_request as u64 / seconds_from_start as u64).to_string()
                 } else {
-                    bar.set_message("warming up...")
+                    "warming up...".to_owned()
+                };
+                if let Some(budget) = pacer.budget() {
+                    message.push_str(&format!(" budget: {}", budget));
                 }
+                bar.set_message(&message);
 
-                let msg = match rx.recv() {
+                let mut msg = match rx.recv() {
                     Ok(msg) => msg,
                     Err(_err) => {
                         error!("{:?}", _err);
@@ -407,11 +1395,14 @@ fn main() {
                     }
                 };
 
+                errors.add_status(&msg.status);
                 match &msg.error {
                     Some(e) => {
                         error!("{} - {:?}", msg.vhost, e);
+                        errors.add_error(e);
                         if current_numbers_of_request == 1 || common_args.exit_on_connection_errors
                         {
+                            exit_code::abort();
                             warn!("Check connectivity to the target");
                             break;
                         }
@@ -421,107 +1412,1192 @@ fn main() {
                     None => (),
                 }
 
-                let n_tabs = match msg.status.len() / 8 {
-                    3 => 1,
-                    2 => 2,
-                    1 => 3,
-                    0 => 4,
-                    _ => 0,
-                };
-
                 if !msg.ignored {
-                    result_processor.maybe_add_result(msg.clone());
-                    if common_args.no_progress_bar {
-                        println!(
-                            "{}\t{}{}{}",
+                    msg.certificate = certificates.take(&http_args.url);
+                    if let (true, Some(entry)) = (har_args.show_curl, &msg.har_entry) {
+                        msg.curl = Some(curl::command_from_env(
+                            &entry.request,
+                            http_args.ignore_certificate,
+                        ));
+                    }
+                    pipeline.maybe_add_result(msg.clone());
+                    if known_results.is_known(&msg) {
+                        continue;
+                    }
+
+                    let line = if !common_args.format.is_empty() {
+                        output::format_result(&common_args.format, &msg, 0)
+                    } else if common_args.quiet {
+                        format!(
+                            "{}\t{}\t{}\t{}\t{}",
                             msg.method,
                             msg.status,
-                            "\t".repeat(n_tabs),
-                            msg.vhost
-                        );
+                            msg.vhost,
+                            msg.location.as_deref().unwrap_or(""),
+                            msg.title.as_deref().unwrap_or("")
+                        )
                     } else {
-                        bar.println(format!(
-                            "{}\t{}{}{}",
-                            msg.method,
+                        format!(
+                            "{}{}{}",
+                            output::format_finding(&msg.method, &msg.status, &msg.vhost, None),
+                            format_details(&msg),
+                            output::format_captured_headers(&msg.captured_headers)
+                        )
+                    };
+                    if common_args.no_progress_bar {
+                        println!("{}", line);
+                    } else {
+                        bar.println(line);
+                    }
+                }
+            }
+
+            let mut result_processor = pipeline.into_processor();
+            progress.finish(
+                current_numbers_of_request,
+                total_numbers_of_request,
+                result_processor.results.len(),
+            );
+            bar.finish();
+            if let Some(header_audit) = &header_audit {
+                crate::say!("{}", header_audit.report());
+            }
+
+            if dns_args.resolve_vhosts && !result_processor.results.is_empty() {
+                crate::say!(
+                    "{}",
+                    resolve_vhosts(&mut result_processor.results, &http_args.url)
+                );
+            }
+
+            if !errors.is_empty() {
+                crate::say!("{}", errors.report());
+            }
+
+            if let Some(budget) = pacer.budget() {
+                crate::say!("{}", banner::rate_budget(&budget));
+            }
+
+            if known_results.skipped > 0 {
+                crate::say!("{}", banner::known_results(known_results.skipped));
+            }
+
+            crate::say!("{}", banner::ending_time());
+
+            if geoip.is_enabled() {
+                for result in result_processor.results.iter_mut() {
+                    result.geo = geoip.annotate(result.server_ip);
+                }
+            }
+
+            if !har_args.har.is_empty() {
+                har::save_har(
+                    &har_args.har,
+                    result_processor
+                        .results
+                        .iter()
+                        .filter_map(|r| r.har_entry.clone())
+                        .collect(),
+                );
+            }
+
+            if !har_args.save_requests.is_empty() {
+                har::save_requests(
+                    &har_args.save_requests,
+                    result_processor
+                        .results
+                        .iter()
+                        .filter_map(|r| r.har_entry.clone())
+                        .collect(),
+                );
+            }
+
+            if !har_args.burp.is_empty() {
+                burp::save_items(
+                    &har_args.burp,
+                    &result_processor
+                        .results
+                        .iter()
+                        .filter_map(|r| r.har_entry.clone())
+                        .collect::<Vec<_>>(),
+                );
+            }
+
+            if !common_args.output.is_empty() && !common_args.format.is_empty() {
+                let lines: Vec<String> = result_processor
+                    .results
+                    .iter()
+                    .map(|r| output::format_result(&common_args.format, r, 0))
+                    .collect();
+                output::save_formatted(&common_args.output, &lines, common_args.output_append);
+            } else if !common_args.output.is_empty() {
+                save_vhost_results(
+                    &common_args.output,
+                    &result_processor.results,
+                    common_args.output_append,
+                );
+            }
+
+            journal.finish();
+            project.record(&result_processor.results);
+            result_processor.results.len()
+        }
+        "methods" => {
+            let wordlist_args = match extract_wordlist_args(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+
+            let http_args = match extract_http_args(submatches)
+                .and_then(|v| prepare_http_args(submatches, v))
+            {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+
+            if !url_is_valid(&http_args.url) {
+                return exit_code::INVALID_CONFIG;
+            }
+
+            let urls = scheduler::prioritize(
+                methodbuster::utils::build_urls(&wordlist_args.wordlist_paths[0], &http_args.url),
+                0,
+                &project.hit_rates(),
+            );
+            let total_numbers_of_request = urls.len() * methodbuster::METHODS.len();
+            let (tx, rx) = channel::<SingleMethodScanResult>();
+            let config = MethodConfig {
+                n_threads: common_args.n_threads,
+                ignore_certificate: http_args.ignore_certificate,
+                http_body: http_args.http_body.to_owned(),
+                user_agent: http_args.user_agent.to_owned(),
+                http_headers: http_args.http_headers.clone(),
+                tls_min_version: http_args.tls_min_version,
+                tls_max_version: http_args.tls_max_version,
+            };
+            let mut result_processor = MethodScanResult::new();
+            let mut journal = output::Journal::open(&common_args.output);
+            let mut progress = ProgressEvents::open(&common_args.progress_json);
+            let mut errors = ErrorSummary::default();
+            let bar = if common_args.no_progress_bar {
+                ProgressBar::hidden()
+            } else {
+                ProgressBar::new(total_numbers_of_request as u64)
+            };
+            bar.set_draw_delta(100);
+            bar.set_style(ProgressStyle::default_bar()
+                .template("{spinner} [{elapsed_precise}] {bar:40.red/white} {pos:>7}/{len:7} ETA: {eta_precise} req/s: {msg}")
+                .progress_chars("#>-"));
+
+            thread::spawn(move || methodbuster::run(tx, urls, config));
+
+            while current_numbers_of_request != total_numbers_of_request {
+                if common_args.max_results > 0
+                    && result_processor.results.len() >= common_args.max_results
+                {
+                    if common_args.no_progress_bar {
+                        crate::say!("{}", banner::max_results(common_args.max_results));
+                    } else {
+                        bar.println(banner::max_results(common_args.max_results));
+                    }
+                    break;
+                }
+
+                current_numbers_of_request += 1;
+                bar.inc(1);
+                progress.update(
+                    current_numbers_of_request,
+                    total_numbers_of_request,
+                    result_processor.results.len(),
+                );
+                let seconds_from_start = start_time.elapsed().unwrap().as_millis() / 1000;
+                if seconds_from_start != 0 {
+                    bar.set_message(
+                        &(current_numbers_of_request as u64 / seconds_from_start as u64)
+                            .to_string(),
+                    );
+                } else {
+                    bar.set_message("warming up...")
+                }
+
+                let msg = match rx.recv() {
+                    Ok(msg) => msg,
+                    Err(_err) => {
+                        error!("{:?}", _err);
+                        break;
+                    }
+                };
+
+                errors.add_status(&msg.status);
+                if let Some(e) = &msg.error {
+                    error!("{} {} - {:?}", msg.method, msg.url, e);
+                    errors.add_error(e);
+                    if current_numbers_of_request == 1 || common_args.exit_on_connection_errors {
+                        exit_code::abort();
+                        warn!("Check connectivity to the target");
+                        break;
+                    }
+
+                    // kept for the errors of the report
+                    result_processor.store(msg);
+                    continue;
+                }
+
+                if result_processor.maybe_add_result(msg.clone()) {
+                    journal.record(&msg);
+                    let line = if !common_args.format.is_empty() {
+                        output::format_result(&common_args.format, &msg, 0)
+                    } else if common_args.quiet {
+                        format!("{}\t{}\t{}", msg.method, msg.status, msg.url)
+                    } else {
+                        output::format_finding(&msg.method, &msg.status, &msg.url, None)
+                    };
+                    if common_args.no_progress_bar {
+                        println!("{}", line);
+                    } else {
+                        bar.println(line);
+                    }
+                }
+            }
+
+            progress.finish(
+                current_numbers_of_request,
+                total_numbers_of_request,
+                result_processor.results.len(),
+            );
+            bar.finish();
+            let report = result_processor.report();
+            if !report.is_empty() {
+                crate::say!("\n{}", report);
+            }
+
+            if !errors.is_empty() {
+                crate::say!("{}", errors.report());
+            }
+
+            crate::say!("{}", banner::ending_time());
+
+            if !common_args.output.is_empty() && !common_args.format.is_empty() {
+                let lines: Vec<String> = result_processor
+                    .results
+                    .iter()
+                    .map(|r| output::format_result(&common_args.format, r, 0))
+                    .collect();
+                output::save_formatted(&common_args.output, &lines, common_args.output_append);
+            } else if !common_args.output.is_empty() {
+                save_method_results(
+                    &common_args.output,
+                    &result_processor.results,
+                    common_args.output_append,
+                );
+            }
+
+            journal.finish();
+            project.record(&result_processor.results);
+            result_processor.results.len()
+        }
+        "ws" => {
+            let wordlist_args = match extract_wordlist_args(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+
+            let http_args = match extract_http_args(submatches)
+                .and_then(|v| prepare_http_args(submatches, v))
+            {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+
+            let ws_args = match extract_ws_args(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+
+            let url = wsbuster::utils::http_url(&http_args.url);
+            if !url_is_valid(&url) {
+                return exit_code::INVALID_CONFIG;
+            }
+
+            let urls = scheduler::prioritize(
+                methodbuster::utils::build_urls(&wordlist_args.wordlist_paths[0], &url),
+                0,
+                &project.hit_rates(),
+            );
+            let mut total_numbers_of_request = urls.len();
+            let (tx, rx) = channel::<SingleWsScanResult>();
+            let config = WsConfig {
+                n_threads: common_args.n_threads,
+                ignore_certificate: http_args.ignore_certificate,
+                user_agent: http_args.user_agent.to_owned(),
+                http_headers: http_args.http_headers.clone(),
+                tls_min_version: http_args.tls_min_version,
+                tls_max_version: http_args.tls_max_version,
+                messages: ws_args.messages.clone(),
+            };
+            let mut result_processor = WsScanResult::new();
+            let mut journal = output::Journal::open(&common_args.output);
+            let mut progress = ProgressEvents::open(&common_args.progress_json);
+            let mut errors = ErrorSummary::default();
+            let bar = if common_args.no_progress_bar {
+                ProgressBar::hidden()
+            } else {
+                ProgressBar::new(total_numbers_of_request as u64)
+            };
+            bar.set_draw_delta(100);
+            bar.set_style(ProgressStyle::default_bar()
+                .template("{spinner} [{elapsed_precise}] {bar:40.red/white} {pos:>7}/{len:7} ETA: {eta_precise} req/s: {msg}")
+                .progress_chars("#>-"));
+
+            thread::spawn(move || wsbuster::run(tx, urls, config));
+
+            while current_numbers_of_request != total_numbers_of_request {
+                if common_args.max_results > 0
+                    && result_processor.results.len() >= common_args.max_results
+                {
+                    if common_args.no_progress_bar {
+                        crate::say!("{}", banner::max_results(common_args.max_results));
+                    } else {
+                        bar.println(banner::max_results(common_args.max_results));
+                    }
+                    break;
+                }
+
+                current_numbers_of_request += 1;
+                bar.inc(1);
+                progress.update(
+                    current_numbers_of_request,
+                    total_numbers_of_request,
+                    result_processor.results.len(),
+                );
+                let seconds_from_start = start_time.elapsed().unwrap().as_millis() / 1000;
+                if seconds_from_start != 0 {
+                    bar.set_message(
+                        &(current_numbers_of_request as u64 / seconds_from_start as u64)
+                            .to_string(),
+                    );
+                } else {
+                    bar.set_message("warming up...")
+                }
+
+                let msg = match rx.recv() {
+                    Ok(msg) => msg,
+                    Err(_err) => {
+                        error!("{:?}", _err);
+                        break;
+                    }
+                };
+
+                errors.add_status(&msg.status);
+                if let Some(e) = &msg.error {
+                    error!("{} - {:?}", msg.url, e);
+                    errors.add_error(e);
+                    if current_numbers_of_request == 1 || common_args.exit_on_connection_errors {
+                        exit_code::abort();
+                        warn!("Check connectivity to the target");
+                        break;
+                    }
+
+                    continue;
+                }
+
+                if msg.upgraded && msg.message.is_none() {
+                    total_numbers_of_request += ws_args.messages.len();
+                    bar.set_length(total_numbers_of_request as u64);
+                }
+
+                if result_processor.maybe_add_result(msg.clone()) {
+                    journal.record(&msg);
+                    let line = if !common_args.format.is_empty() {
+                        output::format_result(&common_args.format, &msg, 0)
+                    } else if common_args.quiet {
+                        format!(
+                            "{}\t{}\t{}\t{}",
                             msg.status,
-                            "\t".repeat(n_tabs),
-                            msg.vhost
-                        ));
+                            msg.url,
+                            msg.message.as_deref().unwrap_or(""),
+                            msg.response.as_deref().unwrap_or("")
+                        )
+                    } else {
+                        match &msg.message {
+                            Some(message) => format!(
+                                "{}\n\t\t\t\t\t\t=> MESSAGE: {:?}\n\t\t\t\t\t\t=> RESPONSE: {:?}",
+                                output::format_finding(&msg.method, &msg.status, &msg.url, None),
+                                message,
+                                msg.response.as_deref().unwrap_or("")
+                            ),
+                            None => {
+                                output::format_finding(&msg.method, &msg.status, &msg.url, None)
+                            }
+                        }
+                    };
+                    if common_args.no_progress_bar {
+                        println!("{}", line);
+                    } else {
+                        bar.println(line);
                     }
                 }
             }
 
+            progress.finish(
+                current_numbers_of_request,
+                total_numbers_of_request,
+                result_processor.results.len(),
+            );
             bar.finish();
-            println!("{}", banner::ending_time());
+            if !errors.is_empty() {
+                crate::say!("{}", errors.report());
+            }
 
-            if !common_args.output.is_empty() {
-                save_vhost_results(&common_args.output, &result_processor.results);
+            crate::say!("{}", banner::ending_time());
+
+            if !common_args.output.is_empty() && !common_args.format.is_empty() {
+                let lines: Vec<String> = result_processor
+                    .results
+                    .iter()
+                    .map(|r| output::format_result(&common_args.format, r, 0))
+                    .collect();
+                output::save_formatted(&common_args.output, &lines, common_args.output_append);
+            } else if !common_args.output.is_empty() {
+                save_ws_results(
+                    &common_args.output,
+                    &result_processor.results,
+                    common_args.output_append,
+                );
             }
+
+            journal.finish();
+            project.record(&result_processor.results);
+            result_processor.results.len()
         }
         "fuzz" => {
-            let http_args = extract_http_args(submatches);
+            let har_args = extract_har_args(submatches);
+            let http_args = match extract_http_args(submatches)
+                .and_then(|v| prepare_http_args(submatches, v))
+            {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+
             if !url_is_valid(&http_args.url) {
-                return;
+                return exit_code::INVALID_CONFIG;
             }
 
             let wordlist_args = match extract_wordlist_args(submatches) {
-                Err(_) => return,
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
                 Ok(v) => v,
             };
 
             let body_args = extract_body_args(submatches);
             let fuzz_args = extract_fuzz_args(submatches);
+            let mut http_body = http_args.http_body.clone();
+            let severity_rules = match extract_severity_rules(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+            let payloads = match extract_payloads(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+            let generator = match extract_generator(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+            let slow_rules = match extract_slow_rules(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+            let max_rate_limit_wait = match extract_max_rate_limit_wait(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+            let (sort, group_by) = extract_report_order(submatches);
+            let interactive = submatches.is_present("interactive");
+            let follow_up = match extract_follow_up(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+            let session = match extract_session_args(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+            let mut http_headers = http_args.http_headers;
+            if !fuzz_args.forms.is_empty() {
+                let boundary = fuzzbuster::utils::multipart_boundary();
+                http_body =
+                    match fuzzbuster::utils::build_multipart_body(&fuzz_args.forms, &boundary) {
+                        Ok(v) => v,
+                        Err(e) => {
+                            error!("{}", e);
+                            return exit_code::INVALID_CONFIG;
+                        }
+                    };
+                http_headers.retain(|(name, _)| !name.eq_ignore_ascii_case("Content-Type"));
+                http_headers.push((
+                    "Content-Type".to_owned(),
+                    format!("multipart/form-data; boundary={}", boundary),
+                ));
+            }
+
+            if fuzz_args.mutate && http_body.is_empty() {
+                error!("Mutation requires an HTTP body to use as seed, use -b");
+                return exit_code::INVALID_CONFIG;
+            }
+            if fuzz_args.mutate && std::str::from_utf8(&http_body).is_err() {
+                error!("Mutation requires a text HTTP body to use as seed");
+                return exit_code::INVALID_CONFIG;
+            }
+
+            if !payloads.is_empty() {
+                let markers: Vec<&str> = payloads.iter().map(|(m, _)| m.as_str()).collect();
+                // the markers are ASCII, a binary body is searched through its lossy text
+                let text_body = String::from_utf8_lossy(&http_body);
+                let mut templates = vec![
+                    http_args.url.as_str(),
+                    http_args.http_method.as_str(),
+                    &text_body,
+                ];
+                for (header, value) in http_headers.iter() {
+                    templates.push(header);
+                    templates.push(value);
+                }
+
+                if let Err(e) = fuzzbuster::utils::check_payload_markers(&markers, &templates) {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+            }
+
+            let mut jwt_tokens = Vec::new();
+            if let Some(claims) = &fuzz_args.jwt_claims {
+                if !serde_json::from_str::<serde_json::Value>(claims)
+                    .map(|v| v.is_object())
+                    .unwrap_or(false)
+                {
+                    error!("Invalid JWT claims, a JSON object is expected: {}", claims);
+                    return exit_code::INVALID_CONFIG;
+                }
+
+                let secrets = match &fuzz_args.jwt_secret_wordlist {
+                    Some(path) => match wordlist::read(path) {
+                        Ok(v) => v
+                            .lines()
+                            .filter(|s| !s.is_empty())
+                            .map(|s| s.to_owned())
+                            .collect(),
+                        Err(e) => {
+                            error!("Unable to read the JWT secret wordlist: {}\n{}", path, e);
+                            return exit_code::INVALID_CONFIG;
+                        }
+                    },
+                    None => Vec::new(),
+                };
+                jwt_tokens = jwt::build_tokens(claims, &secrets, fuzz_args.jwt_none);
+            }
 
             let fuzzbuster = FuzzBuster {
                 n_threads: common_args.n_threads,
                 ignore_certificate: http_args.ignore_certificate,
                 http_method: http_args.http_method.to_owned(),
-                http_body: http_args.http_body.to_owned(),
+                http_body,
                 user_agent: http_args.user_agent.to_owned(),
-                http_headers: http_args.http_headers,
+                http_headers,
                 wordlist_paths: wordlist_args.wordlist_paths,
+                payloads,
+                mutate: fuzz_args.mutate,
+                auto_calibrate: fuzz_args.auto_calibrate,
+                hex_wordlists: fuzz_args.hex_wordlists,
+                generator,
+                slow_rules,
+                max_rate_limit_wait,
+                favicon: http_args.favicon,
+                certificate: true,
+                sort,
+                group_by,
+                interactive,
                 url: http_args.url.to_owned(),
                 ignore_status_codes: http_args.ignore_status_codes,
                 include_status_codes: http_args.include_status_codes,
                 no_progress_bar: common_args.no_progress_bar,
+                progress_json: common_args.progress_json.to_owned(),
                 exit_on_connection_errors: common_args.exit_on_connection_errors,
                 output: common_args.output.to_owned(),
+                output_append: common_args.output_append,
+                known: common_args.known.to_owned(),
+                tls_min_version: http_args.tls_min_version,
+                tls_max_version: http_args.tls_max_version,
+                har: har_args.har.to_owned(),
+                save_requests: har_args.save_requests.to_owned(),
+                show_curl: har_args.show_curl,
+                burp: har_args.burp.to_owned(),
+                raw_path: fuzz_args.raw_path,
+                jwt_tokens,
+                digest: http_args.digest.clone(),
+                negotiate: http_args.negotiate.clone(),
+                aws_sigv4: http_args.aws_sigv4.clone(),
+                capture_headers: http_args.capture_headers.clone(),
+                check_cors: fuzz_args.check_cors,
+                audit_headers: http_args.audit_headers,
+                max_results: common_args.max_results,
+                severity_rules,
+                format: common_args.format.to_owned(),
                 include_body: body_args.include_strings,
                 ignore_body: body_args.ignore_strings,
                 csrf_url: fuzz_args.csrf_url,
                 csrf_regex: fuzz_args.csrf_regex,
                 csrf_headers: fuzz_args.csrf_headers,
+                follow_up,
+                session,
+                geoip: geoip.clone(),
             };
 
             debug!("FuzzBuster {:#?}", fuzzbuster);
 
-            fuzzbuster.run();
+            let findings = fuzzbuster.run();
+            project.record(&findings);
+            findings.len()
+        }
+        "graphql" => {
+            let http_args = match extract_http_args(submatches)
+                .and_then(|v| prepare_http_args(submatches, v))
+            {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+
+            if !url_is_valid(&http_args.url) {
+                return exit_code::INVALID_CONFIG;
+            }
+
+            let wordlist_args = match extract_wordlist_args(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+
+            let config = GraphqlConfig {
+                ignore_certificate: http_args.ignore_certificate,
+                user_agent: http_args.user_agent.to_owned(),
+                http_headers: http_args.http_headers.clone(),
+                tls_min_version: http_args.tls_min_version,
+                tls_max_version: http_args.tls_max_version,
+            };
+
+            if let Some(schema) = graphql::introspect(&http_args.url, &config) {
+                crate::say!("[+] Introspection enabled\n{}", graphql::report(&schema));
+                crate::say!("{}", banner::ending_time());
+                if !common_args.output.is_empty() {
+                    graphql::save_schema(&common_args.output, &schema);
+                }
+
+                project.record(&[schema]);
+
+                return exit_code::FINDINGS;
+            }
+
+            warn!("Introspection is disabled, brute forcing the field names");
+            let mut http_headers = http_args.http_headers;
+            http_headers.retain(|(name, _)| !name.eq_ignore_ascii_case("Content-Type"));
+            http_headers.push(("Content-Type".to_owned(), "application/json".to_owned()));
+
+            let certificate = certificate::fetch_certificate(
+                &http_args.url,
+                http_args.tls_min_version,
+                http_args.tls_max_version,
+            );
+            if let Some(info) = &certificate {
+                crate::say!("{}", banner::certificate(info));
+            }
+
+            if http_args.favicon {
+                if let Some((hash, product)) =
+                    favicon::fingerprint(&http_args.url, http_args.ignore_certificate)
+                {
+                    crate::say!("{}", banner::favicon(hash, product));
+                }
+            }
+
+            let mut findings = Vec::new();
+            for operation in ["query", "mutation"].iter() {
+                crate::say!("[?] Brute forcing {} fields", operation);
+                let fuzzbuster = FuzzBuster {
+                    n_threads: common_args.n_threads,
+                    ignore_certificate: http_args.ignore_certificate,
+                    http_method: "POST".to_owned(),
+                    http_body: graphql::brute_force_body(operation).into_bytes(),
+                    user_agent: http_args.user_agent.to_owned(),
+                    http_headers: http_headers.clone(),
+                    wordlist_paths: vec![wordlist_args.wordlist_paths[0].to_owned()],
+                    certificate: false,
+                    url: http_args.url.to_owned(),
+                    no_progress_bar: common_args.no_progress_bar,
+                    progress_json: common_args.progress_json.to_owned(),
+                    exit_on_connection_errors: common_args.exit_on_connection_errors,
+                    output: common_args.output.to_owned(),
+                    output_append: common_args.output_append || *operation == "mutation",
+                    known: common_args.known.to_owned(),
+                    tls_min_version: http_args.tls_min_version,
+                    tls_max_version: http_args.tls_max_version,
+                    max_results: common_args.max_results,
+                    format: common_args.format.to_owned(),
+                    ignore_body: graphql::BRUTE_FORCE_IGNORE
+                        .iter()
+                        .map(|s| s.to_string())
+                        .collect(),
+                    geoip: geoip.clone(),
+                    ..Default::default()
+                };
+
+                debug!("FuzzBuster {:#?}", fuzzbuster);
+
+                findings.extend(fuzzbuster.run());
+            }
+
+            project.record(&findings);
+            findings.len()
+        }
+        "soap" => {
+            let http_args = match extract_http_args(submatches)
+                .and_then(|v| prepare_http_args(submatches, v))
+            {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+
+            if !url_is_valid(&http_args.url) {
+                return exit_code::INVALID_CONFIG;
+            }
+
+            let wordlist_args = match extract_wordlist_args(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+            let body_args = extract_body_args(submatches);
+            let soap_args = extract_soap_args(submatches);
+
+            let config = SoapConfig {
+                ignore_certificate: http_args.ignore_certificate,
+                user_agent: http_args.user_agent.to_owned(),
+                http_headers: http_args.http_headers.clone(),
+                tls_min_version: http_args.tls_min_version,
+                tls_max_version: http_args.tls_max_version,
+            };
+
+            let wsdl_url = soap::wsdl_url(&http_args.url);
+            let service = match soap::fetch_wsdl(&wsdl_url, &config)
+                .as_deref()
+                .and_then(soap::parse_wsdl)
+            {
+                Some(v) => v,
+                None => {
+                    error!("Unable to retrieve a WSDL document from {}", wsdl_url);
+                    exit_code::abort();
+                    warn!("Check connectivity to the target");
+                    return exit_code::from_findings(0);
+                }
+            };
+
+            let endpoint = soap::endpoint_url(&http_args.url, &service.endpoint);
+            crate::say!("{}", soap::report(&service));
+
+            let mut http_headers = http_args.http_headers;
+            http_headers.retain(|(name, _)| {
+                !name.eq_ignore_ascii_case("Content-Type")
+                    && !name.eq_ignore_ascii_case("SOAPAction")
+            });
+            http_headers.push((
+                "Content-Type".to_owned(),
+                "text/xml; charset=utf-8".to_owned(),
+            ));
+
+            let certificate = certificate::fetch_certificate(
+                &endpoint,
+                http_args.tls_min_version,
+                http_args.tls_max_version,
+            );
+            if let Some(info) = &certificate {
+                crate::say!("{}", banner::certificate(info));
+            }
+
+            if http_args.favicon {
+                if let Some((hash, product)) =
+                    favicon::fingerprint(&endpoint, http_args.ignore_certificate)
+                {
+                    crate::say!("{}", banner::favicon(hash, product));
+                }
+            }
+
+            let mut findings = Vec::new();
+            for operation in service.operations.iter() {
+                if !soap_args.operations.is_empty()
+                    && !soap_args.operations.contains(&operation.name)
+                {
+                    continue;
+                }
+
+                let http_body =
+                    soap::request_body(&service.namespace, operation, &soap_args.fuzz_params);
+                let positions = http_body.matches("FUZZ").count();
+                if positions == 0 {
+                    continue;
+                }
+
+                crate::say!("[?] Fuzzing {}", operation.name);
+                let mut operation_headers = http_headers.clone();
+                operation_headers
+                    .push(("SOAPAction".to_owned(), format!("\"{}\"", operation.action)));
+                let fuzzbuster = FuzzBuster {
+                    n_threads: common_args.n_threads,
+                    ignore_certificate: http_args.ignore_certificate,
+                    http_method: "POST".to_owned(),
+                    http_body: http_body.into_bytes(),
+                    user_agent: http_args.user_agent.to_owned(),
+                    http_headers: operation_headers,
+                    wordlist_paths: (0..positions)
+                        .map(|i| {
+                            wordlist_args
+                                .wordlist_paths
+                                .get(i)
+                                .or_else(|| wordlist_args.wordlist_paths.last())
+                                .unwrap()
+                                .to_owned()
+                        })
+                        .collect(),
+                    certificate: false,
+                    url: endpoint.to_owned(),
+                    no_progress_bar: common_args.no_progress_bar,
+                    progress_json: common_args.progress_json.to_owned(),
+                    exit_on_connection_errors: common_args.exit_on_connection_errors,
+                    output: common_args.output.to_owned(),
+                    output_append: common_args.output_append || !findings.is_empty(),
+                    known: common_args.known.to_owned(),
+                    tls_min_version: http_args.tls_min_version,
+                    tls_max_version: http_args.tls_max_version,
+                    max_results: common_args.max_results,
+                    format: common_args.format.to_owned(),
+                    include_body: body_args.include_strings.clone(),
+                    ignore_body: body_args.ignore_strings.clone(),
+                    geoip: geoip.clone(),
+                    ..Default::default()
+                };
+
+                debug!("FuzzBuster {:#?}", fuzzbuster);
+
+                findings.extend(fuzzbuster.run());
+            }
+
+            project.record(&findings);
+            findings.len()
+        }
+        "auth" => {
+            let http_args = match extract_http_args(submatches)
+                .and_then(|v| prepare_http_args(submatches, v))
+            {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+
+            if !url_is_valid(&http_args.url) {
+                return exit_code::INVALID_CONFIG;
+            }
+
+            let http_body = match String::from_utf8(http_args.http_body.clone()) {
+                Ok(v) => v,
+                Err(_) => {
+                    error!("Binary bodies are not supported in auth mode, use a text body");
+                    return exit_code::INVALID_CONFIG;
+                }
+            };
+
+            let auth_args = match extract_auth_args(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+
+            let authbuster = AuthBuster {
+                n_threads: common_args.n_threads,
+                ignore_certificate: http_args.ignore_certificate,
+                http_method: http_args.http_method.to_owned(),
+                http_body,
+                user_agent: http_args.user_agent.to_owned(),
+                http_headers: http_args.http_headers,
+                url: http_args.url.to_owned(),
+                usernames: auth_args.usernames,
+                passwords: auth_args.passwords,
+                no_progress_bar: common_args.no_progress_bar,
+                progress_json: common_args.progress_json.to_owned(),
+                exit_on_connection_errors: common_args.exit_on_connection_errors,
+                output: common_args.output.to_owned(),
+                output_append: common_args.output_append,
+                tls_min_version: http_args.tls_min_version,
+                tls_max_version: http_args.tls_max_version,
+                lockout_threshold: auth_args.lockout_threshold,
+                account_delay: auth_args.account_delay,
+            };
+
+            debug!("AuthBuster {:#?}", authbuster);
+
+            let findings = authbuster.run();
+            project.record(&findings);
+            findings.len()
         }
         "tilde" => {
-            let http_args = extract_http_args(submatches);
+            let http_args = match extract_http_args(submatches)
+                .and_then(|v| prepare_http_args(submatches, v))
+            {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+
             if !url_is_valid(&http_args.url) {
-                return;
+                return exit_code::INVALID_CONFIG;
             }
 
+            let http_body = match String::from_utf8(http_args.http_body.clone()) {
+                Ok(v) => v,
+                Err(_) => {
+                    error!("Binary bodies are not supported in tilde mode, use a text body");
+                    return exit_code::INVALID_CONFIG;
+                }
+            };
+
             let tilde_args = extract_tilde_args(submatches);
             let tildebuster = TildeBuster {
                 n_threads: common_args.n_threads,
                 ignore_certificate: http_args.ignore_certificate,
                 http_method: http_args.http_method.to_owned(),
-                http_body: http_args.http_body.to_owned(),
+                http_body,
                 user_agent: http_args.user_agent.to_owned(),
                 http_headers: http_args.http_headers,
                 url: http_args.url.to_owned(),
                 no_progress_bar: common_args.no_progress_bar,
                 exit_on_connection_errors: common_args.exit_on_connection_errors,
                 output: common_args.output.to_owned(),
+                output_append: common_args.output_append,
+                known: common_args.known.to_owned(),
+                tls_min_version: http_args.tls_min_version,
+                tls_max_version: http_args.tls_max_version,
                 extension: tilde_args.extension,
             };
 
             debug!("TildeBuster {:#?}", tildebuster);
 
-            tildebuster.run();
+            let findings = tildebuster.run();
+            project.record(&findings);
+            findings.len()
         }
-        _ => (),
-    }
+        "fronting" => {
+            let http_args = match extract_http_args(submatches)
+                .and_then(|v| prepare_http_args(submatches, v))
+            {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+
+            if !url_is_valid(&http_args.url) {
+                return exit_code::INVALID_CONFIG;
+            }
+
+            let fronting_args = extract_fronting_args(submatches);
+            let config = FrontingConfig {
+                n_threads: common_args.n_threads,
+                ignore_certificate: http_args.ignore_certificate,
+                http_method: http_args.http_method.to_owned(),
+                http_body: http_args.http_body.to_owned(),
+                user_agent: http_args.user_agent.to_owned(),
+                http_headers: http_args.http_headers.clone(),
+                tls_min_version: http_args.tls_min_version,
+                tls_max_version: http_args.tls_max_version,
+            };
+
+            debug!("Fronting {:#?}", config);
+
+            let results = fronting::run(&http_args.url, &fronting_args.domains, &config);
+            crate::say!("{}", fronting::report(&results));
+            crate::say!("{}", banner::ending_time());
+            if !common_args.output.is_empty() {
+                fronting::save_fronting_results(&common_args.output, &results);
+            }
+
+            project.record(&results);
+            results.iter().filter(|r| r.distinct).count()
+        }
+        "chain" => {
+            let wordlist_args = match extract_wordlist_args(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+
+            let http_args = match extract_http_args(submatches)
+                .and_then(|v| prepare_http_args(submatches, v))
+            {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+
+            if !url_is_valid(&http_args.url) {
+                return exit_code::INVALID_CONFIG;
+            }
+
+            let chain_args = match extract_chain_args(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(v) => v,
+            };
+            let body_args = extract_body_args(submatches);
+            let domains = match extract_subdomains(submatches) {
+                Err(e) => {
+                    error!("{}", e);
+                    return exit_code::INVALID_CONFIG;
+                }
+                Ok(Some(subdomains)) => subdomains
+                    .iter()
+                    .map(|name| format!("{}:80", name))
+                    .collect(),
+                Ok(None) => build_domains(&wordlist_args.wordlist_paths[0], &chain_args.domain),
+            };
+
+            let config = chain::ChainConfig {
+                domains,
+                dir_wordlist: chain_args.dir_wordlist,
+                url: http_args.url.to_owned(),
+                dir: DirConfig {
+                    n_threads: common_args.n_threads,
+                    ignore_certificate: http_args.ignore_certificate,
+                    http_method: http_args.http_method.to_owned(),
+                    http_body: http_args.http_body.to_owned(),
+                    user_agent: http_args.user_agent.to_owned(),
+                    http_headers: http_args.http_headers.clone(),
+                    save_har: false,
+                    // the soft 404 probes and the body filters look at every body
+                    read_body: true,
+                    tls_min_version: http_args.tls_min_version,
+                    tls_max_version: http_args.tls_max_version,
+                    digest: http_args.digest.clone(),
+                    negotiate: http_args.negotiate.clone(),
+                    aws_sigv4: http_args.aws_sigv4.clone(),
+                    capture_headers: http_args.capture_headers.clone(),
+                    header_audit: None,
+                    session: None,
+                    range_threshold: 0,
+                    range_size: 0,
+                    request_tag: None,
+                },
+                vhost: VhostConfig {
+                    n_threads: common_args.n_threads,
+                    ignore_certificate: http_args.ignore_certificate,
+                    http_method: http_args.http_method.to_owned(),
+                    http_body: http_args.http_body.to_owned(),
+                    user_agent: http_args.user_agent.to_owned(),
+                    ignore_strings: body_args.ignore_strings.clone(),
+                    original_url: http_args.url.to_owned(),
+                    save_har: false,
+                    tls_min_version: http_args.tls_min_version,
+                    tls_max_version: http_args.tls_max_version,
+                    digest: http_args.digest.clone(),
+                    capture_headers: http_args.capture_headers.clone(),
+                    header_audit: None,
+                    negotiate: http_args.negotiate.clone(),
+                    absolute_uri: false,
+                },
+                processor: ResultProcessorConfig {
+                    include: http_args.include_status_codes,
+                    ignore: http_args.ignore_status_codes,
+                    include_body: body_args.include_strings,
+                    ignore_body: body_args.ignore_strings,
+                    rules: Vec::new(),
+                },
+            };
+
+            let mut journal = output::Journal::open(&common_args.output);
+            let report = chain::run(config, &mut journal);
+            crate::say!("\n{}", report.summary());
+            crate::say!("{}", banner::ending_time());
+
+            if !common_args.output.is_empty() {
+                chain::save_chain_report(&common_args.output, &report);
+            }
+            journal.finish();
+
+            let findings = report.findings();
+            project.record(&findings);
+            findings.len()
+        }
+        _ => 0,
+    };
+
+    exit_code::from_findings(findings)
 }
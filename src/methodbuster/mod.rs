@@ -0,0 +1,124 @@
+use futures::Stream;
+use hyper::{
+    rt::{self, Future},
+    Body, Client, Request, StatusCode, Uri,
+};
+use hyper_tls::HttpsConnector;
+use std::sync::{mpsc::Sender, Arc};
+
+pub mod result_processor;
+pub mod utils;
+
+use crate::{
+    proxy::ProxyConnector,
+    ratelimit::Pacer,
+    template::{expand, expand_bytes, word_from_uri},
+};
+use result_processor::SingleMethodScanResult;
+
+pub const METHODS: &[&str] = &[
+    "OPTIONS", "GET", "POST", "PUT", "DELETE", "PATCH", "TRACE", "PROPFIND",
+];
+
+#[derive(Debug, Clone)]
+pub struct MethodConfig {
+    pub n_threads: usize,
+    pub ignore_certificate: bool,
+    pub http_body: Vec<u8>,
+    pub user_agent: String,
+    pub http_headers: Vec<(String, String)>,
+    pub tls_min_version: Option<native_tls::Protocol>,
+    pub tls_max_version: Option<native_tls::Protocol>,
+}
+
+fn make_request_future(
+    tx: Sender<SingleMethodScanResult>,
+    client: &Client<HttpsConnector<ProxyConnector>>,
+    url: Uri,
+    method: &str,
+    config: &MethodConfig,
+    pacer: Arc<Pacer>,
+) -> impl Future<Item = (), Error = ()> {
+    let tx_err = tx.clone();
+    let mut target = SingleMethodScanResult {
+        url: url.to_string(),
+        method: method.to_owned(),
+        status: StatusCode::default().to_string(),
+        error: None,
+        extra: None,
+    };
+    let mut target_err = target.clone();
+    let mut request_builder = Request::builder();
+    let word = word_from_uri(&url);
+
+    for header_tuple in &config.http_headers {
+        request_builder.header(
+            expand(&header_tuple.0, &word).as_str(),
+            expand(&header_tuple.1, &word).as_str(),
+        );
+    }
+
+    let request = request_builder
+        .header("User-Agent", &config.user_agent[..])
+        .method(method)
+        .uri(&url)
+        .header("Host", url.host().unwrap())
+        .body(Body::from(expand_bytes(&config.http_body, &word)))
+        .expect("Request builder");
+
+    let client = client.clone();
+
+    pacer
+        .wait()
+        .then(move |_| client.request(request))
+        .and_then(move |res| {
+            pacer.observe(res.headers());
+            let status = res.status();
+            target.status = status.to_string();
+            target.extra = res
+                .headers()
+                .get("Allow")
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_owned());
+
+            res.into_body().concat2().map(move |_| target)
+        })
+        .and_then(move |target| {
+            tx.send(target).unwrap();
+            Ok(())
+        })
+        .or_else(move |e| {
+            target_err.error = Some(e.to_string());
+            tx_err.send(target_err).unwrap_or(());
+            Ok(())
+        })
+}
+
+pub fn run(tx: Sender<SingleMethodScanResult>, urls: Vec<hyper::Uri>, config: MethodConfig) {
+    let mut tls_connector_builder = native_tls::TlsConnector::builder();
+    tls_connector_builder.danger_accept_invalid_certs(config.ignore_certificate);
+    tls_connector_builder.min_protocol_version(config.tls_min_version);
+    tls_connector_builder.max_protocol_version(config.tls_max_version);
+    let tls_connector = tls_connector_builder
+        .build()
+        .expect("TLS initialization failed");
+    let http_connector = ProxyConnector::new(config.n_threads);
+    let https_connector = HttpsConnector::from((http_connector, tls_connector));
+    let client = Client::builder().build(https_connector);
+    let n_threads = config.n_threads;
+
+    let pacer = Arc::new(Pacer::default());
+
+    let requests = urls
+        .into_iter()
+        .flat_map(|url| METHODS.iter().map(move |method| (url.clone(), *method)));
+    let stream = futures::stream::iter_ok(requests)
+        .map(move |(url, method)| {
+            make_request_future(tx.clone(), &client, url, method, &config, pacer.clone())
+        })
+        .buffer_unordered(n_threads)
+        .for_each(Ok)
+        .map_err(|err| eprintln!("Err {:?}", err));
+
+    rt::run(stream);
+}
@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+
+use crate::sink::ResultSink;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SingleMethodScanResult {
+    pub url: String,
+    pub method: String,
+    pub status: String,
+    pub error: Option<String>,
+    pub extra: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct MethodScanResult {
+    pub results: Vec<SingleMethodScanResult>,
+}
+
+/// A success or a redirect, the method is served
+fn is_allowed(status: &str) -> bool {
+    status.starts_with('2') || status.starts_with('3')
+}
+
+/// The method may be served, but only with credentials
+fn is_auth(status: &str) -> bool {
+    ["401", "403", "407"]
+        .iter()
+        .any(|code| status.starts_with(code))
+}
+
+/// Neither allowed nor refused, 501 is the server refusing the method
+fn is_error(res: &SingleMethodScanResult) -> bool {
+    res.error.is_some() || (res.status.starts_with('5') && !res.status.starts_with("501"))
+}
+
+impl MethodScanResult {
+    pub fn new() -> Self {
+        MethodScanResult {
+            results: Vec::<SingleMethodScanResult>::new(),
+        }
+    }
+
+    fn methods(&self, url: &str, filter: impl Fn(&SingleMethodScanResult) -> bool) -> Vec<String> {
+        self.results
+            .iter()
+            .filter(|r| r.url == url && filter(r))
+            .map(|r| r.method.clone())
+            .collect()
+    }
+
+    /// The methods answered with a success or a redirect
+    pub fn confirmed(&self, url: &str) -> Vec<String> {
+        self.methods(url, |r| r.error.is_none() && is_allowed(&r.status))
+    }
+
+    /// The confirmed methods and the ones declared by the Allow header of OPTIONS
+    pub fn allowed(&self, url: &str) -> Vec<String> {
+        let mut allowed = self.confirmed(url);
+        for method in self.advertised(url).unwrap_or_default() {
+            if !allowed.contains(&method) {
+                allowed.push(method);
+            }
+        }
+
+        allowed
+    }
+
+    pub fn auth(&self, url: &str) -> Vec<String> {
+        self.methods(url, |r| r.error.is_none() && is_auth(&r.status))
+    }
+
+    pub fn errors(&self, url: &str) -> Vec<String> {
+        self.methods(url, is_error)
+    }
+
+    pub fn advertised(&self, url: &str) -> Option<Vec<String>> {
+        self.results
+            .iter()
+            .find(|r| r.url == url && r.method == "OPTIONS")
+            .and_then(|r| r.extra.as_ref())
+            .map(|allow| {
+                allow
+                    .split(',')
+                    .map(|m| m.trim().to_uppercase())
+                    .filter(|m| !m.is_empty())
+                    .collect()
+            })
+    }
+
+    pub fn report(&self) -> String {
+        let mut urls: Vec<&str> = Vec::new();
+        for res in self.results.iter() {
+            if !urls.contains(&res.url.as_str()) {
+                urls.push(&res.url);
+            }
+        }
+
+        urls.sort();
+        let mut lines = Vec::new();
+        for url in urls {
+            let mut allowed = self.allowed(url);
+            let auth = self.auth(url);
+            let errors = self.errors(url);
+            if allowed.is_empty() && auth.is_empty() && errors.is_empty() {
+                continue;
+            }
+
+            allowed.sort();
+            lines.push(format!("[+] {}", url));
+            if !allowed.is_empty() {
+                lines.push(format!("    Allowed\t: {}", allowed.join(", ")));
+            }
+            match self.advertised(url) {
+                Some(advertised) => {
+                    lines.push(format!("    OPTIONS\t: {}", advertised.join(", ")));
+                    let undeclared: Vec<String> = self
+                        .confirmed(url)
+                        .into_iter()
+                        .filter(|m| m != "OPTIONS" && !advertised.contains(m))
+                        .collect();
+                    if !undeclared.is_empty() {
+                        lines.push(format!("    Undeclared\t: {}", undeclared.join(", ")));
+                    }
+                }
+                None => lines.push("    OPTIONS\t: no Allow header".to_owned()),
+            }
+            if !auth.is_empty() {
+                lines.push(format!("    Auth\t\t: {}", auth.join(", ")));
+            }
+            if !errors.is_empty() {
+                lines.push(format!("    Errors\t: {}", errors.join(", ")));
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+// every response is kept for the report, the allowed and the auth protected methods count as results
+impl ResultSink<SingleMethodScanResult> for MethodScanResult {
+    fn store(&mut self, res: SingleMethodScanResult) {
+        self.results.push(res);
+    }
+
+    fn maybe_add_result(&mut self, res: SingleMethodScanResult) -> bool {
+        trace!("{:?}", res);
+        let found = res.error.is_none()
+            && (is_allowed(&res.status)
+                || is_auth(&res.status)
+                || self
+                    .advertised(&res.url)
+                    .is_some_and(|advertised| advertised.contains(&res.method)));
+        self.store(res);
+        found
+    }
+}
@@ -0,0 +1,52 @@
+use std::{fs::File, io::Write, path::Path};
+
+use super::result_processor::SingleMethodScanResult;
+use crate::{output::merge_results, template::expand, wordlist};
+
+pub fn build_urls(wordlist_path: &str, url: &str) -> Vec<hyper::Uri> {
+    debug!("building urls");
+    wordlist::read_filtered(wordlist_path)
+        .expect("Something went wrong reading the wordlist file")
+        .lines()
+        .filter(|word| !word.starts_with('#') && !word.starts_with(' '))
+        .map(|word| {
+            let url = if url.ends_with('/') {
+                format!("{}{}", url, word)
+            } else {
+                format!("{}/{}", url, word)
+            };
+            expand(&url, word)
+        })
+        .filter_map(|url| match url.parse::<hyper::Uri>() {
+            Ok(v) => Some(v),
+            Err(e) => {
+                trace!("URI: {}", e);
+                None
+            }
+        })
+        .collect()
+}
+
+pub fn save_method_results(path: &str, results: &Vec<SingleMethodScanResult>, append: bool) {
+    let json_string = if append {
+        serde_json::to_string(&merge_results(path, results, |r| {
+            (r.method.clone(), r.url.clone())
+        }))
+        .unwrap()
+    } else {
+        serde_json::to_string(&results).unwrap()
+    };
+
+    let mut file = match File::create(Path::new(path)) {
+        Ok(f) => f,
+        Err(e) => {
+            error!("Error while creating file: {}\n{}", path, e);
+            return;
+        }
+    };
+
+    match file.write_all(json_string.as_bytes()) {
+        Ok(_) => debug!("Results saved to: {}", path),
+        Err(e) => error!("Error while writing results to file: {}\n{}", path, e),
+    };
+}
@@ -0,0 +1,120 @@
+use regex::Regex;
+
+const BIT_FLIPS: usize = 32;
+
+const BOUNDARY_NUMBERS: &[&str] = &[
+    "0",
+    "-1",
+    "1",
+    "127",
+    "128",
+    "255",
+    "256",
+    "32767",
+    "32768",
+    "65535",
+    "65536",
+    "2147483647",
+    "2147483648",
+    "-2147483648",
+    "-2147483649",
+    "4294967295",
+    "4294967296",
+    "9223372036854775807",
+    "9223372036854775808",
+    "-9223372036854775809",
+    "18446744073709551616",
+    "0.0",
+    "-0.0",
+    "1e308",
+    "1e-308",
+    "NaN",
+    "Infinity",
+];
+
+const INSERTIONS: &[&str] = &[
+    "%s%s%s%s%s",
+    "%x%x%x%x",
+    "%n%n%n%n",
+    "%99999999s",
+    "'",
+    "\"",
+    "\\",
+    "`",
+    ";",
+    "|",
+    "&",
+    "$(id)",
+    "${7*7}",
+    "{{7*7}}",
+    "<>",
+    "../../../../",
+    "%00",
+    "\r\n",
+];
+
+const OVERLONG_VALUE: usize = 1024;
+
+fn push(variants: &mut Vec<String>, seed: &str, variant: String) {
+    if variant != seed && !variants.contains(&variant) {
+        variants.push(variant);
+    }
+}
+
+pub fn mutations(seed: &str) -> Vec<String> {
+    let mut variants: Vec<String> = Vec::new();
+
+    let bytes = seed.as_bytes();
+    let step = (bytes.len() / BIT_FLIPS).max(1);
+    for i in (0..bytes.len()).step_by(step) {
+        if !bytes[i].is_ascii() {
+            continue;
+        }
+
+        let mut flipped = bytes.to_vec();
+        flipped[i] ^= 1 << (i % 7);
+        if let Ok(v) = String::from_utf8(flipped) {
+            push(&mut variants, seed, v);
+        }
+    }
+
+    let number_re = Regex::new(r"-?\d+(?:\.\d+)?").unwrap();
+    for number in number_re.find_iter(seed) {
+        for boundary in BOUNDARY_NUMBERS.iter() {
+            let variant = format!(
+                "{}{}{}",
+                &seed[..number.start()],
+                boundary,
+                &seed[number.end()..]
+            );
+            push(&mut variants, seed, variant);
+        }
+    }
+
+    let value_re = Regex::new(r#""([^"\\]*)"|=([^&]*)"#).unwrap();
+    let mut values: Vec<(usize, usize)> = value_re
+        .captures_iter(seed)
+        .filter_map(|caps| caps.get(1).or_else(|| caps.get(2)))
+        .map(|value| (value.start(), value.end()))
+        .collect();
+    if values.is_empty() {
+        values.push((0, seed.len()));
+    }
+
+    for (start, end) in values {
+        for insertion in INSERTIONS.iter() {
+            let variant = format!("{}{}{}", &seed[..end], insertion, &seed[end..]);
+            push(&mut variants, seed, variant);
+        }
+
+        let variant = format!(
+            "{}{}{}",
+            &seed[..start],
+            "A".repeat(OVERLONG_VALUE),
+            &seed[end..]
+        );
+        push(&mut variants, seed, variant);
+    }
+
+    variants
+}
@@ -0,0 +1,307 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use libloading::{Library, Symbol};
+use std::{
+    os::raw::{c_uint, c_void},
+    ptr,
+    sync::Arc,
+};
+
+const LIBRARIES: &[&str] = &[
+    "libgssapi_krb5.so.2",
+    "libgssapi_krb5.so",
+    "libgssapi_krb5.dylib",
+    "/System/Library/Frameworks/GSS.framework/GSS",
+];
+
+// 1.2.840.113554.1.2.1.4
+const NT_HOSTBASED_SERVICE: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x12, 0x01, 0x02, 0x01, 0x04];
+// 1.3.6.1.5.5.2
+const MECH_SPNEGO: &[u8] = &[0x2b, 0x06, 0x01, 0x05, 0x05, 0x02];
+
+const GSS_C_MUTUAL_FLAG: c_uint = 2;
+const GSS_S_CONTINUE_NEEDED: c_uint = 1;
+
+#[repr(C)]
+struct GssBuffer {
+    length: usize,
+    value: *mut c_void,
+}
+
+#[repr(C)]
+struct GssOid {
+    length: c_uint,
+    elements: *mut c_void,
+}
+
+type ImportName =
+    unsafe extern "C" fn(*mut c_uint, *const GssBuffer, *const GssOid, *mut *mut c_void) -> c_uint;
+type InitSecContext = unsafe extern "C" fn(
+    *mut c_uint,
+    *mut c_void,
+    *mut *mut c_void,
+    *mut c_void,
+    *const GssOid,
+    c_uint,
+    c_uint,
+    *const c_void,
+    *const GssBuffer,
+    *mut *mut GssOid,
+    *mut GssBuffer,
+    *mut c_uint,
+    *mut c_uint,
+) -> c_uint;
+type ReleaseBuffer = unsafe extern "C" fn(*mut c_uint, *mut GssBuffer) -> c_uint;
+type ReleaseName = unsafe extern "C" fn(*mut c_uint, *mut *mut c_void) -> c_uint;
+type DeleteSecContext =
+    unsafe extern "C" fn(*mut c_uint, *mut *mut c_void, *mut GssBuffer) -> c_uint;
+
+fn oid(bytes: &[u8]) -> GssOid {
+    GssOid {
+        length: bytes.len() as c_uint,
+        elements: bytes.as_ptr() as *mut c_void,
+    }
+}
+
+fn gss_error(what: &str, service: &str, major: c_uint, minor: c_uint) -> String {
+    format!(
+        "{} for {} (major: {:#x}, minor: {})",
+        what, service, major, minor
+    )
+}
+
+/// The token of the server's `WWW-Authenticate: Negotiate <token>` reply, if it sent one
+pub fn server_token(headers: &hyper::HeaderMap) -> Option<Vec<u8>> {
+    headers
+        .get_all("WWW-Authenticate")
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .find_map(|v| match v.trim().split_once(' ') {
+            Some((scheme, token)) if scheme.eq_ignore_ascii_case("Negotiate") => {
+                STANDARD.decode(token.trim()).ok()
+            }
+            _ => None,
+        })
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Functions {
+    import_name: ImportName,
+    init_sec_context: InitSecContext,
+    release_buffer: ReleaseBuffer,
+    release_name: ReleaseName,
+    delete_sec_context: DeleteSecContext,
+}
+
+/// The GSSAPI library, every request gets its own security context since the servers keeping a
+/// replay cache reject a token seen before
+#[derive(Debug)]
+pub struct Negotiate {
+    functions: Functions,
+    // keeps the functions above loaded
+    _library: Library,
+}
+
+impl Negotiate {
+    pub fn load(keytab: Option<&str>) -> Result<Self, String> {
+        let library = LIBRARIES
+            .iter()
+            .find_map(|name| Library::new(name).ok())
+            .ok_or_else(|| "unable to load the GSSAPI library (libgssapi_krb5)".to_owned())?;
+
+        let symbol_error = |e: std::io::Error| format!("missing GSSAPI symbol: {}", e);
+        let functions = unsafe {
+            let import_name: Symbol<ImportName> =
+                library.get(b"gss_import_name").map_err(symbol_error)?;
+            let init_sec_context: Symbol<InitSecContext> =
+                library.get(b"gss_init_sec_context").map_err(symbol_error)?;
+            let release_buffer: Symbol<ReleaseBuffer> =
+                library.get(b"gss_release_buffer").map_err(symbol_error)?;
+            let release_name: Symbol<ReleaseName> =
+                library.get(b"gss_release_name").map_err(symbol_error)?;
+            let delete_sec_context: Symbol<DeleteSecContext> = library
+                .get(b"gss_delete_sec_context")
+                .map_err(symbol_error)?;
+            Functions {
+                import_name: *import_name,
+                init_sec_context: *init_sec_context,
+                release_buffer: *release_buffer,
+                release_name: *release_name,
+                delete_sec_context: *delete_sec_context,
+            }
+        };
+
+        // GSSAPI has no portable call taking a client keytab, gss_acquire_cred_from is an MIT
+        // extension, while both MIT and Heimdal read this variable when they get the initial
+        // credentials. It is set once here, before the scan threads start.
+        if let Some(keytab) = keytab {
+            std::env::set_var("KRB5_CLIENT_KTNAME", keytab);
+        }
+
+        Ok(Negotiate {
+            functions,
+            _library: library,
+        })
+    }
+
+    /// A new security context for the HTTP service of the host, with the token of its first leg
+    pub fn start(self: &Arc<Self>, host: &str) -> Result<SecurityContext, String> {
+        let service = format!("HTTP@{}", host);
+        let service_buffer = GssBuffer {
+            length: service.len(),
+            value: service.as_ptr() as *mut c_void,
+        };
+        let name_type = oid(NT_HOSTBASED_SERVICE);
+        let mut minor: c_uint = 0;
+        let mut name: *mut c_void = ptr::null_mut();
+        let major = unsafe {
+            (self.functions.import_name)(&mut minor, &service_buffer, &name_type, &mut name)
+        };
+        if major != 0 {
+            return Err(gss_error(
+                "unable to import the service name",
+                &service,
+                major,
+                minor,
+            ));
+        }
+
+        let mut context = SecurityContext {
+            negotiate: self.clone(),
+            service,
+            context: ptr::null_mut(),
+            name,
+            token: String::new(),
+        };
+        match context.step(None)? {
+            (GSS_S_CONTINUE_NEEDED, token) | (0, token) if !token.is_empty() => {
+                context.token = STANDARD.encode(token);
+                Ok(context)
+            }
+            _ => Err(format!("no token to send to {}", context.service)),
+        }
+    }
+
+    pub fn authorization(self: &Arc<Self>, host: &str) -> Option<SecurityContext> {
+        match self.start(host) {
+            Ok(context) => Some(context),
+            Err(e) => {
+                debug!("{}", e);
+                None
+            }
+        }
+    }
+}
+
+/// The security context of a single request, dropping it deletes it
+pub struct SecurityContext {
+    negotiate: Arc<Negotiate>,
+    service: String,
+    context: *mut c_void,
+    name: *mut c_void,
+    token: String,
+}
+
+// the context is only used by the request owning it, never from two threads at once
+unsafe impl Send for SecurityContext {}
+
+impl SecurityContext {
+    pub fn authorization(&self) -> String {
+        format!("Negotiate {}", self.token)
+    }
+
+    /// Runs one leg of the handshake, the status is either complete (0) or continue needed
+    fn step(&mut self, input: Option<&[u8]>) -> Result<(c_uint, Vec<u8>), String> {
+        let functions = self.negotiate.functions;
+        let input = input.map(|input| GssBuffer {
+            length: input.len(),
+            value: input.as_ptr() as *mut c_void,
+        });
+        let mech = oid(MECH_SPNEGO);
+        let mut minor: c_uint = 0;
+        let mut output = GssBuffer {
+            length: 0,
+            value: ptr::null_mut(),
+        };
+        unsafe {
+            let major = (functions.init_sec_context)(
+                &mut minor,
+                ptr::null_mut(),
+                &mut self.context,
+                self.name,
+                &mech,
+                GSS_C_MUTUAL_FLAG,
+                0,
+                ptr::null(),
+                input
+                    .as_ref()
+                    .map_or(ptr::null(), |input| input as *const GssBuffer),
+                ptr::null_mut(),
+                &mut output,
+                ptr::null_mut(),
+                ptr::null_mut(),
+            );
+            let token = if output.value.is_null() {
+                Vec::new()
+            } else {
+                std::slice::from_raw_parts(output.value as *const u8, output.length).to_vec()
+            };
+            (functions.release_buffer)(&mut minor, &mut output);
+
+            if major != 0 && major != GSS_S_CONTINUE_NEEDED {
+                return Err(match input {
+                    None => format!(
+                        "unable to get a Kerberos ticket for {}, check the credentials cache or keytab (major: {:#x}, minor: {})",
+                        self.service, major, minor
+                    ),
+                    Some(_) => gss_error(
+                        "the mutual authentication failed",
+                        &self.service,
+                        major,
+                        minor,
+                    ),
+                });
+            }
+
+            Ok((major, token))
+        }
+    }
+
+    /// Checks the token of the server's reply, which authenticates the server since the context
+    /// asked for mutual authentication. A reply without a token, a 401 for one, leaves nothing to
+    /// check.
+    pub fn complete(mut self, headers: &hyper::HeaderMap) -> Result<(), String> {
+        let token = match server_token(headers) {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+
+        match self.step(Some(&token))? {
+            (0, _) => Ok(()),
+            // a single round trip is all HTTP Negotiate has
+            _ => Err(format!(
+                "{} asked for another authentication round",
+                self.service
+            )),
+        }
+    }
+}
+
+impl Drop for SecurityContext {
+    fn drop(&mut self) {
+        let functions = self.negotiate.functions;
+        let mut minor: c_uint = 0;
+        unsafe {
+            if !self.context.is_null() {
+                (functions.delete_sec_context)(&mut minor, &mut self.context, ptr::null_mut());
+            }
+            (functions.release_name)(&mut minor, &mut self.name);
+        }
+    }
+}
+
+/// Completes the context of a request with the headers of its response
+pub fn verify(context: Option<SecurityContext>, headers: &hyper::HeaderMap) {
+    if let Some(Err(e)) = context.map(|context| context.complete(headers)) {
+        warn!("{}", e);
+    }
+}
@@ -0,0 +1,190 @@
+use std::{fs, process::Command};
+
+use crate::{environment::env_name, exit_code, xml::tags};
+
+const HTTP_PORTS: &[u16] = &[80, 591, 8000, 8008, 8080, 8888];
+const HTTPS_PORTS: &[u16] = &[443, 4443, 8443, 9443];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NmapTarget {
+    pub host: String,
+    pub port: u16,
+    pub scheme: &'static str,
+}
+
+impl NmapTarget {
+    pub fn url(&self) -> String {
+        let host = if self.host.contains(':') {
+            format!("[{}]", self.host)
+        } else {
+            self.host.to_owned()
+        };
+        match (self.scheme, self.port) {
+            ("http", 80) | ("https", 443) => format!("{}://{}/", self.scheme, host),
+            _ => format!("{}://{}:{}/", self.scheme, host, self.port),
+        }
+    }
+}
+
+/// The service detection wins, the port only decides when nmap reported no service
+pub fn scheme(service: Option<&str>, tunnel: Option<&str>, port: u16) -> Option<&'static str> {
+    match service {
+        Some(name) if name.contains("http") => {
+            if tunnel == Some("ssl") || name.starts_with("https") || name.starts_with("ssl/") {
+                Some("https")
+            } else {
+                Some("http")
+            }
+        }
+        Some(_) => None,
+        None if HTTPS_PORTS.contains(&port) => Some("https"),
+        None if HTTP_PORTS.contains(&port) => Some("http"),
+        None => None,
+    }
+}
+
+#[derive(Default)]
+struct Port {
+    id: u16,
+    open: bool,
+    service: Option<String>,
+    tunnel: Option<String>,
+}
+
+pub fn parse(xml: &str) -> Vec<NmapTarget> {
+    let mut targets = Vec::new();
+    let mut address = None;
+    let mut hostname = None;
+    let mut ports: Vec<(u16, &'static str)> = Vec::new();
+    let mut port: Option<Port> = None;
+
+    for tag in tags(xml) {
+        match (tag.name.as_str(), tag.closing) {
+            ("host", false) => {
+                address = None;
+                hostname = None;
+                ports.clear();
+            }
+            ("address", false) => {
+                if let Some("ipv4") | Some("ipv6") = tag.attr("addrtype") {
+                    address = tag.attr("addr").map(|v| v.to_owned());
+                }
+            }
+            // PTR names may not be served by the target, the ones given on the command line are
+            ("hostname", false) if tag.attr("type") == Some("user") => {
+                hostname = tag.attr("name").map(|v| v.to_owned());
+            }
+            ("port", false) => {
+                port = match (tag.attr("protocol"), tag.attr("portid")) {
+                    (Some("tcp"), Some(id)) => id.parse().ok().map(|id| Port {
+                        id,
+                        ..Port::default()
+                    }),
+                    _ => None,
+                };
+            }
+            ("state", false) => {
+                if let Some(port) = port.as_mut() {
+                    port.open = tag.attr("state") == Some("open");
+                }
+            }
+            ("service", false) => {
+                if let Some(port) = port.as_mut() {
+                    port.service = tag.attr("name").map(|v| v.to_owned());
+                    port.tunnel = tag.attr("tunnel").map(|v| v.to_owned());
+                }
+            }
+            ("port", true) => {
+                if let Some(port) = port.take().filter(|p| p.open) {
+                    if let Some(scheme) =
+                        scheme(port.service.as_deref(), port.tunnel.as_deref(), port.id)
+                    {
+                        ports.push((port.id, scheme));
+                    }
+                }
+            }
+            ("host", true) => {
+                let host = match hostname.take().or_else(|| address.take()) {
+                    Some(v) => v,
+                    None => continue,
+                };
+                for (port, scheme) in ports.drain(..) {
+                    targets.push(NmapTarget {
+                        host: host.to_owned(),
+                        port,
+                        scheme,
+                    });
+                }
+            }
+            _ => (),
+        }
+    }
+
+    targets
+}
+
+/// The command line of one target: the same arguments without the nmap input, and its URL
+pub fn target_arguments(args: &[String], url: &str, append: bool) -> Vec<String> {
+    let mut arguments = Vec::new();
+    let mut skip = false;
+    for arg in args.iter() {
+        if skip {
+            skip = false;
+        } else if arg == "--input-nmap" {
+            skip = true;
+        } else if !arg.starts_with("--input-nmap=") {
+            arguments.push(arg.to_owned());
+        }
+    }
+
+    arguments.push("-u".to_owned());
+    arguments.push(url.to_owned());
+    if append && !arguments.iter().any(|a| a == "--output-append") {
+        arguments.push("--output-append".to_owned());
+    }
+
+    arguments
+}
+
+/// Scans the targets one after the other, the worst exit code wins
+pub fn run(path: &str, has_output: bool) -> i32 {
+    let xml = match fs::read_to_string(path) {
+        Ok(v) => v,
+        Err(e) => {
+            error!("Unable to read the nmap report {}: {}", path, e);
+            return exit_code::INVALID_CONFIG;
+        }
+    };
+    let targets = parse(&xml);
+    if targets.is_empty() {
+        warn!("No open HTTP(S) port in {}", path);
+        return exit_code::NO_FINDINGS;
+    }
+
+    let program = match std::env::current_exe() {
+        Ok(v) => v,
+        Err(e) => {
+            error!("Unable to find the rustbuster executable: {}", e);
+            return exit_code::INVALID_CONFIG;
+        }
+    };
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut code = exit_code::NO_FINDINGS;
+    for (i, target) in targets.iter().enumerate() {
+        let url = target.url();
+        crate::say!("[+] Target {}/{}\t: {}", i + 1, targets.len(), url);
+        let status = Command::new(&program)
+            .args(target_arguments(&args, &url, has_output && i > 0))
+            .env_remove(env_name("input-nmap"))
+            .status();
+        match status {
+            Ok(status) => code = code.max(status.code().unwrap_or(exit_code::ABORTED)),
+            Err(e) => {
+                error!("Unable to scan {}: {}", url, e);
+                code = code.max(exit_code::ABORTED);
+            }
+        }
+    }
+
+    code
+}
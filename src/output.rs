@@ -0,0 +1,265 @@
+use regex::{Captures, Regex};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fs::{self, File, OpenOptions},
+    hash::Hash,
+    io::{BufWriter, Write},
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::{severity::Severity, sink::ResultSink};
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+static COLOR: AtomicBool = AtomicBool::new(false);
+
+const STATUS_WIDTH: usize = 23;
+const JOURNAL_SYNC_EVERY: usize = 100;
+const JOURNAL_SYNC_INTERVAL: Duration = Duration::from_secs(5);
+
+#[macro_export]
+macro_rules! say {
+    ($($arg:tt)*) => {
+        if !$crate::output::is_quiet() {
+            println!($($arg)*);
+        }
+    };
+}
+
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::SeqCst);
+}
+
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::SeqCst)
+}
+
+pub fn set_color(color: bool) {
+    COLOR.store(color, Ordering::SeqCst);
+}
+
+pub fn use_color() -> bool {
+    COLOR.load(Ordering::SeqCst)
+}
+
+pub fn status_color(status: &str) -> &'static str {
+    match status.chars().next() {
+        Some('2') => "\x1b[32m",
+        Some('3') => "\x1b[36m",
+        Some('4') => "\x1b[33m",
+        Some('5') => "\x1b[31m",
+        _ => "",
+    }
+}
+
+pub fn paint(color: &str, text: &str) -> String {
+    if color.is_empty() || !use_color() {
+        return text.to_owned();
+    }
+
+    format!("{}{}\x1b[0m", color, text)
+}
+
+pub fn format_finding(
+    method: &str,
+    status: &str,
+    target: &str,
+    severity: Option<Severity>,
+) -> String {
+    let status = format!("{:<width$} ", status, width = STATUS_WIDTH);
+    match severity {
+        Some(Severity::High) => paint(
+            "\x1b[1;37;41m",
+            &format!("{}\t{}{}", method, status, target),
+        ),
+        _ => format!(
+            "{}\t{}{}",
+            method,
+            paint(status_color(&status), &status),
+            target
+        ),
+    }
+}
+
+pub fn merge_results<T, K, F>(path: &str, results: &[T], key: F) -> Vec<T>
+where
+    T: DeserializeOwned + Clone,
+    K: Eq + Hash,
+    F: Fn(&T) -> K,
+{
+    let mut merged: Vec<T> = match fs::read_to_string(path) {
+        Ok(v) => match serde_json::from_str(&v) {
+            Ok(v) => v,
+            Err(e) => {
+                warn!("Unable to parse the existing results in {}: {}", path, e);
+                Vec::new()
+            }
+        },
+        Err(_) => Vec::new(),
+    };
+
+    let mut positions: HashMap<K, usize> = HashMap::new();
+    for (i, res) in merged.iter().enumerate() {
+        positions.entry(key(res)).or_insert(i);
+    }
+
+    for res in results.iter() {
+        match positions.entry(key(res)) {
+            Entry::Occupied(entry) => merged[*entry.get()] = res.clone(),
+            Entry::Vacant(entry) => {
+                entry.insert(merged.len());
+                merged.push(res.clone());
+            }
+        }
+    }
+
+    merged
+}
+
+pub fn capture_headers(headers: &hyper::HeaderMap, names: &[String]) -> Vec<(String, String)> {
+    names
+        .iter()
+        .filter_map(|name| {
+            headers.get(name.as_str()).map(|v| {
+                (
+                    name.to_owned(),
+                    String::from_utf8_lossy(v.as_bytes()).into_owned(),
+                )
+            })
+        })
+        .collect()
+}
+
+pub fn format_captured_headers(headers: &[(String, String)]) -> String {
+    headers
+        .iter()
+        .map(|(name, value)| format!("\n\t\t\t\t\t\t=> {}: {}", name, value))
+        .collect()
+}
+
+fn template_value(value: &Value) -> String {
+    match value {
+        Value::Null => "".to_owned(),
+        Value::String(v) => v.to_owned(),
+        Value::Array(v) => v.iter().map(template_value).collect::<Vec<_>>().join(","),
+        v => v.to_string(),
+    }
+}
+
+pub fn format_result<T: Serialize>(template: &str, result: &T, length: usize) -> String {
+    let value = serde_json::to_value(result).unwrap_or(Value::Null);
+    let status = value.get("status").map(template_value).unwrap_or_default();
+    let re = Regex::new(r"\{(\w+)\}").unwrap();
+
+    re.replace_all(template, |caps: &Captures| match &caps[1] {
+        "length" => length.to_string(),
+        "redirect" if status.starts_with('3') => value
+            .get("extra")
+            .or_else(|| value.get("location"))
+            .map(template_value)
+            .unwrap_or_default(),
+        "redirect" => "".to_owned(),
+        field => value.get(field).map(template_value).unwrap_or_default(),
+    })
+    .into_owned()
+}
+
+pub fn save_formatted(path: &str, lines: &[String], append: bool) {
+    let file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .append(append)
+        .truncate(!append)
+        .open(path);
+    let mut file = match file {
+        Ok(f) => f,
+        Err(e) => {
+            error!("Error while creating file: {}\n{}", path, e);
+            return;
+        }
+    };
+
+    let content: String = lines.iter().map(|line| format!("{}\n", line)).collect();
+    match file.write_all(content.as_bytes()) {
+        Ok(_) => debug!("Results saved to: {}", path),
+        Err(e) => error!("Error while writing results to file: {}\n{}", path, e),
+    };
+}
+
+pub struct Journal {
+    path: String,
+    writer: Option<BufWriter<File>>,
+    pending: usize,
+    synced: Instant,
+}
+
+impl Journal {
+    pub fn open(output: &str) -> Self {
+        let path = format!("{}.partial", output);
+        let writer = if output.is_empty() {
+            None
+        } else {
+            match File::create(&path) {
+                Ok(file) => Some(BufWriter::new(file)),
+                Err(e) => {
+                    warn!("Cannot write partial results to {}: {}", path, e);
+                    None
+                }
+            }
+        };
+
+        Journal {
+            path,
+            writer,
+            pending: 0,
+            synced: Instant::now(),
+        }
+    }
+
+    pub fn record<T: Serialize>(&mut self, result: &T) {
+        let writer = match self.writer.as_mut() {
+            Some(writer) => writer,
+            None => return,
+        };
+
+        match serde_json::to_string(result) {
+            Ok(line) => {
+                if let Err(e) = writeln!(writer, "{}", line) {
+                    warn!("Error while writing partial results: {}", e);
+                }
+            }
+            Err(e) => debug!("{}", e),
+        }
+
+        self.pending += 1;
+        if self.pending >= JOURNAL_SYNC_EVERY || self.synced.elapsed() >= JOURNAL_SYNC_INTERVAL {
+            self.sync();
+        }
+    }
+
+    fn sync(&mut self) {
+        if let Some(writer) = self.writer.as_mut() {
+            if let Err(e) = writer.flush().and_then(|_| writer.get_ref().sync_data()) {
+                warn!("Error while syncing partial results: {}", e);
+            }
+        }
+        self.pending = 0;
+        self.synced = Instant::now();
+    }
+
+    pub fn finish(mut self) {
+        if self.writer.take().is_some() {
+            if let Err(e) = fs::remove_file(&self.path) {
+                debug!("{}", e);
+            }
+        }
+    }
+}
+
+impl<T: Serialize> ResultSink<T> for Journal {
+    fn store(&mut self, result: T) {
+        self.record(&result);
+    }
+}
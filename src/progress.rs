@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::OpenOptions,
+    io::{self, Write},
+    time::{Duration, Instant},
+};
+
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ProgressEvent {
+    pub done: usize,
+    pub total: usize,
+    pub rps: u64,
+    pub eta: Option<u64>,
+    pub findings: usize,
+}
+
+impl ProgressEvent {
+    pub fn new(done: usize, total: usize, findings: usize, elapsed: Duration) -> Self {
+        let rps = (done as u64).checked_div(elapsed.as_secs()).unwrap_or(0);
+        let eta = (total.saturating_sub(done) as u64).checked_div(rps);
+
+        ProgressEvent {
+            done,
+            total,
+            rps,
+            eta,
+            findings,
+        }
+    }
+}
+
+pub struct ProgressEvents {
+    writer: Option<Box<dyn Write>>,
+    started: Instant,
+    emitted: Option<Instant>,
+}
+
+impl ProgressEvents {
+    pub fn open(target: &str) -> Self {
+        let writer: Option<Box<dyn Write>> = match target {
+            "" => None,
+            "-" => Some(Box::new(io::stderr())),
+            path => match OpenOptions::new().create(true).append(true).open(path) {
+                Ok(file) => Some(Box::new(file)),
+                Err(e) => {
+                    warn!("Cannot write progress events to {}: {}", path, e);
+                    None
+                }
+            },
+        };
+
+        ProgressEvents {
+            writer,
+            started: Instant::now(),
+            emitted: None,
+        }
+    }
+
+    pub fn update(&mut self, done: usize, total: usize, findings: usize) {
+        if self
+            .emitted
+            .is_some_and(|emitted| emitted.elapsed() < PROGRESS_INTERVAL)
+        {
+            return;
+        }
+
+        self.emit(done, total, findings);
+    }
+
+    pub fn finish(&mut self, done: usize, total: usize, findings: usize) {
+        self.emit(done, total, findings);
+    }
+
+    fn emit(&mut self, done: usize, total: usize, findings: usize) {
+        let writer = match self.writer.as_mut() {
+            Some(writer) => writer,
+            None => return,
+        };
+
+        let event = ProgressEvent::new(done, total, findings, self.started.elapsed());
+        let line = serde_json::to_string(&event).unwrap();
+        if let Err(e) = writeln!(writer, "{}", line).and_then(|_| writer.flush()) {
+            debug!("{}", e);
+            self.writer = None;
+        }
+        self.emitted = Some(Instant::now());
+    }
+}
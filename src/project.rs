@@ -0,0 +1,214 @@
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{collections::BTreeMap, fs, io::Write, path::Path, process::Command};
+
+use crate::{
+    diff::{describe, diff_results, result_key},
+    export,
+    scheduler::HitRates,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ProjectScan {
+    pub mode: String,
+    pub arguments: Vec<String>,
+    pub started_at: String,
+    pub ended_at: Option<String>,
+    pub results: usize,
+    pub added: usize,
+    pub changed: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ProjectFile {
+    pub scans: Vec<ProjectScan>,
+    pub findings: BTreeMap<String, Vec<Value>>,
+}
+
+#[derive(Debug, Default)]
+pub struct Project {
+    path: String,
+    file: ProjectFile,
+    scan: usize,
+}
+
+fn now() -> String {
+    Local::now().format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+impl ProjectFile {
+    pub fn load(path: &str) -> Option<Self> {
+        let content = match fs::read_to_string(path) {
+            Ok(v) => v,
+            Err(_) => return Some(ProjectFile::default()),
+        };
+
+        match serde_json::from_str(&content) {
+            Ok(v) => Some(v),
+            Err(e) => {
+                error!("Error while parsing project file: {}\n{}", path, e);
+                None
+            }
+        }
+    }
+
+    pub fn save(&self, path: &str) {
+        let json_string = serde_json::to_string(self).unwrap();
+
+        let mut file = match fs::File::create(Path::new(path)) {
+            Ok(f) => f,
+            Err(e) => {
+                error!("Error while creating file: {}\n{}", path, e);
+                return;
+            }
+        };
+
+        match file.write_all(json_string.as_bytes()) {
+            Ok(_) => debug!("Project saved to: {}", path),
+            Err(e) => error!("Error while writing project to file: {}\n{}", path, e),
+        };
+    }
+
+    pub fn merge(&mut self, mode: &str, results: Vec<Value>) -> (usize, usize) {
+        let findings = self.findings.entry(mode.to_owned()).or_default();
+        let diff = diff_results(findings, &results);
+        for res in results {
+            let key = result_key(&res);
+            match findings.iter().position(|f| result_key(f) == key) {
+                Some(i) => findings[i] = res,
+                None => findings.push(res),
+            }
+        }
+
+        (diff.added.len(), diff.changed.len())
+    }
+
+    pub fn history(&self) -> String {
+        let mut history = "[+] Scans:".to_owned();
+        for scan in self.scans.iter() {
+            let state = match &scan.ended_at {
+                Some(_) => format!(
+                    "{} results, {} new, {} changed",
+                    scan.results, scan.added, scan.changed
+                ),
+                None => "interrupted".to_owned(),
+            };
+            history.push_str(&format!(
+                "\n\t{}\t{}\t{}\n\t\t\t=> {}",
+                scan.started_at,
+                scan.mode,
+                state,
+                scan.arguments.join(" ")
+            ));
+        }
+
+        history.push_str("\n[+] Findings:");
+        for (mode, findings) in self.findings.iter() {
+            for finding in findings.iter() {
+                history.push_str(&format!("\n\t{}\t{}", mode, describe(finding)));
+            }
+        }
+
+        history
+    }
+}
+
+impl Project {
+    pub fn open(path: &str, mode: &str) -> Option<Self> {
+        if path.is_empty() {
+            return Some(Project::default());
+        }
+
+        let mut file = ProjectFile::load(path)?;
+        file.scans.push(ProjectScan {
+            mode: mode.to_owned(),
+            arguments: std::env::args().collect(),
+            started_at: now(),
+            ..ProjectScan::default()
+        });
+        file.save(path);
+
+        Some(Project {
+            path: path.to_owned(),
+            scan: file.scans.len() - 1,
+            file,
+        })
+    }
+
+    pub fn hit_rates(&self) -> HitRates {
+        HitRates::from_findings(&self.file.findings)
+    }
+
+    pub fn record<T: Serialize>(&mut self, results: &[T]) {
+        if self.path.is_empty() {
+            return;
+        }
+
+        let values: Vec<Value> = results
+            .iter()
+            .filter_map(|r| serde_json::to_value(r).ok())
+            .collect();
+        let count = values.len();
+        let mode = self.file.scans[self.scan].mode.to_owned();
+        let (added, changed) = self.file.merge(&mode, values);
+
+        let scan = &mut self.file.scans[self.scan];
+        scan.ended_at = Some(now());
+        scan.results = count;
+        scan.added = added;
+        scan.changed = changed;
+        self.file.save(&self.path);
+
+        crate::say!(
+            "[+] Project\t: {} new, {} changed since the previous scans ({})",
+            added,
+            changed,
+            self.path
+        );
+    }
+}
+
+pub fn run(path: &str, export: &str, export_format: &str, resume: bool) {
+    let file = match ProjectFile::load(path) {
+        Some(v) => v,
+        None => return,
+    };
+
+    if resume {
+        let scan = match file.scans.last().filter(|s| s.ended_at.is_none()) {
+            Some(v) => v,
+            None => {
+                crate::say!("[+] No interrupted scan in {}", path);
+                return;
+            }
+        };
+
+        crate::say!("[+] Resuming: {}", scan.arguments.join(" "));
+        let program = std::env::current_exe()
+            .map(|p| p.to_string_lossy().into_owned())
+            .unwrap_or_else(|_| scan.arguments[0].to_owned());
+        if let Err(e) = Command::new(program).args(&scan.arguments[1..]).status() {
+            error!("Unable to resume the scan: {}", e);
+        }
+
+        return;
+    }
+
+    crate::say!("{}", file.history());
+
+    if !export.is_empty() {
+        let json_string = match export_format {
+            "defectdojo" => {
+                let date = Local::now().format("%Y-%m-%d").to_string();
+                export::defectdojo(&export::findings(&file.findings), &date).to_string()
+            }
+            "faraday" => export::faraday(&export::findings(&file.findings)).to_string(),
+            _ => serde_json::to_string(&file.findings).unwrap(),
+        };
+        match fs::write(export, json_string) {
+            Ok(_) => crate::say!("[+] Findings exported to {}", export),
+            Err(e) => error!("Error while writing results to file: {}\n{}", export, e),
+        }
+    }
+}
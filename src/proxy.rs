@@ -0,0 +1,299 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use futures::{
+    future::{self, Loop},
+    Future,
+};
+use hyper::client::{
+    connect::{Connect, Connected, Destination},
+    HttpConnector,
+};
+use std::{
+    io,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use crate::family::FamilyResolver;
+
+static ENV_PROXY: AtomicBool = AtomicBool::new(true);
+
+const MAX_RESPONSE_HEAD: usize = 8192;
+
+pub fn set_env_proxy(enabled: bool) {
+    ENV_PROXY.store(enabled, Ordering::SeqCst);
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Proxy {
+    pub host: String,
+    pub port: u16,
+    pub authorization: Option<String>,
+}
+
+impl Proxy {
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let rest = match value.find("://") {
+            Some(i) if value[..i].eq_ignore_ascii_case("http") => &value[i + 3..],
+            Some(_) => {
+                warn!(
+                    "Ignoring proxy {}, only http:// proxies are supported",
+                    value
+                );
+                return None;
+            }
+            None => value,
+        };
+        let authority = rest.split('/').next().unwrap_or("");
+        let (credentials, address) = match authority.rfind('@') {
+            Some(i) => (Some(&authority[..i]), &authority[i + 1..]),
+            None => (None, authority),
+        };
+        let (host, port) = match address.rfind(':') {
+            Some(i) if !address[i..].contains(']') => {
+                (&address[..i], address[i + 1..].parse().ok()?)
+            }
+            _ => (address, 80),
+        };
+        if host.is_empty() {
+            return None;
+        }
+
+        Some(Proxy {
+            host: host
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .to_owned(),
+            port,
+            authorization: credentials.map(|c| STANDARD.encode(c)),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProxySettings {
+    pub http: Option<Proxy>,
+    pub https: Option<Proxy>,
+    pub no_proxy: Vec<String>,
+}
+
+impl ProxySettings {
+    pub fn from_lookup<F: Fn(&str) -> Option<String>>(lookup: F) -> Self {
+        let first = |names: &[&str]| {
+            names
+                .iter()
+                .filter_map(|name| lookup(name))
+                .find(|v| !v.trim().is_empty())
+        };
+        let all = first(&["all_proxy", "ALL_PROXY"]);
+        let http = first(&["http_proxy", "HTTP_PROXY"]).or_else(|| all.clone());
+        let https = first(&["https_proxy", "HTTPS_PROXY"]).or(all);
+        let no_proxy = first(&["no_proxy", "NO_PROXY"])
+            .map(|v| {
+                v.split(',')
+                    .map(|e| e.trim().to_lowercase())
+                    .filter(|e| !e.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        ProxySettings {
+            http: http.and_then(|v| Proxy::parse(&v)),
+            https: https.and_then(|v| Proxy::parse(&v)),
+            no_proxy,
+        }
+    }
+
+    pub fn from_env() -> Self {
+        if !ENV_PROXY.load(Ordering::SeqCst) {
+            return ProxySettings::default();
+        }
+
+        ProxySettings::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    pub fn bypass(&self, host: &str) -> bool {
+        let host = host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_lowercase();
+        self.no_proxy.iter().any(|entry| {
+            if entry == "*" {
+                return true;
+            }
+
+            let entry = entry.trim_start_matches('*').trim_start_matches('.');
+            let entry = match entry.rfind(':') {
+                Some(i) if entry.matches(':').count() == 1 => &entry[..i],
+                _ => entry,
+            };
+            let entry = entry.trim_start_matches('[').trim_end_matches(']');
+            host == entry || host.ends_with(&format!(".{}", entry))
+        })
+    }
+
+    pub fn proxy_for(&self, scheme: &str, host: &str) -> Option<&Proxy> {
+        let proxy = match scheme {
+            "https" | "wss" => self.https.as_ref(),
+            _ => self.http.as_ref(),
+        }?;
+        if self.bypass(host) {
+            None
+        } else {
+            Some(proxy)
+        }
+    }
+}
+
+type Transport = <HttpConnector<FamilyResolver> as Connect>::Transport;
+
+fn tunnel(
+    stream: Transport,
+    target: String,
+    authorization: Option<String>,
+) -> impl Future<Item = Transport, Error = io::Error> {
+    let mut request = format!("CONNECT {0} HTTP/1.1\r\nHost: {0}\r\n", target);
+    if let Some(authorization) = authorization {
+        request.push_str(&format!("Proxy-Authorization: Basic {}\r\n", authorization));
+    }
+    request.push_str("\r\n");
+
+    tokio_io::io::write_all(stream, request.into_bytes())
+        .and_then(|(stream, _)| {
+            future::loop_fn((stream, Vec::new()), |(stream, mut head)| {
+                tokio_io::io::read(stream, vec![0; 1024]).and_then(move |(stream, chunk, n)| {
+                    if n == 0 {
+                        return Err(io::Error::new(
+                            io::ErrorKind::UnexpectedEof,
+                            "the proxy closed the connection",
+                        ));
+                    }
+
+                    head.extend_from_slice(&chunk[..n]);
+                    if head.windows(4).any(|w| w == b"\r\n\r\n") {
+                        Ok(Loop::Break((stream, head)))
+                    } else if head.len() > MAX_RESPONSE_HEAD {
+                        Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "the proxy response is too large",
+                        ))
+                    } else {
+                        Ok(Loop::Continue((stream, head)))
+                    }
+                })
+            })
+        })
+        .and_then(|(stream, head)| {
+            let head = String::from_utf8_lossy(&head);
+            let status_line = head.lines().next().unwrap_or("");
+            if status_line.split_whitespace().nth(1) == Some("200") {
+                Ok(stream)
+            } else {
+                Err(io::Error::other(format!(
+                    "the proxy refused the tunnel: {}",
+                    status_line
+                )))
+            }
+        })
+}
+
+#[derive(Clone)]
+pub struct ProxyConnector {
+    http: HttpConnector<FamilyResolver>,
+    settings: Arc<ProxySettings>,
+}
+
+impl ProxyConnector {
+    pub fn new(threads: usize) -> Self {
+        let mut http = HttpConnector::new_with_resolver(FamilyResolver::new(threads));
+        http.enforce_http(false);
+
+        ProxyConnector {
+            http,
+            settings: Arc::new(ProxySettings::from_env()),
+        }
+    }
+}
+
+impl Connect for ProxyConnector {
+    type Transport = Transport;
+    type Error = io::Error;
+    type Future = Box<dyn Future<Item = (Transport, Connected), Error = io::Error> + Send>;
+
+    fn connect(&self, dst: Destination) -> Self::Future {
+        let proxy = match self.settings.proxy_for(dst.scheme(), dst.host()) {
+            Some(v) => v.clone(),
+            None => return Box::new(self.http.connect(dst)),
+        };
+
+        let default_port = match dst.scheme() {
+            "https" | "wss" => 443,
+            _ => 80,
+        };
+        let target = format!("{}:{}", dst.host(), dst.port().unwrap_or(default_port));
+        // plain HTTP goes through the proxy in absolute form, unless it needs credentials
+        let use_tunnel = dst.scheme() == "https" || proxy.authorization.is_some();
+
+        let mut proxy_dst = dst;
+        let redirected = proxy_dst
+            .set_scheme("http")
+            .and_then(|_| proxy_dst.set_host(&proxy.host))
+            .map(|_| proxy_dst.set_port(proxy.port));
+        if let Err(e) = redirected {
+            return Box::new(future::err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                e.to_string(),
+            )));
+        }
+
+        trace!(
+            "Connecting to {} through {}:{}",
+            target,
+            proxy.host,
+            proxy.port
+        );
+        let connecting = self.http.connect(proxy_dst);
+        if use_tunnel {
+            Box::new(connecting.and_then(move |(stream, connected)| {
+                tunnel(stream, target, proxy.authorization).map(move |stream| (stream, connected))
+            }))
+        } else {
+            Box::new(connecting.map(|(stream, connected)| (stream, connected.proxy(true))))
+        }
+    }
+}
+
+// always dials the target, whatever the request URI says, so the URI only picks the SNI
+#[derive(Clone)]
+pub struct PinnedConnector {
+    inner: ProxyConnector,
+    host: String,
+}
+
+impl PinnedConnector {
+    pub fn new(inner: ProxyConnector, host: &str) -> Self {
+        PinnedConnector {
+            inner,
+            host: host.to_owned(),
+        }
+    }
+}
+
+impl Connect for PinnedConnector {
+    type Transport = Transport;
+    type Error = io::Error;
+    type Future = <ProxyConnector as Connect>::Future;
+
+    fn connect(&self, mut dst: Destination) -> Self::Future {
+        if let Err(e) = dst.set_host(&self.host) {
+            return Box::new(future::err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                e.to_string(),
+            )));
+        }
+
+        self.inner.connect(dst)
+    }
+}
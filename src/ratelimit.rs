@@ -0,0 +1,325 @@
+use chrono::{DateTime, Utc};
+use futures::{
+    future::{self, Loop},
+    Future,
+};
+use std::{
+    collections::VecDeque,
+    convert::TryFrom,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+use tokio_timer::Delay;
+
+const SLOW_WINDOW: usize = 20;
+const SLOW_SPIKE: usize = 5;
+const PAUSE_POLL: Duration = Duration::from_millis(200);
+/// The longest pause a Retry-After or a rate limit reset can ask for, unless configured
+pub const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Default)]
+pub struct Pacer {
+    resume_at: Mutex<Option<Instant>>,
+    budget: Mutex<Option<RateBudget>>,
+    next_slot: Mutex<Option<Instant>>,
+    slow_rules: Vec<SlowRule>,
+    statuses: Mutex<VecDeque<u16>>,
+    slow_interval: Mutex<Option<Duration>>,
+    paused: Arc<AtomicBool>,
+    max_delay: Option<Duration>,
+}
+
+/// Spacing between two requests while a status class spikes, e.g. 403:2000ms or 5xx:5s
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlowRule {
+    pub status: String,
+    pub delay: Duration,
+}
+
+impl SlowRule {
+    pub fn parse(rule: &str) -> Result<Self, String> {
+        let (class, delay) = match rule.find(':') {
+            Some(i) => (rule[..i].trim(), rule[i + 1..].trim()),
+            None => return Err(format!("Invalid rule: {}, expected status:delay", rule)),
+        };
+        let status = class.trim_end_matches(['x', 'X']);
+        if class.len() != 3
+            || status.is_empty()
+            || !status.chars().all(|c| c.is_ascii_digit())
+            || !('1'..='5').contains(&status.chars().next().unwrap())
+        {
+            return Err(format!("Invalid status class: {}, e.g. 403 or 5xx", class));
+        }
+
+        let delay = if let Some(ms) = delay.strip_suffix("ms") {
+            ms.parse().map(Duration::from_millis)
+        } else if let Some(s) = delay.strip_suffix('s') {
+            s.parse().map(Duration::from_secs)
+        } else {
+            delay.parse().map(Duration::from_millis)
+        }
+        .map_err(|_| format!("Invalid delay: {}, e.g. 2000ms or 5s", delay))?;
+
+        Ok(SlowRule {
+            status: status.to_owned(),
+            delay,
+        })
+    }
+
+    fn matches(&self, status: u16) -> bool {
+        status.to_string().starts_with(&self.status)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateBudget {
+    pub limit: Option<u64>,
+    pub remaining: u64,
+    pub reset: Option<Duration>,
+}
+
+impl RateBudget {
+    /// Spacing between two requests that spends 90% of the remaining budget before the reset
+    pub fn interval(&self) -> Option<Duration> {
+        let usable = u32::try_from(self.remaining * 9 / 10).unwrap_or(u32::MAX);
+        if usable == 0 {
+            return None;
+        }
+
+        self.reset.map(|reset| reset / usable)
+    }
+}
+
+impl fmt::Display for RateBudget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.limit {
+            Some(limit) => write!(f, "{}/{}", self.remaining, limit)?,
+            None => write!(f, "{}", self.remaining)?,
+        }
+        if let Some(reset) = self.reset {
+            write!(f, " reset in {}s", reset.as_secs())?;
+        }
+
+        Ok(())
+    }
+}
+
+fn header_value<'a>(headers: &'a hyper::HeaderMap, names: &[&str]) -> Option<&'a str> {
+    names
+        .iter()
+        .filter_map(|name| headers.get(*name))
+        .filter_map(|v| v.to_str().ok())
+        .map(|v| v.trim())
+        .next()
+}
+
+fn leading_number(value: &str) -> Option<u64> {
+    // the IETF draft allows a quota policy after the value, e.g. 100, 100;w=60
+    value.split([',', ';']).next()?.trim().parse().ok()
+}
+
+pub fn parse_rate_budget(headers: &hyper::HeaderMap) -> Option<RateBudget> {
+    let remaining = leading_number(header_value(
+        headers,
+        &["X-RateLimit-Remaining", "RateLimit-Remaining"],
+    )?)?;
+    let limit =
+        header_value(headers, &["X-RateLimit-Limit", "RateLimit-Limit"]).and_then(leading_number);
+    let reset = header_value(headers, &["X-RateLimit-Reset", "RateLimit-Reset"])
+        .and_then(leading_number)
+        .map(|reset| {
+            // some servers send an epoch timestamp, others the amount of seconds left
+            let seconds = if reset > 1_000_000_000 {
+                reset as i64 - Utc::now().timestamp()
+            } else {
+                reset as i64
+            };
+            Duration::from_secs(seconds.max(0) as u64)
+        });
+
+    Some(RateBudget {
+        limit,
+        remaining,
+        reset,
+    })
+}
+
+pub fn parse_rate_limit_delay(headers: &hyper::HeaderMap) -> Option<Duration> {
+    if let Some(v) = header_value(headers, &["Retry-After"]) {
+        if let Ok(seconds) = v.parse::<u64>() {
+            return Some(Duration::from_secs(seconds));
+        }
+
+        if let Ok(date) = DateTime::parse_from_rfc2822(v) {
+            let seconds = date.timestamp() - Utc::now().timestamp();
+            return Some(Duration::from_secs(seconds.max(0) as u64));
+        }
+    }
+
+    let budget = parse_rate_budget(headers)?;
+    if budget.remaining > 0 {
+        return None;
+    }
+
+    budget.reset
+}
+
+impl Pacer {
+    pub fn new(slow_rules: Vec<SlowRule>) -> Self {
+        Pacer {
+            slow_rules,
+            ..Pacer::default()
+        }
+    }
+
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = Some(max_delay);
+        self
+    }
+
+    /// The pause the target asks for, a bogus far away date or reset can't stall the scan
+    pub fn delay_for(&self, headers: &hyper::HeaderMap) -> Option<Duration> {
+        let delay = parse_rate_limit_delay(headers)?;
+        let max_delay = self.max_delay.unwrap_or(DEFAULT_MAX_DELAY);
+        if delay <= max_delay {
+            return Some(delay);
+        }
+
+        warn!(
+            "The target asks to wait {}s, pausing for the maximum of {}s instead",
+            delay.as_secs(),
+            max_delay.as_secs()
+        );
+        Some(max_delay)
+    }
+
+    pub fn wait(&self) -> impl Future<Item = (), Error = ()> {
+        let now = Instant::now();
+        let mut start = match *self.resume_at.lock().unwrap() {
+            Some(instant) if instant > now => instant,
+            _ => now,
+        };
+        let budget_interval = self.budget().and_then(|b| b.interval());
+        let interval = match (budget_interval, self.slow_interval()) {
+            (Some(budget), Some(slow)) => Some(budget.max(slow)),
+            (budget, slow) => budget.or(slow),
+        };
+        if let Some(interval) = interval {
+            let mut next_slot = self.next_slot.lock().unwrap();
+            if let Some(slot) = *next_slot {
+                start = start.max(slot);
+            }
+            *next_slot = Some(start + interval);
+        }
+
+        let paced = if start > now {
+            future::Either::A(
+                Delay::new(start).map_err(|e| warn!("Timer error while pacing: {}", e)),
+            )
+        } else {
+            future::Either::B(future::ok(()))
+        };
+
+        let paused = self.paused.clone();
+        paced.and_then(move |_| {
+            future::loop_fn((), move |_| {
+                if paused.load(Ordering::SeqCst) {
+                    future::Either::A(
+                        Delay::new(Instant::now() + PAUSE_POLL)
+                            .map(|_| Loop::Continue(()))
+                            .map_err(|e| warn!("Timer error while paused: {}", e)),
+                    )
+                } else {
+                    future::Either::B(future::ok(Loop::Break(())))
+                }
+            })
+        })
+    }
+
+    /// Holds the requests not sent yet until resumed, the ones in flight still complete
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::SeqCst);
+    }
+
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::SeqCst);
+    }
+
+    pub fn budget(&self) -> Option<RateBudget> {
+        *self.budget.lock().unwrap()
+    }
+
+    /// The spacing of the slow rules whose status class is a spike in the latest responses
+    pub fn slow_interval(&self) -> Option<Duration> {
+        *self.slow_interval.lock().unwrap()
+    }
+
+    pub fn observe_status(&self, status: hyper::StatusCode) {
+        if self.slow_rules.is_empty() {
+            return;
+        }
+
+        let mut statuses = self.statuses.lock().unwrap();
+        statuses.push_back(status.as_u16());
+        if statuses.len() > SLOW_WINDOW {
+            statuses.pop_front();
+        }
+
+        let interval = self
+            .slow_rules
+            .iter()
+            .filter(|rule| statuses.iter().filter(|s| rule.matches(**s)).count() >= SLOW_SPIKE)
+            .map(|rule| rule.delay)
+            .max();
+        let mut current = self.slow_interval.lock().unwrap();
+        match (*current, interval) {
+            (None, Some(delay)) => warn!(
+                "Status spike in the latest {} responses, one request every {}ms",
+                statuses.len(),
+                delay.as_millis()
+            ),
+            (Some(_), None) => warn!("The status spike is over, resuming the normal pace"),
+            _ => (),
+        }
+        *current = interval;
+    }
+
+    pub fn observe(&self, headers: &hyper::HeaderMap) {
+        if let Some(budget) = parse_rate_budget(headers) {
+            let mut current = self.budget.lock().unwrap();
+            if current.is_none() {
+                warn!(
+                    "The target declares a rate limit of {}, pacing the requests to stay under it",
+                    budget
+                );
+            }
+            *current = Some(budget);
+        }
+
+        let delay = match self.delay_for(headers) {
+            Some(v) => v,
+            None => return,
+        };
+
+        let resume_at = Instant::now() + delay;
+        let mut current = self.resume_at.lock().unwrap();
+        match *current {
+            Some(instant) if instant > Instant::now() => {
+                if resume_at > instant {
+                    *current = Some(resume_at);
+                }
+            }
+            _ => {
+                warn!(
+                    "Rate limited by the target, pausing for {}s",
+                    delay.as_secs()
+                );
+                *current = Some(resume_at);
+            }
+        }
+    }
+}
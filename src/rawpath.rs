@@ -0,0 +1,23 @@
+const UNSENDABLE: &[u8] = b" \"#<>`{}";
+
+pub fn escape_raw_path(url: &str) -> String {
+    let path_start = match url.find("://") {
+        Some(i) => url[i + 3..]
+            .find('/')
+            .map(|j| i + 3 + j)
+            .unwrap_or(url.len()),
+        None => 0,
+    };
+
+    let mut escaped = url[..path_start].to_owned();
+    for b in url[path_start..].bytes() {
+        // only the bytes that can't appear in a request line are escaped
+        if !(0x21..=0x7e).contains(&b) || UNSENDABLE.contains(&b) {
+            escaped.push_str(&format!("%{:02X}", b));
+        } else {
+            escaped.push(b as char);
+        }
+    }
+
+    escaped
+}
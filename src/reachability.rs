@@ -0,0 +1,188 @@
+use futures::Future;
+use hyper::{rt, Client, Uri};
+use hyper_tls::HttpsConnector;
+use std::{
+    fmt, io,
+    net::{TcpStream, ToSocketAddrs},
+    sync::mpsc::channel,
+    time::Duration,
+};
+use tokio_timer::Timeout;
+
+use crate::{
+    proxy::{ProxyConnector, ProxySettings},
+    summary::ErrorCategory,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The step of the probe that failed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unreachable {
+    pub step: &'static str,
+    pub category: ErrorCategory,
+    pub error: String,
+}
+
+impl Unreachable {
+    fn new(step: &'static str, category: ErrorCategory, error: &str) -> Self {
+        Unreachable {
+            step,
+            category,
+            error: error.to_owned(),
+        }
+    }
+
+    pub fn hint(&self) -> &'static str {
+        match self.category {
+            ErrorCategory::Dns => "check the host name and the DNS resolver",
+            ErrorCategory::ConnectionRefused => "check the port and that the service is running",
+            ErrorCategory::Tls => "check the scheme, or add -k for self-signed certificates",
+            ErrorCategory::Timeout => "check the firewall rules and the route to the target",
+            _ => "check the URL and the proxy settings",
+        }
+    }
+}
+
+impl fmt::Display for Unreachable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} failed ({}): {}, {}",
+            self.step,
+            self.category.label(),
+            self.error,
+            self.hint()
+        )
+    }
+}
+
+fn io_category(error: &io::Error) -> ErrorCategory {
+    match error.kind() {
+        io::ErrorKind::ConnectionRefused => ErrorCategory::ConnectionRefused,
+        io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock => ErrorCategory::Timeout,
+        _ => ErrorCategory::of(&error.to_string()),
+    }
+}
+
+fn connect(host: &str, port: u16) -> Result<TcpStream, Unreachable> {
+    let addrs: Vec<_> = match (host, port).to_socket_addrs() {
+        Ok(v) => v.collect(),
+        Err(e) => {
+            return Err(Unreachable::new(
+                "DNS lookup",
+                ErrorCategory::Dns,
+                &e.to_string(),
+            ))
+        }
+    };
+    if addrs.is_empty() {
+        return Err(Unreachable::new(
+            "DNS lookup",
+            ErrorCategory::Dns,
+            "no address",
+        ));
+    }
+
+    let mut last_error = None;
+    for addr in addrs {
+        match TcpStream::connect_timeout(&addr, TIMEOUT) {
+            Ok(v) => return Ok(v),
+            Err(e) => {
+                debug!("Unable to connect to {}: {}", addr, e);
+                last_error = Some(e);
+            }
+        }
+    }
+
+    let error = last_error.unwrap();
+    Err(Unreachable::new(
+        "TCP connect",
+        io_category(&error),
+        &error.to_string(),
+    ))
+}
+
+/// The scheme spoken by a target given without one, HTTPS is tried first
+pub fn detect_scheme(target: &str) -> Result<&'static str, Unreachable> {
+    let invalid = |e: &str| Unreachable::new("URL parsing", ErrorCategory::Other, e);
+    let uri = format!("https://{}", target)
+        .parse::<Uri>()
+        .map_err(|e| invalid(&e.to_string()))?;
+    let host = uri.host().ok_or_else(|| invalid("no host"))?;
+
+    let stream = match connect(host, uri.port_u16().unwrap_or(443)) {
+        Err(e) if e.category == ErrorCategory::Dns => return Err(e),
+        v => v.ok(),
+    };
+    let connector = native_tls::TlsConnector::builder()
+        .danger_accept_invalid_certs(true)
+        .danger_accept_invalid_hostnames(true)
+        .build();
+    if let (Some(stream), Ok(connector)) = (stream, connector) {
+        stream.set_read_timeout(Some(TIMEOUT)).unwrap_or(());
+        match connector.connect(host, stream) {
+            Ok(_) => return Ok("https"),
+            Err(e) => debug!("No TLS on {}: {}", target, e),
+        }
+    }
+
+    connect(host, uri.port_u16().unwrap_or(80))?;
+    Ok("http")
+}
+
+/// Connects to the target and requests its root, the status of the response when it answers
+pub fn probe(url: &str, ignore_certificate: bool) -> Result<String, Unreachable> {
+    let invalid = |e: &str| Unreachable::new("URL parsing", ErrorCategory::Other, e);
+    let url = if url.contains("://") {
+        url.to_owned()
+    } else {
+        format!("{}://{}", detect_scheme(url)?, url)
+    };
+    let uri = url.parse::<Uri>().map_err(|e| invalid(&e.to_string()))?;
+    let scheme = uri.scheme_str().unwrap_or("http");
+    let host = uri.host().ok_or_else(|| invalid("no host"))?;
+    let port = uri
+        .port_u16()
+        .unwrap_or(if scheme == "https" { 443 } else { 80 });
+
+    // the proxy resolves and connects to the target itself
+    if ProxySettings::from_env().proxy_for(scheme, host).is_none() {
+        connect(host, port)?;
+    }
+
+    let root = format!("{}://{}/", scheme, uri.authority_part().unwrap().as_str())
+        .parse::<Uri>()
+        .map_err(|e| invalid(&e.to_string()))?;
+    let mut tls_connector_builder = native_tls::TlsConnector::builder();
+    tls_connector_builder.danger_accept_invalid_certs(ignore_certificate);
+    let tls_connector = tls_connector_builder
+        .build()
+        .map_err(|e| Unreachable::new("TLS setup", ErrorCategory::Tls, &e.to_string()))?;
+    let client: Client<HttpsConnector<ProxyConnector>> = Client::builder().build(
+        HttpsConnector::from((ProxyConnector::new(1), tls_connector)),
+    );
+
+    let (tx, rx) = channel::<Result<String, Unreachable>>();
+    let tx_err = tx.clone();
+    let request = client.get(root).map(move |res| {
+        tx.send(Ok(res.status().to_string())).unwrap_or(());
+    });
+    rt::run(Timeout::new(request, TIMEOUT).map_err(move |e| {
+        let error = if e.is_elapsed() {
+            Unreachable::new("HTTP request", ErrorCategory::Timeout, "no response")
+        } else {
+            let error = e.into_inner().map(|e| e.to_string()).unwrap_or_default();
+            Unreachable::new("HTTP request", ErrorCategory::of(&error), &error)
+        };
+        tx_err.send(Err(error)).unwrap_or(());
+    }));
+
+    rx.try_recv().unwrap_or_else(|_| {
+        Err(Unreachable::new(
+            "HTTP request",
+            ErrorCategory::Other,
+            "no response",
+        ))
+    })
+}
@@ -0,0 +1,150 @@
+use std::cmp::Ordering;
+
+use crate::{
+    dirbuster::result_processor::SingleDirScanResult,
+    fuzzbuster::result_processor::SingleFuzzScanResult,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortKey {
+    Status,
+    Length,
+    Url,
+}
+
+impl SortKey {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "status" => Some(SortKey::Status),
+            "length" => Some(SortKey::Length),
+            "url" => Some(SortKey::Url),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GroupBy {
+    Directory,
+    Status,
+}
+
+impl GroupBy {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "directory" => Some(GroupBy::Directory),
+            "status" => Some(GroupBy::Status),
+            _ => None,
+        }
+    }
+}
+
+/// What the results are sorted and grouped by
+pub trait Reported {
+    fn url(&self) -> &str;
+    fn status(&self) -> &str;
+    fn length(&self) -> usize;
+}
+
+impl Reported for SingleDirScanResult {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn status(&self) -> &str {
+        &self.status
+    }
+
+    fn length(&self) -> usize {
+        self.length
+    }
+}
+
+impl Reported for SingleFuzzScanResult {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn status(&self) -> &str {
+        &self.status
+    }
+
+    fn length(&self) -> usize {
+        self.body.len()
+    }
+}
+
+/// The directory holding the URL, a directory found by the scan belongs to its parent
+pub fn directory(url: &str) -> String {
+    let uri = match url.parse::<hyper::Uri>() {
+        Ok(v) => v,
+        Err(_) => return url.to_owned(),
+    };
+    let path = uri.path().trim_end_matches('/');
+    let parent = match path.rfind('/') {
+        Some(i) => &path[..=i],
+        None => "/",
+    };
+
+    format!(
+        "{}://{}{}",
+        uri.scheme_str().unwrap_or("http"),
+        uri.authority_part().map(|a| a.as_str()).unwrap_or(""),
+        parent
+    )
+}
+
+fn status_code(status: &str) -> u16 {
+    status
+        .split_whitespace()
+        .next()
+        .and_then(|code| code.parse().ok())
+        .unwrap_or(0)
+}
+
+fn group_key<T: Reported>(group_by: GroupBy, result: &T) -> String {
+    match group_by {
+        GroupBy::Directory => directory(result.url()),
+        GroupBy::Status => result.status().to_owned(),
+    }
+}
+
+/// Sorts the results within their groups, the ties keep the discovery order
+pub fn order<T: Reported>(results: &mut [T], sort: Option<SortKey>, group_by: Option<GroupBy>) {
+    results.sort_by(|a, b| {
+        let group = match group_by {
+            Some(GroupBy::Status) => status_code(a.status()).cmp(&status_code(b.status())),
+            Some(group_by) => group_key(group_by, a).cmp(&group_key(group_by, b)),
+            None => Ordering::Equal,
+        };
+        group.then_with(|| match sort {
+            Some(SortKey::Status) => status_code(a.status()).cmp(&status_code(b.status())),
+            Some(SortKey::Length) => a.length().cmp(&b.length()),
+            Some(SortKey::Url) => a.url().cmp(b.url()),
+            None => Ordering::Equal,
+        })
+    });
+}
+
+/// The ordered results, under a heading for each group
+pub fn summary<T: Reported>(results: &[T], group_by: Option<GroupBy>) -> String {
+    let mut report = "\n[+] Results".to_owned();
+    let mut current_group = None;
+    for result in results.iter() {
+        if let Some(group_by) = group_by {
+            let group = group_key(group_by, result);
+            if current_group.as_ref() != Some(&group) {
+                report.push_str(&format!("\n    {}", group));
+                current_group = Some(group);
+            }
+        }
+        report.push_str(&format!(
+            "\n\t{}\t{}\t{}",
+            result.status(),
+            result.length(),
+            result.url()
+        ));
+    }
+
+    report
+}
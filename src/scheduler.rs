@@ -0,0 +1,272 @@
+use futures::{
+    task::{self, Task},
+    Async, Poll, Stream,
+};
+use serde_json::Value;
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BinaryHeap, HashMap},
+    sync::{Arc, Mutex},
+};
+
+use crate::template::word_from_uri;
+
+/// Later kinds are scheduled first, follow-ups of a finding before the remaining brute force
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum JobKind {
+    BruteForce,
+    History,
+    FollowUp,
+}
+
+struct Entry<T> {
+    kind: JobKind,
+    weight: usize,
+    seq: usize,
+    item: T,
+}
+
+impl<T> Ord for Entry<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.kind
+            .cmp(&other.kind)
+            .then(self.weight.cmp(&other.weight))
+            // same priority, wordlist order
+            .then(other.seq.cmp(&self.seq))
+    }
+}
+
+impl<T> PartialOrd for Entry<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> PartialEq for Entry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T> Eq for Entry<T> {}
+
+pub struct Scheduler<T> {
+    heap: BinaryHeap<Entry<T>>,
+    seq: usize,
+}
+
+impl<T> Default for Scheduler<T> {
+    fn default() -> Self {
+        Scheduler {
+            heap: BinaryHeap::new(),
+            seq: 0,
+        }
+    }
+}
+
+impl<T> Scheduler<T> {
+    pub fn push(&mut self, kind: JobKind, weight: usize, item: T) {
+        self.heap.push(Entry {
+            kind,
+            weight,
+            seq: self.seq,
+            item,
+        });
+        self.seq += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.heap.pop().map(|entry| entry.item)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.peek().map(|entry| &entry.item)
+    }
+
+    /// The first `follow_ups` items come from earlier findings and keep the lead, the others are
+    /// weighted by how often their word was found before
+    pub fn extend_weighted<F>(
+        &mut self,
+        items: Vec<T>,
+        follow_ups: usize,
+        hit_rates: &HitRates,
+        word: F,
+    ) where
+        F: Fn(&T) -> String,
+    {
+        for (i, item) in items.into_iter().enumerate() {
+            match hit_rates.hits(&word(&item)) {
+                _ if i < follow_ups => self.push(JobKind::FollowUp, 0, item),
+                0 => self.push(JobKind::BruteForce, 0, item),
+                hits => self.push(JobKind::History, hits, item),
+            }
+        }
+    }
+}
+
+impl<T> Iterator for Scheduler<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.pop()
+    }
+}
+
+struct QueueState<T> {
+    scheduler: Scheduler<T>,
+    closed: bool,
+    waiting: Option<Task>,
+}
+
+/// The queue a running scan pulls its jobs from. Jobs pushed while it runs are ordered with the
+/// remaining ones, so a follow-up of a finding is sent before the rest of the brute force. The
+/// stream ends once the queue is closed and drained.
+pub struct JobQueue<T> {
+    state: Arc<Mutex<QueueState<T>>>,
+}
+
+impl<T> Clone for JobQueue<T> {
+    fn clone(&self) -> Self {
+        JobQueue {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T> Default for JobQueue<T> {
+    fn default() -> Self {
+        JobQueue {
+            state: Arc::new(Mutex::new(QueueState {
+                scheduler: Scheduler::default(),
+                closed: false,
+                waiting: None,
+            })),
+        }
+    }
+}
+
+impl<T> JobQueue<T> {
+    fn update<F: FnOnce(&mut QueueState<T>)>(&self, f: F) {
+        let mut state = self.state.lock().unwrap();
+        f(&mut state);
+        if let Some(task) = state.waiting.take() {
+            task.notify();
+        }
+    }
+
+    pub fn push(&self, kind: JobKind, weight: usize, item: T) {
+        self.update(|state| state.scheduler.push(kind, weight, item));
+    }
+
+    pub fn extend_weighted<F>(
+        &self,
+        items: Vec<T>,
+        follow_ups: usize,
+        hit_rates: &HitRates,
+        word: F,
+    ) where
+        F: Fn(&T) -> String,
+    {
+        self.update(|state| {
+            state
+                .scheduler
+                .extend_weighted(items, follow_ups, hit_rates, word)
+        });
+    }
+
+    /// No more jobs will be pushed
+    pub fn close(&self) {
+        self.update(|state| state.closed = true);
+    }
+
+    /// The jobs not handed to the scan yet
+    pub fn pending(&self) -> usize {
+        self.state.lock().unwrap().scheduler.heap.len()
+    }
+
+    pub fn peek(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.state.lock().unwrap().scheduler.peek().cloned()
+    }
+}
+
+impl<T> Stream for JobQueue<T> {
+    type Item = T;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Option<T>, ()> {
+        let mut state = self.state.lock().unwrap();
+        match state.scheduler.pop() {
+            Some(item) => Ok(Async::Ready(Some(item))),
+            None if state.closed => Ok(Async::Ready(None)),
+            None => {
+                state.waiting = Some(task::current());
+                Ok(Async::NotReady)
+            }
+        }
+    }
+}
+
+/// The word of a path, or the first label of a virtual host
+pub fn word_of(uri: &hyper::Uri) -> String {
+    let word = word_from_uri(uri);
+    if !word.is_empty() {
+        return word;
+    }
+
+    uri.host()
+        .and_then(|host| host.split('.').next())
+        .unwrap_or("")
+        .to_owned()
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HitRates {
+    hits: HashMap<String, usize>,
+}
+
+impl HitRates {
+    pub fn from_findings(findings: &BTreeMap<String, Vec<Value>>) -> Self {
+        let mut hit_rates = HitRates::default();
+        for result in findings.values().flatten() {
+            if let Some(uri) = result["url"].as_str().and_then(|v| v.parse().ok()) {
+                hit_rates.add(&word_of(&uri));
+            } else if let Some(name) = result["vhost"].as_str().or(result["domain"].as_str()) {
+                hit_rates.add(name.split('.').next().unwrap_or(""));
+            }
+        }
+
+        hit_rates
+    }
+
+    pub fn add(&mut self, word: &str) {
+        if !word.is_empty() {
+            *self.hits.entry(word.to_lowercase()).or_insert(0) += 1;
+        }
+    }
+
+    pub fn hits(&self, word: &str) -> usize {
+        self.hits.get(&word.to_lowercase()).cloned().unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hits.is_empty()
+    }
+}
+
+/// The order of a fixed set of URLs, for the modes that don't discover jobs while they run
+pub fn prioritize(
+    urls: Vec<hyper::Uri>,
+    follow_ups: usize,
+    hit_rates: &HitRates,
+) -> Vec<hyper::Uri> {
+    if hit_rates.is_empty() {
+        return urls;
+    }
+
+    let mut scheduler = Scheduler::default();
+    scheduler.extend_weighted(urls, follow_ups, hit_rates, word_of);
+    scheduler.collect()
+}
@@ -0,0 +1,282 @@
+use futures::{
+    future::{self, Either},
+    Future, Stream,
+};
+use hyper::{
+    header::{HeaderName, HeaderValue},
+    rt, Body, Client, HeaderMap, Request, Response, StatusCode,
+};
+use hyper_tls::HttpsConnector;
+use regex::Regex;
+use serde::Deserialize;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+
+use crate::proxy::ProxyConnector;
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct LoginRequest {
+    pub url: String,
+    #[serde(default = "default_method")]
+    pub method: String,
+    #[serde(default)]
+    pub headers: Vec<String>,
+    #[serde(default)]
+    pub body: String,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct SessionExtract {
+    #[serde(default)]
+    pub cookies: Vec<String>,
+    pub regex: Option<String>,
+    pub header: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct SessionExpired {
+    #[serde(default = "default_expired_status")]
+    pub status: Vec<u16>,
+    pub location: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct SessionConfig {
+    pub login: LoginRequest,
+    #[serde(default)]
+    pub extract: SessionExtract,
+    #[serde(default)]
+    pub expired: SessionExpired,
+}
+
+fn default_method() -> String {
+    "GET".to_owned()
+}
+
+fn default_expired_status() -> Vec<u16> {
+    vec![401]
+}
+
+impl Default for SessionExpired {
+    fn default() -> Self {
+        SessionExpired {
+            status: default_expired_status(),
+            location: None,
+        }
+    }
+}
+
+fn split_header(header: &str) -> Option<(String, String)> {
+    let index = header.find(':')?;
+    Some((
+        header[..index].trim().to_owned(),
+        header[index + 1..].trim().to_owned(),
+    ))
+}
+
+#[derive(Debug)]
+pub struct Session {
+    config: SessionConfig,
+    regex: Option<Regex>,
+    headers: Mutex<Vec<(String, String)>>,
+    generation: AtomicUsize,
+}
+
+impl Session {
+    pub fn new(config: SessionConfig) -> Result<Self, String> {
+        if let Err(e) = config.login.url.parse::<hyper::Uri>() {
+            return Err(format!("Invalid login URL: {}", e));
+        }
+
+        if let Some(header) = config.login.headers.iter().find(|h| !h.contains(':')) {
+            return Err(format!("Invalid login header: {}", header));
+        }
+
+        let regex = match &config.extract.regex {
+            Some(regex) => match Regex::new(regex) {
+                Ok(v) => Some(v),
+                Err(e) => return Err(format!("Invalid session regex: {}", e)),
+            },
+            None => None,
+        };
+
+        Ok(Session {
+            config,
+            regex,
+            headers: Mutex::new(Vec::new()),
+            generation: AtomicUsize::new(0),
+        })
+    }
+
+    pub fn parse(yaml: &str) -> Result<Self, String> {
+        match serde_yaml::from_str::<SessionConfig>(yaml) {
+            Ok(config) => Session::new(config),
+            Err(e) => Err(format!("Invalid session file: {}", e)),
+        }
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(yaml) => Session::parse(&yaml),
+            Err(e) => Err(format!("Unable to read the session file: {}\n{}", path, e)),
+        }
+    }
+
+    pub fn headers(&self) -> Vec<(String, String)> {
+        self.headers.lock().unwrap().clone()
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation.load(Ordering::SeqCst)
+    }
+
+    pub fn is_expired(&self, status: StatusCode, headers: &HeaderMap) -> bool {
+        if self.config.expired.status.contains(&status.as_u16()) {
+            return true;
+        }
+
+        match &self.config.expired.location {
+            Some(location) if status.is_redirection() => headers
+                .get("Location")
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.contains(location.as_str()))
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+
+    pub fn observe(&self, headers: &HeaderMap, body: &str) -> bool {
+        let mut session_headers = Vec::new();
+        let extract = &self.config.extract;
+        let cookies: Vec<String> = crate::fuzzbuster::utils::session_cookies(headers)
+            .unwrap_or_default()
+            .split("; ")
+            .filter(|cookie| {
+                let name = cookie.split('=').next().unwrap_or("");
+                !name.is_empty()
+                    && (extract.cookies.is_empty() || extract.cookies.iter().any(|c| c == name))
+            })
+            .map(|cookie| cookie.to_owned())
+            .collect();
+        if !cookies.is_empty() {
+            session_headers.push(("Cookie".to_owned(), cookies.join("; ")));
+        }
+
+        if let (Some(regex), Some(header)) = (&self.regex, &extract.header) {
+            if let Some(header) = crate::fuzzbuster::utils::extract_value(regex, body)
+                .and_then(|value| split_header(&header.replace("EXTRACT", &value)))
+            {
+                session_headers.push(header);
+            }
+        }
+
+        if session_headers.is_empty() {
+            return false;
+        }
+
+        *self.headers.lock().unwrap() = session_headers;
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        true
+    }
+
+    fn login_request(&self) -> Request<Body> {
+        let mut request_builder = Request::builder();
+        for header in self.config.login.headers.iter() {
+            if let Some((name, value)) = split_header(header) {
+                request_builder.header(name.as_str(), value.as_str());
+            }
+        }
+
+        request_builder
+            .method(&self.config.login.method[..])
+            .uri(&self.config.login.url[..])
+            .body(Body::from(self.config.login.body.clone()))
+            .expect("Request builder")
+    }
+
+    pub fn login(
+        self: Arc<Self>,
+        client: &Client<HttpsConnector<ProxyConnector>>,
+    ) -> impl Future<Item = bool, Error = hyper::Error> {
+        client.request(self.login_request()).and_then(move |res| {
+            let (parts, body) = res.into_parts();
+            body.concat2()
+                .map(move |body| self.observe(&parts.headers, &String::from_utf8_lossy(&body)))
+        })
+    }
+
+    /// Takes a dedicated client, it's dropped with the request so its idle connection doesn't
+    /// keep the runtime running
+    pub fn handshake(self: Arc<Self>, client: Client<HttpsConnector<ProxyConnector>>) {
+        let (tx, rx) = std::sync::mpsc::channel();
+        rt::run(
+            future::lazy(move || self.login(&client))
+                .map(move |logged_in| tx.send(logged_in).unwrap_or(()))
+                .map_err(|e| warn!("Login failed: {}", e)),
+        );
+
+        if !rx.try_recv().unwrap_or(false) {
+            warn!("The login response didn't contain a session, requests won't be authenticated");
+        }
+    }
+}
+
+fn with_session(
+    parts: &hyper::http::request::Parts,
+    body: &[u8],
+    session: &Session,
+) -> Request<Body> {
+    let mut request = Request::new(Body::from(body.to_vec()));
+    *request.method_mut() = parts.method.clone();
+    *request.uri_mut() = parts.uri.clone();
+    *request.headers_mut() = parts.headers.clone();
+    for (name, value) in session.headers() {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            request.headers_mut().insert(name, value);
+        }
+    }
+
+    request
+}
+
+pub fn send(
+    client: &Client<HttpsConnector<ProxyConnector>>,
+    session: Option<Arc<Session>>,
+    request: Request<Body>,
+    body: Vec<u8>,
+) -> impl Future<Item = Response<Body>, Error = hyper::Error> {
+    let session = match session {
+        Some(v) => v,
+        None => return Either::A(client.request(request)),
+    };
+
+    let (parts, _) = request.into_parts();
+    let generation = session.generation();
+    let client = client.clone();
+
+    Either::B(
+        client
+            .request(with_session(&parts, &body, &session))
+            .and_then(move |res| {
+                if !session.is_expired(res.status(), res.headers()) {
+                    return Either::A(futures::future::ok(res));
+                }
+
+                let login = if session.generation() != generation {
+                    Either::A(futures::future::ok(true))
+                } else {
+                    debug!("Session expired, logging in again");
+                    Either::B(session.clone().login(&client))
+                };
+
+                Either::B(
+                    login.and_then(move |_| client.request(with_session(&parts, &body, &session))),
+                )
+            }),
+    )
+}
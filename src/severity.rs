@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_RULES: &[&str] = &[
+    "info:status=401",
+    "info:status=403",
+    "info:status=3",
+    "high:path=/.git/",
+    "high:path=/.svn/",
+    "high:path=/.hg/",
+    "high:path=/.env",
+    "high:path=/.htpasswd",
+    "high:path=/id_rsa",
+    "high:path=.sql",
+    "high:path=.bak",
+    "high:path=.old",
+    "medium:body=<title>Index of /",
+    "medium:body=Directory listing for /",
+    "medium:path=/.DS_Store",
+    "medium:path=/server-status",
+    "medium:path=/phpinfo.php",
+];
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Matcher {
+    Path(String),
+    Status(String),
+    Body(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeverityRule {
+    pub severity: Severity,
+    pub matcher: Matcher,
+}
+
+#[derive(Debug, Clone)]
+pub struct Classifier {
+    rules: Vec<SeverityRule>,
+}
+
+impl Severity {
+    pub fn parse(v: &str) -> Option<Self> {
+        match v.to_lowercase().as_str() {
+            "info" => Some(Severity::Info),
+            "low" => Some(Severity::Low),
+            "medium" => Some(Severity::Medium),
+            "high" => Some(Severity::High),
+            _ => None,
+        }
+    }
+}
+
+pub fn parse_rule(rule: &str) -> Option<SeverityRule> {
+    let mut parts = rule.splitn(2, ':');
+    let severity = Severity::parse(parts.next()?.trim())?;
+    let mut condition = parts.next()?.splitn(2, '=');
+    let field = condition.next()?.trim();
+    let value = condition.next()?.to_owned();
+    let matcher = match field {
+        "path" => Matcher::Path(value),
+        "status" => Matcher::Status(value),
+        "body" => Matcher::Body(value),
+        _ => return None,
+    };
+
+    Some(SeverityRule { severity, matcher })
+}
+
+impl SeverityRule {
+    pub fn matches(&self, url: &str, status: &str, body: &str) -> bool {
+        match &self.matcher {
+            Matcher::Path(v) => {
+                let path = url.split(&['?', '#'][..]).next().unwrap_or("");
+                path.contains(v.as_str())
+            }
+            Matcher::Status(v) => status.starts_with(v.as_str()),
+            Matcher::Body(v) => body.contains(v.as_str()),
+        }
+    }
+}
+
+impl Classifier {
+    pub fn new(user_rules: Vec<SeverityRule>) -> Self {
+        let mut rules = user_rules;
+        rules.extend(DEFAULT_RULES.iter().filter_map(|rule| parse_rule(rule)));
+
+        Classifier { rules }
+    }
+
+    pub fn classify(&self, url: &str, status: &str, body: &str) -> Severity {
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(url, status, body))
+            .map(|rule| rule.severity)
+            .unwrap_or(Severity::Low)
+    }
+}
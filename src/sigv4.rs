@@ -0,0 +1,260 @@
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::{env, fs};
+
+use crate::digest::to_hex;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AwsCredentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+}
+
+impl AwsCredentials {
+    pub fn from_env() -> Option<Self> {
+        Some(AwsCredentials {
+            access_key_id: env::var("AWS_ACCESS_KEY_ID").ok()?,
+            secret_access_key: env::var("AWS_SECRET_ACCESS_KEY").ok()?,
+            session_token: env::var("AWS_SESSION_TOKEN").ok(),
+        })
+    }
+
+    pub fn from_profile(content: &str, profile: &str) -> Option<Self> {
+        let mut in_profile = false;
+        let mut access_key_id = None;
+        let mut secret_access_key = None;
+        let mut session_token = None;
+        for line in content.lines().map(|l| l.trim()) {
+            if line.starts_with('[') && line.ends_with(']') {
+                in_profile = line[1..line.len() - 1].trim() == profile;
+                continue;
+            }
+
+            if !in_profile {
+                continue;
+            }
+
+            let mut parts = line.splitn(2, '=');
+            let key = parts.next().unwrap_or("").trim();
+            let value = parts.next().unwrap_or("").trim().to_owned();
+            match key {
+                "aws_access_key_id" => access_key_id = Some(value),
+                "aws_secret_access_key" => secret_access_key = Some(value),
+                "aws_session_token" => session_token = Some(value),
+                _ => (),
+            }
+        }
+
+        Some(AwsCredentials {
+            access_key_id: access_key_id?,
+            secret_access_key: secret_access_key?,
+            session_token,
+        })
+    }
+
+    pub fn load() -> Result<Self, String> {
+        if let Some(credentials) = Self::from_env() {
+            return Ok(credentials);
+        }
+
+        let path = match env::var("AWS_SHARED_CREDENTIALS_FILE") {
+            Ok(v) => v,
+            Err(_) => format!("{}/.aws/credentials", env::var("HOME").unwrap_or_default()),
+        };
+        let profile = env::var("AWS_PROFILE").unwrap_or_else(|_| "default".to_owned());
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Unable to read the AWS credentials: {}\n{}", path, e))?;
+
+        Self::from_profile(&content, &profile)
+            .ok_or_else(|| format!("AWS profile {} not found in {}", profile, path))
+    }
+}
+
+fn uri_encode(value: &str, encode_slash: bool) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            b'/' if !encode_slash => "/".to_owned(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+pub fn uri_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = value
+            .get(i + 1..i + 3)
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(b)) => {
+                decoded.push(b);
+                i += 3;
+            }
+            (b, _) => {
+                decoded.push(b);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// The Host header of a request to the URI, the port is kept when it is explicit so the signed
+/// value matches the one sent
+pub fn host_header(uri: &hyper::Uri) -> Option<String> {
+    let host = uri.host()?;
+    Some(match uri.port_part() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.to_owned(),
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct SigV4 {
+    pub region: String,
+    pub service: String,
+    pub credentials: AwsCredentials,
+}
+
+impl SigV4 {
+    pub fn new(scope: &str, credentials: AwsCredentials) -> Result<Self, String> {
+        let mut parts = scope.splitn(2, '/');
+        match (parts.next(), parts.next()) {
+            (Some(region), Some(service)) if !region.is_empty() && !service.is_empty() => {
+                Ok(SigV4 {
+                    region: region.to_owned(),
+                    service: service.to_owned(),
+                    credentials,
+                })
+            }
+            _ => Err("The SigV4 scope must be in the form region/service".to_owned()),
+        }
+    }
+
+    fn canonical_query(query: &str) -> String {
+        let mut pairs: Vec<(String, String)> = query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let mut parts = pair.splitn(2, '=');
+                (
+                    uri_encode(&uri_decode(parts.next().unwrap_or("")), true),
+                    uri_encode(&uri_decode(parts.next().unwrap_or("")), true),
+                )
+            })
+            .collect();
+        pairs.sort();
+
+        pairs
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<String>>()
+            .join("&")
+    }
+
+    pub fn sign_at(
+        &self,
+        method: &str,
+        host: &str,
+        uri: &hyper::Uri,
+        body: &[u8],
+        now: DateTime<Utc>,
+    ) -> Vec<(String, String)> {
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let payload_hash = to_hex(&Sha256::digest(body));
+
+        let mut headers = vec![
+            ("host".to_owned(), host.to_owned()),
+            ("x-amz-date".to_owned(), amz_date.clone()),
+        ];
+        // S3 refuses requests without the payload hash, other services don't need it
+        if self.service == "s3" {
+            headers.push(("x-amz-content-sha256".to_owned(), payload_hash.clone()));
+        }
+        if let Some(token) = &self.credentials.session_token {
+            headers.push(("x-amz-security-token".to_owned(), token.to_owned()));
+        }
+        headers.sort();
+
+        let path = if uri.path().is_empty() {
+            "/"
+        } else {
+            uri.path()
+        };
+        let canonical_uri = if self.service == "s3" {
+            path.to_owned()
+        } else {
+            uri_encode(path, false)
+        };
+        let canonical_headers: String = headers
+            .iter()
+            .map(|(k, v)| format!("{}:{}\n", k, v.trim()))
+            .collect();
+        let signed_headers = headers
+            .iter()
+            .map(|(k, _)| k.as_str())
+            .collect::<Vec<&str>>()
+            .join(";");
+        let canonical_request = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            method,
+            canonical_uri,
+            Self::canonical_query(uri.query().unwrap_or("")),
+            canonical_headers,
+            signed_headers,
+            payload_hash
+        );
+
+        let scope = format!("{}/{}/{}/aws4_request", date, self.region, self.service);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            to_hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+        let key = format!("AWS4{}", self.credentials.secret_access_key);
+        let key = hmac_sha256(key.as_bytes(), date.as_bytes());
+        let key = hmac_sha256(&key, self.region.as_bytes());
+        let key = hmac_sha256(&key, self.service.as_bytes());
+        let key = hmac_sha256(&key, b"aws4_request");
+        let signature = to_hex(&hmac_sha256(&key, string_to_sign.as_bytes()));
+
+        let mut signed: Vec<(String, String)> =
+            headers.into_iter().filter(|(k, _)| k != "host").collect();
+        signed.push((
+            "Authorization".to_owned(),
+            format!(
+                "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+                self.credentials.access_key_id, scope, signed_headers, signature
+            ),
+        ));
+
+        signed
+    }
+
+    pub fn sign(
+        &self,
+        method: &str,
+        host: &str,
+        uri: &hyper::Uri,
+        body: &[u8],
+    ) -> Vec<(String, String)> {
+        self.sign_at(method, host, uri, body, Utc::now())
+    }
+}
@@ -0,0 +1,92 @@
+/// Receives the results of a scan one at a time, the per-mode processors are sinks keeping the
+/// results their filters accept
+pub trait ResultSink<T> {
+    fn accept(&self, _result: &T) -> bool {
+        true
+    }
+
+    fn transform(&mut self, result: T) -> T {
+        result
+    }
+
+    fn store(&mut self, result: T);
+
+    /// Stores the accepted results once transformed, true when the result was kept
+    fn maybe_add_result(&mut self, result: T) -> bool {
+        if !self.accept(&result) {
+            return false;
+        }
+
+        let result = self.transform(result);
+        self.store(result);
+        true
+    }
+}
+
+/// A mode processor followed by custom sinks, which only see the results it kept
+pub struct Pipeline<'a, P, T> {
+    pub processor: P,
+    sinks: Vec<Box<dyn ResultSink<T> + Send + 'a>>,
+}
+
+impl<'a, P: ResultSink<T>, T: Clone> Pipeline<'a, P, T> {
+    pub fn new(processor: P) -> Self {
+        Pipeline {
+            processor,
+            sinks: Vec::new(),
+        }
+    }
+
+    pub fn register(&mut self, sink: Box<dyn ResultSink<T> + Send + 'a>) -> &mut Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Drops the sinks, giving back the ones lent to the pipeline
+    pub fn into_processor(self) -> P {
+        self.processor
+    }
+}
+
+impl<'a, P: ResultSink<T>, T: Clone> ResultSink<T> for Pipeline<'a, P, T> {
+    fn store(&mut self, result: T) {
+        self.maybe_add_result(result);
+    }
+
+    fn maybe_add_result(&mut self, result: T) -> bool {
+        if !self.processor.maybe_add_result(result.clone()) {
+            return false;
+        }
+
+        for sink in self.sinks.iter_mut() {
+            sink.maybe_add_result(result.clone());
+        }
+        true
+    }
+}
+
+/// Lends a sink to a pipeline, the scan keeps it once the pipeline is done
+impl<T, S: ResultSink<T> + ?Sized> ResultSink<T> for &mut S {
+    fn accept(&self, result: &T) -> bool {
+        (**self).accept(result)
+    }
+
+    fn transform(&mut self, result: T) -> T {
+        (**self).transform(result)
+    }
+
+    fn store(&mut self, result: T) {
+        (**self).store(result)
+    }
+
+    fn maybe_add_result(&mut self, result: T) -> bool {
+        (**self).maybe_add_result(result)
+    }
+}
+
+/// Collects the results as they are, for library users keeping their own lists
+impl<T> ResultSink<T> for Vec<T> {
+    fn store(&mut self, result: T) {
+        self.push(result);
+    }
+}
@@ -0,0 +1,270 @@
+use futures::Stream;
+use hyper::{
+    rt::{self, Future},
+    Body, Client, Request,
+};
+use hyper_tls::HttpsConnector;
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc::channel;
+
+use crate::{
+    proxy::ProxyConnector,
+    xml::{local, tags},
+};
+
+#[derive(Debug, Clone)]
+pub struct SoapConfig {
+    pub ignore_certificate: bool,
+    pub user_agent: String,
+    pub http_headers: Vec<(String, String)>,
+    pub tls_min_version: Option<native_tls::Protocol>,
+    pub tls_max_version: Option<native_tls::Protocol>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct SoapOperation {
+    pub name: String,
+    pub action: String,
+    pub wrapper: String,
+    pub params: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct SoapService {
+    pub endpoint: String,
+    pub namespace: String,
+    pub operations: Vec<SoapOperation>,
+}
+
+type Message = (String, Vec<(String, Option<String>)>);
+
+pub fn parse_wsdl(xml: &str) -> Option<SoapService> {
+    let tags = tags(xml);
+    let root = tags.first()?;
+    if root.name != "definitions" {
+        return None;
+    }
+
+    let mut service = SoapService {
+        namespace: root.attr("targetNamespace").unwrap_or("").to_owned(),
+        ..SoapService::default()
+    };
+    let mut messages: Vec<Message> = Vec::new();
+    let mut elements: Vec<(String, Vec<String>)> = Vec::new();
+    let mut inputs: Vec<(String, String)> = Vec::new();
+    let mut actions: Vec<(String, String)> = Vec::new();
+    let mut section = "";
+    let mut current = String::new();
+    let mut element_depth = 0;
+
+    for tag in tags.iter() {
+        if tag.closing {
+            match tag.name.as_str() {
+                "element" if section == "schema" => element_depth -= 1,
+                "schema" | "message" | "portType" | "binding" => section = "",
+                _ => (),
+            }
+            continue;
+        }
+
+        match (section, tag.name.as_str()) {
+            (_, "schema") => section = "schema",
+            (_, "message") => {
+                messages.push((tag.attr("name").unwrap_or("").to_owned(), Vec::new()));
+                if !tag.empty {
+                    section = "message";
+                }
+            }
+            (_, "portType") => section = "portType",
+            (_, "binding") if tag.attr("type").is_some() => section = "binding",
+            ("schema", "element") => {
+                if let Some(name) = tag.attr("name") {
+                    match element_depth {
+                        0 => elements.push((name.to_owned(), Vec::new())),
+                        1 => elements.last_mut().unwrap().1.push(name.to_owned()),
+                        _ => (),
+                    }
+                }
+
+                if !tag.empty {
+                    element_depth += 1;
+                }
+            }
+            ("message", "part") => messages.last_mut().unwrap().1.push((
+                tag.attr("name").unwrap_or("").to_owned(),
+                tag.attr("element").map(|e| local(e).to_owned()),
+            )),
+            ("portType", "operation") => current = tag.attr("name").unwrap_or("").to_owned(),
+            ("portType", "input") => {
+                if let Some(message) = tag.attr("message") {
+                    inputs.push((current.to_owned(), local(message).to_owned()));
+                }
+            }
+            ("binding", "operation") => {
+                if let Some(name) = tag.attr("name") {
+                    current = name.to_owned();
+                }
+
+                if let Some(action) = tag.attr("soapAction") {
+                    actions.push((current.to_owned(), action.to_owned()));
+                }
+            }
+            (_, "address") if service.endpoint.is_empty() => {
+                service.endpoint = tag.attr("location").unwrap_or("").to_owned()
+            }
+            _ => (),
+        }
+    }
+
+    for (name, message) in inputs {
+        if service.operations.iter().any(|op| op.name == name) {
+            continue;
+        }
+
+        let mut operation = SoapOperation {
+            action: actions
+                .iter()
+                .find(|(op, _)| *op == name)
+                .map(|(_, action)| action.to_owned())
+                .unwrap_or_default(),
+            wrapper: name.to_owned(),
+            name,
+            params: Vec::new(),
+        };
+
+        let parts = messages
+            .iter()
+            .find(|(n, _)| *n == message)
+            .map(|(_, parts)| parts.clone())
+            .unwrap_or_default();
+        for (part, element) in parts {
+            match element {
+                Some(element) => {
+                    operation.params.extend(
+                        elements
+                            .iter()
+                            .find(|(n, _)| *n == element)
+                            .map(|(_, children)| children.clone())
+                            .unwrap_or_default(),
+                    );
+                    operation.wrapper = element;
+                }
+                None => operation.params.push(part),
+            }
+        }
+
+        service.operations.push(operation);
+    }
+
+    Some(service)
+}
+
+pub fn wsdl_url(url: &str) -> String {
+    if url.to_lowercase().contains("wsdl") {
+        url.to_owned()
+    } else if url.contains('?') {
+        format!("{}&wsdl", url)
+    } else {
+        format!("{}?wsdl", url)
+    }
+}
+
+pub fn endpoint_url(url: &str, location: &str) -> String {
+    let base = url.split('?').next().unwrap_or(url);
+    let (uri, location) = match (base.parse::<hyper::Uri>(), location.parse::<hyper::Uri>()) {
+        (Ok(uri), Ok(location)) => (uri, location),
+        _ => return base.to_owned(),
+    };
+
+    match (
+        uri.scheme_part(),
+        uri.authority_part(),
+        location.path_and_query(),
+    ) {
+        (Some(scheme), Some(authority), Some(path)) if !location.path().is_empty() => {
+            format!("{}://{}{}", scheme, authority, path)
+        }
+        _ => base.to_owned(),
+    }
+}
+
+pub fn request_body(namespace: &str, operation: &SoapOperation, fuzz_params: &[String]) -> String {
+    let params: String = operation
+        .params
+        .iter()
+        .enumerate()
+        .map(|(i, param)| {
+            let fuzz = if fuzz_params.is_empty() {
+                i == 0
+            } else {
+                fuzz_params.contains(param)
+            };
+
+            format!(
+                "<tns:{0}>{1}</tns:{0}>",
+                param,
+                if fuzz { "FUZZ" } else { "?" }
+            )
+        })
+        .collect();
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\
+         <soap:Envelope xmlns:soap=\"http://schemas.xmlsoap.org/soap/envelope/\" xmlns:tns=\"{}\">\
+         <soap:Body><tns:{2}>{1}</tns:{2}></soap:Body></soap:Envelope>",
+        namespace, params, operation.wrapper
+    )
+}
+
+pub fn fetch_wsdl(url: &str, config: &SoapConfig) -> Option<String> {
+    let mut tls_connector_builder = native_tls::TlsConnector::builder();
+    tls_connector_builder.danger_accept_invalid_certs(config.ignore_certificate);
+    tls_connector_builder.min_protocol_version(config.tls_min_version);
+    tls_connector_builder.max_protocol_version(config.tls_max_version);
+    let tls_connector = tls_connector_builder
+        .build()
+        .expect("TLS initialization failed");
+    let http_connector = ProxyConnector::new(1);
+    let https_connector = HttpsConnector::from((http_connector, tls_connector));
+    let client = Client::builder().build::<_, Body>(https_connector);
+
+    let mut request_builder = Request::builder();
+    for header_tuple in &config.http_headers {
+        request_builder.header(header_tuple.0.as_str(), header_tuple.1.as_str());
+    }
+
+    let request = request_builder
+        .header("User-Agent", &config.user_agent[..])
+        .method("GET")
+        .uri(url)
+        .body(Body::empty())
+        .expect("Request builder");
+
+    let (tx, rx) = channel::<String>();
+    rt::run(
+        client
+            .request(request)
+            .and_then(|res| res.into_body().concat2())
+            .map(move |body| {
+                tx.send(String::from_utf8_lossy(&body).into_owned())
+                    .unwrap_or(());
+            })
+            .map_err(|e| debug!("WSDL request failed: {}", e)),
+    );
+
+    rx.try_recv().ok()
+}
+
+pub fn report(service: &SoapService) -> String {
+    let mut lines = vec![format!("[+] Endpoint\t: {}", service.endpoint)];
+    for operation in service.operations.iter() {
+        lines.push(format!(
+            "[+] {}({})\t=> SOAPAction: {:?}",
+            operation.name,
+            operation.params.join(", "),
+            operation.action
+        ));
+    }
+
+    lines.join("\n")
+}
@@ -0,0 +1,57 @@
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// The name of one line of amass or subfinder output, in text or JSON lines
+fn name(line: &str) -> Option<String> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let name = if line.starts_with('{') {
+        // subfinder -oJ has "host", amass -json has "name"
+        let value: Value = serde_json::from_str(line).ok()?;
+        value["host"]
+            .as_str()
+            .or_else(|| value["name"].as_str())?
+            .to_owned()
+    } else {
+        // amass -src prefixes the sources, amass -ip and subfinder -cs append the addresses or sources
+        let line = match (line.starts_with('['), line.find(']')) {
+            (true, Some(i)) => &line[i + 1..],
+            _ => line,
+        };
+        line.split(|c: char| c.is_whitespace() || c == ',')
+            .find(|token| !token.is_empty())?
+            .to_owned()
+    };
+
+    let name = name
+        .trim_start_matches("*.")
+        .trim_end_matches('.')
+        .to_lowercase();
+    if name.is_empty() {
+        None
+    } else {
+        Some(name)
+    }
+}
+
+/// The unique names of the file, outside of the domain ones dropped when it is given
+pub fn parse(content: &str, domain: &str) -> Vec<String> {
+    let domain = domain.trim_end_matches('.').to_lowercase();
+    let suffix = format!(".{}", domain);
+    let mut seen = HashSet::new();
+    let mut names: Vec<String> = Vec::new();
+    for name in content.lines().filter_map(name) {
+        if !domain.is_empty() && name != domain && !name.ends_with(&suffix) {
+            trace!("{} is out of scope", name);
+            continue;
+        }
+        if seen.insert(name.to_owned()) {
+            names.push(name);
+        }
+    }
+
+    names
+}
@@ -0,0 +1,267 @@
+use std::sync::Mutex;
+
+use crate::dirbuster::crawler::resolve_link;
+
+pub const SECURITY_HEADERS: &[&str] = &[
+    "Strict-Transport-Security",
+    "Content-Security-Policy",
+    "X-Frame-Options",
+    "X-Content-Type-Options",
+    "Referrer-Policy",
+    "Permissions-Policy",
+];
+
+#[derive(Debug, Clone, Default)]
+pub struct AuthSurface {
+    pub unauthorized: Vec<(String, String)>,
+    pub forbidden: Vec<String>,
+}
+
+impl AuthSurface {
+    pub fn maybe_add(&mut self, url: &str, status: &str, www_authenticate: Option<&str>) {
+        if status.starts_with("401") {
+            let scheme = www_authenticate
+                .and_then(|v| v.split_whitespace().next())
+                .unwrap_or("unknown");
+            self.unauthorized.push((url.to_owned(), scheme.to_owned()));
+        } else if status.starts_with("403") {
+            self.forbidden.push(url.to_owned());
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.unauthorized.is_empty() && self.forbidden.is_empty()
+    }
+
+    pub fn report(&self) -> String {
+        let mut report = "\n[+] Authentication surface".to_owned();
+        if !self.unauthorized.is_empty() {
+            report.push_str(&format!(
+                "\n[+] Authentication required\t: {}",
+                self.unauthorized.len()
+            ));
+            for (url, scheme) in self.unauthorized.iter() {
+                report.push_str(&format!("\n\t{}\t{}", scheme, url));
+            }
+        }
+
+        if !self.forbidden.is_empty() {
+            report.push_str(&format!("\n[+] Forbidden\t\t\t: {}", self.forbidden.len()));
+            for url in self.forbidden.iter() {
+                report.push_str(&format!("\n\t{}", url));
+            }
+        }
+
+        report
+    }
+}
+
+#[derive(Debug)]
+struct HostAudit {
+    host: String,
+    responses: usize,
+    present: Vec<usize>,
+    values: Vec<Vec<String>>,
+}
+
+#[derive(Debug, Default)]
+pub struct HeaderAudit {
+    hosts: Mutex<Vec<HostAudit>>,
+}
+
+impl HeaderAudit {
+    pub fn observe(&self, uri: &hyper::Uri, headers: &hyper::HeaderMap) {
+        let host = match uri.authority_part() {
+            Some(v) => v.as_str().to_owned(),
+            None => return,
+        };
+
+        let mut hosts = self.hosts.lock().unwrap();
+        let i = match hosts.iter().position(|h| h.host == host) {
+            Some(i) => i,
+            None => {
+                hosts.push(HostAudit {
+                    host,
+                    responses: 0,
+                    present: vec![0; SECURITY_HEADERS.len()],
+                    values: vec![Vec::new(); SECURITY_HEADERS.len()],
+                });
+                hosts.len() - 1
+            }
+        };
+
+        let audit = &mut hosts[i];
+        audit.responses += 1;
+        for (j, name) in SECURITY_HEADERS.iter().enumerate() {
+            if let Some(value) = headers.get(*name) {
+                audit.present[j] += 1;
+                let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+                if !audit.values[j].contains(&value) {
+                    audit.values[j].push(value);
+                }
+            }
+        }
+    }
+
+    pub fn report(&self) -> String {
+        let mut report = "\n[+] Security headers audit".to_owned();
+        for audit in self.hosts.lock().unwrap().iter() {
+            report.push_str(&format!(
+                "\n[+] {}\t\t: {} responses",
+                audit.host, audit.responses
+            ));
+            for (j, name) in SECURITY_HEADERS.iter().enumerate() {
+                let missing = audit.responses - audit.present[j];
+                let status = if audit.present[j] == 0 {
+                    "missing".to_owned()
+                } else if missing > 0 {
+                    format!("{} (missing in {})", audit.values[j].join(" | "), missing)
+                } else {
+                    audit.values[j].join(" | ")
+                };
+                report.push_str(&format!("\n\t{}: {}", name, status));
+            }
+        }
+
+        report
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RedirectGroups {
+    pub groups: Vec<(String, usize)>,
+}
+
+impl RedirectGroups {
+    pub fn normalize(url: &str, location: &str) -> String {
+        url.parse::<hyper::Uri>()
+            .ok()
+            .and_then(|uri| resolve_link(&uri, location))
+            .map(|uri| uri.to_string())
+            .unwrap_or_else(|| location.to_owned())
+    }
+
+    pub fn add(&mut self, url: &str, location: &str) -> bool {
+        let target = Self::normalize(url, location);
+        match self.groups.iter_mut().find(|(t, _)| *t == target) {
+            Some((_, count)) => {
+                *count += 1;
+                false
+            }
+            None => {
+                self.groups.push((target, 1));
+                true
+            }
+        }
+    }
+
+    pub fn collapsed(&self) -> usize {
+        self.groups.iter().map(|(_, count)| count - 1).sum()
+    }
+
+    pub fn report(&self) -> String {
+        let mut report = "\n[+] Redirect groups".to_owned();
+        for (target, count) in self.groups.iter().filter(|(_, count)| *count > 1) {
+            report.push_str(&format!("\n\t{} paths redirect to {}", count, target));
+        }
+
+        report
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorCategory {
+    Dns,
+    ConnectionRefused,
+    Tls,
+    Timeout,
+    ServerError,
+    Other,
+}
+
+const ERROR_CATEGORIES: [ErrorCategory; 6] = [
+    ErrorCategory::Dns,
+    ErrorCategory::ConnectionRefused,
+    ErrorCategory::Tls,
+    ErrorCategory::Timeout,
+    ErrorCategory::ServerError,
+    ErrorCategory::Other,
+];
+
+impl ErrorCategory {
+    pub fn of(error: &str) -> Self {
+        let error = error.to_lowercase();
+        if error.contains("lookup address") || error.contains("dns") || error.contains("resolve") {
+            ErrorCategory::Dns
+        } else if error.contains("connection refused") {
+            ErrorCategory::ConnectionRefused
+        } else if error.contains("tls")
+            || error.contains("ssl")
+            || error.contains("certificate")
+            || error.contains("handshake")
+        {
+            ErrorCategory::Tls
+        } else if error.contains("timed out") || error.contains("timeout") {
+            ErrorCategory::Timeout
+        } else {
+            ErrorCategory::Other
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ErrorCategory::Dns => "DNS failure",
+            ErrorCategory::ConnectionRefused => "Connection refused",
+            ErrorCategory::Tls => "TLS error",
+            ErrorCategory::Timeout => "Timeout",
+            ErrorCategory::ServerError => "Server error (5xx)",
+            ErrorCategory::Other => "Other",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ErrorSummary {
+    counts: [usize; 6],
+}
+
+impl ErrorSummary {
+    pub fn add_error(&mut self, error: &str) {
+        self.add(ErrorCategory::of(error));
+    }
+
+    pub fn add_status(&mut self, status: &str) {
+        if status.starts_with('5') {
+            self.add(ErrorCategory::ServerError);
+        }
+    }
+
+    fn add(&mut self, category: ErrorCategory) {
+        if let Some(i) = ERROR_CATEGORIES.iter().position(|c| *c == category) {
+            self.counts[i] += 1;
+        }
+    }
+
+    pub fn count(&self, category: ErrorCategory) -> usize {
+        ERROR_CATEGORIES
+            .iter()
+            .position(|c| *c == category)
+            .map_or(0, |i| self.counts[i])
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.iter().all(|n| *n == 0)
+    }
+
+    pub fn report(&self) -> String {
+        let mut report = "\n[+] Errors".to_owned();
+        for category in ERROR_CATEGORIES.iter() {
+            let count = self.count(*category);
+            if count > 0 {
+                report.push_str(&format!("\n\t{:<20}: {}", category.label(), count));
+            }
+        }
+
+        report
+    }
+}
@@ -0,0 +1,107 @@
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const RAND_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+pub fn random_u64() -> u64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0);
+    let mut z = nanos.wrapping_add(
+        (COUNTER.fetch_add(1, Ordering::SeqCst) as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15),
+    );
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+fn random_string(len: usize) -> String {
+    (0..len)
+        .map(|_| RAND_CHARS[(random_u64() % RAND_CHARS.len() as u64) as usize] as char)
+        .collect()
+}
+
+fn uuid() -> String {
+    let hex = format!("{:016x}{:016x}", random_u64(), random_u64());
+    format!(
+        "{}-{}-4{}-{:x}{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[13..16],
+        8 + (random_u64() % 4),
+        &hex[17..20],
+        &hex[20..32]
+    )
+}
+
+fn expand_variable(name: &str, word: &str) -> Option<String> {
+    match name {
+        "word" => Some(word.to_owned()),
+        "timestamp" => Some(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0)
+                .to_string(),
+        ),
+        "uuid" => Some(uuid()),
+        _ => {
+            let len = name
+                .strip_prefix("rand(")?
+                .strip_suffix(')')?
+                .trim()
+                .parse::<usize>()
+                .ok()?;
+            Some(random_string(len))
+        }
+    }
+}
+
+pub fn expand(input: &str, word: &str) -> String {
+    if !input.contains("{{") {
+        return input.to_owned();
+    }
+
+    let mut expanded = String::new();
+    let mut rest = input;
+    while let Some(start) = rest.find("{{") {
+        expanded.push_str(&rest[..start]);
+        let end = match rest[start..].find("}}") {
+            Some(i) => start + i,
+            None => {
+                expanded.push_str(&rest[start..]);
+                return expanded;
+            }
+        };
+
+        match expand_variable(rest[start + 2..end].trim(), word) {
+            Some(v) => expanded.push_str(&v),
+            None => expanded.push_str(&rest[start..end + 2]),
+        }
+        rest = &rest[end + 2..];
+    }
+    expanded.push_str(rest);
+
+    expanded
+}
+
+pub fn expand_bytes(input: &[u8], word: &str) -> Vec<u8> {
+    match std::str::from_utf8(input) {
+        Ok(v) if v.contains("{{") => expand(v, word).into_bytes(),
+        _ => input.to_vec(),
+    }
+}
+
+pub fn word_from_uri(uri: &hyper::Uri) -> String {
+    uri.path()
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or("")
+        .to_owned()
+}
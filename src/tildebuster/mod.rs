@@ -1,6 +1,5 @@
 use futures::Stream;
 use hyper::{
-    client::HttpConnector,
     rt::{self, Future},
     Body, Client, Request,
 };
@@ -14,6 +13,10 @@ use futures::sync::mpsc;
 
 pub mod result_processor;
 
+use crate::{
+    diff::KnownResults, output::Journal, proxy::ProxyConnector, sink::ResultSink,
+    summary::ErrorSummary,
+};
 use result_processor::{FSObject, SingleTildeScanResult, TildeRequest, TildeScanProcessor};
 
 use std::time::SystemTime;
@@ -32,6 +35,10 @@ pub struct TildeBuster {
     pub no_progress_bar: bool,
     pub exit_on_connection_errors: bool,
     pub output: String,
+    pub output_append: bool,
+    pub known: String,
+    pub tls_min_version: Option<native_tls::Protocol>,
+    pub tls_max_version: Option<native_tls::Protocol>,
     pub extension: Option<String>,
 }
 
@@ -52,15 +59,16 @@ pub enum IISVersion {
 }
 
 impl TildeBuster {
-    pub fn run(mut self) {
+    pub fn run(mut self) -> Vec<SingleTildeScanResult> {
         let (tx, rx) = channel::<SingleTildeScanResult>();
         let mut tls_connector_builder = native_tls::TlsConnector::builder();
         tls_connector_builder.danger_accept_invalid_certs(self.ignore_certificate);
+        tls_connector_builder.min_protocol_version(self.tls_min_version);
+        tls_connector_builder.max_protocol_version(self.tls_max_version);
         let tls_connector = tls_connector_builder
             .build()
             .expect("TLS initialization failed");
-        let mut http_connector = HttpConnector::new(self.n_threads);
-        http_connector.enforce_http(false);
+        let http_connector = ProxyConnector::new(self.n_threads);
         let https_connector = HttpsConnector::from((http_connector, tls_connector));
         let client = Client::builder().build(https_connector);
         let mut current_numbers_of_request = 0;
@@ -76,7 +84,11 @@ impl TildeBuster {
             .collect::<Vec<String>>();
         let start_time = SystemTime::now();
         let mut result_processor = TildeScanProcessor::new();
+        let mut journal = Journal::open(&self.output);
+        let mut errors = ErrorSummary::default();
+        let mut known_results = KnownResults::load(&self.known);
         let output = self.output.clone();
+        let output_append = self.output_append;
         let no_progress_bar = self.no_progress_bar;
         let exit_on_connection_errors = self.exit_on_connection_errors;
         let bar = if self.no_progress_bar {
@@ -154,7 +166,9 @@ impl TildeBuster {
             match &msg.error {
                 Some(e) => {
                     error!("{} - {:?}", msg.request.url, e);
+                    errors.add_error(e);
                     if current_numbers_of_request == 1 || exit_on_connection_errors {
+                        crate::exit_code::abort();
                         warn!("Check connectivity to the target");
                         break;
                     }
@@ -194,7 +208,9 @@ impl TildeBuster {
                         trace!("{:?}", msg);
                     }
                     FSObject::DuplicateFile => {
-                        if no_progress_bar {
+                        if known_results.is_known(&msg) {
+                            trace!("known: {:?}", msg.request);
+                        } else if no_progress_bar {
                             println!(
                                 "File\t\t{}~{}.{}",
                                 msg.request.filename,
@@ -210,10 +226,13 @@ impl TildeBuster {
                             ));
                         }
 
+                        journal.record(&msg);
                         result_processor.maybe_add_result(msg);
                     }
                     FSObject::DuplicateDirectory => {
-                        if no_progress_bar {
+                        if known_results.is_known(&msg) {
+                            trace!("known: {:?}", msg.request);
+                        } else if no_progress_bar {
                             println!(
                                 "Directory\t{}~{}",
                                 msg.request.filename, msg.request.duplicate_index,
@@ -225,10 +244,13 @@ impl TildeBuster {
                             ));
                         }
 
+                        journal.record(&msg);
                         result_processor.maybe_add_result(msg);
                     }
                     FSObject::File => {
-                        if no_progress_bar {
+                        if known_results.is_known(&msg) {
+                            trace!("known: {:?}", msg.request);
+                        } else if no_progress_bar {
                             println!(
                                 "File\t\t{}~{}.{}",
                                 msg.request.filename,
@@ -257,10 +279,13 @@ impl TildeBuster {
                             spawned_futures = spawned_futures + 1;
                         }
 
+                        journal.record(&msg);
                         result_processor.maybe_add_result(msg);
                     }
                     FSObject::Directory => {
-                        if no_progress_bar {
+                        if known_results.is_known(&msg) {
+                            trace!("known: {:?}", msg.request);
+                        } else if no_progress_bar {
                             println!(
                                 "Directory\t{}~{}",
                                 msg.request.filename, msg.request.duplicate_index
@@ -285,6 +310,7 @@ impl TildeBuster {
                             spawned_futures = spawned_futures + 1;
                         }
 
+                        journal.record(&msg);
                         result_processor.maybe_add_result(msg);
                     }
                     FSObject::BruteExtension => {
@@ -330,16 +356,27 @@ impl TildeBuster {
         }
 
         bar.finish();
-        println!("{}", crate::banner::ending_time());
+        if !errors.is_empty() {
+            crate::say!("{}", errors.report());
+        }
+
+        if known_results.skipped > 0 {
+            crate::say!("{}", crate::banner::known_results(known_results.skipped));
+        }
+
+        crate::say!("{}", crate::banner::ending_time());
 
         if !output.is_empty() {
-            result_processor.save_tilde_results(&output);
+            result_processor.save_tilde_results(&output, output_append);
         }
+        journal.finish();
+
+        result_processor.results
     }
 
     fn _brute_extension(
         tx: Sender<SingleTildeScanResult>,
-        client: Client<HttpsConnector<HttpConnector>>,
+        client: Client<HttpsConnector<ProxyConnector>>,
         request: TildeRequest,
     ) -> impl Future<Item = (), Error = ()> {
         let magic_suffix = match &request.redirect_extension {
@@ -407,7 +444,7 @@ impl TildeBuster {
 
     fn _brute_filename(
         tx: Sender<SingleTildeScanResult>,
-        client: Client<HttpsConnector<HttpConnector>>,
+        client: Client<HttpsConnector<ProxyConnector>>,
         request: TildeRequest,
     ) -> impl Future<Item = (), Error = ()> {
         let magic_suffix = match &request.redirect_extension {
@@ -490,7 +527,7 @@ impl TildeBuster {
 
     fn _check_if_directory(
         tx: Sender<SingleTildeScanResult>,
-        client: Client<HttpsConnector<HttpConnector>>,
+        client: Client<HttpsConnector<ProxyConnector>>,
         request: TildeRequest,
     ) -> impl Future<Item = (), Error = ()> {
         let magic_suffix = match &request.redirect_extension {
@@ -541,7 +578,7 @@ impl TildeBuster {
     }
 
     pub fn check_iis_version(
-        client: &Client<HttpsConnector<HttpConnector>>,
+        client: &Client<HttpsConnector<ProxyConnector>>,
         request: TildeRequest,
     ) -> impl Future<Item = IISVersion, Error = hyper::Error> {
         let hyper_request = Request::builder()
@@ -577,7 +614,7 @@ impl TildeBuster {
     }
 
     pub fn check_if_vulnerable(
-        client: &Client<HttpsConnector<HttpConnector>>,
+        client: &Client<HttpsConnector<ProxyConnector>>,
         request: TildeRequest,
         _version: IISVersion,
     ) -> impl Future<Item = bool, Error = hyper::Error> {
@@ -641,7 +678,7 @@ impl TildeBuster {
 
     pub fn _run_checks(
         tx: Sender<SingleTildeScanResult>,
-        client: Client<HttpsConnector<HttpConnector>>,
+        client: Client<HttpsConnector<ProxyConnector>>,
         request: TildeRequest,
     ) -> impl Future<Item = (), Error = ()> {
         TildeBuster::check_iis_version(&client, request.clone())
@@ -680,7 +717,7 @@ impl TildeBuster {
 
     pub fn _brute_duplicate(
         tx: Sender<SingleTildeScanResult>,
-        client: Client<HttpsConnector<HttpConnector>>,
+        client: Client<HttpsConnector<ProxyConnector>>,
         request: TildeRequest,
     ) -> impl Future<Item = (), Error = ()> {
         let vuln_url = match (&request.extension.len(), &request.redirect_extension) {
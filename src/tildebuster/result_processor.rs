@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::{fs::File, io::Write, path::Path, str};
 
+use crate::{output::merge_results, sink::ResultSink};
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum FSObject {
     File,
@@ -47,14 +49,21 @@ impl TildeScanProcessor {
         }
     }
 
-    pub fn maybe_add_result(&mut self, res: SingleTildeScanResult) -> bool {
-        trace!("{:?}", res);
-        self.results.push(res);
-        return true;
-    }
-
-    pub fn save_tilde_results(&self, path: &str) {
-        let json_string = serde_json::to_string(&self.results).unwrap();
+    pub fn save_tilde_results(&self, path: &str, append: bool) {
+        let json_string = if append {
+            serde_json::to_string(&merge_results(path, &self.results, |r| {
+                format!(
+                    "{}{}~{}.{}",
+                    r.request.url,
+                    r.request.filename,
+                    r.request.duplicate_index,
+                    r.request.extension
+                )
+            }))
+            .unwrap()
+        } else {
+            serde_json::to_string(&self.results).unwrap()
+        };
 
         let mut file = match File::create(Path::new(path)) {
             Ok(f) => f,
@@ -70,3 +79,10 @@ impl TildeScanProcessor {
         };
     }
 }
+
+impl ResultSink<SingleTildeScanResult> for TildeScanProcessor {
+    fn store(&mut self, res: SingleTildeScanResult) {
+        trace!("{:?}", res);
+        self.results.push(res);
+    }
+}
@@ -0,0 +1,44 @@
+use native_tls::Protocol;
+
+/// From the oldest to the newest, the order is what the range check compares
+const VERSIONS: &[(&str, Protocol)] = &[
+    ("1.0", Protocol::Tlsv10),
+    ("1.1", Protocol::Tlsv11),
+    ("1.2", Protocol::Tlsv12),
+    ("1.3", Protocol::Tlsv13),
+];
+
+fn position(version: &str) -> Result<usize, String> {
+    VERSIONS
+        .iter()
+        .position(|(name, _)| *name == version.trim())
+        .ok_or_else(|| {
+            format!(
+                "Unknown TLS version {}, expected one of 1.0, 1.1, 1.2 or 1.3",
+                version
+            )
+        })
+}
+
+/// The bounds handed to the TLS connector, a missing one leaves the backend default. The cipher
+/// suites are always the backend defaults, native-tls has no API to select them
+pub fn version_range(
+    min: Option<&str>,
+    max: Option<&str>,
+) -> Result<(Option<Protocol>, Option<Protocol>), String> {
+    let min_position = min.map(position).transpose()?;
+    let max_position = max.map(position).transpose()?;
+    if let (Some(min), Some(max)) = (min_position, max_position) {
+        if min > max {
+            return Err(format!(
+                "The minimum TLS version {} is above the maximum {}",
+                VERSIONS[min].0, VERSIONS[max].0
+            ));
+        }
+    }
+
+    Ok((
+        min_position.map(|i| VERSIONS[i].1),
+        max_position.map(|i| VERSIONS[i].1),
+    ))
+}
@@ -0,0 +1,43 @@
+use futures::{future::Map, Future};
+use hyper::{
+    client::connect::{Connect, Connected, Destination},
+    Uri,
+};
+
+#[derive(Clone)]
+pub struct ProxyFormConnector<C> {
+    inner: C,
+    target: Uri,
+}
+
+impl<C> ProxyFormConnector<C> {
+    pub fn new(inner: C, target: Uri) -> Self {
+        ProxyFormConnector { inner, target }
+    }
+}
+
+type Connection<C> = (<C as Connect>::Transport, Connected);
+
+fn proxied<T>((transport, connected): (T, Connected)) -> (T, Connected) {
+    (transport, connected.proxy(true))
+}
+
+impl<C: Connect> Connect for ProxyFormConnector<C> {
+    type Transport = C::Transport;
+    type Error = C::Error;
+    type Future = Map<C::Future, fn(Connection<C>) -> Connection<C>>;
+
+    fn connect(&self, mut dst: Destination) -> Self::Future {
+        if let Some(scheme) = self.target.scheme_str() {
+            dst.set_scheme(scheme).expect("Valid target scheme");
+        }
+        if let Some(host) = self.target.host() {
+            dst.set_host(host).expect("Valid target host");
+        }
+        dst.set_port(self.target.port_u16());
+
+        self.inner
+            .connect(dst)
+            .map(proxied as fn(Connection<C>) -> Connection<C>)
+    }
+}
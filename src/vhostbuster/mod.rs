@@ -1,18 +1,39 @@
-use futures::Stream;
+use chrono::Local;
+use futures::{future::Either, Stream};
 use hyper::{
-    client::HttpConnector,
+    client::connect::Connect,
     rt::{self, Future},
     Body, Client, Request, StatusCode, Uri,
 };
 use hyper_tls::{self, HttpsConnector};
 use native_tls;
 
-use std::sync::{mpsc::Sender, Arc, Mutex};
+use std::sync::{
+    mpsc::{channel, Sender},
+    Arc, Mutex,
+};
 
+pub mod connector;
 pub mod result_processor;
+pub mod sni;
 pub mod utils;
 
+use crate::{
+    banner,
+    digest::DigestAuth,
+    geoip::remote_ip,
+    har::{HarEntry, HarRequest, HarResponse},
+    negotiate::{self, Negotiate, SecurityContext},
+    output::capture_headers,
+    proxy::ProxyConnector,
+    ratelimit::Pacer,
+    summary::HeaderAudit,
+    template::{expand, expand_bytes},
+};
+use connector::ProxyFormConnector;
 use result_processor::SingleVhostScanResult;
+use sni::SniProbe;
+use utils::{baseline_host, html_title, ResponseSignature};
 
 #[derive(Debug, Clone)]
 pub struct VhostConfig {
@@ -21,44 +42,204 @@ pub struct VhostConfig {
     pub ignore_strings: Vec<String>,
     pub user_agent: String,
     pub http_method: String,
+    pub http_body: Vec<u8>,
     pub original_url: String,
+    pub save_har: bool,
+    pub tls_min_version: Option<native_tls::Protocol>,
+    pub tls_max_version: Option<native_tls::Protocol>,
+    pub digest: Option<(String, String)>,
+    pub capture_headers: Vec<String>,
+    pub header_audit: Option<Arc<HeaderAudit>>,
+    pub negotiate: Option<Arc<Negotiate>>,
+    pub absolute_uri: bool,
+}
+
+fn absolute_uri(original_url: &str, host: &str) -> String {
+    let path = original_url
+        .parse::<Uri>()
+        .ok()
+        .and_then(|uri| uri.path_and_query().map(|p| p.as_str().to_owned()))
+        .unwrap_or_else(|| "/".to_owned());
+
+    format!("http://{}{}", host, path)
 }
 
-fn make_request_future(
+fn build_request(
+    config: &VhostConfig,
+    host: &str,
+    digest: &Option<Arc<DigestAuth>>,
+    absolute: bool,
+) -> (Request<Body>, Option<SecurityContext>) {
+    let mut request_builder = Request::builder();
+    let original_url = expand(&config.original_url, host);
+    let uri = if absolute {
+        absolute_uri(&original_url, host)
+    } else {
+        original_url.to_owned()
+    };
+    let http_body = expand_bytes(&config.http_body, host);
+    if let Some(authorization) = digest.as_ref().and_then(|d| {
+        original_url
+            .parse::<Uri>()
+            .ok()
+            .and_then(|uri| d.authorization(&config.http_method, &uri))
+    }) {
+        request_builder.header("Authorization", authorization.as_str());
+    }
+
+    let context = config
+        .negotiate
+        .as_ref()
+        .and_then(|n| n.authorization(host));
+    if let Some(context) = &context {
+        request_builder.header("Authorization", context.authorization().as_str());
+    }
+
+    let request = request_builder
+        .header("User-Agent", &config.user_agent[..])
+        .method(&config.http_method[..])
+        .uri(&uri)
+        .header("Host", host)
+        .body(Body::from(http_body))
+        .expect("Request builder");
+
+    (request, context)
+}
+
+fn learn_baseline<C: Connect + 'static>(
+    client: &Client<C>,
+    config: &VhostConfig,
+    host: &str,
+    digest: &Option<Arc<DigestAuth>>,
+    absolute: bool,
+) -> Option<ResponseSignature> {
+    let (tx, rx) = channel::<ResponseSignature>();
+    let (request, context) = build_request(config, host, digest, absolute);
+    let host = host.to_owned();
+    rt::run(
+        client
+            .request(request)
+            .and_then(|res| {
+                negotiate::verify(context, res.headers());
+                let (parts, body) = res.into_parts();
+                body.concat2().map(move |body| (parts, body))
+            })
+            .map(move |(parts, body)| {
+                let location = parts.headers.get("Location").and_then(|v| v.to_str().ok());
+                tx.send(ResponseSignature::new(
+                    &host,
+                    &parts.status.to_string(),
+                    location,
+                    &String::from_utf8_lossy(&body),
+                ))
+                .unwrap_or(());
+            })
+            .map_err(|e| debug!("Baseline request failed: {}", e)),
+    );
+
+    rx.try_recv().ok()
+}
+
+#[allow(clippy::too_many_arguments)]
+fn make_request_future<C: Connect + 'static>(
     tx: Sender<SingleVhostScanResult>,
-    client: &Client<HttpsConnector<HttpConnector>>,
+    client: &Client<C>,
     url: Uri,
     config: &VhostConfig,
+    pacer: Arc<Pacer>,
+    digest: Option<Arc<DigestAuth>>,
+    baseline: Option<Arc<ResponseSignature>>,
+    sni_probe: Option<Arc<SniProbe>>,
+    absolute: bool,
 ) -> impl Future<Item = (), Error = ()> {
     let tx_err = tx.clone();
+    let host = url.host().unwrap().to_owned();
     let target = Arc::new(Mutex::new(SingleVhostScanResult {
-        vhost: url.to_string(),
+        vhost: if absolute {
+            absolute_uri(&config.original_url, &host)
+        } else {
+            url.to_string()
+        },
         status: StatusCode::default().to_string(),
         error: None,
         method: config.http_method.clone(),
         ignored: false,
+        location: None,
+        title: None,
+        certificate: None,
+        sni_certificate: None,
+        captured_headers: Vec::new(),
+        resolved: None,
+        geo: None,
+        server_ip: None,
+        curl: None,
+        har_entry: None,
     }));
     let target_res = target.clone();
     let mut target_err = (*target.lock().unwrap()).clone();
-    let mut request_builder = Request::builder();
     let ignore_strings = config.ignore_strings.clone();
-    let request = request_builder
-        .header("User-Agent", &config.user_agent[..])
-        .method(&config.http_method[..])
-        .uri(&config.original_url)
-        .header("Host", url.host().unwrap())
-        .body(Body::from(""))
-        .expect("Request builder");
+    let http_body = expand_bytes(&config.http_body, &host);
+    let (request, context) = build_request(config, &host, &digest, absolute);
+    let capture_names = config.capture_headers.clone();
+    let header_audit = config.header_audit.clone();
+    let har_request = if config.save_har {
+        Some(HarRequest::new(&request, &http_body))
+    } else {
+        None
+    };
+    let started = Local::now();
+    let sni_certificate = match &sni_probe {
+        Some(sni_probe) => Either::A(sni_probe.certificate(&host)),
+        None => Either::B(futures::future::ok(None)),
+    };
+    let sni_target = target.clone();
+
+    let client = client.clone();
 
-    client
-        .request(request)
+    pacer
+        .wait()
+        .then(move |_| sni_certificate)
+        .then(move |certificate| {
+            if let Some(sni_probe) = sni_probe {
+                sni_target.lock().unwrap().sni_certificate =
+                    sni_probe.distinct(certificate.unwrap_or(None));
+            }
+            client.request(request)
+        })
         .and_then(move |res| {
+            negotiate::verify(context, res.headers());
+            pacer.observe(res.headers());
+            pacer.observe_status(res.status());
+            if let Some(digest) = digest {
+                digest.observe(res.headers());
+            }
+            if let Some(header_audit) = header_audit {
+                header_audit.observe(&url, res.headers());
+            }
             target.lock().unwrap().status = res.status().to_string();
-            res.into_body().concat2()
+            target.lock().unwrap().captured_headers =
+                capture_headers(res.headers(), &capture_names);
+            target.lock().unwrap().server_ip = remote_ip(&res);
+            target.lock().unwrap().location = res
+                .headers()
+                .get("Location")
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_owned());
+            let (parts, body) = res.into_parts();
+            futures::future::ok(parts).join(body.concat2())
         })
-        .and_then(move |body| {
+        .and_then(move |(parts, body)| {
+            if let Some(har_request) = har_request {
+                target_res.lock().unwrap().har_entry = Some(HarEntry::new(
+                    started,
+                    har_request,
+                    HarResponse::new(&parts, &body),
+                ));
+            }
+
             let vec = body.iter().cloned().collect();
             let body = String::from_utf8(vec).unwrap();
+            target_res.lock().unwrap().title = html_title(&body);
             target_res.lock().unwrap().ignored = false;
             for s in ignore_strings {
                 if body.contains(&s) {
@@ -67,6 +248,24 @@ fn make_request_future(
                 }
             }
 
+            if let Some(baseline) = baseline {
+                let mut target = target_res.lock().unwrap();
+                let signature = ResponseSignature::new(
+                    &host,
+                    &target.status,
+                    target.location.as_deref(),
+                    &body,
+                );
+                if signature == *baseline {
+                    target.ignored = true;
+                }
+            }
+
+            // another certificate is configured for the name, whatever the body says
+            if target_res.lock().unwrap().sni_certificate.is_some() {
+                target_res.lock().unwrap().ignored = false;
+            }
+
             let target = Arc::try_unwrap(target_res).unwrap().into_inner().unwrap();
             tx.send(target).unwrap();
             Ok(())
@@ -78,21 +277,119 @@ fn make_request_future(
         })
 }
 
-pub fn run(tx: Sender<SingleVhostScanResult>, urls: Vec<hyper::Uri>, config: VhostConfig) {
+fn report_baseline(
+    label: &str,
+    signature: Option<ResponseSignature>,
+) -> Option<Arc<ResponseSignature>> {
+    match signature {
+        Some(signature) => {
+            crate::say!(
+                "{}",
+                banner::vhost_baseline(label, &signature.status, signature.length)
+            );
+            Some(Arc::new(signature))
+        }
+        None => {
+            warn!("Unable to learn the default vhost response, nothing will be ignored");
+            None
+        }
+    }
+}
+
+pub fn run_with_pacer(
+    tx: Sender<SingleVhostScanResult>,
+    urls: Vec<hyper::Uri>,
+    config: VhostConfig,
+    pacer: Arc<Pacer>,
+) {
     let mut tls_connector_builder = native_tls::TlsConnector::builder();
     tls_connector_builder.danger_accept_invalid_certs(config.ignore_certificate);
+    tls_connector_builder.min_protocol_version(config.tls_min_version);
+    tls_connector_builder.max_protocol_version(config.tls_max_version);
     let tls_connector = tls_connector_builder
         .build()
         .expect("TLS initialization failed");
-    let mut http_connector = HttpConnector::new(config.n_threads);
-    http_connector.enforce_http(false);
+    let http_connector = ProxyConnector::new(config.n_threads);
     let https_connector = HttpsConnector::from((http_connector, tls_connector));
-    let client = Client::builder().build(https_connector);
+    let absolute_client = if config.absolute_uri {
+        config.original_url.parse::<Uri>().ok().map(|target| {
+            Client::builder().build(ProxyFormConnector::new(https_connector.clone(), target))
+        })
+    } else {
+        None
+    };
+    let client = Client::builder().build(https_connector.clone());
     let n_threads = config.n_threads;
     debug!("{:?}", urls);
 
-    let stream = futures::stream::iter_ok(urls)
-        .map(move |url| make_request_future(tx.clone(), &client, url, &config))
+    let digest = config
+        .digest
+        .as_ref()
+        .map(|(username, password)| Arc::new(DigestAuth::new(username, password)));
+    if let Some(digest) = &digest {
+        digest.handshake(
+            Client::builder().build(https_connector),
+            &config.original_url,
+        );
+    }
+
+    let sni_probe = SniProbe::new(&config).map(|mut sni_probe| {
+        let host = baseline_host(urls.first().and_then(|url| url.host()).unwrap_or(""));
+        crate::say!("{}", banner::sni_default(sni_probe.learn_default(&host)));
+        Arc::new(sni_probe)
+    });
+
+    let baseline_host = urls
+        .first()
+        .filter(|_| config.ignore_strings.is_empty())
+        .map(|url| baseline_host(url.host().unwrap_or("")));
+    let baseline = baseline_host.as_ref().and_then(|host| {
+        report_baseline(
+            "Baseline",
+            learn_baseline(&client, &config, host, &digest, false),
+        )
+    });
+    let absolute_baseline = match (&baseline_host, &absolute_client) {
+        (Some(host), Some(absolute_client)) => report_baseline(
+            "Absolute URI baseline",
+            learn_baseline(absolute_client, &config, host, &digest, true),
+        ),
+        _ => None,
+    };
+
+    let absolute = absolute_client.is_some();
+    let probes = urls.into_iter().flat_map(move |url| {
+        if absolute {
+            vec![(url.clone(), false), (url, true)]
+        } else {
+            vec![(url, false)]
+        }
+    });
+    let stream = futures::stream::iter_ok(probes)
+        .map(move |(url, absolute)| match (&absolute_client, absolute) {
+            (Some(absolute_client), true) => Either::A(make_request_future(
+                tx.clone(),
+                absolute_client,
+                url,
+                &config,
+                pacer.clone(),
+                digest.clone(),
+                absolute_baseline.clone(),
+                None,
+                true,
+            )),
+            _ => Either::B(make_request_future(
+                tx.clone(),
+                &client,
+                url,
+                &config,
+                pacer.clone(),
+                digest.clone(),
+                baseline.clone(),
+                sni_probe.clone(),
+                false,
+            )),
+        })
         .buffer_unordered(n_threads)
         .for_each(Ok)
         .map_err(|err| eprintln!("Err {:?}", err));
@@ -1,4 +1,7 @@
 use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+
+use crate::{certificate::CertificateInfo, geoip::GeoInfo, har::HarEntry, sink::ResultSink};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SingleVhostScanResult {
@@ -7,6 +10,26 @@ pub struct SingleVhostScanResult {
     pub method: String,
     pub error: Option<String>,
     pub ignored: bool,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub certificate: Option<CertificateInfo>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub sni_certificate: Option<CertificateInfo>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub captured_headers: Vec<(String, String)>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub resolved: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub geo: Option<GeoInfo>,
+    #[serde(skip)]
+    pub server_ip: Option<IpAddr>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub curl: Option<String>,
+    #[serde(skip)]
+    pub har_entry: Option<HarEntry>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -20,10 +43,11 @@ impl VhostScanResult {
             results: Vec::<SingleVhostScanResult>::new(),
         }
     }
+}
 
-    pub fn maybe_add_result(&mut self, res: SingleVhostScanResult) -> bool {
+impl ResultSink<SingleVhostScanResult> for VhostScanResult {
+    fn store(&mut self, res: SingleVhostScanResult) {
         trace!("{:?}", res);
         self.results.push(res);
-        true
     }
 }
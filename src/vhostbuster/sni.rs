@@ -0,0 +1,104 @@
+use futures::Future;
+use hyper::{
+    client::connect::{Connect, Destination},
+    rt, Uri,
+};
+use hyper_tls::{HttpsConnector, MaybeHttpsStream};
+use std::sync::mpsc::channel;
+
+use super::VhostConfig;
+use crate::{
+    certificate::{parse_certificate, CertificateInfo},
+    proxy::{PinnedConnector, ProxyConnector},
+};
+
+/// Handshakes with the target using each vhost as SNI, the certificates that differ from the
+/// default one point to vhosts actually configured on the target
+#[derive(Clone)]
+pub struct SniProbe {
+    connector: HttpsConnector<PinnedConnector>,
+    port: u16,
+    default: Option<CertificateInfo>,
+}
+
+impl SniProbe {
+    /// Only TLS targets present certificates
+    pub fn new(config: &VhostConfig) -> Option<Self> {
+        let uri = config.original_url.parse::<Uri>().ok()?;
+        if uri.scheme_str() != Some("https") {
+            return None;
+        }
+
+        let mut tls_connector_builder = native_tls::TlsConnector::builder();
+        // the certificate is what is recorded, not what is validated
+        tls_connector_builder.danger_accept_invalid_certs(true);
+        tls_connector_builder.danger_accept_invalid_hostnames(true);
+        tls_connector_builder.min_protocol_version(config.tls_min_version);
+        tls_connector_builder.max_protocol_version(config.tls_max_version);
+        let tls_connector = tls_connector_builder
+            .build()
+            .expect("TLS initialization failed");
+        let http_connector = PinnedConnector::new(ProxyConnector::new(1), uri.host()?);
+
+        Some(SniProbe {
+            connector: HttpsConnector::from((http_connector, tls_connector)),
+            port: uri.port_u16().unwrap_or(443),
+            default: None,
+        })
+    }
+
+    pub fn certificate(
+        &self,
+        sni: &str,
+    ) -> impl Future<Item = Option<CertificateInfo>, Error = ()> {
+        let sni = sni.to_owned();
+        let dst = format!("https://{}:{}/", sni, self.port)
+            .parse::<Uri>()
+            .ok()
+            .and_then(|uri| Destination::try_from_uri(uri).ok());
+        let connecting = dst.map(|dst| self.connector.connect(dst));
+
+        futures::future::result(connecting.ok_or(()))
+            .and_then(move |connecting| {
+                connecting
+                    .map(move |(stream, _)| match stream {
+                        MaybeHttpsStream::Https(stream) => {
+                            let der = stream.get_ref().peer_certificate().ok()??.to_der().ok()?;
+                            parse_certificate(&sni, &der)
+                        }
+                        MaybeHttpsStream::Http(_) => None,
+                    })
+                    .map_err(|e| debug!("SNI handshake failed: {}", e))
+            })
+            .or_else(|_| Ok(None))
+    }
+
+    /// Learns the certificate presented for an unknown name before the scan
+    pub fn learn_default(&mut self, sni: &str) -> Option<&CertificateInfo> {
+        let (tx, rx) = channel::<CertificateInfo>();
+        rt::run(self.certificate(sni).map(move |certificate| {
+            if let Some(certificate) = certificate {
+                tx.send(certificate).unwrap_or(());
+            }
+        }));
+
+        self.default = rx.try_recv().ok();
+        self.default.as_ref()
+    }
+
+    /// The certificate when it is not the default one
+    pub fn distinct(&self, certificate: Option<CertificateInfo>) -> Option<CertificateInfo> {
+        distinct_certificate(self.default.as_ref(), certificate)
+    }
+}
+
+pub fn distinct_certificate(
+    default: Option<&CertificateInfo>,
+    certificate: Option<CertificateInfo>,
+) -> Option<CertificateInfo> {
+    let certificate = certificate?;
+    match default {
+        Some(default) if default.same_certificate(&certificate) => None,
+        _ => Some(certificate),
+    }
+}
@@ -1,12 +1,14 @@
-use std::{fs, fs::File, io::Write, path::Path, str};
+use regex::Regex;
+use std::{fs::File, io::Write, net::ToSocketAddrs, path::Path, str};
 
 use super::result_processor::SingleVhostScanResult;
+use crate::{output::merge_results, template::expand, wordlist};
 
 pub fn build_vhosts(wordlist_path: &str, url: &str) -> Vec<hyper::Uri> {
     debug!("building urls");
     let mut urls: Vec<hyper::Uri> = Vec::new();
-    let wordlist =
-        fs::read_to_string(wordlist_path).expect("Something went wrong reading the wordlist file");
+    let wordlist = wordlist::read_filtered(wordlist_path)
+        .expect("Something went wrong reading the wordlist file");
     let urls_iter = wordlist
         .lines()
         .filter(|word| !word.starts_with('#') && !word.starts_with(' '))
@@ -26,8 +28,152 @@ pub fn build_vhosts(wordlist_path: &str, url: &str) -> Vec<hyper::Uri> {
     urls
 }
 
-pub fn save_vhost_results(path: &str, results: &Vec<SingleVhostScanResult>) {
-    let json_string = serde_json::to_string(&results).unwrap();
+pub fn seed_vhosts(vhosts: Vec<hyper::Uri>, seeds: Vec<String>) -> Vec<hyper::Uri> {
+    let mut urls: Vec<hyper::Uri> = Vec::new();
+    for seed in seeds {
+        match seed.parse::<hyper::Uri>() {
+            Ok(v) => {
+                if !vhosts.contains(&v) && !urls.contains(&v) {
+                    urls.push(v);
+                }
+            }
+            Err(e) => {
+                trace!("URI: {}", e);
+            }
+        }
+    }
+
+    urls.extend(vhosts);
+    urls
+}
+
+pub fn resolve_host(host: &str, port: u16) -> Vec<String> {
+    let mut addrs: Vec<String> = Vec::new();
+    match (host, port).to_socket_addrs() {
+        Ok(v) => {
+            for addr in v {
+                let ip = addr.ip().to_string();
+                if !addrs.contains(&ip) {
+                    addrs.push(ip);
+                }
+            }
+        }
+        Err(e) => debug!("Unable to resolve {}: {}", host, e),
+    }
+
+    addrs
+}
+
+pub fn describe_resolution(resolved: &[String], target: &[String]) -> String {
+    if resolved.is_empty() {
+        "no public record, only reachable behind the target".to_owned()
+    } else if resolved.iter().any(|ip| target.contains(ip)) {
+        format!(
+            "public record pointing to the target: {}",
+            resolved.join(", ")
+        )
+    } else {
+        format!("public record pointing elsewhere: {}", resolved.join(", "))
+    }
+}
+
+pub fn resolve_vhosts(results: &mut [SingleVhostScanResult], url: &str) -> String {
+    let uri = match url.parse::<hyper::Uri>() {
+        Ok(v) => v,
+        Err(_) => return "".to_owned(),
+    };
+    let target = resolve_host(uri.host().unwrap_or(""), uri.port_u16().unwrap_or(80));
+
+    let mut report = "[+] Vhost resolution:".to_owned();
+    for result in results.iter_mut() {
+        let resolved = resolve_host(&result.vhost, 80);
+        report.push_str(&format!(
+            "\n\t{}\t=> {}",
+            result.vhost,
+            describe_resolution(&resolved, &target)
+        ));
+        result.resolved = Some(resolved);
+    }
+
+    report
+}
+
+pub fn html_title(body: &str) -> Option<String> {
+    let re = Regex::new(r"(?is)<title[^>]*>(.*?)</title>").unwrap();
+    let title = re
+        .captures(body)?
+        .get(1)?
+        .as_str()
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ");
+
+    if title.is_empty() {
+        None
+    } else {
+        Some(title)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponseSignature {
+    pub status: String,
+    pub length: usize,
+    pub location: Option<String>,
+}
+
+impl ResponseSignature {
+    pub fn new(host: &str, status: &str, location: Option<&str>, body: &str) -> Self {
+        ResponseSignature {
+            status: status.to_owned(),
+            length: body.replace(host, "").len(),
+            location: location.map(|l| l.replace(host, "")),
+        }
+    }
+}
+
+pub fn baseline_host(host: &str) -> String {
+    let label = expand("{{uuid}}", "").replace('-', "");
+    match host.find('.') {
+        Some(i) => format!("{}{}", label, &host[i..]),
+        None => label,
+    }
+}
+
+pub fn format_details(result: &SingleVhostScanResult) -> String {
+    let mut details = String::new();
+    if let Some(location) = &result.location {
+        details.push_str(&format!("\n\t\t\t\t\t\t=> {}", location));
+    }
+
+    if let Some(title) = &result.title {
+        details.push_str(&format!("\n\t\t\t\t\t\t=> Title: {}", title));
+    }
+
+    if let Some(certificate) = &result.sni_certificate {
+        details.push_str(&format!(
+            "\n\t\t\t\t\t\t=> Certificate: {} ({})",
+            certificate.common_name,
+            certificate.subject_alt_names.join(", ")
+        ));
+    }
+
+    if let Some(curl) = &result.curl {
+        details.push_str(&format!("\n\t\t\t\t\t\t=> {}", curl));
+    }
+
+    details
+}
+
+pub fn save_vhost_results(path: &str, results: &Vec<SingleVhostScanResult>, append: bool) {
+    let json_string = if append {
+        serde_json::to_string(&merge_results(path, results, |r| {
+            (r.method.clone(), r.vhost.clone())
+        }))
+        .unwrap()
+    } else {
+        serde_json::to_string(&results).unwrap()
+    };
 
     let mut file = match File::create(Path::new(path)) {
         Ok(f) => f,
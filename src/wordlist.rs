@@ -0,0 +1,87 @@
+use std::{collections::HashSet, fs, io, sync::Mutex};
+
+pub const INLINE_PREFIX: &str = "inline:";
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WordlistFilter {
+    pub dedupe: bool,
+    pub trim: bool,
+    pub skip_comments: bool,
+    pub min_length: usize,
+    pub max_length: usize,
+}
+
+static FILTER: Mutex<WordlistFilter> = Mutex::new(WordlistFilter {
+    dedupe: false,
+    trim: false,
+    skip_comments: false,
+    min_length: 0,
+    max_length: 0,
+});
+
+static REPORTED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+impl WordlistFilter {
+    pub fn is_active(&self) -> bool {
+        *self != WordlistFilter::default()
+    }
+
+    pub fn apply(&self, content: &str) -> (String, usize) {
+        let mut seen = HashSet::new();
+        let mut removed = 0;
+        let mut words = Vec::new();
+        for line in content.lines() {
+            let word = if self.trim { line.trim() } else { line };
+            let length = word.chars().count();
+            let skip = (self.trim && word.is_empty())
+                || (self.skip_comments && word.starts_with('#'))
+                || length < self.min_length
+                || (self.max_length > 0 && length > self.max_length)
+                || (self.dedupe && !seen.insert(word));
+            if skip {
+                removed += 1;
+            } else {
+                words.push(word);
+            }
+        }
+
+        (words.join("\n"), removed)
+    }
+}
+
+pub fn set_filter(filter: WordlistFilter) {
+    *FILTER.lock().unwrap() = filter;
+}
+
+/// Reads a file or an inline list as it is, for the lists that are not scan candidates
+pub fn read(path: &str) -> io::Result<String> {
+    match path.strip_prefix(INLINE_PREFIX) {
+        Some(words) => Ok(words.split(',').collect::<Vec<&str>>().join("\n")),
+        None => fs::read_to_string(path),
+    }
+}
+
+/// Reads a candidate wordlist through the preprocessing filter, its removals are reported once
+pub fn read_filtered(path: &str) -> io::Result<String> {
+    let content = read(path)?;
+    let filter = FILTER.lock().unwrap().clone();
+    if !filter.is_active() {
+        return Ok(content);
+    }
+
+    let (filtered, removed) = filter.apply(&content);
+    let mut reported = REPORTED.lock().unwrap();
+    if !reported.iter().any(|p| p == path) {
+        reported.push(path.to_owned());
+        crate::say!(
+            "{}",
+            crate::banner::wordlist_filtered(path, removed, content.lines().count())
+        );
+    }
+
+    Ok(filtered)
+}
+
+pub fn exists(path: &str) -> bool {
+    path.starts_with(INLINE_PREFIX) || fs::metadata(path).is_ok()
+}
@@ -0,0 +1,199 @@
+use futures::{future::Either, Stream};
+use hyper::{
+    rt::{self, Future},
+    Body, Client, Request, StatusCode, Uri,
+};
+use hyper_tls::HttpsConnector;
+use std::{
+    sync::{mpsc::Sender, Arc, Mutex},
+    time::Duration,
+};
+use tokio_io::io::{read, write_all};
+use tokio_timer::Timeout;
+
+pub mod result_processor;
+pub mod utils;
+
+use crate::{
+    proxy::ProxyConnector,
+    ratelimit::Pacer,
+    template::{expand, random_u64, word_from_uri},
+};
+use result_processor::SingleWsScanResult;
+use utils::{decode_frame, encode_frame, websocket_key};
+
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone)]
+pub struct WsConfig {
+    pub n_threads: usize,
+    pub ignore_certificate: bool,
+    pub user_agent: String,
+    pub http_headers: Vec<(String, String)>,
+    pub tls_min_version: Option<native_tls::Protocol>,
+    pub tls_max_version: Option<native_tls::Protocol>,
+    pub messages: Vec<String>,
+}
+
+fn build_client(config: &WsConfig) -> Client<HttpsConnector<ProxyConnector>> {
+    let mut tls_connector_builder = native_tls::TlsConnector::builder();
+    tls_connector_builder.danger_accept_invalid_certs(config.ignore_certificate);
+    tls_connector_builder.min_protocol_version(config.tls_min_version);
+    tls_connector_builder.max_protocol_version(config.tls_max_version);
+    let tls_connector = tls_connector_builder
+        .build()
+        .expect("TLS initialization failed");
+    let http_connector = ProxyConnector::new(config.n_threads);
+    let https_connector = HttpsConnector::from((http_connector, tls_connector));
+    Client::builder().build(https_connector)
+}
+
+fn make_request_future(
+    client: &Client<HttpsConnector<ProxyConnector>>,
+    url: Uri,
+    message: Option<String>,
+    config: &WsConfig,
+    pacer: Arc<Pacer>,
+) -> impl Future<Item = SingleWsScanResult, Error = ()> {
+    let mut target = SingleWsScanResult {
+        url: url.to_string(),
+        method: "GET".to_owned(),
+        status: StatusCode::default().to_string(),
+        upgraded: false,
+        error: None,
+        extra: None,
+        message: message.clone(),
+        response: None,
+    };
+    let mut target_err = target.clone();
+    let mut request_builder = Request::builder();
+    let word = word_from_uri(&url);
+
+    for header_tuple in &config.http_headers {
+        request_builder.header(
+            expand(&header_tuple.0, &word).as_str(),
+            expand(&header_tuple.1, &word).as_str(),
+        );
+    }
+
+    let request = request_builder
+        .header("User-Agent", &config.user_agent[..])
+        .header("Connection", "Upgrade")
+        .header("Upgrade", "websocket")
+        .header("Sec-WebSocket-Version", "13")
+        .header("Sec-WebSocket-Key", websocket_key().as_str())
+        .method("GET")
+        .uri(&url)
+        .header("Host", url.host().unwrap())
+        .body(Body::empty())
+        .expect("Request builder");
+
+    let client = client.clone();
+
+    pacer
+        .wait()
+        .then(move |_| client.request(request))
+        .map_err(|e| e.to_string())
+        .and_then(move |res| {
+            pacer.observe(res.headers());
+            target.status = res.status().to_string();
+            target.upgraded = res.status() == StatusCode::SWITCHING_PROTOCOLS;
+            target.extra = res
+                .headers()
+                .get("Sec-WebSocket-Protocol")
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_owned());
+
+            let message = match (target.upgraded, message) {
+                (true, Some(message)) => message,
+                _ => {
+                    return Either::A(
+                        res.into_body()
+                            .concat2()
+                            .map_err(|e| e.to_string())
+                            .map(move |_| target),
+                    )
+                }
+            };
+
+            let mask = (random_u64() as u32).to_be_bytes();
+            Either::B(
+                res.into_body()
+                    .on_upgrade()
+                    .map_err(|e| e.to_string())
+                    .and_then(move |upgraded| {
+                        write_all(upgraded, encode_frame(message.as_bytes(), mask))
+                            .map_err(|e| e.to_string())
+                    })
+                    .and_then(|(upgraded, _)| {
+                        Timeout::new(read(upgraded, vec![0; 4096]), RESPONSE_TIMEOUT)
+                            .then(|res| Ok(res.ok()))
+                    })
+                    .map(move |res| {
+                        target.response = res
+                            .and_then(|(_, buf, n)| decode_frame(&buf[..n]))
+                            .map(|(_, payload)| String::from_utf8_lossy(&payload).into_owned());
+                        target
+                    }),
+            )
+        })
+        .or_else(move |e| {
+            target_err.error = Some(e);
+            Ok(target_err)
+        })
+}
+
+fn run_stage(
+    tx: Sender<SingleWsScanResult>,
+    jobs: Vec<(Uri, Option<String>)>,
+    config: &WsConfig,
+    upgraded: Arc<Mutex<Vec<Uri>>>,
+) {
+    let client = build_client(config);
+    let pacer = Arc::new(Pacer::default());
+    let config = config.clone();
+    let n_threads = config.n_threads;
+
+    let stream = futures::stream::iter_ok(jobs)
+        .map(move |(url, message)| {
+            let upgraded = upgraded.clone();
+            let future = make_request_future(&client, url.clone(), message, &config, pacer.clone());
+            future.map(move |res| {
+                if res.upgraded && res.message.is_none() {
+                    upgraded.lock().unwrap().push(url);
+                }
+                res
+            })
+        })
+        .buffer_unordered(n_threads)
+        .for_each(move |res| {
+            tx.send(res).unwrap_or(());
+            Ok(())
+        })
+        .map_err(|err| eprintln!("Err {:?}", err));
+
+    rt::run(stream);
+}
+
+pub fn run(tx: Sender<SingleWsScanResult>, urls: Vec<hyper::Uri>, config: WsConfig) {
+    let upgraded = Arc::new(Mutex::new(Vec::new()));
+    let jobs = urls.into_iter().map(|url| (url, None)).collect();
+    run_stage(tx.clone(), jobs, &config, upgraded.clone());
+
+    if config.messages.is_empty() {
+        return;
+    }
+
+    let jobs = upgraded
+        .lock()
+        .unwrap()
+        .iter()
+        .flat_map(|url| {
+            config
+                .messages
+                .iter()
+                .map(move |message| (url.clone(), Some(message.to_owned())))
+        })
+        .collect();
+    run_stage(tx, jobs, &config, upgraded.clone());
+}
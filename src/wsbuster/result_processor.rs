@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use crate::sink::ResultSink;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SingleWsScanResult {
+    pub url: String,
+    pub method: String,
+    pub status: String,
+    pub upgraded: bool,
+    pub error: Option<String>,
+    pub extra: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub response: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct WsScanResult {
+    pub results: Vec<SingleWsScanResult>,
+}
+
+impl WsScanResult {
+    pub fn new() -> Self {
+        WsScanResult {
+            results: Vec::<SingleWsScanResult>::new(),
+        }
+    }
+}
+
+impl ResultSink<SingleWsScanResult> for WsScanResult {
+    fn accept(&self, res: &SingleWsScanResult) -> bool {
+        trace!("{:?}", res);
+        res.upgraded
+    }
+
+    fn store(&mut self, res: SingleWsScanResult) {
+        self.results.push(res);
+    }
+}
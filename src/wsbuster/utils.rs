@@ -0,0 +1,98 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use std::{fs::File, io::Write, path::Path};
+
+use super::result_processor::SingleWsScanResult;
+use crate::{output::merge_results, template::random_u64};
+
+pub fn http_url(url: &str) -> String {
+    if let Some(rest) = url.strip_prefix("ws://") {
+        format!("http://{}", rest)
+    } else if let Some(rest) = url.strip_prefix("wss://") {
+        format!("https://{}", rest)
+    } else {
+        url.to_owned()
+    }
+}
+
+pub fn websocket_key() -> String {
+    let mut key = random_u64().to_be_bytes().to_vec();
+    key.extend_from_slice(&random_u64().to_be_bytes());
+    STANDARD.encode(key)
+}
+
+pub fn encode_frame(payload: &[u8], mask: [u8; 4]) -> Vec<u8> {
+    let mut frame = vec![0x81];
+    match payload.len() {
+        len if len < 126 => frame.push(0x80 | len as u8),
+        len if len <= 0xffff => {
+            frame.push(0x80 | 126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(0x80 | 127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+
+    frame.extend_from_slice(&mask);
+    frame.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+    frame
+}
+
+pub fn decode_frame(data: &[u8]) -> Option<(u8, Vec<u8>)> {
+    let opcode = data.first()? & 0x0f;
+    let masked = data.get(1)? & 0x80 != 0;
+    let (len, mut offset) = match data[1] & 0x7f {
+        126 => (
+            u16::from_be_bytes([*data.get(2)?, *data.get(3)?]) as usize,
+            4,
+        ),
+        127 => {
+            let mut len = [0; 8];
+            len.copy_from_slice(data.get(2..10)?);
+            (u64::from_be_bytes(len) as usize, 10)
+        }
+        len => (len as usize, 2),
+    };
+
+    let mask = if masked {
+        let mask = data.get(offset..offset + 4)?.to_vec();
+        offset += 4;
+        mask
+    } else {
+        vec![0; 4]
+    };
+
+    let payload = data[offset.min(data.len())..]
+        .iter()
+        .take(len)
+        .enumerate()
+        .map(|(i, b)| b ^ mask[i % 4])
+        .collect();
+
+    Some((opcode, payload))
+}
+
+pub fn save_ws_results(path: &str, results: &Vec<SingleWsScanResult>, append: bool) {
+    let json_string = if append {
+        serde_json::to_string(&merge_results(path, results, |r| {
+            (r.url.clone(), r.message.clone())
+        }))
+        .unwrap()
+    } else {
+        serde_json::to_string(&results).unwrap()
+    };
+
+    let mut file = match File::create(Path::new(path)) {
+        Ok(f) => f,
+        Err(e) => {
+            error!("Error while creating file: {}\n{}", path, e);
+            return;
+        }
+    };
+
+    match file.write_all(json_string.as_bytes()) {
+        Ok(_) => debug!("Results saved to: {}", path),
+        Err(e) => error!("Error while writing results to file: {}\n{}", path, e),
+    };
+}
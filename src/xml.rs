@@ -0,0 +1,44 @@
+use regex::Regex;
+
+pub struct Tag {
+    pub name: String,
+    pub attrs: Vec<(String, String)>,
+    pub closing: bool,
+    pub empty: bool,
+}
+
+impl Tag {
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Strips the namespace prefix of a tag or attribute name
+pub fn local(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+/// Splits an XML document into its tags, enough for the WSDL and nmap reports without a full parser
+pub fn tags(xml: &str) -> Vec<Tag> {
+    let tag_re = Regex::new(r#"<(/?)([\w.:-]+)((?:[^>"']|"[^"]*"|'[^']*')*?)(/?)>"#).unwrap();
+    let attr_re = Regex::new(r#"([\w.:-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap();
+
+    tag_re
+        .captures_iter(xml)
+        .map(|caps| Tag {
+            name: local(&caps[2]).to_owned(),
+            attrs: attr_re
+                .captures_iter(&caps[3])
+                .map(|attr| {
+                    let value = attr.get(2).or_else(|| attr.get(3)).unwrap().as_str();
+                    (attr[1].to_owned(), value.to_owned())
+                })
+                .collect(),
+            closing: &caps[1] == "/",
+            empty: &caps[4] == "/",
+        })
+        .collect()
+}
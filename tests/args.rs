@@ -0,0 +1,124 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name args;
+
+    use librustbuster::args::*;
+    use librustbuster::environment::arguments;
+
+    test max_results_not_a_number() {
+        let app = set_common_args(clap::App::new("dir"));
+        let matches = app.clone().get_matches_from(vec!["dir", "--max-results", "abc"]);
+        assert_eq!(
+            Err("Invalid --max-results: abc, expected a number".to_owned()),
+            extract_common_args(&matches).map(|v| v.max_results)
+        );
+
+        let matches = app.get_matches_from(vec!["dir", "--max-results", "10"]);
+        assert_eq!(10, extract_common_args(&matches).unwrap().max_results);
+    }
+
+    test dns_timeout_not_a_number() {
+        let app = set_dns_args(clap::App::new("dns"));
+        let matches = app.clone().get_matches_from(vec!["dns", "-d", "example.com", "--dns-timeout", "x"]);
+        assert!(extract_dns_args(&matches).is_err());
+
+        let matches = app.get_matches_from(vec!["dns", "-d", "example.com", "--dns-timeout", "500"]);
+        assert_eq!(500, extract_dns_args(&matches).unwrap().timeout);
+    }
+
+    test dns_retries_not_a_number() {
+        let app = set_dns_args(clap::App::new("dns"));
+        let matches = app.get_matches_from(vec!["dns", "-d", "example.com", "--dns-retries", "two"]);
+        assert!(extract_dns_args(&matches).is_err());
+    }
+
+    test http_args_without_network() {
+        let app = set_mode_args("dir", clap::App::new("dir"));
+        let matches = app.get_matches_from(vec!["dir", "-u", "localhost:1", "-w", "words", "--tag-requests"]);
+        let http_args = extract_http_args(&matches).unwrap();
+        // the scheme is detected later on, by prepare_http_args
+        assert_eq!("localhost:1", http_args.url);
+        assert!(http_args.negotiate.is_none());
+        assert!(http_args.request_tag.is_some());
+    }
+
+    test env_tables() {
+        let modes = ["dir", "dns", "vhost", "tilde", "fuzz", "methods", "ws", "graphql", "soap", "auth", "chain", "fronting"];
+        let option = regex::Regex::new(r"(?m)^\s+(?:-(\w), )?--([\w-]+)( <[\w-]+>(\.\.\.)?)?").unwrap();
+        for mode in modes.iter() {
+            let app = set_mode_args(mode, clap::App::new("scan"));
+            let table = env_args(mode);
+            // the table has every option of the mode, read the same way as the command line
+            let mut help = Vec::new();
+            app.write_help(&mut help).unwrap();
+            let help = String::from_utf8(help).unwrap();
+            let mut options: Vec<(&str, Option<char>, bool, bool)> = option
+                .captures_iter(&help)
+                .map(|c| {
+                    let short = c.get(1).and_then(|s| s.as_str().chars().next());
+                    (c.get(2).unwrap().as_str(), short, c.get(3).is_some(), c.get(4).is_some())
+                })
+                .filter(|(long, _, _, _)| *long != "help" && *long != "version")
+                .collect();
+            let mut names: Vec<(&str, Option<char>, bool, bool)> =
+                table.iter().map(|arg| (arg.long, arg.short, arg.takes_value, arg.multiple)).collect();
+            options.sort_unstable();
+            names.sort_unstable();
+            names.dedup();
+            assert_eq!(options, names, "{}", mode);
+
+            // the arguments built from the environment are read the same way as the command line
+            let mut required = Vec::new();
+            if *mode != "dns" {
+                required.push(("url", "-u", "http://localhost/"));
+            }
+            if table.iter().any(|arg| arg.long == "wordlist") {
+                required.push(("wordlist", "-w", "words"));
+            }
+            if ["dns", "vhost", "chain", "fronting"].contains(mode) {
+                required.push(("domain", "-d", "example.com"));
+            }
+            if *mode == "auth" {
+                required.push(("usernames", "-U", "users"));
+                required.push(("passwords", "-P", "passwords"));
+            }
+            if *mode == "chain" {
+                required.push(("dir-wordlist", "--dir-wordlist", "words"));
+            }
+            let base = |skip: &str| {
+                let mut argv = vec!["scan".to_owned()];
+                for (_, flag, value) in required.iter().filter(|(long, _, _)| *long != skip) {
+                    argv.push((*flag).to_owned());
+                    argv.push((*value).to_owned());
+                }
+                argv
+            };
+            assert!(app.clone().get_matches_from_safe(base("")).is_ok(), "{}", mode);
+
+            for arg in table.iter() {
+                let value = if arg.takes_value { "1\n2" } else { "true" };
+                let mut env_argv = base(arg.long);
+                env_argv.extend(arguments(&[*arg], &[], |_| Some(value.to_owned())));
+                match app.clone().get_matches_from_safe(env_argv) {
+                    Ok(matches) => {
+                        let expected = if arg.multiple { 2 } else { 1 };
+                        assert_eq!(expected, matches.occurrences_of(arg.long), "{} --{}", mode, arg.long);
+                    }
+                    Err(e) => assert!(
+                        [clap::ErrorKind::InvalidValue, clap::ErrorKind::MissingRequiredArgument, clap::ErrorKind::ArgumentConflict].contains(&e.kind),
+                        "{} --{}: {}",
+                        mode,
+                        arg.long,
+                        e.message
+                    ),
+                }
+            }
+        }
+
+        assert_eq!(0, env_args("daemon").len());
+        assert!(env_args("dir").iter().any(|arg| arg.long == "http-header" && arg.multiple));
+        let argv = apply_env_args(vec!["rustbuster".to_owned(), "project".to_owned(), "client.rbp".to_owned()]);
+        assert_eq!(vec!["rustbuster", "project", "client.rbp"], argv);
+    }
+}
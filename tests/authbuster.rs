@@ -0,0 +1,38 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name authbuster;
+
+    use librustbuster::authbuster::result_processor::{FailureSignature, LockoutDetector, SingleAuthScanResult};
+
+    fn attempt(username: &str, status: &str, body: &str) -> SingleAuthScanResult {
+        SingleAuthScanResult {
+            url: "http://localhost/login".to_owned(),
+            method: "POST".to_owned(),
+            username: username.to_owned(),
+            password: "password".to_owned(),
+            status: status.to_owned(),
+            error: None,
+            body: body.to_owned(),
+        }
+    }
+
+    test failure_signature() {
+        let samples = vec![
+            attempt("rb1", "200 OK", "<input value=\"123\"><p>Invalid password for rb1</p>"),
+            attempt("rb2", "200 OK", "<input value=\"456\"><p>Invalid password for rb2</p>"),
+        ];
+        let signature = FailureSignature::learn(&samples).unwrap();
+        assert!(signature.matches(&attempt("admin", "200 OK", "<input value=\"789\"><p>Invalid password for admin</p>")));
+        assert!(!signature.matches(&attempt("admin", "200 OK", "<p>Welcome admin</p>")));
+        assert!(!signature.matches(&attempt("admin", "302 Found", "")));
+    }
+
+    test lockout_detector() {
+        let mut lockout = LockoutDetector::new("200 OK", 2);
+        assert!(!lockout.observe("429 Too Many Requests"));
+        assert!(!lockout.observe("200 OK"));
+        assert!(!lockout.observe("403 Forbidden"));
+        assert!(lockout.observe("429 Too Many Requests"));
+    }
+}
@@ -0,0 +1,39 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name burp;
+
+    use librustbuster::{
+        burp::items,
+        har::{HarEntry, HarRequest, HarResponse},
+        import::burp,
+    };
+
+    test items_are_read_back_by_the_importer() {
+        let request = hyper::Request::builder()
+            .method("POST")
+            .uri("https://localhost:8443/login.php?next=/admin")
+            .body(())
+            .unwrap();
+        let (parts, _) = hyper::Response::builder()
+            .status(200)
+            .header("Content-Type", "text/html")
+            .body(())
+            .unwrap()
+            .into_parts();
+        let entry = HarEntry::new(
+            chrono::Local::now(),
+            HarRequest::new(&request, b"user=admin"),
+            HarResponse::new(&parts, b"<html></html>"),
+        );
+
+        let xml = items(&[entry]);
+        assert!(xml.contains("<port>8443</port>"));
+        assert!(xml.contains("<extension>php</extension>"));
+        assert!(xml.contains("<mimetype>HTML</mimetype>"));
+        let exchanges = burp(&xml);
+        assert_eq!(1, exchanges.len());
+        assert_eq!("https://localhost:8443/login.php?next=/admin", exchanges[0].url);
+        assert_eq!("user=admin", exchanges[0].body);
+    }
+}
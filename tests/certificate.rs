@@ -0,0 +1,121 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name certificate;
+
+    test parse_certificate() {
+        let der = std::fs::read("./examples/certificate.der").unwrap();
+        let info = librustbuster::certificate::parse_certificate("localhost", &der).unwrap();
+        assert_eq!("localhost", info.host);
+        assert_eq!("example.test", info.common_name);
+        assert_eq!("example.test", info.issuer);
+        assert_eq!(vec!["a.example.test".to_owned(), "b.example.test".to_owned(), "10.0.0.1".to_owned()], info.subject_alt_names);
+        assert!(info.not_after.ends_with("UTC"));
+    }
+
+    test parse_certificate_non_ascii_time() {
+        let mut der = std::fs::read("./examples/certificate.der").unwrap();
+        // the expiry is the second UTCTime of the validity
+        let not_after = der
+            .windows(2)
+            .enumerate()
+            .filter(|(_, w)| w == &[0x17, 0x0d])
+            .map(|(i, _)| i)
+            .nth(1)
+            .unwrap();
+        der[not_after + 4] = 0xe9;
+        let info = librustbuster::certificate::parse_certificate("localhost", &der).unwrap();
+        assert_eq!("", info.not_after);
+    }
+
+    test certificate_report() {
+        use librustbuster::certificate::{CertificateInfo, CertificateReport};
+
+        let info = CertificateInfo {
+            host: "Example.test".to_owned(),
+            common_name: "example.test".to_owned(),
+            issuer: "example.test".to_owned(),
+            subject_alt_names: vec![],
+            not_after: "".to_owned(),
+        };
+        let mut report = CertificateReport::new(Some(info.clone()));
+        assert_eq!(None, report.take("https://other.test/admin"));
+        assert_eq!(Some(info), report.take("https://example.test/admin"));
+        assert_eq!(None, report.take("https://example.test/login"));
+    }
+
+    test parse_certificate_invalid() {
+        assert_eq!(None, librustbuster::certificate::parse_certificate("localhost", &[0x30, 0x82, 0xff]));
+    }
+
+    test seed_hosts() {
+        let info = librustbuster::certificate::CertificateInfo {
+            host: "localhost".to_owned(),
+            common_name: "example.test".to_owned(),
+            issuer: "example.test".to_owned(),
+            subject_alt_names: vec![
+                "*.example.test".to_owned(),
+                "api.example.test".to_owned(),
+                "*.dev.example.test".to_owned(),
+                "example.test".to_owned(),
+                "10.0.0.1".to_owned(),
+            ],
+            not_after: "".to_owned(),
+        };
+        let expected = vec!["example.test".to_owned(), "api.example.test".to_owned(), "dev.example.test".to_owned()];
+        assert_eq!(expected, librustbuster::certificate::seed_hosts(&info, "example.test"));
+    }
+
+    test seed_hosts_labels() {
+        let info = librustbuster::certificate::CertificateInfo {
+            host: "localhost".to_owned(),
+            common_name: "example.test".to_owned(),
+            issuer: "example.test".to_owned(),
+            subject_alt_names: vec![
+                "api.example.net".to_owned(),
+                "*.dev.example.net".to_owned(),
+                "www.shop.example.test".to_owned(),
+                "example.net".to_owned(),
+            ],
+            not_after: "".to_owned(),
+        };
+        let expected = vec![
+            "api.example.net".to_owned(),
+            "api.example.test".to_owned(),
+            "dev.example.net".to_owned(),
+            "dev.example.test".to_owned(),
+            "www.shop.example.test".to_owned(),
+            "www.example.test".to_owned(),
+            "example.net".to_owned(),
+        ];
+        assert_eq!(expected, librustbuster::certificate::seed_hosts(&info, "example.test"));
+    }
+
+    test distinct_certificate() {
+        use librustbuster::{certificate::CertificateInfo, vhostbuster::sni::distinct_certificate};
+
+        let default = CertificateInfo {
+            host: "random.example.test".to_owned(),
+            common_name: "example.test".to_owned(),
+            issuer: "example.test".to_owned(),
+            subject_alt_names: vec!["example.test".to_owned()],
+            not_after: "2030-01-01 00:00:00 UTC".to_owned(),
+        };
+        let same = CertificateInfo {
+            host: "www.example.test".to_owned(),
+            ..default.clone()
+        };
+        let other = CertificateInfo {
+            host: "admin.example.test".to_owned(),
+            common_name: "admin.example.test".to_owned(),
+            subject_alt_names: vec!["admin.example.test".to_owned()],
+            ..default.clone()
+        };
+
+        assert!(default.same_certificate(&same));
+        assert_eq!(None, distinct_certificate(Some(&default), Some(same)));
+        assert_eq!(Some(other.clone()), distinct_certificate(Some(&default), Some(other.clone())));
+        assert_eq!(Some(other.clone()), distinct_certificate(None, Some(other)));
+        assert_eq!(None, distinct_certificate(Some(&default), None));
+    }
+}
@@ -0,0 +1,15 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name chain;
+
+    test hostname() {
+        assert_eq!("www.example.com", librustbuster::chain::hostname("www.example.com:80"));
+        assert_eq!("www.example.com", librustbuster::chain::hostname("www.example.com"));
+    }
+
+    test base_url() {
+        assert_eq!("https://www.example.com/", librustbuster::chain::base_url("https://10.0.0.1/", "www.example.com"));
+        assert_eq!("http://www.example.com:8080/", librustbuster::chain::base_url("http://10.0.0.1:8080/admin", "www.example.com"));
+    }
+}
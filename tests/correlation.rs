@@ -0,0 +1,15 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name correlation;
+
+    use librustbuster::correlation::RequestTag;
+
+    test request_tag() {
+        let tag = RequestTag::new();
+        assert_eq!(12, tag.scan_id.len());
+        assert_ne!(tag.scan_id, RequestTag::new().scan_id);
+        assert_eq!(format!("{}-1", tag.scan_id), tag.next());
+        assert_eq!(format!("{}-2", tag.scan_id), tag.next());
+    }
+}
@@ -0,0 +1,27 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name cors;
+
+    use librustbuster::cors::{attacker_origins, is_vulnerable};
+
+    test attacker_origins_include_host_suffix() {
+        let uri = "https://app.example.com/api".parse::<hyper::Uri>().unwrap();
+        let origins = attacker_origins(&uri);
+        assert!(origins.contains(&"null".to_owned()));
+        assert!(origins.contains(&"https://app.example.com.rustbuster-cors.example".to_owned()));
+    }
+
+    test reflected_origin_with_credentials() {
+        let origin = "https://rustbuster-cors.example";
+        let mut headers = hyper::HeaderMap::new();
+        headers.insert("Access-Control-Allow-Origin", origin.parse().unwrap());
+        assert_eq!(None, is_vulnerable(origin, &headers));
+
+        headers.insert("Access-Control-Allow-Credentials", "true".parse().unwrap());
+        assert_eq!(Some(origin.to_owned()), is_vulnerable(origin, &headers));
+
+        headers.insert("Access-Control-Allow-Origin", "*".parse().unwrap());
+        assert_eq!(None, is_vulnerable(origin, &headers));
+    }
+}
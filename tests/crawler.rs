@@ -0,0 +1,21 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name crawler;
+
+    use librustbuster::dirbuster::crawler::{parent_directories, resolve_link};
+
+    test resolve_links() {
+        let page = "http://localhost/app/index.php".parse::<hyper::Uri>().unwrap();
+        assert_eq!(Some("http://localhost/app/api/login".parse::<hyper::Uri>().unwrap()), resolve_link(&page, "api/login?next=1"));
+        assert_eq!(Some("http://localhost/admin/".parse::<hyper::Uri>().unwrap()), resolve_link(&page, "../admin/"));
+        assert_eq!(Some("http://localhost/static/app.css".parse::<hyper::Uri>().unwrap()), resolve_link(&page, "//localhost/static/app.css"));
+        assert_eq!(None, resolve_link(&page, "http://example.com/"));
+        assert_eq!(None, resolve_link(&page, "mailto:admin@localhost"));
+    }
+
+    test parent_directories_of_path() {
+        assert_eq!(vec!["/app/".to_owned(), "/app/api/".to_owned()], parent_directories("/app/api/login"));
+        assert!(parent_directories("/index.php").is_empty());
+    }
+}
@@ -0,0 +1,142 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name daemon;
+
+    use chrono::{Local, TimeZone};
+    use hyper::{HeaderMap, Method, StatusCode};
+    use librustbuster::daemon::{job_arguments, schedule::Schedule, validate_job, Daemon, DaemonConfig, DaemonJob, JobQueue};
+
+    test cron_schedule() {
+        let monday_morning = Local.ymd(2019, 5, 6).and_hms(9, 30, 0);
+        assert!(Schedule::parse("*/15 9-17 * * 1-5").unwrap().matches(&monday_morning));
+        assert!(Schedule::parse("30 9 * * *").unwrap().matches(&monday_morning));
+        assert!(!Schedule::parse("30 9 * * 0,6").unwrap().matches(&monday_morning));
+        assert!(Schedule::parse("30 9 1 * 1").unwrap().matches(&monday_morning));
+        assert!(!Schedule::parse("@daily").unwrap().matches(&monday_morning));
+        assert_eq!(None, Schedule::parse("61 * * * *"));
+        assert_eq!(None, Schedule::parse("*/0 * * * *"));
+        assert_eq!(None, Schedule::parse("* * * *"));
+    }
+
+    test job_validation() {
+        let job = DaemonJob {
+            name: "nightly".to_owned(),
+            arguments: vec!["dir".to_owned(), "-u".to_owned(), "http://localhost/".to_owned()],
+            schedule: "0 2 * * *".to_owned(),
+            ..DaemonJob::default()
+        };
+        assert_eq!(Ok(()), validate_job(&job, ""));
+        assert!(validate_job(&DaemonJob { name: "../etc".to_owned(), ..job.clone() }, "").is_err());
+        assert!(validate_job(&DaemonJob { arguments: vec!["daemon".to_owned()], ..job.clone() }, "").is_err());
+        assert!(validate_job(&DaemonJob { arguments: vec!["dir".to_owned(), "-o".to_owned(), "x".to_owned()], ..job.clone() }, "").is_err());
+        let refused = [
+            "-ko", "--har=/etc/cron.d/x", "--save-requests", "--burp", "--project", "--output-append", "--progress-json",
+            "--body-file", "--session", "--known", "--geoip", "--input-nmap", "--payload", "--form", "-kw", "-w/etc/passwd",
+        ];
+        for flag in refused.iter() {
+            let arguments = vec!["dir".to_owned(), (*flag).to_owned(), "x".to_owned()];
+            assert!(validate_job(&DaemonJob { arguments, ..job.clone() }, "/opt/wordlists").is_err());
+        }
+        assert!(validate_job(&DaemonJob { schedule: "every day".to_owned(), ..job }, "").is_err());
+    }
+
+    test job_wordlists() {
+        let job = |arguments: &[&str]| DaemonJob {
+            name: "nightly".to_owned(),
+            arguments: arguments.iter().map(|a| (*a).to_owned()).collect(),
+            ..DaemonJob::default()
+        };
+
+        assert_eq!(
+            Ok(vec!["dir".to_owned(), "--url".to_owned(), "http://localhost/".to_owned(), "--wordlist".to_owned(), "inline:admin,/etc/passwd".to_owned(), "--append-slash".to_owned()]),
+            job_arguments(&job(&["dir", "-u", "http://localhost/", "-w", "inline:admin,/etc/passwd", "-f"]), "")
+        );
+        assert_eq!(
+            Ok(vec!["dir".to_owned(), "--wordlist".to_owned(), "/opt/wordlists/common.txt,inline:admin".to_owned()]),
+            job_arguments(&job(&["dir", "--wordlist=common.txt,inline:admin"]), "/opt/wordlists")
+        );
+        assert!(job_arguments(&job(&["dir", "-w", "common.txt"]), "").is_err());
+        for wordlist in ["/etc/passwd", "../passwd", "lists/../../passwd", "common.txt,/etc/passwd", ""].iter() {
+            assert!(job_arguments(&job(&["dir", "-w", wordlist]), "/opt/wordlists").is_err());
+        }
+        assert!(job_arguments(&job(&["dir", "-w"]), "/opt/wordlists").is_err());
+        assert!(job_arguments(&job(&["dir", "-w", "--known"]), "/opt/wordlists").is_err());
+        assert!(job_arguments(&job(&["dir", "--append-slash=x"]), "/opt/wordlists").is_err());
+    }
+
+    test invalid_queued_jobs() {
+        let config = DaemonConfig {
+            queue: "".to_owned(),
+            listen: "127.0.0.1:7070".parse().unwrap(),
+            max_jobs: 1,
+            results_dir: "".to_owned(),
+            wordlists_dir: "".to_owned(),
+            token: "0123456789abcdef".to_owned(),
+        };
+        let queue = JobQueue {
+            jobs: vec![
+                DaemonJob { name: "leak".to_owned(), arguments: vec!["dir".to_owned(), "--body-file".to_owned(), "/etc/passwd".to_owned()], ..DaemonJob::default() },
+                DaemonJob { name: "nightly".to_owned(), arguments: vec!["dir".to_owned(), "-u".to_owned(), "http://localhost/".to_owned()], ..DaemonJob::default() },
+            ],
+            pending: vec!["leak".to_owned(), "nightly".to_owned()],
+        };
+        let daemon = Daemon::new(config, queue);
+        assert_eq!(vec!["nightly".to_owned()], daemon.queue.pending);
+        assert_eq!(Some(3), daemon.queue.jobs[0].last_exit_code);
+        assert_eq!(None, daemon.queue.jobs[1].last_exit_code);
+    }
+
+    test control_api() {
+        let config = DaemonConfig {
+            queue: "".to_owned(),
+            listen: "127.0.0.1:7070".parse().unwrap(),
+            max_jobs: 1,
+            results_dir: "".to_owned(),
+            wordlists_dir: "".to_owned(),
+            token: "0123456789abcdef".to_owned(),
+        };
+        let mut daemon = Daemon::new(config, JobQueue::default());
+
+        let body = br#"{"name":"nightly","arguments":["dir","-u","http://localhost/"],"schedule":"0 2 * * *"}"#;
+        assert_eq!(StatusCode::OK, daemon.handle(&Method::POST, "/jobs", body).0);
+        assert_eq!(StatusCode::BAD_REQUEST, daemon.handle(&Method::POST, "/jobs", b"{}").0);
+        assert_eq!(StatusCode::ACCEPTED, daemon.handle(&Method::POST, "/jobs/nightly/run", b"").0);
+        assert_eq!(StatusCode::NOT_FOUND, daemon.handle(&Method::POST, "/jobs/weekly/run", b"").0);
+        assert_eq!(vec!["nightly".to_owned()], daemon.queue.pending);
+
+        let (status, body) = daemon.handle(&Method::GET, "/jobs", b"");
+        assert_eq!(StatusCode::OK, status);
+        let status: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!("0 2 * * *", status["jobs"][0]["schedule"]);
+        assert_eq!("nightly", status["pending"][0]);
+
+        assert_eq!(StatusCode::OK, daemon.handle(&Method::DELETE, "/jobs/nightly", b"").0);
+        assert!(daemon.queue.jobs.is_empty());
+        assert!(daemon.queue.pending.is_empty());
+    }
+
+    test control_api_authorization() {
+        let config = DaemonConfig {
+            queue: "".to_owned(),
+            listen: "127.0.0.1:7070".parse().unwrap(),
+            max_jobs: 1,
+            results_dir: "".to_owned(),
+            wordlists_dir: "".to_owned(),
+            token: "0123456789abcdef".to_owned(),
+        };
+        let daemon = Daemon::new(config, JobQueue::default());
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(StatusCode::UNAUTHORIZED, daemon.authorize(&Method::GET, &headers).unwrap_err().0);
+        headers.insert("Authorization", "Bearer 0123456789abcdeX".parse().unwrap());
+        assert_eq!(StatusCode::UNAUTHORIZED, daemon.authorize(&Method::GET, &headers).unwrap_err().0);
+        headers.insert("Authorization", "Bearer 0123456789abcdef".parse().unwrap());
+        assert_eq!(Ok(()), daemon.authorize(&Method::GET, &headers));
+
+        headers.insert("Content-Type", "text/plain".parse().unwrap());
+        assert_eq!(StatusCode::UNSUPPORTED_MEDIA_TYPE, daemon.authorize(&Method::POST, &headers).unwrap_err().0);
+        headers.insert("Content-Type", "application/json; charset=utf-8".parse().unwrap());
+        assert_eq!(Ok(()), daemon.authorize(&Method::POST, &headers));
+    }
+}
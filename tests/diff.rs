@@ -0,0 +1,20 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name diff;
+
+    test diff_results() {
+        let old: Vec<serde_json::Value> = serde_json::from_str(r#"[
+            {"url":"http://localhost/a","method":"GET","status":"200 OK","error":null,"extra":null},
+            {"url":"http://localhost/b","method":"GET","status":"200 OK","error":null,"extra":null}
+        ]"#).unwrap();
+        let new: Vec<serde_json::Value> = serde_json::from_str(r#"[
+            {"url":"http://localhost/b","method":"GET","status":"403 Forbidden","error":null,"extra":null},
+            {"url":"http://localhost/c","method":"GET","status":"200 OK","error":null,"extra":null}
+        ]"#).unwrap();
+        let diff = librustbuster::diff::diff_results(&old, &new);
+        assert_eq!(vec![new[1].clone()], diff.added);
+        assert_eq!(vec![old[0].clone()], diff.removed);
+        assert_eq!(vec![(old[1].clone(), new[0].clone())], diff.changed);
+    }
+}
@@ -0,0 +1,54 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name digest;
+
+    use librustbuster::digest::{parse_challenge, DigestAuth};
+
+    const CHALLENGE: &str = "Digest realm=\"testrealm@host.com\", qop=\"auth,auth-int\", nonce=\"dcd98b7102dd2f0e8b11d0f600bfb0c093\", opaque=\"5ccc069c403ebaf9f0171e9517f40e41\"";
+
+    test parse_digest_challenge() {
+        let challenge = parse_challenge(CHALLENGE).unwrap();
+        assert_eq!("testrealm@host.com", challenge.realm);
+        assert_eq!("dcd98b7102dd2f0e8b11d0f600bfb0c093", challenge.nonce);
+        assert_eq!(Some("auth".to_owned()), challenge.qop);
+        assert_eq!("MD5", challenge.algorithm);
+        assert_eq!(None, parse_challenge("Basic realm=\"x\""));
+    }
+
+    test rfc2617_response() {
+        let auth = DigestAuth::new("Mufasa", "Circle Of Life");
+        let mut headers = hyper::HeaderMap::new();
+        headers.insert("WWW-Authenticate", CHALLENGE.parse().unwrap());
+        auth.observe(&headers);
+
+        let header = auth
+            .authorization_with_cnonce("GET", "/dir/index.html", "0a4f113b")
+            .unwrap();
+        assert!(header.contains("nc=00000001"));
+        assert!(header.contains("response=\"6629fae49393a05397450978507c4ef1\""));
+        assert!(header.contains("opaque=\"5ccc069c403ebaf9f0171e9517f40e41\""));
+    }
+
+    test nonce_count_resets_on_new_nonce() {
+        let auth = DigestAuth::new("Mufasa", "Circle Of Life");
+        let mut headers = hyper::HeaderMap::new();
+        headers.insert("WWW-Authenticate", CHALLENGE.parse().unwrap());
+        auth.observe(&headers);
+        auth.authorization_with_cnonce("GET", "/", "0a4f113b").unwrap();
+        let header = auth.authorization_with_cnonce("GET", "/", "0a4f113b").unwrap();
+        assert!(header.contains("nc=00000002"));
+
+        // the same nonce again keeps counting
+        auth.observe(&headers);
+        let header = auth.authorization_with_cnonce("GET", "/", "0a4f113b").unwrap();
+        assert!(header.contains("nc=00000003"));
+
+        let stale = CHALLENGE.replace("dcd98b7102dd2f0e8b11d0f600bfb0c093", "e966c932a9242554e42c8ee200cec7f6") + ", stale=true";
+        headers.insert("WWW-Authenticate", stale.parse().unwrap());
+        auth.observe(&headers);
+        let header = auth.authorization_with_cnonce("GET", "/", "0a4f113b").unwrap();
+        assert!(header.contains("nc=00000001"));
+        assert!(header.contains("nonce=\"e966c932a9242554e42c8ee200cec7f6\""));
+    }
+}
@@ -0,0 +1,239 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name dirbuster;
+
+    test build_urls_wraps() {
+        let config = librustbuster::dirbuster::utils::UrlConfig {
+            extensions: vec!["php".to_owned()],
+            append_slash: false,
+            raw_path: false,
+            mutations: false,
+            backslash: false,
+            force_extensions: false,
+            wraps: vec![(".".to_owned(), ".swp".to_owned()), ("_".to_owned(), ".old".to_owned())],
+        };
+        let urls: Vec<String> = librustbuster::dirbuster::utils::build_urls("inline:index", "http://localhost/", &config)
+            .iter()
+            .map(|u| u.to_string())
+            .collect();
+        let expected = vec![
+            "http://localhost/index",
+            "http://localhost/.index.swp",
+            "http://localhost/_index.old",
+            "http://localhost/index.php",
+            "http://localhost/.index.php.swp",
+            "http://localhost/_index.php.old",
+        ];
+        assert_eq!(expected, urls);
+    }
+
+    test slash_redirect() {
+        let url = "http://localhost/admin".parse::<hyper::Uri>().unwrap();
+        let slashed = Some("http://localhost/admin/".parse::<hyper::Uri>().unwrap());
+        assert_eq!(slashed, librustbuster::dirbuster::utils::slash_redirect(&url, "/admin/"));
+        assert_eq!(slashed, librustbuster::dirbuster::utils::slash_redirect(&url, "http://localhost/admin/"));
+        assert_eq!(None, librustbuster::dirbuster::utils::slash_redirect(&url, "/login"));
+        assert_eq!(None, librustbuster::dirbuster::utils::slash_redirect(&url, "https://localhost/admin/"));
+
+        let url = "http://localhost/admin/".parse::<hyper::Uri>().unwrap();
+        assert_eq!(None, librustbuster::dirbuster::utils::slash_redirect(&url, "/admin//"));
+    }
+
+    test needs_range() {
+        let mut headers = hyper::HeaderMap::new();
+        assert_eq!(None, librustbuster::dirbuster::utils::needs_range(&headers, 1024));
+
+        headers.insert("Content-Length", "4096".parse().unwrap());
+        assert_eq!(Some(4096), librustbuster::dirbuster::utils::needs_range(&headers, 1024));
+        assert_eq!(None, librustbuster::dirbuster::utils::needs_range(&headers, 4096));
+        assert_eq!(None, librustbuster::dirbuster::utils::needs_range(&headers, 0));
+    }
+
+    fn dir_result(url: &str, status: &str, body: &str) -> librustbuster::dirbuster::result_processor::SingleDirScanResult {
+        librustbuster::dirbuster::result_processor::SingleDirScanResult {
+            url: url.to_owned(),
+            method: "GET".to_owned(),
+            status: status.to_owned(),
+            error: None,
+            extra: None,
+            certificate: None,
+            captured_headers: Vec::new(),
+            severity: None,
+            range_supported: None,
+            request_id: None,
+            geo: None,
+            server_ip: None,
+            curl: None,
+            har_entry: None,
+            body: body.to_owned(),
+            length: body.len(),
+        }
+    }
+
+    test host_filters_soft404() {
+        use librustbuster::dirbuster::{result_processor::ResultProcessorConfig, soft404::{HostFilters, Soft404s}};
+        use librustbuster::sink::ResultSink;
+
+        let config = ResultProcessorConfig { include: vec![], ignore: vec!["404".to_owned()], include_body: vec![], ignore_body: vec![], rules: vec![] };
+        let mut soft404s = Soft404s::default();
+        soft404s.learn(&dir_result("http://a.example.com/x1y2z3", "200 OK", "<p>No page at /x1y2z3</p>"));
+        soft404s.learn(&dir_result("http://b.example.com/q9w8e7", "404 Not Found", "not found"));
+        let mut filters = HostFilters::new(config).with_soft404s(soft404s);
+
+        assert!(!filters.maybe_add_result(dir_result("http://a.example.com/admin", "200 OK", "<p>No page at /admin</p>")));
+        assert!(filters.maybe_add_result(dir_result("http://a.example.com/login", "200 OK", "<form>login</form>")));
+        assert!(filters.maybe_add_result(dir_result("http://b.example.com/admin", "200 OK", "<p>No page at /admin</p>")));
+        assert!(!filters.maybe_add_result(dir_result("http://b.example.com/missing", "404 Not Found", "not found")));
+        assert!(filters.maybe_add_result(dir_result("http://c.example.com/admin", "200 OK", "<p>No page at /admin</p>")));
+
+        let urls: Vec<String> = filters.into_results().into_iter().map(|r| r.url).collect();
+        assert_eq!(vec!["http://a.example.com/login", "http://b.example.com/admin", "http://c.example.com/admin"], urls);
+    }
+
+    test stored_results_drop_the_body() {
+        use librustbuster::dirbuster::result_processor::{ResultProcessorConfig, ScanResult};
+        use librustbuster::sink::ResultSink;
+
+        let config = ResultProcessorConfig { include: vec![], ignore: vec![], include_body: vec!["admin".to_owned()], ignore_body: vec![], rules: vec![] };
+        let mut result_processor = ScanResult::new(config);
+        assert!(!result_processor.maybe_add_result(dir_result("http://example.com/login", "200 OK", "<form>login</form>")));
+        assert!(result_processor.maybe_add_result(dir_result("http://example.com/admin", "200 OK", "<h1>admin</h1>")));
+
+        let result = &result_processor.results[0];
+        assert_eq!("", result.body);
+        assert_eq!(14, result.length);
+    }
+
+    test fail_string() {
+        use librustbuster::args::{extract_body_args, set_body_args};
+        use librustbuster::dirbuster::result_processor::{ResultProcessorConfig, ScanResult};
+        use librustbuster::sink::ResultSink;
+
+        let app = set_body_args(clap::App::new("dir"));
+        let matches = app.get_matches_from(vec!["dir", "--fail-string", "Page not found", "-x", "Error"]);
+        let body_args = extract_body_args(&matches);
+        assert_eq!(vec!["Page not found", "Error"], body_args.ignore_strings);
+
+        let config = ResultProcessorConfig { include: vec![], ignore: vec![], include_body: vec![], ignore_body: body_args.ignore_strings, rules: vec![] };
+        let result_processor = ScanResult::new(config);
+        assert!(!result_processor.accept(&dir_result("http://example.com/admin", "200 OK", "<h1>Page not found</h1>")));
+        assert!(!result_processor.accept(&dir_result("http://example.com/login", "200 OK", "Error: try again")));
+        assert!(result_processor.accept(&dir_result("http://example.com/about", "200 OK", "<h1>About</h1>")));
+    }
+
+    test body_strings() {
+        use librustbuster::dirbuster::result_processor::{ResultProcessorConfig, ScanResult};
+        use librustbuster::sink::ResultSink;
+
+        let config = ResultProcessorConfig {
+            include: vec![],
+            ignore: vec![],
+            include_body: vec!["admin".to_owned(), "dashboard".to_owned()],
+            ignore_body: vec!["disabled".to_owned()],
+            rules: vec![],
+        };
+        let result_processor = ScanResult::new(config);
+        assert!(result_processor.accept(&dir_result("http://example.com/admin", "200 OK", "<h1>admin</h1>")));
+        assert!(result_processor.accept(&dir_result("http://example.com/home", "200 OK", "<h1>dashboard</h1>")));
+        assert!(!result_processor.accept(&dir_result("http://example.com/login", "200 OK", "<form>login</form>")));
+        assert!(!result_processor.accept(&dir_result("http://example.com/old", "200 OK", "<h1>admin</h1> disabled")));
+    }
+
+    test soft404_tolerates_dynamic_parts() {
+        use librustbuster::dirbuster::soft404::Soft404;
+
+        let soft404 = Soft404::new(&dir_result("http://example.com/x1y2z3", "200 OK", "<p>No page at /x1y2z3, 2026-10-18 10:00:00</p>"));
+        assert!(soft404.matches(&dir_result("http://example.com/admin", "200 OK", "<p>No page at /admin, 2026-10-18 10:00:01</p>")));
+        assert!(soft404.matches(&dir_result("http://example.com/admin", "200 OK", "<p>No page at /admin, 2026-10-18 10:00:01.482913</p>")));
+        assert!(!soft404.matches(&dir_result("http://example.com/admin", "200 OK", "<h1>Admin</h1><p>Sign in to continue</p>")));
+        assert!(!soft404.matches(&dir_result("http://example.com/admin", "200 OK", "<p>No page at /admin, 2026-10-18 10:00:01, request 7f3a9c2e81b4d6e0</p>")));
+        assert!(!soft404.matches(&dir_result("http://example.com/admin", "404 Not Found", "<p>No page at /admin, 2026-10-18 10:00:01</p>")));
+    }
+
+    test is_skipped() {
+        use librustbuster::dirbuster::utils::is_skipped;
+
+        let skip_dirs = vec![regex::Regex::new("^/static/").unwrap(), regex::Regex::new("/\\.well-known/").unwrap()];
+        assert!(is_skipped("/static/", &skip_dirs));
+        assert!(is_skipped("/static", &skip_dirs));
+        assert!(is_skipped("/static/css/site.css", &skip_dirs));
+        assert!(is_skipped("/app/.well-known/security.txt", &skip_dirs));
+        assert!(!is_skipped("/app/static/", &skip_dirs));
+        assert!(!is_skipped("/statics", &skip_dirs));
+        assert!(!is_skipped("/static", &[]));
+    }
+
+    test adaptive_tokens() {
+        let body = r#"<a href="/admin/user-list.php?page=2">users</a>
+<a href="https://cdn.example.net/lib/jquery.js">cdn</a><a href="http://app.example.com:8080/reports/">reports</a>
+<a href="mailto:root@example.com">mail</a><script>fetch('/api/v2/orders')</script>
+<form action="login"><input type="text" name="account[email]"><input name=csrf_token></form>"#;
+        assert_eq!(
+            vec![
+                "admin", "user-list.php", "user-list", "user", "list", "reports", "login", "api",
+                "v2", "orders", "account", "email", "csrf_token", "csrf", "token",
+            ],
+            librustbuster::dirbuster::adaptive::tokens(body, "app.example.com")
+        );
+    }
+
+    test adaptive_learner() {
+        let mut learner = librustbuster::dirbuster::adaptive::Learner::new("admin\nlogin", "example.com");
+        assert_eq!(vec!["backup"], learner.learn(r#"<a href="/admin/backup">"#));
+        assert!(learner.learn(r#"<a href="/backup">"#).is_empty());
+        assert_eq!(1, learner.learned());
+    }
+
+    test both_schemes() {
+        use librustbuster::dirbuster::schemes::{other_scheme, with_both_schemes};
+
+        let other = |url: &str| other_scheme(&url.parse().unwrap()).map(|u| u.to_string());
+        assert_eq!(Some("https://example.com/admin?x=1".to_owned()), other("http://example.com/admin?x=1"));
+        assert_eq!(Some("http://example.com/".to_owned()), other("https://example.com:443/"));
+        assert_eq!(Some("https://example.com:8080/".to_owned()), other("http://example.com:8080/"));
+
+        let urls = with_both_schemes(vec!["http://example.com/a".parse().unwrap(), "https://example.com/b".parse().unwrap()]);
+        let urls: Vec<String> = urls.iter().map(|u| u.to_string()).collect();
+        assert_eq!(
+            vec!["http://example.com/a", "https://example.com/a", "https://example.com/b", "http://example.com/b"],
+            urls
+        );
+    }
+
+    test scheme_differences() {
+        use librustbuster::dirbuster::schemes::{SchemeDiff, SchemeResponse};
+
+        let response = |status: &str, location: Option<&str>| SchemeResponse {
+            status: status.to_owned(),
+            length: 0,
+            location: location.map(|l| l.to_owned()),
+        };
+        let mut diff = SchemeDiff::default();
+        diff.observe("http://example.com/same", response("200 OK", None), true);
+        diff.observe("https://example.com/same", response("200 OK", None), true);
+        diff.observe("http://example.com/upgraded", response("301 Moved Permanently", Some("https://example.com/upgraded")), false);
+        diff.observe("https://example.com/upgraded", response("200 OK", None), true);
+        diff.observe("http://example.com/admin", response("404 Not Found", None), false);
+        diff.observe("https://example.com/admin", response("200 OK", None), true);
+        diff.observe("http://example.com/legacy", response("200 OK", None), true);
+        diff.observe("http://example.com/missing", response("404 Not Found", None), false);
+        diff.observe("https://example.com/missing", response("403 Forbidden", None), false);
+
+        let paths: Vec<&str> = diff.differences().iter().map(|(path, _, _)| *path).collect();
+        assert_eq!(vec!["example.com/admin", "example.com/legacy"], paths);
+    }
+
+    test ports() {
+        use librustbuster::dirbuster::ports::{parse_ports, with_port};
+
+        assert_eq!(Ok(vec![80, 443, 8000, 8001, 8002]), parse_ports("80, 443,8000-8002,443"));
+        assert!(parse_ports("80,http").is_err());
+        assert!(parse_ports("8100-8000").is_err());
+        assert!(parse_ports("0").is_err());
+
+        assert_eq!(Some("https://example.com:443/admin/".to_owned()), with_port("http://example.com/admin/", 443));
+        assert_eq!(Some("http://example.com:80/".to_owned()), with_port("https://example.com:8443/", 80));
+        assert_eq!(Some("https://example.com:8443/?q=1".to_owned()), with_port("https://example.com/?q=1", 8443));
+    }
+}
@@ -0,0 +1,75 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name dnsbuster;
+
+    test base_name() {
+        assert_eq!("example", librustbuster::dnsbuster::utils::base_name("example"));
+        assert_eq!("example", librustbuster::dnsbuster::utils::base_name("example.com"));
+        assert_eq!("example", librustbuster::dnsbuster::utils::base_name(".example.co.uk"));
+    }
+
+    test tld_domains() {
+        let tlds = vec!["com", ".net", "# comment", "", " io "];
+        let expected = vec![
+            "example.com:80".to_owned(),
+            "example.net:80".to_owned(),
+            "example.io:80".to_owned(),
+        ];
+        assert_eq!(expected, librustbuster::dnsbuster::utils::tld_domains(tlds.into_iter(), "example.org"));
+    }
+
+    test hosts_lines() {
+        use librustbuster::dnsbuster::result_processor::SingleDnsScanResult;
+
+        let results = vec![
+            SingleDnsScanResult {
+                domain: "www.example.com:80".to_owned(),
+                status: true,
+                extra: Some(vec!["192.0.2.1:80".parse().unwrap(), "[2001:db8::1]:80".parse().unwrap()]),
+                asn: Vec::new(),
+                geo: Vec::new(),
+            },
+            SingleDnsScanResult {
+                domain: "missing.example.com:80".to_owned(),
+                status: false,
+                extra: None,
+                asn: Vec::new(),
+                geo: Vec::new(),
+            },
+        ];
+        let expected = vec![
+            "192.0.2.1 www.example.com".to_owned(),
+            "2001:db8::1 www.example.com".to_owned(),
+        ];
+        assert_eq!(expected, librustbuster::dnsbuster::utils::hosts_lines(&results));
+    }
+
+    test asn_lookup_names() {
+        use librustbuster::dnsbuster::asn;
+
+        assert_eq!("1.2.0.192.origin.asn.cymru.com.", asn::origin_name(&"192.0.2.1".parse().unwrap()));
+        assert!(asn::origin_name(&"2001:db8::1".parse().unwrap())
+            .starts_with("1.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.8.b.d.0.1.0.0.2.origin6."));
+
+        assert_eq!(
+            Some((15169, "8.8.8.0/24".to_owned(), "US".to_owned())),
+            asn::parse_origin("15169 36040 | 8.8.8.0/24 | US | arin | 2000-03-30")
+        );
+        assert_eq!(None, asn::parse_origin("NA | 192.0.2.0/24"));
+        assert_eq!(
+            Some("GOOGLE, US".to_owned()),
+            asn::parse_owner("15169 | US | arin | 2000-03-30 | GOOGLE, US")
+        );
+        assert_eq!(None, asn::parse_owner("15169 | US | arin | 2000-03-30 | "));
+    }
+
+    test zone_walk_rdata() {
+        let nsec = b"\x03www\x07Example\x03com\x00\x00\x06\x40\x00\x00\x00\x00\x03";
+        assert_eq!(Some("www.example.com".to_owned()), librustbuster::dnsbuster::zone_walk::parse_name(nsec));
+        assert_eq!(None, librustbuster::dnsbuster::zone_walk::parse_name(b"\x03ww"));
+
+        let nsec3param = b"\x01\x00\x00\x0a\x04\xaa\xbb\xcc\xdd";
+        assert_eq!(Some(10), librustbuster::dnsbuster::zone_walk::parse_nsec3param(nsec3param));
+    }
+}
@@ -0,0 +1,36 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name downgrade;
+
+    use librustbuster::downgrade::{compare, is_candidate, DowngradeFinding};
+
+    test candidates() {
+        assert!(is_candidate("403 Forbidden"));
+        assert!(is_candidate("404 Not Found"));
+        assert!(!is_candidate("200 OK"));
+        assert!(!is_candidate("401 Unauthorized"));
+    }
+
+    test downgraded_response() {
+        let url = "http://localhost/admin";
+        assert_eq!(None, compare(url, "HTTP/1.0", ("403 Forbidden", 10), ("403 Forbidden", 10)));
+        assert_eq!(None, compare(url, "HTTP/1.0", ("404 Not Found", 300), ("404 Not Found", 320)));
+        assert_eq!(None, compare(url, "HTTP/1.0", ("404 Not Found", 4000), ("404 Not Found", 4150)));
+        assert!(compare(url, "HTTP/1.0", ("404 Not Found", 4000), ("404 Not Found", 4300)).is_some());
+        assert!(compare(url, "HTTP/1.0", ("404 Not Found", 300), ("403 Forbidden", 300)).is_some());
+
+        let expected = DowngradeFinding {
+            url: url.to_owned(),
+            variant: "HTTP/1.0 without Host".to_owned(),
+            status: "403 Forbidden".to_owned(),
+            length: 10,
+            downgraded_status: "200 OK".to_owned(),
+            downgraded_length: 512,
+        };
+        assert_eq!(
+            Some(expected),
+            compare(url, "HTTP/1.0 without Host", ("403 Forbidden", 10), ("200 OK", 512))
+        );
+    }
+}
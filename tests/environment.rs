@@ -0,0 +1,39 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name environment;
+
+    use librustbuster::environment::{arguments, env_name, EnvArg};
+
+    test env_arguments() {
+        assert_eq!("RUSTBUSTER_NO_BANNER", env_name("no-banner"));
+
+        let specs = vec![
+            EnvArg::flag("no-banner", None),
+            EnvArg::flag("ignore-certificate", Some('k')),
+            EnvArg::value("threads", Some('t')),
+            EnvArg::values("http-header", Some('H')),
+            EnvArg::value("url", Some('u')),
+        ];
+        let env = |name: &str| match name {
+            "RUSTBUSTER_NO_BANNER" => Some("true".to_owned()),
+            "RUSTBUSTER_IGNORE_CERTIFICATE" => Some("1".to_owned()),
+            "RUSTBUSTER_THREADS" => Some("50".to_owned()),
+            "RUSTBUSTER_HTTP_HEADER" => Some("X-A: 1\nX-B: 2".to_owned()),
+            "RUSTBUSTER_URL" => Some("http://localhost/".to_owned()),
+            _ => None,
+        };
+
+        let argv: Vec<String> = vec![];
+        assert_eq!(
+            vec!["--no-banner", "--ignore-certificate", "--threads=50", "--http-header=X-A: 1", "--http-header=X-B: 2", "--url=http://localhost/"],
+            arguments(&specs, &argv, env)
+        );
+
+        let argv: Vec<String> = vec!["-kt4".to_owned(), "--url=http://target/".to_owned()];
+        assert_eq!(
+            vec!["--no-banner", "--http-header=X-A: 1", "--http-header=X-B: 2"],
+            arguments(&specs, &argv, env)
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name export;
+
+    use librustbuster::{export, severity::Severity};
+    use std::collections::BTreeMap;
+
+    fixture results() -> BTreeMap<String, Vec<serde_json::Value>> {
+        setup(&mut self) {
+            let mut results = BTreeMap::new();
+            results.insert("dir".to_owned(), vec![
+                serde_json::json!({"method": "GET", "url": "https://example.com:8443/.git/HEAD", "status": "200 OK", "error": null, "severity": "high"}),
+                serde_json::json!({"method": "GET", "url": "https://example.com:8443/down", "status": "200 OK", "error": "timeout"}),
+            ]);
+            results.insert("dns".to_owned(), vec![
+                serde_json::json!({"domain": "www.example.com:80", "status": true, "extra": ["192.0.2.1:80"]}),
+            ]);
+            results
+        }
+    }
+
+    test findings(results) {
+        let findings = export::findings(&results.val);
+        assert_eq!(2, findings.len());
+
+        let dir = &findings[0];
+        assert_eq!("rustbuster dir: GET 200 OK https://example.com:8443/.git/HEAD", dir.title);
+        assert_eq!(Severity::High, dir.severity);
+        assert_eq!((Some("https".to_owned()), "example.com", Some(8443), "/.git/HEAD"),
+            (dir.protocol.clone(), dir.host.as_str(), dir.port, dir.path.as_str()));
+
+        let dns = &findings[1];
+        assert_eq!(("www.example.com", None, Severity::Info), (dns.host.as_str(), dns.port, dns.severity));
+        assert_eq!(vec!["192.0.2.1".to_owned()], dns.addresses);
+    }
+
+    test defectdojo(results) {
+        let report = export::defectdojo(&export::findings(&results.val), "2020-01-01");
+        let finding = &report["findings"][0];
+        assert_eq!("High", finding["severity"]);
+        assert_eq!("2020-01-01", finding["date"]);
+        assert_eq!(serde_json::json!({"host": "example.com", "protocol": "https", "port": 8443, "path": ".git/HEAD"}),
+            finding["endpoints"][0]);
+        assert_eq!(serde_json::json!([{"host": "www.example.com"}]), report["findings"][1]["endpoints"]);
+    }
+
+    test faraday(results) {
+        let report = export::faraday(&export::findings(&results.val));
+        let hosts = report["hosts"].as_array().unwrap();
+        assert_eq!(2, hosts.len());
+        assert_eq!("192.0.2.1", hosts[0]["ip"]);
+        assert_eq!(serde_json::json!(["www.example.com"]), hosts[0]["hostnames"]);
+
+        let service = &hosts[1]["services"][0];
+        assert_eq!(8443, service["port"]);
+        assert_eq!("https", service["name"]);
+        assert_eq!("high", service["vulnerabilities"][0]["severity"]);
+        assert_eq!(200, service["vulnerabilities"][0]["status_code"]);
+        assert_eq!("/.git/HEAD", service["vulnerabilities"][0]["path"]);
+    }
+}
@@ -0,0 +1,30 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name family;
+
+    use librustbuster::family::{order_by_family, Family};
+    use std::net::{IpAddr, SocketAddr};
+
+    test parse_family() {
+        assert_eq!(Some(Family::V4), Family::parse("4"));
+        assert_eq!(Some(Family::V6), Family::parse(" 6"));
+        assert_eq!(None, Family::parse("5"));
+    }
+
+    test order_addresses() {
+        let v4: IpAddr = "192.0.2.1".parse().unwrap();
+        let v4_bis: IpAddr = "192.0.2.2".parse().unwrap();
+        let v6: IpAddr = "2001:db8::1".parse().unwrap();
+
+        let mut addrs = vec![v4, v6, v4_bis];
+        order_by_family(&mut addrs, Some(Family::V6), |ip| *ip);
+        assert_eq!(vec![v6, v4, v4_bis], addrs);
+        order_by_family(&mut addrs, Some(Family::V4), |ip| *ip);
+        assert_eq!(vec![v4, v4_bis, v6], addrs);
+
+        let mut addrs = vec![SocketAddr::new(v6, 80), SocketAddr::new(v4, 80)];
+        order_by_family(&mut addrs, None, SocketAddr::ip);
+        assert_eq!(v6, addrs[0].ip());
+    }
+}
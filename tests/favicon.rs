@@ -0,0 +1,23 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name favicon;
+
+    use librustbuster::favicon::{favicon_hash, identify, murmur3_32};
+
+    test murmur3_vectors() {
+        assert_eq!(-156908512, murmur3_32(b"foo", 0) as i32);
+        assert_eq!(613153351, murmur3_32(b"hello", 0));
+        assert_eq!(0, murmur3_32(b"", 0));
+    }
+
+    test shodan_style_hash() {
+        let data: Vec<u8> = (0..=255u8).cycle().take(768).collect();
+        assert_eq!(1836528006, favicon_hash(&data));
+    }
+
+    test identify_known_hash() {
+        assert_eq!(Some("Spring Boot"), identify(116323821));
+        assert_eq!(None, identify(0));
+    }
+}
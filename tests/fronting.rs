@@ -0,0 +1,59 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name fronting;
+
+    use librustbuster::fronting::{combinations, mark_distinct, FrontingResult};
+
+    fn result(status: &str, hash: u32, error: Option<&str>) -> FrontingResult {
+        FrontingResult {
+            sni: None,
+            host: None,
+            status: status.to_owned(),
+            length: 0,
+            hash,
+            distinct: false,
+            error: error.map(|e| e.to_owned()),
+        }
+    }
+
+    test sni_host_combinations() {
+        let domains = vec!["CDN.example.com".to_owned(), "cdn.example.com".to_owned()];
+        let tls = combinations("origin.example.com", &domains, true);
+        assert_eq!(9, tls.len());
+        assert_eq!(
+            (Some("origin.example.com".to_owned()), Some("origin.example.com".to_owned())),
+            tls[0]
+        );
+        assert!(tls.contains(&(Some("origin.example.com".to_owned()), Some("cdn.example.com".to_owned()))));
+        assert!(tls.contains(&(None, Some("cdn.example.com".to_owned()))));
+        assert!(tls.contains(&(Some("cdn.example.com".to_owned()), None)));
+
+        let plain = combinations("10.0.0.1", &domains, false);
+        assert_eq!(
+            vec![
+                (None, Some("10.0.0.1".to_owned())),
+                (None, Some("cdn.example.com".to_owned())),
+                (None, None),
+            ],
+            plain
+        );
+    }
+
+    test distinct_content() {
+        let mut results = vec![
+            result("200 OK", 1, None),
+            result("200 OK", 1, None),
+            result("200 OK", 2, None),
+            result("403 Forbidden", 1, None),
+            result("", 0, Some("connection reset")),
+        ];
+        mark_distinct(&mut results);
+        let distinct: Vec<bool> = results.iter().map(|r| r.distinct).collect();
+        assert_eq!(vec![false, false, true, true, false], distinct);
+
+        let mut results = vec![result("", 0, Some("handshake failed")), result("200 OK", 1, None)];
+        mark_distinct(&mut results);
+        assert!(results[1].distinct);
+    }
+}
@@ -6,24 +6,16 @@ test_suite! {
     fixture fuzzbuster_url_single() -> librustbuster::fuzzbuster::FuzzBuster {
         setup(&mut self) {
             librustbuster::fuzzbuster::FuzzBuster {
-                n_threads: 1,
                 ignore_certificate: true,
-                http_method: "GET".to_owned(),
-                http_body: "body".to_owned(),
+                http_body: b"body".to_vec(),
                 user_agent: "ua".to_owned(),
                 http_headers: vec![("Header".to_owned(), "Value".to_owned())],
                 wordlist_paths: vec!["./examples/wordlist_short".to_owned()],
                 url: "http://localhost/FUZZ".to_owned(),
-                include_status_codes: vec![],
                 ignore_status_codes: vec!["404".to_owned()],
-                include_body: vec![],
-                ignore_body: vec![],
                 no_progress_bar: true,
-                exit_on_connection_errors: false,
-                output: "".to_owned(),
-                csrf_url: None,
-                csrf_regex: None,
-                csrf_headers: None,
+                certificate: false,
+                ..Default::default()
             }
         }
     }
@@ -31,24 +23,16 @@ test_suite! {
     fixture fuzzbuster_url_multiple() -> librustbuster::fuzzbuster::FuzzBuster {
         setup(&mut self) {
             librustbuster::fuzzbuster::FuzzBuster {
-                n_threads: 1,
                 ignore_certificate: true,
-                http_method: "GET".to_owned(),
-                http_body: "body".to_owned(),
+                http_body: b"body".to_vec(),
                 user_agent: "ua".to_owned(),
                 http_headers: vec![("Header".to_owned(), "Value".to_owned())],
                 wordlist_paths: vec!["./examples/wordlist_short".to_owned(), "./examples/wordlist_short".to_owned()],
                 url: "http://localhost/FUZZ/FUZZ".to_owned(),
-                include_status_codes: vec![],
                 ignore_status_codes: vec!["404".to_owned()],
-                include_body: vec![],
-                ignore_body: vec![],
                 no_progress_bar: true,
-                exit_on_connection_errors: false,
-                output: "".to_owned(),
-                csrf_url: None,
-                csrf_regex: None,
-                csrf_headers: None,
+                certificate: false,
+                ..Default::default()
             }
         }
     }
@@ -56,24 +40,16 @@ test_suite! {
     fixture fuzzbuster_header_single() -> librustbuster::fuzzbuster::FuzzBuster {
         setup(&mut self) {
             librustbuster::fuzzbuster::FuzzBuster {
-                n_threads: 1,
                 ignore_certificate: true,
-                http_method: "GET".to_owned(),
-                http_body: "body".to_owned(),
+                http_body: b"body".to_vec(),
                 user_agent: "ua".to_owned(),
                 http_headers: vec![("Header".to_owned(), "FUZZ".to_owned())],
                 wordlist_paths: vec!["./examples/wordlist_short".to_owned()],
                 url: "http://localhost/".to_owned(),
-                include_status_codes: vec![],
                 ignore_status_codes: vec!["404".to_owned()],
-                include_body: vec![],
-                ignore_body: vec![],
                 no_progress_bar: true,
-                exit_on_connection_errors: false,
-                output: "".to_owned(),
-                csrf_url: None,
-                csrf_regex: None,
-                csrf_headers: None,
+                certificate: false,
+                ..Default::default()
             }
         }
     }
@@ -81,24 +57,16 @@ test_suite! {
     fixture fuzzbuster_header_multiple() -> librustbuster::fuzzbuster::FuzzBuster {
         setup(&mut self) {
             librustbuster::fuzzbuster::FuzzBuster {
-                n_threads: 1,
                 ignore_certificate: true,
-                http_method: "GET".to_owned(),
-                http_body: "body".to_owned(),
+                http_body: b"body".to_vec(),
                 user_agent: "ua".to_owned(),
                 http_headers: vec![("FUZZ".to_owned(), "FUZZ".to_owned())],
                 wordlist_paths: vec!["./examples/wordlist_short".to_owned(), "./examples/wordlist_short".to_owned()],
                 url: "http://localhost/".to_owned(),
-                include_status_codes: vec![],
                 ignore_status_codes: vec!["404".to_owned()],
-                include_body: vec![],
-                ignore_body: vec![],
                 no_progress_bar: true,
-                exit_on_connection_errors: false,
-                output: "".to_owned(),
-                csrf_url: None,
-                csrf_regex: None,
-                csrf_headers: None,
+                certificate: false,
+                ..Default::default()
             }
         }
     }
@@ -106,24 +74,16 @@ test_suite! {
     fixture fuzzbuster_body_single() -> librustbuster::fuzzbuster::FuzzBuster {
         setup(&mut self) {
             librustbuster::fuzzbuster::FuzzBuster {
-                n_threads: 1,
                 ignore_certificate: true,
-                http_method: "GET".to_owned(),
-                http_body: "FUZZ".to_owned(),
+                http_body: b"FUZZ".to_vec(),
                 user_agent: "ua".to_owned(),
                 http_headers: vec![("Header".to_owned(), "Value".to_owned())],
                 wordlist_paths: vec!["./examples/wordlist_short".to_owned()],
                 url: "http://localhost/".to_owned(),
-                include_status_codes: vec![],
                 ignore_status_codes: vec!["404".to_owned()],
-                include_body: vec![],
-                ignore_body: vec![],
                 no_progress_bar: true,
-                exit_on_connection_errors: false,
-                output: "".to_owned(),
-                csrf_url: None,
-                csrf_regex: None,
-                csrf_headers: None,
+                certificate: false,
+                ..Default::default()
             }
         }
     }
@@ -131,24 +91,16 @@ test_suite! {
     fixture fuzzbuster_body_multiple() -> librustbuster::fuzzbuster::FuzzBuster {
         setup(&mut self) {
             librustbuster::fuzzbuster::FuzzBuster {
-                n_threads: 1,
                 ignore_certificate: true,
-                http_method: "GET".to_owned(),
-                http_body: "FUZZ:FUZZ".to_owned(),
+                http_body: b"FUZZ:FUZZ".to_vec(),
                 user_agent: "ua".to_owned(),
                 http_headers: vec![("Header".to_owned(), "Value".to_owned())],
                 wordlist_paths: vec!["./examples/wordlist_short".to_owned(), "./examples/wordlist_short".to_owned()],
                 url: "http://localhost/".to_owned(),
-                include_status_codes: vec![],
                 ignore_status_codes: vec!["404".to_owned()],
-                include_body: vec![],
-                ignore_body: vec![],
                 no_progress_bar: true,
-                exit_on_connection_errors: false,
-                output: "".to_owned(),
-                csrf_url: None,
-                csrf_regex: None,
-                csrf_headers: None,
+                certificate: false,
+                ..Default::default()
             }
         }
     }
@@ -159,7 +111,7 @@ test_suite! {
                 uri: "http://localhost/".parse::<hyper::Uri>().unwrap(),
                 http_method: "GET".to_owned(),
                 http_headers: vec![],
-                http_body: "CSRFCSRF".to_owned(),
+                http_body: b"CSRFCSRF".to_vec(),
                 user_agent: "ua".to_owned(),
                 payload: vec!["1".to_owned()],
                 csrf_uri: Some("http://localhost/".parse::<hyper::Uri>().unwrap()),
@@ -175,7 +127,7 @@ test_suite! {
                 uri: "http://localhost/".parse::<hyper::Uri>().unwrap(),
                 http_method: "GET".to_owned(),
                 http_headers: vec![("X-CSRF-Token".to_owned(), "CSRFCSRF".to_owned())],
-                http_body: "body".to_owned(),
+                http_body: b"body".to_vec(),
                 user_agent: "ua".to_owned(),
                 payload: vec!["1".to_owned()],
                 csrf_uri: Some("http://localhost/".parse::<hyper::Uri>().unwrap()),
@@ -191,7 +143,7 @@ test_suite! {
                 uri: "http://localhost/CSRFCSRF".parse::<hyper::Uri>().unwrap(),
                 http_method: "GET".to_owned(),
                 http_headers: vec![],
-                http_body: "body".to_owned(),
+                http_body: b"body".to_vec(),
                 user_agent: "ua".to_owned(),
                 payload: vec!["1".to_owned()],
                 csrf_uri: Some("http://localhost/".parse::<hyper::Uri>().unwrap()),
@@ -213,8 +165,8 @@ test_suite! {
     test build_requests_fuzz_url_single(fuzzbuster_url_single()) {
         let requests = fuzzbuster_url_single.val.build_requests();
         let expected: Vec<librustbuster::fuzzbuster::FuzzRequest> = vec![
-            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/1".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: "body".to_owned(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
-            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/2".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: "body".to_owned(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/1".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/2".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
         ];
         assert_eq!(expected, requests);
     }
@@ -222,10 +174,10 @@ test_suite! {
     test build_requests_fuzz_url_multiple(fuzzbuster_url_multiple()) {
         let requests = fuzzbuster_url_multiple.val.build_requests();
         let expected: Vec<librustbuster::fuzzbuster::FuzzRequest> = vec![
-            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/1/1".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: "body".to_owned(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
-            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/1/2".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: "body".to_owned(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
-            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/2/1".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: "body".to_owned(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
-            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/2/2".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: "body".to_owned(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None }
+            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/1/1".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/1/2".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/2/1".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/2/2".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None }
         ];
         assert_eq!(expected, requests);
     }
@@ -233,8 +185,8 @@ test_suite! {
     test build_requests_fuzz_header_single(fuzzbuster_header_single()) {
         let requests = fuzzbuster_header_single.val.build_requests();
         let expected: Vec<librustbuster::fuzzbuster::FuzzRequest> = vec![
-            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "1".to_owned())], http_body: "body".to_owned(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
-            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "2".to_owned())], http_body: "body".to_owned(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "1".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "2".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
         ];
         assert_eq!(expected, requests);
     }
@@ -242,10 +194,10 @@ test_suite! {
     test build_requests_fuzz_header_multiple(fuzzbuster_header_multiple()) {
         let requests = fuzzbuster_header_multiple.val.build_requests();
         let expected: Vec<librustbuster::fuzzbuster::FuzzRequest> = vec![
-            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("1".to_owned(), "1".to_owned())], http_body: "body".to_owned(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
-            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("1".to_owned(), "2".to_owned())], http_body: "body".to_owned(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
-            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("2".to_owned(), "1".to_owned())], http_body: "body".to_owned(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
-            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("2".to_owned(), "2".to_owned())], http_body: "body".to_owned(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("1".to_owned(), "1".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("1".to_owned(), "2".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("2".to_owned(), "1".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("2".to_owned(), "2".to_owned())], http_body: b"body".to_vec(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
         ];
         assert_eq!(expected, requests);
     }
@@ -253,8 +205,8 @@ test_suite! {
     test build_requests_fuzz_body_single(fuzzbuster_body_single()) {
         let requests = fuzzbuster_body_single.val.build_requests();
         let expected: Vec<librustbuster::fuzzbuster::FuzzRequest> = vec![
-            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: "1".to_owned(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
-            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: "2".to_owned(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"1".to_vec(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"2".to_vec(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
         ];
         assert_eq!(expected, requests);
     }
@@ -262,24 +214,133 @@ test_suite! {
     test build_requests_fuzz_body_multiple(fuzzbuster_body_multiple()) {
         let requests = fuzzbuster_body_multiple.val.build_requests();
         let expected: Vec<librustbuster::fuzzbuster::FuzzRequest> = vec![
-            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: "1:1".to_owned(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
-            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: "1:2".to_owned(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
-            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: "2:1".to_owned(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
-            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: "2:2".to_owned(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"1:1".to_vec(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"1:2".to_vec(), user_agent: "ua".to_owned(), payload: vec!["1".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"2:1".to_vec(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "1".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
+            librustbuster::fuzzbuster::FuzzRequest { uri: "http://localhost/".parse::<hyper::Uri>().unwrap(), http_method: "GET".to_owned(), http_headers: vec![("Header".to_owned(), "Value".to_owned())], http_body: b"2:2".to_vec(), user_agent: "ua".to_owned(), payload: vec!["2".to_owned(), "2".to_owned()], csrf_uri: None, csrf_regex: None, csrf_headers: None },
         ];
         assert_eq!(expected, requests);
     }
 
+    test calibration_requests_random_payloads(fuzzbuster_url_multiple()) {
+        let requests = fuzzbuster_url_multiple.val.calibration_requests();
+        assert_eq!(librustbuster::fuzzbuster::calibrate::SAMPLES, requests.len());
+        for request in requests.iter() {
+            assert_eq!(2, request.payload.len());
+            assert_eq!(12, request.payload[0].len());
+            assert_eq!(format!("/{}/{}", request.payload[0], request.payload[1]), request.uri.path());
+        }
+    }
+
+    test baseline_deviations() {
+        use librustbuster::fuzzbuster::calibrate::{Baseline, Sample};
+
+        let samples = vec![
+            Sample::new("404 Not Found", &"not found ".repeat(100), 20),
+            Sample::new("404 Not Found", &"not found ".repeat(101), 30),
+            Sample::new("404 Not Found", &"not found ".repeat(99), 25),
+        ];
+        let baseline = Baseline::new(&samples).unwrap();
+        assert_eq!(vec![404], baseline.statuses);
+        assert!(baseline.deviations(&Sample::new("404 Not Found", &"not found ".repeat(100), 40)).is_empty());
+        assert_eq!(vec!["status"], baseline.deviations(&Sample::new("200 OK", &"not found ".repeat(100), 25)));
+        assert_eq!(vec!["length", "words"], baseline.deviations(&Sample::new("404 Not Found", &"not found ".repeat(200), 25)));
+        assert_eq!(vec!["latency"], baseline.deviations(&Sample::new("404 Not Found", &"not found ".repeat(100), 5000)));
+        assert_eq!(None, Baseline::new(&[]));
+    }
+
+    test build_requests_hex_wordlist(fuzzbuster_body_single()) {
+        let mut fuzzbuster = fuzzbuster_body_single.val;
+        fuzzbuster.hex_wordlists = true;
+        fuzzbuster.url = "http://localhost/FUZZ".to_owned();
+        fuzzbuster.http_body = b"len=\x00\x02FUZZ\xfe".to_vec();
+        fuzzbuster.wordlist_paths = vec!["inline:00ff,zz,0x2f 41".to_owned()];
+        let requests = fuzzbuster.build_requests();
+        assert_eq!(2, requests.len());
+        assert_eq!("/%00%FF", requests[0].uri.path());
+        assert_eq!(vec!["00ff".to_owned()], requests[0].payload);
+        assert_eq!(b"len=\x00\x02\x00\xff\xfe".to_vec(), requests[0].http_body);
+        assert_eq!(b"len=\x00\x02/A\xfe".to_vec(), requests[1].http_body);
+        assert_eq!("//A", requests[1].uri.path());
+    }
+
+    test decode_hex() {
+        use librustbuster::fuzzbuster::utils::decode_hex;
+
+        assert_eq!(Some(b"A\x00\xff".to_vec()), decode_hex("4100ff"));
+        assert_eq!(Some(b"AB".to_vec()), decode_hex("\\x41\\x42"));
+        assert_eq!(Some(Vec::new()), decode_hex(""));
+        assert_eq!(None, decode_hex("abc"));
+        assert_eq!(None, decode_hex("zz"));
+    }
+
+    test generator_words() {
+        use librustbuster::fuzzbuster::generator::Generator;
+
+        let generator = Generator::parse("1:2:ab!").unwrap();
+        assert_eq!(27, generator.charset.len());
+        assert_eq!(27 + 27 * 27, generator.count());
+        let words: Vec<String> = generator.words().collect();
+        assert_eq!(generator.count() as usize, words.len());
+        assert_eq!(vec!["a", "b", "c"], words[..3].to_vec());
+        assert_eq!("!", words[26]);
+        assert_eq!(vec!["aa", "ab"], words[27..29].to_vec());
+        assert_eq!("!!", words[words.len() - 1]);
+
+        let digits: Vec<String> = Generator::parse("2:2:1").unwrap().words().collect();
+        assert_eq!(100, digits.len());
+        assert_eq!("00", digits[0]);
+        assert_eq!("99", digits[99]);
+    }
+
+    test generator_parse_errors() {
+        use librustbuster::fuzzbuster::generator::Generator;
+
+        assert!(Generator::parse("4:6").is_err());
+        assert!(Generator::parse("0:2:a").is_err());
+        assert!(Generator::parse("3:2:a").is_err());
+        assert!(Generator::parse("x:2:a").is_err());
+        assert!(Generator::parse("1:2:").is_err());
+        assert_eq!(vec![':', 'x'], Generator::parse("1:1::x").unwrap().charset);
+        assert_eq!(u64::MAX, Generator::parse("1:64:aA1").unwrap().count());
+    }
+
+    test build_requests_generator(fuzzbuster_url_multiple()) {
+        let mut fuzzbuster = fuzzbuster_url_multiple.val;
+        fuzzbuster.wordlist_paths = vec!["./examples/wordlist_short".to_owned()];
+        fuzzbuster.generator = Some(librustbuster::fuzzbuster::generator::Generator::parse("1:1:xy").unwrap());
+        let paths: Vec<String> = fuzzbuster.build_requests().iter().map(|r| r.uri.path().to_owned()).collect();
+        assert_eq!(vec!["/1/x", "/1/y", "/2/x", "/2/y"], paths);
+    }
+
     test split_http_headers() {
         let header = "Header: Value";
         let expected = ("Header".to_owned(), "Value".to_owned());
         assert_eq!(expected, librustbuster::fuzzbuster::utils::split_http_headers(header));
     }
 
+    test build_multipart_body() {
+        let forms = vec!["user=FUZZ".to_owned()];
+        let expected = "--b\r\nContent-Disposition: form-data; name=\"user\"\r\n\r\nFUZZ\r\n--b--\r\n";
+        assert_eq!(Ok(expected.as_bytes().to_vec()), librustbuster::fuzzbuster::utils::build_multipart_body(&forms, "b"));
+    }
+
+    test build_multipart_body_binary_file() {
+        let path = std::env::temp_dir().join("rustbuster_multipart.png");
+        std::fs::write(&path, b"\x89PNG\x00\xff").unwrap();
+        let forms = vec![format!("avatar=@{};type=image/png", path.display())];
+        let body = librustbuster::fuzzbuster::utils::build_multipart_body(&forms, "b").unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let mut expected = b"--b\r\nContent-Disposition: form-data; name=\"avatar\"; filename=\"rustbuster_multipart.png\"\r\nContent-Type: image/png\r\n\r\n".to_vec();
+        expected.extend_from_slice(b"\x89PNG\x00\xff\r\n--b--\r\n");
+        assert_eq!(expected, body);
+    }
+
     test replace_csrf_body(fuzzrequest_csrf_body) {
         let request = fuzzrequest_csrf_body.val;
         let actual = librustbuster::fuzzbuster::FuzzBuster::replace_csrf(request, "VALUE".to_owned());
-        assert_eq!("VALUE", actual.http_body);
+        assert_eq!(b"VALUE".to_vec(), actual.http_body);
     }
 
     test replace_csrf_header(fuzzrequest_csrf_header) {
@@ -0,0 +1,54 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name geoip;
+
+    use librustbuster::geoip::{GeoInfo, GeoIp};
+    use maxminddb::geoip2::{city, City};
+    use std::collections::BTreeMap;
+
+    fn names(name: &'static str) -> Option<BTreeMap<&'static str, &'static str>> {
+        let mut names = BTreeMap::new();
+        names.insert("en", name);
+        Some(names)
+    }
+
+    test geo_info_from_city() {
+        let ip = "192.0.2.1".parse().unwrap();
+        let mut record = City {
+            city: Some(city::City { geoname_id: None, names: names("Lyon") }),
+            continent: None,
+            country: None,
+            location: None,
+            postal: None,
+            registered_country: Some(city::Country {
+                geoname_id: None,
+                is_in_european_union: Some(true),
+                iso_code: Some("FR"),
+                names: names("France"),
+            }),
+            represented_country: None,
+            subdivisions: None,
+            traits: None,
+        };
+
+        let expected = GeoInfo {
+            ip,
+            country: Some("FR".to_owned()),
+            country_name: Some("France".to_owned()),
+            city: Some("Lyon".to_owned()),
+        };
+        assert_eq!(Some(expected), GeoInfo::from_city(ip, &record));
+
+        record.city = None;
+        record.registered_country = None;
+        assert_eq!(None, GeoInfo::from_city(ip, &record));
+    }
+
+    test open_database() {
+        let geoip = GeoIp::open("").unwrap();
+        assert!(!geoip.is_enabled());
+        assert_eq!(None, geoip.lookup("192.0.2.1".parse().unwrap()));
+        assert!(GeoIp::open("/nonexistent/GeoLite2-City.mmdb").is_none());
+    }
+}
@@ -0,0 +1,15 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name graphql;
+
+    test parse_introspection() {
+        let body = r#"{"data":{"__schema":{"queryType":{"name":"Query"},"mutationType":null,"types":[{"name":"Query","fields":[{"name":"users"}]},{"name":"User","fields":[{"name":"id"}]}]}}}"#;
+        let expected = librustbuster::graphql::GraphqlSchema {
+            queries: vec!["users".to_owned()],
+            mutations: vec![],
+        };
+        assert_eq!(Some(expected), librustbuster::graphql::parse_introspection(body));
+        assert_eq!(None, librustbuster::graphql::parse_introspection(r#"{"errors":[{"message":"introspection disabled"}]}"#));
+    }
+}
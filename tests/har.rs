@@ -0,0 +1,71 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name har;
+
+    use librustbuster::har::{raw_request, request_file_name, HarRequest, HarResponse};
+
+    test raw_request_adds_the_headers_hyper_sends() {
+        let request = hyper::Request::builder()
+            .method("POST")
+            .uri("http://localhost:8080/login?next=/admin")
+            .header("User-Agent", "rustbuster")
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(())
+            .unwrap();
+        let request = HarRequest::new(&request, b"user=admin&pass=admin");
+
+        assert_eq!(
+            "POST /login?next=/admin HTTP/1.1\r\n\
+             Host: localhost:8080\r\n\
+             user-agent: rustbuster\r\n\
+             content-type: application/x-www-form-urlencoded\r\n\
+             Content-Length: 21\r\n\
+             \r\n\
+             user=admin&pass=admin"
+                .as_bytes(),
+            &raw_request(&request)[..]
+        );
+        assert_eq!(
+            "0003-POST-localhost_8080_login_next__admin.txt",
+            request_file_name(2, &request)
+        );
+    }
+
+    test raw_request_keeps_the_sent_host() {
+        let request = hyper::Request::builder()
+            .uri("http://127.0.0.1/")
+            .header("Host", "admin.example.com")
+            .body(())
+            .unwrap();
+
+        assert_eq!(
+            &b"GET / HTTP/1.1\r\nhost: admin.example.com\r\n\r\n"[..],
+            &raw_request(&HarRequest::new(&request, b""))[..]
+        );
+    }
+
+    test binary_bodies_are_kept_as_base64() {
+        let request = hyper::Request::builder()
+            .method("POST")
+            .uri("http://localhost/upload")
+            .body(())
+            .unwrap();
+        let body = [0x89, b'P', b'N', b'G', 0x00, 0xff];
+        let request = HarRequest::new(&request, &body);
+        let post_data = request.post_data.clone().unwrap();
+
+        assert_eq!(Some("base64".to_owned()), post_data.encoding);
+        assert_eq!("iVBORwD/", post_data.text);
+        assert_eq!(&body[..], &post_data.bytes()[..]);
+        assert!(raw_request(&request).ends_with(&body));
+
+        let (parts, _) = hyper::Response::builder()
+            .body(())
+            .unwrap()
+            .into_parts();
+        let response = HarResponse::new(&parts, b"plain");
+        assert_eq!(None, response.content.encoding);
+        assert!(!serde_json::to_string(&response).unwrap().contains("encoding"));
+    }
+}
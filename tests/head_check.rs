@@ -0,0 +1,23 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name head_check;
+
+    use librustbuster::head_check::{discrepancy, LengthDiscrepancy};
+
+    test content_length_mismatch() {
+        let mut headers = hyper::HeaderMap::new();
+        assert_eq!(None, discrepancy("http://localhost/admin", &headers, 120));
+
+        headers.insert("Content-Length", "120".parse().unwrap());
+        assert_eq!(None, discrepancy("http://localhost/admin", &headers, 120));
+
+        headers.insert("Content-Length", "4096".parse().unwrap());
+        let expected = LengthDiscrepancy {
+            url: "http://localhost/admin".to_owned(),
+            head_length: 4096,
+            get_length: 120,
+        };
+        assert_eq!(Some(expected), discrepancy("http://localhost/admin", &headers, 120));
+    }
+}
@@ -0,0 +1,71 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name import;
+
+    use librustbuster::import::{exchanges, merge, wordlists};
+
+    const BURP: &str = r#"<?xml version="1.0"?>
+<items burpVersion="2023.1">
+  <item>
+    <url><![CDATA[https://app.example.com/api/v1/users?id=1&sort=name]]></url>
+    <host ip="192.0.2.10">app.example.com</host>
+    <method><![CDATA[GET]]></method>
+    <request base64="false"><![CDATA[GET /api/v1/users?id=1&sort=name HTTP/1.1
+Host: app.example.com
+
+]]></request>
+  </item>
+  <item>
+    <url><![CDATA[https://app.example.com/api/login]]></url>
+    <request base64="true"><![CDATA[UE9TVCAvYXBpL2xvZ2luIEhUVFAvMS4xCkhvc3Q6IGFwcC5leGFtcGxlLmNvbQpDb250ZW50LVR5cGU6IGFwcGxpY2F0aW9uL2pzb24KCnsidXNlciI6eyJuYW1lIjoiYSJ9LCJwYXNzd29yZCI6ImIifQ==]]></request>
+  </item>
+  <item>
+    <url><![CDATA[https://cdn.example.net/static/app.js?v=3]]></url>
+  </item>
+</items>"#;
+
+    test burp_export() {
+        let (paths, params) = wordlists(&exchanges(BURP), "APP.example.com");
+        assert_eq!(vec!["api", "api/v1", "api/v1/users", "api/login"], paths);
+        assert_eq!(vec!["id", "sort", "password", "user", "name"], params);
+    }
+
+    test zap_reports() {
+        let xml = r#"<OWASPZAPReport version="2.14.0"><site name="https://app.example.com" host="app.example.com">
+<alerts><alertitem><instances><instance>
+<uri>https://app.example.com/search?q=x&amp;page=2</uri><method>GET</method><param>q</param>
+</instance></instances></alertitem></alerts></site></OWASPZAPReport>"#;
+        assert_eq!(
+            (vec!["search".to_owned()], vec!["q".to_owned(), "page".to_owned()]),
+            wordlists(&exchanges(xml), "")
+        );
+
+        let json = r#"{"site":[{"alerts":[{"instances":[{"uri":"https://app.example.com/admin/","param":"token"}]}]}],
+"messages":[{"requestHeader":"POST https://app.example.com/cart HTTP/1.1\r\n","requestBody":"item=1&qty=2"}]}"#;
+        assert_eq!(
+            (
+                vec!["cart".to_owned(), "admin".to_owned()],
+                vec!["item".to_owned(), "qty".to_owned(), "token".to_owned()]
+            ),
+            wordlists(&exchanges(json), "")
+        );
+
+        let urls = "https://app.example.com/a/b\nhttp://app.example.com/c?d=1\n";
+        assert_eq!(
+            (vec!["a".to_owned(), "a/b".to_owned(), "c".to_owned()], vec!["d".to_owned()]),
+            wordlists(&exchanges(urls), "")
+        );
+    }
+
+    test merge_wordlist() {
+        let path = std::env::temp_dir().join("rustbuster-import-merge.txt");
+        let path = path.to_str().unwrap();
+        std::fs::write(path, "admin\napi").unwrap();
+        let words = vec!["api".to_owned(), "api/v1".to_owned(), "admin".to_owned()];
+        assert_eq!(1, merge(path, &words).unwrap());
+        assert_eq!(0, merge(path, &words).unwrap());
+        assert_eq!("admin\napi\napi/v1\n", std::fs::read_to_string(path).unwrap());
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,61 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name interactive;
+
+    use librustbuster::interactive::{Console, LiveFilters, Seen};
+
+    test live_filters() {
+        let mut filters = LiveFilters::default();
+        assert!(!filters.hides_seen("404 Not Found", 12) && !filters.hides_body("not here"));
+
+        filters.apply("fc 404, 30").unwrap();
+        filters.apply("fs 1234").unwrap();
+        filters.apply("fr (?i)error").unwrap();
+        assert!(filters.hides_seen("404 Not Found", 0));
+        assert!(filters.hides_seen("301 Moved Permanently", 0));
+        assert!(filters.hides_seen("200 OK", 1234));
+        assert!(filters.hides_body("An ERROR occurred"));
+        assert!(!filters.hides_seen("200 OK", 12) && !filters.hides_body("welcome"));
+        assert_eq!("fc: 404,30\tfs: 1234\tfr: (?i)error", filters.describe());
+
+        filters.apply("fc").unwrap();
+        assert!(!filters.hides_seen("404 Not Found", 0));
+        assert!(filters.apply("fs big").is_err());
+        assert!(filters.apply("fr (").is_err());
+        assert!(filters.apply("mc 200").is_err());
+        assert!(filters.hides_seen("200 OK", 1234));
+        assert!(!filters.hides_seen("200 OK", 0));
+    }
+
+    test console_keeps_hidden_results() {
+        let console = Console::new(std::sync::Arc::new(librustbuster::ratelimit::Pacer::new(Vec::new())));
+        let seen = |url: &str, status: &str, length: usize| Seen {
+            method: "GET".to_owned(),
+            status: status.to_owned(),
+            url: url.to_owned(),
+            length,
+        };
+
+        console.apply("fc 404").unwrap();
+        console.apply("fr (?i)error").unwrap();
+        assert!(console.record(seen("http://example.com/missing", "404 Not Found", 9), "not found"));
+        assert!(console.record(seen("http://example.com/broken", "200 OK", 5), "Error"));
+        assert!(!console.record(seen("http://example.com/admin", "200 OK", 5), "admin"));
+
+        let mut results = vec![
+            ("http://example.com/missing", "404 Not Found", 9),
+            ("http://example.com/broken", "200 OK", 5),
+            ("http://example.com/admin", "200 OK", 5),
+        ];
+        console.apply("fc").unwrap();
+        console.apply("fr").unwrap();
+        results.retain(|(url, status, length)| !console.hides(url, status, *length));
+        assert_eq!(3, results.len());
+
+        console.apply("fr (?i)error").unwrap();
+        assert!(console.hides("http://example.com/broken", "200 OK", 5));
+        console.apply("fr fail").unwrap();
+        assert!(!console.hides("http://example.com/broken", "200 OK", 5));
+    }
+}
@@ -0,0 +1,24 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name logging;
+
+    use librustbuster::logging::json_record;
+    use log::{Level, Record};
+
+    test json_log_record() {
+        let line = json_record(
+            &Record::builder()
+                .args(format_args!("http://localhost/ - {:?}", "Connection refused"))
+                .level(Level::Error)
+                .target("rustbuster")
+                .build(),
+            "2019-05-01T10:00:00+02:00",
+        );
+        let record: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!("ERROR", record["level"]);
+        assert_eq!("rustbuster", record["target"]);
+        assert_eq!("2019-05-01T10:00:00+02:00", record["timestamp"]);
+        assert_eq!("http://localhost/ - \"Connection refused\"", record["message"]);
+    }
+}
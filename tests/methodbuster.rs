@@ -0,0 +1,48 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name methodbuster;
+
+    use librustbuster::methodbuster::result_processor::{MethodScanResult, SingleMethodScanResult};
+    use librustbuster::sink::ResultSink;
+
+    fn result(method: &str, status: &str, allow: Option<&str>) -> SingleMethodScanResult {
+        SingleMethodScanResult {
+            url: "http://localhost/a".to_owned(),
+            method: method.to_owned(),
+            status: status.to_owned(),
+            error: None,
+            extra: allow.map(|v| v.to_owned()),
+        }
+    }
+
+    test allowed_methods() {
+        let mut result_processor = MethodScanResult::new();
+        assert!(result_processor.maybe_add_result(result("OPTIONS", "200 OK", Some("GET, HEAD, OPTIONS"))));
+        assert!(result_processor.maybe_add_result(result("GET", "200 OK", None)));
+        assert!(result_processor.maybe_add_result(result("PUT", "201 Created", None)));
+        assert!(!result_processor.maybe_add_result(result("POST", "405 Method Not Allowed", None)));
+        assert_eq!(vec!["OPTIONS", "GET", "PUT", "HEAD"], result_processor.allowed("http://localhost/a"));
+        assert_eq!(Some(vec!["GET".to_owned(), "HEAD".to_owned(), "OPTIONS".to_owned()]), result_processor.advertised("http://localhost/a"));
+        assert!(result_processor.report().contains("Undeclared\t: PUT"));
+    }
+
+    test auth_and_errors_are_not_allowed() {
+        let mut result_processor = MethodScanResult::new();
+        assert!(!result_processor.maybe_add_result(result("GET", "400 Bad Request", None)));
+        assert!(result_processor.maybe_add_result(result("DELETE", "403 Forbidden", None)));
+        assert!(!result_processor.maybe_add_result(result("PATCH", "500 Internal Server Error", None)));
+        assert!(!result_processor.maybe_add_result(result("TRACE", "501 Not Implemented", None)));
+        let mut failed = result("PUT", "200 OK", None);
+        failed.error = Some("connection reset".to_owned());
+        result_processor.store(failed);
+
+        assert!(result_processor.allowed("http://localhost/a").is_empty());
+        assert_eq!(vec!["DELETE"], result_processor.auth("http://localhost/a"));
+        assert_eq!(vec!["PATCH", "PUT"], result_processor.errors("http://localhost/a"));
+        let report = result_processor.report();
+        assert!(!report.contains("Allowed"));
+        assert!(report.contains("Auth\t\t: DELETE"));
+        assert!(report.contains("Errors\t: PATCH, PUT"));
+    }
+}
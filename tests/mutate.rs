@@ -0,0 +1,24 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name mutate;
+
+    test form_mutations() {
+        let variants = librustbuster::mutate::mutations("id=5");
+        assert!(variants.contains(&"hd=5".to_owned()));
+        assert!(variants.contains(&"id=2147483648".to_owned()));
+        assert!(variants.contains(&"id=5%s%s%s%s%s".to_owned()));
+        assert!(variants.contains(&format!("id={}", "A".repeat(1024))));
+        assert!(!variants.contains(&"id=5".to_owned()));
+    }
+
+    test json_mutations() {
+        let variants = librustbuster::mutate::mutations(r#"{"user":"admin"}"#);
+        assert!(variants.contains(&r#"{"user":"admin'"}"#.to_owned()));
+        assert!(variants.contains(&r#"{"user":"admin{{7*7}}"}"#.to_owned()));
+        let mut unique = variants.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(variants.len(), unique.len());
+    }
+}
@@ -0,0 +1,36 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name negotiate;
+
+    use librustbuster::negotiate::{server_token, Negotiate};
+    use std::sync::Arc;
+
+    test token_without_ticket() {
+        // an empty credentials cache, the library is not always installed
+        std::env::set_var("KRB5CCNAME", "FILE:/nonexistent/rustbuster-krb5cc");
+        let negotiate = match Negotiate::load(None) {
+            Ok(negotiate) => Arc::new(negotiate),
+            Err(e) => {
+                assert!(e.contains("libgssapi_krb5"));
+                return;
+            }
+        };
+
+        let error = negotiate.start("localhost").err().unwrap();
+        assert!(error.contains("HTTP@localhost"), "{}", error);
+        assert!(negotiate.start("localhost").is_err());
+        assert!(negotiate.authorization("localhost").is_none());
+    }
+
+    test reply_token() {
+        let mut headers = hyper::HeaderMap::new();
+        assert_eq!(None, server_token(&headers));
+
+        headers.append("WWW-Authenticate", "Basic realm=\"test\"".parse().unwrap());
+        assert_eq!(None, server_token(&headers));
+
+        headers.append("WWW-Authenticate", "negotiate b2s=".parse().unwrap());
+        assert_eq!(Some(b"ok".to_vec()), server_token(&headers));
+    }
+}
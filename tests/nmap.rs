@@ -0,0 +1,58 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name nmap;
+
+    use librustbuster::nmap::{parse, scheme, target_arguments, NmapTarget};
+
+    const REPORT: &str = r#"<?xml version="1.0"?>
+<nmaprun scanner="nmap" args="nmap -sV app.example.com 192.0.2.20">
+<host><status state="up"/>
+<address addr="192.0.2.10" addrtype="ipv4"/>
+<hostnames><hostname name="app.example.com" type="user"/><hostname name="ptr.example.net" type="PTR"/></hostnames>
+<ports>
+<port protocol="tcp" portid="22"><state state="open"/><service name="ssh" method="probed"/></port>
+<port protocol="tcp" portid="443"><state state="open"/><service name="http" tunnel="ssl" product="nginx" method="probed"/></port>
+<port protocol="tcp" portid="8080"><state state="open"/><service name="http-proxy" method="table"/></port>
+<port protocol="tcp" portid="8443"><state state="filtered"/><service name="https-alt" method="table"/></port>
+</ports>
+</host>
+<host><status state="up"/>
+<address addr="2001:db8::20" addrtype="ipv6"/><address addr="00:11:22:33:44:55" addrtype="mac"/>
+<hostnames><hostname name="ptr.example.net" type="PTR"/></hostnames>
+<ports><port protocol="tcp" portid="8000"><state state="open"/></port></ports>
+</host>
+</nmaprun>"#;
+
+    test parse_report() {
+        let urls: Vec<String> = parse(REPORT).iter().map(NmapTarget::url).collect();
+        assert_eq!(vec![
+            "https://app.example.com/",
+            "http://app.example.com:8080/",
+            "http://[2001:db8::20]:8000/",
+        ], urls);
+    }
+
+    test service_scheme() {
+        assert_eq!(Some("https"), scheme(Some("ssl/http"), None, 4433));
+        assert_eq!(Some("https"), scheme(Some("https-alt"), None, 8443));
+        assert_eq!(Some("http"), scheme(Some("http"), None, 443));
+        assert_eq!(None, scheme(Some("ms-wbt-server"), None, 80));
+        assert_eq!(Some("https"), scheme(None, None, 8443));
+        assert_eq!(None, scheme(None, None, 3306));
+    }
+
+    test arguments() {
+        let args: Vec<String> = vec!["dir", "--input-nmap", "scan.xml", "-w", "words", "-o", "out.json"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(
+            vec!["dir", "-w", "words", "-o", "out.json", "-u", "http://a/", "--output-append"],
+            target_arguments(&args, "http://a/", true)
+        );
+
+        let args = vec!["vhost".to_owned(), "--input-nmap=scan.xml".to_owned()];
+        assert_eq!(vec!["vhost", "-u", "https://b/"], target_arguments(&args, "https://b/", false));
+    }
+}
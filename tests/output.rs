@@ -0,0 +1,93 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name output;
+
+    use librustbuster::output::{capture_headers, format_captured_headers, format_finding, format_result, merge_results, status_color, Journal};
+
+    test capture_requested_headers() {
+        let mut headers = hyper::HeaderMap::new();
+        headers.insert("Server", "nginx".parse().unwrap());
+        headers.insert("X-Powered-By", "PHP/7.4".parse().unwrap());
+        let names = vec!["server".to_owned(), "X-Missing".to_owned()];
+
+        let captured = capture_headers(&headers, &names);
+        assert_eq!(vec![("server".to_owned(), "nginx".to_owned())], captured);
+        assert_eq!("\n\t\t\t\t\t\t=> server: nginx", format_captured_headers(&captured));
+    }
+
+    test status_colors() {
+        assert_eq!("\x1b[32m", status_color("200 OK"));
+        assert_eq!("\x1b[36m", status_color("301 Moved Permanently"));
+        assert_eq!("\x1b[33m", status_color("404 Not Found"));
+        assert_eq!("\x1b[31m", status_color("500 Internal Server Error"));
+        assert_eq!("", status_color(""));
+    }
+
+    test aligned_finding() {
+        assert_eq!(
+            "GET\t200 OK                  http://localhost/admin",
+            format_finding("GET", "200 OK", "http://localhost/admin", None)
+        );
+        assert_eq!(
+            "GET\t407 Proxy Authentication Required http://localhost/",
+            format_finding("GET", "407 Proxy Authentication Required", "http://localhost/", None)
+        );
+    }
+
+    test result_template() {
+        let redirect = serde_json::json!({
+            "method": "GET",
+            "status": "301 Moved Permanently",
+            "url": "http://localhost/admin",
+            "extra": "http://localhost/admin/",
+            "payload": ["admin", "php"],
+        });
+        assert_eq!(
+            "GET 301 Moved Permanently 12 http://localhost/admin -> http://localhost/admin/",
+            format_result("{method} {status} {length} {url} -> {redirect}", &redirect, 12)
+        );
+        assert_eq!("admin,php ", format_result("{payload} {missing}", &redirect, 0));
+
+        let found = serde_json::json!({"status": "401 Unauthorized", "extra": "Basic"});
+        assert_eq!("401 Unauthorized -> ", format_result("{status} -> {redirect}", &found, 0));
+    }
+
+    test partial_journal() {
+        let output = std::env::temp_dir().join("rustbuster-journal.json");
+        let output = output.to_str().unwrap();
+        let partial = format!("{}.partial", output);
+
+        let mut journal = Journal::open(output);
+        for i in 0..100 {
+            journal.record(&serde_json::json!({"url": format!("http://localhost/{}", i)}));
+        }
+        let lines = std::fs::read_to_string(&partial).unwrap();
+        assert_eq!(100, lines.lines().count());
+        assert_eq!("{\"url\":\"http://localhost/0\"}", lines.lines().next().unwrap());
+
+        journal.finish();
+        assert!(!std::path::Path::new(&partial).exists());
+    }
+
+    test merge_with_existing_results() {
+        let output = std::env::temp_dir().join("rustbuster-merge.json");
+        let output = output.to_str().unwrap();
+        std::fs::write(output, r#"[["/a","200"],["/b","200"],["/c","200"]]"#).unwrap();
+
+        let results = vec![
+            ("/b".to_owned(), "403".to_owned()),
+            ("/d".to_owned(), "200".to_owned()),
+            ("/d".to_owned(), "301".to_owned()),
+        ];
+        let merged = merge_results(output, &results, |r: &(String, String)| r.0.clone());
+        let expected: Vec<(String, String)> = vec![
+            ("/a".to_owned(), "200".to_owned()),
+            ("/b".to_owned(), "403".to_owned()),
+            ("/c".to_owned(), "200".to_owned()),
+            ("/d".to_owned(), "301".to_owned()),
+        ];
+        assert_eq!(expected, merged);
+        std::fs::remove_file(output).unwrap();
+    }
+}
@@ -0,0 +1,22 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name progress;
+
+    use librustbuster::progress::ProgressEvent;
+    use std::time::Duration;
+
+    test progress_event() {
+        let event = ProgressEvent::new(300, 1000, 2, Duration::from_secs(3));
+        assert_eq!(100, event.rps);
+        assert_eq!(Some(7), event.eta);
+        assert_eq!(
+            "{\"done\":300,\"total\":1000,\"rps\":100,\"eta\":7,\"findings\":2}",
+            serde_json::to_string(&event).unwrap()
+        );
+
+        let warming_up = ProgressEvent::new(5, 1000, 0, Duration::from_millis(500));
+        assert_eq!(0, warming_up.rps);
+        assert_eq!(None, warming_up.eta);
+    }
+}
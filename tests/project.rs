@@ -0,0 +1,37 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name project;
+
+    test merge() {
+        let mut file = librustbuster::project::ProjectFile::default();
+        let first = vec![
+            serde_json::json!({"method": "GET", "url": "http://localhost/admin", "status": "200 OK"}),
+            serde_json::json!({"method": "GET", "url": "http://localhost/foo", "status": "403 Forbidden"}),
+        ];
+        assert_eq!((2, 0), file.merge("dir", first));
+
+        let second = vec![
+            serde_json::json!({"method": "GET", "url": "http://localhost/foo", "status": "200 OK"}),
+            serde_json::json!({"method": "GET", "url": "http://localhost/bar", "status": "200 OK"}),
+        ];
+        assert_eq!((1, 1), file.merge("dir", second));
+        assert_eq!(3, file.findings["dir"].len());
+        assert_eq!("200 OK", file.findings["dir"][1]["status"]);
+    }
+
+    test history() {
+        let mut file = librustbuster::project::ProjectFile::default();
+        file.scans.push(librustbuster::project::ProjectScan {
+            mode: "dns".to_owned(),
+            arguments: vec!["rustbuster".to_owned(), "dns".to_owned()],
+            started_at: "2020-01-01 00:00:00".to_owned(),
+            ..librustbuster::project::ProjectScan::default()
+        });
+        file.merge("dns", vec![serde_json::json!({"domain": "www.example.com:80", "status": true})]);
+
+        let history = file.history();
+        assert!(history.contains("dns\tinterrupted\n\t\t\t=> rustbuster dns"));
+        assert!(history.contains("dns\ttrue\twww.example.com:80"));
+    }
+}
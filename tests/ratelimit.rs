@@ -0,0 +1,93 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name ratelimit;
+
+    use librustbuster::ratelimit::{parse_rate_budget, parse_rate_limit_delay, Pacer, RateBudget, SlowRule, DEFAULT_MAX_DELAY};
+    use std::time::Duration;
+
+    test rate_budget_headers() {
+        let mut headers = hyper::HeaderMap::new();
+        assert_eq!(None, parse_rate_budget(&headers));
+
+        headers.insert("X-RateLimit-Limit", "100".parse().unwrap());
+        headers.insert("X-RateLimit-Remaining", "40".parse().unwrap());
+        headers.insert("X-RateLimit-Reset", "60".parse().unwrap());
+        let budget = parse_rate_budget(&headers).unwrap();
+        assert_eq!(
+            RateBudget {
+                limit: Some(100),
+                remaining: 40,
+                reset: Some(Duration::from_secs(60)),
+            },
+            budget
+        );
+        assert_eq!("40/100 reset in 60s", budget.to_string());
+        assert_eq!(None, parse_rate_limit_delay(&headers));
+
+        let mut headers = hyper::HeaderMap::new();
+        headers.insert("RateLimit-Limit", "100, 100;w=60".parse().unwrap());
+        headers.insert("RateLimit-Remaining", "0".parse().unwrap());
+        headers.insert("RateLimit-Reset", "30".parse().unwrap());
+        assert_eq!(Some(100), parse_rate_budget(&headers).unwrap().limit);
+        assert_eq!(Some(Duration::from_secs(30)), parse_rate_limit_delay(&headers));
+    }
+
+    test rate_limit_delay_is_capped() {
+        let mut headers = hyper::HeaderMap::new();
+        headers.insert("Retry-After", "86400".parse().unwrap());
+        assert_eq!(Some(Duration::from_secs(86400)), parse_rate_limit_delay(&headers));
+        assert_eq!(Some(DEFAULT_MAX_DELAY), Pacer::default().delay_for(&headers));
+
+        let pacer = Pacer::default().with_max_delay(Duration::from_secs(10));
+        assert_eq!(Some(Duration::from_secs(10)), pacer.delay_for(&headers));
+        headers.insert("Retry-After", "5".parse().unwrap());
+        assert_eq!(Some(Duration::from_secs(5)), pacer.delay_for(&headers));
+
+        let mut headers = hyper::HeaderMap::new();
+        headers.insert("X-RateLimit-Remaining", "0".parse().unwrap());
+        headers.insert("X-RateLimit-Reset", "4102444800".parse().unwrap());
+        assert_eq!(Some(Duration::from_secs(10)), pacer.delay_for(&headers));
+        assert_eq!(None, pacer.delay_for(&hyper::HeaderMap::new()));
+    }
+
+    test budget_interval() {
+        let budget = RateBudget {
+            limit: Some(100),
+            remaining: 50,
+            reset: Some(Duration::from_secs(45)),
+        };
+        assert_eq!(Some(Duration::from_secs(1)), budget.interval());
+
+        assert_eq!(None, RateBudget { remaining: 1, ..budget }.interval());
+        assert_eq!(None, RateBudget { reset: None, ..budget }.interval());
+    }
+
+    test slow_rules() {
+        let rule = SlowRule::parse("5xx:5s").unwrap();
+        assert_eq!("5", rule.status);
+        assert_eq!(Duration::from_secs(5), rule.delay);
+        assert_eq!(Duration::from_millis(2000), SlowRule::parse("403:2000ms").unwrap().delay);
+        assert_eq!(Duration::from_millis(300), SlowRule::parse("40x:300").unwrap().delay);
+        assert!(SlowRule::parse("403").is_err());
+        assert!(SlowRule::parse("4xxx:1s").is_err());
+        assert!(SlowRule::parse("x03:1s").is_err());
+        assert!(SlowRule::parse("403:fast").is_err());
+
+        let pacer = Pacer::new(vec![SlowRule::parse("403:2000ms").unwrap(), rule]);
+        for _ in 0..4 {
+            pacer.observe_status(hyper::StatusCode::FORBIDDEN);
+        }
+        assert_eq!(None, pacer.slow_interval());
+        pacer.observe_status(hyper::StatusCode::FORBIDDEN);
+        assert_eq!(Some(Duration::from_millis(2000)), pacer.slow_interval());
+        for _ in 0..5 {
+            pacer.observe_status(hyper::StatusCode::BAD_GATEWAY);
+        }
+        assert_eq!(Some(Duration::from_secs(5)), pacer.slow_interval());
+        for _ in 0..20 {
+            pacer.observe_status(hyper::StatusCode::OK);
+        }
+        assert_eq!(None, pacer.slow_interval());
+    }
+}
@@ -0,0 +1,11 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name rawpath;
+
+    test escape_raw_path() {
+        assert_eq!("http://localhost/../%2e%2e//etc/passwd", librustbuster::rawpath::escape_raw_path("http://localhost/../%2e%2e//etc/passwd"));
+        assert_eq!("http://localhost/a%20b%23c%7B%7D", librustbuster::rawpath::escape_raw_path("http://localhost/a b#c{}"));
+        assert_eq!("http://localhost", librustbuster::rawpath::escape_raw_path("http://localhost"));
+    }
+}
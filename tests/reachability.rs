@@ -0,0 +1,60 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name reachability;
+
+    use librustbuster::{
+        reachability::{detect_scheme, probe},
+        summary::ErrorCategory,
+    };
+
+    test probe_unreachable() {
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let error = probe(&format!("http://127.0.0.1:{}/", port), false).unwrap_err();
+        assert_eq!("TCP connect", error.step);
+        assert_eq!(ErrorCategory::ConnectionRefused, error.category);
+        assert!(error.to_string().ends_with("check the port and that the service is running"));
+
+        let error = probe("not a url", false).unwrap_err();
+        assert_eq!("URL parsing", error.step);
+    }
+
+    test probe_reachable() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            use std::io::{Read, Write};
+            // the TCP connect of the probe comes first, then its request
+            for stream in listener.incoming().take(2) {
+                let mut stream = stream.unwrap();
+                let mut buffer = [0; 1024];
+                if stream.read(&mut buffer).unwrap_or(0) > 0 {
+                    stream.write_all(b"HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\n\r\n").unwrap();
+                }
+            }
+        });
+
+        assert_eq!(Ok("403 Forbidden".to_owned()), probe(&format!("http://127.0.0.1:{}/", port), false));
+    }
+
+    test detect_scheme_plain_http() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            use std::io::{Read, Write};
+            for stream in listener.incoming().take(2) {
+                let mut stream = stream.unwrap();
+                let mut buffer = [0; 1024];
+                if stream.read(&mut buffer).unwrap_or(0) > 0 {
+                    stream.write_all(b"HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\n\r\n").unwrap_or(());
+                }
+            }
+        });
+
+        assert_eq!(Ok("http"), detect_scheme(&format!("127.0.0.1:{}", port)));
+
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let error = detect_scheme(&format!("127.0.0.1:{}/admin", port)).unwrap_err();
+        assert_eq!(ErrorCategory::ConnectionRefused, error.category);
+    }
+}
@@ -0,0 +1,87 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name report;
+
+    use librustbuster::report::{directory, order, summary, GroupBy, Reported, SortKey};
+
+    struct Result(&'static str, &'static str, usize);
+
+    impl Reported for Result {
+        fn url(&self) -> &str {
+            self.0
+        }
+
+        fn status(&self) -> &str {
+            self.1
+        }
+
+        fn length(&self) -> usize {
+            self.2
+        }
+    }
+
+    fn results() -> Vec<Result> {
+        vec![
+            Result("http://localhost/admin/login", "200 OK", 30),
+            Result("http://localhost/backup", "403 Forbidden", 10),
+            Result("http://localhost/admin/", "301 Moved Permanently", 0),
+            Result("http://localhost/admin/config", "403 Forbidden", 20),
+            Result("http://localhost/index.php?id=1", "200 OK", 20),
+        ]
+    }
+
+    fn urls(results: &[Result]) -> Vec<&str> {
+        results.iter().map(|r| r.0).collect()
+    }
+
+    test directory_of_urls() {
+        assert_eq!("http://localhost/admin/", directory("http://localhost/admin/login"));
+        assert_eq!("http://localhost/", directory("http://localhost/admin/"));
+        assert_eq!("https://localhost:8443/", directory("https://localhost:8443/?id=1"));
+    }
+
+    test order_sorts_within_groups() {
+        let mut sorted = results();
+        order(&mut sorted, Some(SortKey::Length), None);
+        assert_eq!(
+            vec![
+                "http://localhost/admin/",
+                "http://localhost/backup",
+                "http://localhost/admin/config",
+                "http://localhost/index.php?id=1",
+                "http://localhost/admin/login",
+            ],
+            urls(&sorted)
+        );
+
+        let mut grouped = results();
+        order(&mut grouped, Some(SortKey::Url), Some(GroupBy::Status));
+        assert_eq!(
+            vec![
+                "http://localhost/admin/login",
+                "http://localhost/index.php?id=1",
+                "http://localhost/admin/",
+                "http://localhost/admin/config",
+                "http://localhost/backup",
+            ],
+            urls(&grouped)
+        );
+    }
+
+    test summary_of_groups() {
+        let mut grouped = results();
+        order(&mut grouped, Some(SortKey::Status), Some(GroupBy::Directory));
+        assert_eq!(
+            "\n[+] Results\
+             \n    http://localhost/\
+             \n\t200 OK\t20\thttp://localhost/index.php?id=1\
+             \n\t301 Moved Permanently\t0\thttp://localhost/admin/\
+             \n\t403 Forbidden\t10\thttp://localhost/backup\
+             \n    http://localhost/admin/\
+             \n\t200 OK\t30\thttp://localhost/admin/login\
+             \n\t403 Forbidden\t20\thttp://localhost/admin/config",
+            summary(&grouped, Some(GroupBy::Directory))
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name scheduler;
+
+    use futures::Stream;
+    use librustbuster::scheduler::{prioritize, word_of, HitRates, JobKind, JobQueue, Scheduler};
+    use std::collections::BTreeMap;
+
+    test priority_queue_order() {
+        let mut scheduler = Scheduler::default();
+        scheduler.push(JobKind::BruteForce, 0, "a");
+        scheduler.push(JobKind::BruteForce, 0, "b");
+        scheduler.push(JobKind::History, 1, "c");
+        scheduler.push(JobKind::History, 3, "d");
+        scheduler.push(JobKind::FollowUp, 0, "e");
+        assert_eq!(vec!["e", "d", "c", "a", "b"], scheduler.collect::<Vec<&str>>());
+    }
+
+    test job_queue_takes_follow_ups_while_running() {
+        let queue = JobQueue::default();
+        queue.push(JobKind::BruteForce, 0, "a");
+        queue.push(JobKind::BruteForce, 0, "b");
+        queue.push(JobKind::BruteForce, 0, "c");
+        assert_eq!(3, queue.pending());
+
+        let mut jobs = queue.clone().wait();
+        assert_eq!(Some(Ok("a")), jobs.next());
+        // found while the scan runs, it goes before the remaining brute force
+        queue.push(JobKind::FollowUp, 0, "a/x");
+        assert_eq!(Some(Ok("a/x")), jobs.next());
+
+        let producer = queue.clone();
+        let (drained_tx, drained_rx) = std::sync::mpsc::channel();
+        let handle = std::thread::spawn(move || {
+            drained_rx.recv().unwrap();
+            producer.push(JobKind::FollowUp, 0, "late");
+            producer.close();
+        });
+        assert_eq!(Some(Ok("b")), jobs.next());
+        assert_eq!(Some(Ok("c")), jobs.next());
+        drained_tx.send(()).unwrap();
+        // the stream waits for the late job, then ends once the queue is closed
+        assert_eq!(Some(Ok("late")), jobs.next());
+        assert_eq!(None, jobs.next());
+        handle.join().unwrap();
+    }
+
+    test history_first() {
+        let mut findings = BTreeMap::new();
+        findings.insert(
+            "dir".to_owned(),
+            vec![
+                serde_json::json!({ "url": "http://old.example/admin/" }),
+                serde_json::json!({ "url": "http://other.example/admin" }),
+                serde_json::json!({ "url": "http://old.example/Backup" }),
+            ],
+        );
+        findings.insert("vhost".to_owned(), vec![serde_json::json!({ "vhost": "dev.example.com" })]);
+        let hit_rates = HitRates::from_findings(&findings);
+        assert_eq!(2, hit_rates.hits("admin"));
+        assert_eq!(1, hit_rates.hits("backup"));
+        assert_eq!(1, hit_rates.hits("dev"));
+
+        let urls: Vec<hyper::Uri> = ["index", "backup", "login", "admin"]
+            .iter()
+            .map(|w| format!("http://localhost/{}", w).parse().unwrap())
+            .collect();
+        let ordered: Vec<String> = prioritize(urls.clone(), 0, &hit_rates).iter().map(word_of).collect();
+        assert_eq!(vec!["admin", "backup", "index", "login"], ordered);
+        let ordered: Vec<String> = prioritize(urls.clone(), 1, &hit_rates).iter().map(word_of).collect();
+        assert_eq!(vec!["index", "admin", "backup", "login"], ordered);
+        assert_eq!(urls, prioritize(urls.clone(), 0, &HitRates::default()));
+
+        assert_eq!("dev", word_of(&"dev.example.com".parse().unwrap()));
+    }
+}
@@ -0,0 +1,32 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name session;
+
+    test parse_session() {
+        let session = librustbuster::session::Session::load("./examples/session.yaml").unwrap();
+        let mut headers = hyper::HeaderMap::new();
+        headers.insert("Location", "/login?next=/admin".parse().unwrap());
+        assert!(session.is_expired(hyper::StatusCode::UNAUTHORIZED, &hyper::HeaderMap::new()));
+        assert!(session.is_expired(hyper::StatusCode::FOUND, &headers));
+        assert!(!session.is_expired(hyper::StatusCode::OK, &headers));
+        assert!(librustbuster::session::Session::parse("login: {}").is_err());
+        assert!(librustbuster::session::Session::parse("login: {url: /, headers: [bad]}").is_err());
+    }
+
+    test observe_login() {
+        let session = librustbuster::session::Session::load("./examples/session.yaml").unwrap();
+        let mut headers = hyper::HeaderMap::new();
+        headers.append("Set-Cookie", "session=abc; HttpOnly".parse().unwrap());
+        headers.append("Set-Cookie", "theme=dark".parse().unwrap());
+        assert!(!session.observe(&hyper::HeaderMap::new(), ""));
+        assert_eq!(0, session.generation());
+        assert!(session.observe(&headers, r#"{"token":"t.k"}"#));
+        assert_eq!(1, session.generation());
+        let expected = vec![
+            ("Cookie".to_owned(), "session=abc".to_owned()),
+            ("Authorization".to_owned(), "Bearer t.k".to_owned()),
+        ];
+        assert_eq!(expected, session.headers());
+    }
+}
@@ -0,0 +1,33 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name severity;
+
+    test parse_rule() {
+        let rule = librustbuster::severity::parse_rule("high:path=/backup").unwrap();
+        assert_eq!(librustbuster::severity::Severity::High, rule.severity);
+        assert_eq!(librustbuster::severity::Matcher::Path("/backup".to_owned()), rule.matcher);
+        assert_eq!(
+            librustbuster::severity::Matcher::Body("a=b".to_owned()),
+            librustbuster::severity::parse_rule("low:body=a=b").unwrap().matcher
+        );
+        assert_eq!(None, librustbuster::severity::parse_rule("critical:path=/backup"));
+        assert_eq!(None, librustbuster::severity::parse_rule("high:header=Server"));
+        assert_eq!(None, librustbuster::severity::parse_rule("high"));
+    }
+
+    test classify_defaults() {
+        let classifier = librustbuster::severity::Classifier::new(vec![]);
+        assert_eq!(librustbuster::severity::Severity::High, classifier.classify("http://localhost/.git/HEAD", "200 OK", ""));
+        assert_eq!(librustbuster::severity::Severity::Info, classifier.classify("http://localhost/.git/HEAD", "403 Forbidden", ""));
+        assert_eq!(librustbuster::severity::Severity::Medium, classifier.classify("http://localhost/files/", "200 OK", "<title>Index of /files</title>"));
+        assert_eq!(librustbuster::severity::Severity::Info, classifier.classify("http://localhost/admin", "301 Moved Permanently", ""));
+        assert_eq!(librustbuster::severity::Severity::Low, classifier.classify("http://localhost/admin", "200 OK", ""));
+        assert_eq!(librustbuster::severity::Severity::Low, classifier.classify("http://localhost/admin?f=.git/", "200 OK", ""));
+    }
+
+    test classify_user_rules() {
+        let classifier = librustbuster::severity::Classifier::new(vec![librustbuster::severity::parse_rule("high:path=/admin").unwrap()]);
+        assert_eq!(librustbuster::severity::Severity::High, classifier.classify("http://localhost/admin", "403 Forbidden", ""));
+    }
+}
@@ -0,0 +1,53 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name sigv4;
+
+    use chrono::{TimeZone, Utc};
+    use librustbuster::sigv4::{AwsCredentials, SigV4};
+
+    fixture signer() -> SigV4 {
+        setup(&mut self) {
+            let credentials = AwsCredentials {
+                access_key_id: "AKIDEXAMPLE".to_owned(),
+                secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_owned(),
+                session_token: None,
+            };
+            SigV4::new("us-east-1/service", credentials).unwrap()
+        }
+    }
+
+    test get_vanilla(signer) {
+        let uri = "https://example.amazonaws.com/".parse::<hyper::Uri>().unwrap();
+        let headers = signer.val.sign_at("GET", "example.amazonaws.com", &uri, b"", Utc.ymd(2015, 8, 30).and_hms(12, 36, 0));
+        assert_eq!(("x-amz-date".to_owned(), "20150830T123600Z".to_owned()), headers[0]);
+        assert_eq!(
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, SignedHeaders=host;x-amz-date, Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31",
+            headers[1].1
+        );
+    }
+
+    test get_query_order(signer) {
+        let uri = "https://example.amazonaws.com/?Param2=value2&Param1=value1".parse::<hyper::Uri>().unwrap();
+        let headers = signer.val.sign_at("GET", "example.amazonaws.com", &uri, b"", Utc.ymd(2015, 8, 30).and_hms(12, 36, 0));
+        assert!(headers[1].1.ends_with("Signature=b97d918cfa904a5beff61c982a1b6f458b799221646efd99d3219ec94cdf2500"));
+    }
+
+    test host_header() {
+        use librustbuster::sigv4::host_header;
+
+        let uri = "https://example.amazonaws.com/".parse::<hyper::Uri>().unwrap();
+        assert_eq!(Some("example.amazonaws.com".to_owned()), host_header(&uri));
+        let uri = "http://localhost:4566/bucket".parse::<hyper::Uri>().unwrap();
+        assert_eq!(Some("localhost:4566".to_owned()), host_header(&uri));
+    }
+
+    test credentials_from_profile() {
+        let content = "[default]\naws_access_key_id = A\naws_secret_access_key = B\n\n[other]\naws_access_key_id=C\naws_secret_access_key=D\naws_session_token=E\n";
+        let credentials = AwsCredentials::from_profile(content, "other").unwrap();
+        assert_eq!("C", credentials.access_key_id);
+        assert_eq!(Some("E".to_owned()), credentials.session_token);
+        assert_eq!(None, AwsCredentials::from_profile(content, "missing"));
+        assert!(SigV4::new("us-east-1", credentials).is_err());
+    }
+}
@@ -0,0 +1,52 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name sink;
+
+    use librustbuster::dnsbuster::result_processor::{DnsScanResult, SingleDnsScanResult};
+    use librustbuster::sink::{Pipeline, ResultSink};
+    use std::sync::{Arc, Mutex};
+
+    fn result(domain: &str, status: bool) -> SingleDnsScanResult {
+        SingleDnsScanResult {
+            domain: domain.to_owned(),
+            status,
+            extra: None,
+            asn: Vec::new(),
+            geo: Vec::new(),
+        }
+    }
+
+    // keeps the resolved names in upper case, as a database would store them
+    struct Database(Arc<Mutex<Vec<String>>>);
+
+    impl ResultSink<SingleDnsScanResult> for Database {
+        fn accept(&self, result: &SingleDnsScanResult) -> bool {
+            result.status
+        }
+
+        fn transform(&mut self, mut result: SingleDnsScanResult) -> SingleDnsScanResult {
+            result.domain = result.domain.to_uppercase();
+            result
+        }
+
+        fn store(&mut self, result: SingleDnsScanResult) {
+            self.0.lock().unwrap().push(result.domain);
+        }
+    }
+
+    test pipeline_sinks() {
+        let rows = Arc::new(Mutex::new(Vec::new()));
+        let mut kept = Vec::new();
+        let mut pipeline = Pipeline::new(DnsScanResult::new());
+        pipeline
+            .register(Box::new(Database(rows.clone())))
+            .register(Box::new(&mut kept));
+
+        assert!(pipeline.maybe_add_result(result("www.example.com:80", true)));
+        assert!(pipeline.maybe_add_result(result("dev.example.com:80", false)));
+        assert_eq!(2, pipeline.into_processor().results.len());
+        assert_eq!(2, kept.len());
+        assert_eq!(vec!["WWW.EXAMPLE.COM:80"], *rows.lock().unwrap());
+    }
+}
@@ -0,0 +1,70 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name soap;
+
+    const WSDL: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<wsdl:definitions xmlns:soap="http://schemas.xmlsoap.org/wsdl/soap/" xmlns:tns="http://tempuri.org/" xmlns:s="http://www.w3.org/2001/XMLSchema" targetNamespace="http://tempuri.org/" xmlns:wsdl="http://schemas.xmlsoap.org/wsdl/">
+  <wsdl:types>
+    <s:schema elementFormDefault="qualified" targetNamespace="http://tempuri.org/">
+      <s:element name="GetUser">
+        <s:complexType><s:sequence>
+          <s:element minOccurs="0" maxOccurs="1" name="username" type="s:string" />
+          <s:element minOccurs="1" maxOccurs="1" name="id" type="s:int" />
+        </s:sequence></s:complexType>
+      </s:element>
+      <s:element name="Ping"><s:complexType /></s:element>
+    </s:schema>
+  </wsdl:types>
+  <wsdl:message name="GetUserSoapIn"><wsdl:part name="parameters" element="tns:GetUser" /></wsdl:message>
+  <wsdl:message name="PingSoapIn"><wsdl:part name="parameters" element="tns:Ping" /></wsdl:message>
+  <wsdl:message name="LookupIn"><wsdl:part name="query" type="s:string" /></wsdl:message>
+  <wsdl:portType name="UserServiceSoap">
+    <wsdl:operation name="GetUser"><wsdl:input message="tns:GetUserSoapIn" /></wsdl:operation>
+    <wsdl:operation name="Ping"><wsdl:input message="tns:PingSoapIn" /></wsdl:operation>
+    <wsdl:operation name="Lookup"><wsdl:input message="tns:LookupIn" /></wsdl:operation>
+  </wsdl:portType>
+  <wsdl:binding name="UserServiceSoap" type="tns:UserServiceSoap">
+    <soap:binding transport="http://schemas.xmlsoap.org/soap/http" />
+    <wsdl:operation name="GetUser"><soap:operation soapAction="http://tempuri.org/GetUser" /></wsdl:operation>
+  </wsdl:binding>
+  <wsdl:service name="UserService">
+    <wsdl:port name="UserServiceSoap" binding="tns:UserServiceSoap"><soap:address location="http://internal.corp/UserService.asmx" /></wsdl:port>
+  </wsdl:service>
+</wsdl:definitions>"#;
+
+    test parse_operations() {
+        let service = librustbuster::soap::parse_wsdl(WSDL).unwrap();
+        assert_eq!("http://internal.corp/UserService.asmx", service.endpoint);
+        assert_eq!("http://tempuri.org/", service.namespace);
+        assert_eq!(3, service.operations.len());
+        assert_eq!(vec!["username".to_owned(), "id".to_owned()], service.operations[0].params);
+        assert_eq!("http://tempuri.org/GetUser", service.operations[0].action);
+        assert_eq!("GetUser", service.operations[0].wrapper);
+        assert!(service.operations[1].params.is_empty());
+        assert_eq!(vec!["query".to_owned()], service.operations[2].params);
+        assert_eq!("Lookup", service.operations[2].wrapper);
+        assert_eq!(None, librustbuster::soap::parse_wsdl("<html><body>Not found</body></html>"));
+    }
+
+    test fuzz_templates() {
+        let service = librustbuster::soap::parse_wsdl(WSDL).unwrap();
+        let body = librustbuster::soap::request_body(&service.namespace, &service.operations[0], &[]);
+        assert!(body.contains("xmlns:tns=\"http://tempuri.org/\""));
+        assert!(body.contains("<tns:GetUser><tns:username>FUZZ</tns:username><tns:id>?</tns:id></tns:GetUser>"));
+
+        let body = librustbuster::soap::request_body(&service.namespace, &service.operations[0], &["id".to_owned()]);
+        assert!(body.contains("<tns:username>?</tns:username><tns:id>FUZZ</tns:id>"));
+    }
+
+    test service_urls() {
+        assert_eq!("http://localhost/a.asmx?wsdl", librustbuster::soap::wsdl_url("http://localhost/a.asmx"));
+        assert_eq!("http://localhost/a?WSDL", librustbuster::soap::wsdl_url("http://localhost/a?WSDL"));
+        assert_eq!("http://localhost/a?v=1&wsdl", librustbuster::soap::wsdl_url("http://localhost/a?v=1"));
+        assert_eq!(
+            "https://target:8443/UserService.asmx",
+            librustbuster::soap::endpoint_url("https://target:8443/svc?wsdl", "http://internal.corp/UserService.asmx")
+        );
+        assert_eq!("http://target/svc", librustbuster::soap::endpoint_url("http://target/svc?wsdl", ""));
+    }
+}
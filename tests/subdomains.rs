@@ -0,0 +1,34 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name subdomains;
+
+    use librustbuster::subdomains::parse;
+
+    test amass_and_subfinder_output() {
+        let content = "\
+# amass enum -src -ip
+[crtsh]           WWW.example.com 192.0.2.10,2001:db8::10
+[Brute Forcing]   api.example.com. 192.0.2.11
+{\"host\":\"mail.example.com\",\"input\":\"example.com\",\"source\":\"crtsh\"}
+{\"name\":\"dev.example.com\",\"domain\":\"example.com\",\"addresses\":[]}
+vpn.example.com,crtsh
+*.cdn.example.com
+
+www.example.com
+";
+        assert_eq!(vec![
+            "www.example.com",
+            "api.example.com",
+            "mail.example.com",
+            "dev.example.com",
+            "vpn.example.com",
+            "cdn.example.com",
+        ], parse(content, ""));
+    }
+
+    test scope() {
+        let content = "a.example.com\nexample.com\nnotexample.com\nb.example.org\n";
+        assert_eq!(vec!["a.example.com", "example.com"], parse(content, "Example.com."));
+    }
+}
@@ -0,0 +1,59 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name summary;
+
+    test auth_surface() {
+        let mut auth_surface = librustbuster::summary::AuthSurface::default();
+        assert!(auth_surface.is_empty());
+        auth_surface.maybe_add("http://localhost/admin", "401 Unauthorized", Some("Basic realm=\"admin\""));
+        auth_surface.maybe_add("http://localhost/private", "403 Forbidden", None);
+        auth_surface.maybe_add("http://localhost/", "200 OK", None);
+        assert_eq!(vec![("http://localhost/admin".to_owned(), "Basic".to_owned())], auth_surface.unauthorized);
+        assert_eq!(vec!["http://localhost/private".to_owned()], auth_surface.forbidden);
+    }
+
+    test header_audit() {
+        let audit = librustbuster::summary::HeaderAudit::default();
+        let uri = "https://localhost:8443/admin".parse::<hyper::Uri>().unwrap();
+        let mut headers = hyper::HeaderMap::new();
+        headers.insert("X-Frame-Options", "DENY".parse().unwrap());
+        audit.observe(&uri, &headers);
+        audit.observe(&uri, &hyper::HeaderMap::new());
+
+        let report = audit.report();
+        assert!(report.contains("[+] localhost:8443\t\t: 2 responses"));
+        assert!(report.contains("X-Frame-Options: DENY (missing in 1)"));
+        assert!(report.contains("Strict-Transport-Security: missing"));
+    }
+
+    test redirect_groups() {
+        let mut groups = librustbuster::summary::RedirectGroups::default();
+        assert!(groups.add("http://localhost/a", "/login?next=/a"));
+        assert!(!groups.add("http://localhost/b", "http://localhost/login?next=/b"));
+        assert!(!groups.add("http://localhost/dir/c", "../login"));
+        assert!(groups.add("http://localhost/admin", "/admin/"));
+        assert_eq!(2, groups.collapsed());
+        assert_eq!("\n[+] Redirect groups\n\t3 paths redirect to http://localhost/login", groups.report());
+    }
+
+    test error_summary() {
+        use librustbuster::summary::{ErrorCategory, ErrorSummary};
+
+        assert_eq!(ErrorCategory::Dns, ErrorCategory::of("error trying to connect: failed to lookup address information: Name or service not known"));
+        assert_eq!(ErrorCategory::ConnectionRefused, ErrorCategory::of("error trying to connect: Connection refused (os error 111)"));
+        assert_eq!(ErrorCategory::Tls, ErrorCategory::of("error trying to connect: the handshake failed: certificate verify failed"));
+        assert_eq!(ErrorCategory::Timeout, ErrorCategory::of("error trying to connect: Connection timed out (os error 110)"));
+        assert_eq!(ErrorCategory::Other, ErrorCategory::of("connection closed before message completed"));
+
+        let mut errors = ErrorSummary::default();
+        assert!(errors.is_empty());
+        errors.add_status("200 OK");
+        assert!(errors.is_empty());
+        errors.add_status("502 Bad Gateway");
+        errors.add_error("Connection refused (os error 111)");
+        errors.add_error("Connection refused (os error 111)");
+        assert_eq!(2, errors.count(ErrorCategory::ConnectionRefused));
+        assert_eq!("\n[+] Errors\n\tConnection refused  : 2\n\tServer error (5xx)  : 1", errors.report());
+    }
+}
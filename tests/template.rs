@@ -0,0 +1,36 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name template;
+
+    use librustbuster::template::{expand, expand_bytes, word_from_uri};
+
+    test expand_word_and_unknown() {
+        assert_eq!("/admin?x={{nope}}", expand("/{{word}}?x={{nope}}", "admin"));
+        assert_eq!("no templates", expand("no templates", "admin"));
+        assert_eq!("broken {{word", expand("broken {{word", "admin"));
+    }
+
+    test expand_random_values() {
+        let first = expand("{{rand(8)}}", "");
+        let second = expand("{{ rand(8) }}", "");
+        assert_eq!(8, first.len());
+        assert!(first.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()));
+        assert_ne!(first, second);
+
+        let uuid = expand("{{uuid}}", "");
+        assert_eq!(36, uuid.len());
+        assert_eq!('4', uuid.chars().nth(14).unwrap());
+        assert!(expand("{{timestamp}}", "").parse::<u64>().unwrap() > 1_500_000_000);
+    }
+
+    test expand_binary_body() {
+        assert_eq!(vec![0xff, b'{', b'{'], expand_bytes(&[0xff, b'{', b'{'], "admin"));
+        assert_eq!(b"id=admin".to_vec(), expand_bytes(b"id={{word}}", "admin"));
+    }
+
+    test uri_word() {
+        let uri = "http://localhost/dir/admin/?x=1".parse::<hyper::Uri>().unwrap();
+        assert_eq!("admin", word_from_uri(&uri));
+    }
+}
@@ -0,0 +1,32 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name tls;
+
+    use librustbuster::tls::version_range;
+    use native_tls::Protocol;
+
+    test parse_known_versions() {
+        assert!(matches!(version_range(Some("1.0"), None), Ok((Some(Protocol::Tlsv10), None))));
+        assert!(matches!(version_range(Some("1.1"), Some("1.2")), Ok((Some(Protocol::Tlsv11), Some(Protocol::Tlsv12)))));
+    }
+
+    test parse_unknown_version() {
+        assert!(version_range(Some("1.4"), None).is_err());
+        assert!(version_range(None, Some("ssl3")).is_err());
+    }
+
+    test range_is_bounded_by_1_3() {
+        assert!(matches!(version_range(None, Some("1.3")), Ok((None, Some(Protocol::Tlsv13)))));
+        assert!(matches!(version_range(Some("1.3"), None), Ok((Some(Protocol::Tlsv13), None))));
+    }
+
+    test range_rejects_min_above_max() {
+        assert!(version_range(Some("1.2"), Some("1.0")).is_err());
+        assert!(matches!(
+            version_range(Some("1.2"), Some("1.2")),
+            Ok((Some(Protocol::Tlsv12), Some(Protocol::Tlsv12)))
+        ));
+        assert!(matches!(version_range(None, None), Ok((None, None))));
+    }
+}
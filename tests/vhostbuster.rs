@@ -0,0 +1,48 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name vhostbuster;
+
+    test describe_resolution() {
+        let target = vec!["10.0.0.1".to_owned()];
+        assert_eq!(
+            "no public record, only reachable behind the target",
+            librustbuster::vhostbuster::utils::describe_resolution(&[], &target)
+        );
+        assert_eq!(
+            "public record pointing to the target: 10.0.0.1",
+            librustbuster::vhostbuster::utils::describe_resolution(&["10.0.0.1".to_owned()], &target)
+        );
+        assert_eq!(
+            "public record pointing elsewhere: 10.0.0.2, 10.0.0.3",
+            librustbuster::vhostbuster::utils::describe_resolution(&["10.0.0.2".to_owned(), "10.0.0.3".to_owned()], &target)
+        );
+    }
+
+    test response_signature() {
+        let baseline = librustbuster::vhostbuster::utils::ResponseSignature::new(
+            "3f2a.test.local", "200 OK", None, "Welcome to 3f2a.test.local",
+        );
+        let same = librustbuster::vhostbuster::utils::ResponseSignature::new(
+            "www.test.local", "200 OK", None, "Welcome to www.test.local",
+        );
+        let different = librustbuster::vhostbuster::utils::ResponseSignature::new(
+            "sso.test.local", "302 Found", Some("https://sso.test.local/login"), "",
+        );
+        assert_eq!(baseline, same);
+        assert_ne!(baseline, different);
+
+        let host = librustbuster::vhostbuster::utils::baseline_host("www.test.local");
+        assert!(host.ends_with(".test.local"));
+        assert_eq!(32, host.len() - ".test.local".len());
+    }
+
+    test html_title() {
+        assert_eq!(
+            Some("Sign in - Corp SSO".to_owned()),
+            librustbuster::vhostbuster::utils::html_title("<html><head><TITLE lang=\"en\">\n  Sign in -\n Corp SSO </TITLE></head>")
+        );
+        assert_eq!(None, librustbuster::vhostbuster::utils::html_title("<title> </title>"));
+        assert_eq!(None, librustbuster::vhostbuster::utils::html_title("no title"));
+    }
+}
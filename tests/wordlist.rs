@@ -0,0 +1,44 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name wordlist;
+
+    test inline_wordlists() {
+        assert_eq!("admin\nroot\ntest", librustbuster::wordlist::read("inline:admin,root,test").unwrap());
+        assert_eq!("", librustbuster::wordlist::read("inline:").unwrap());
+        assert!(librustbuster::wordlist::exists("inline:admin"));
+    }
+
+    test file_wordlists() {
+        assert_eq!("1\n2\n", librustbuster::wordlist::read("./examples/wordlist_short").unwrap());
+        assert!(librustbuster::wordlist::exists("./examples/wordlist_short"));
+        assert!(!librustbuster::wordlist::exists("./examples/missing"));
+        assert!(librustbuster::wordlist::read("./examples/missing").is_err());
+    }
+
+    test filter_wordlists() {
+        let filter = librustbuster::wordlist::WordlistFilter {
+            dedupe: true,
+            trim: true,
+            skip_comments: true,
+            min_length: 2,
+            max_length: 5,
+        };
+        assert!(filter.is_active());
+        assert!(!librustbuster::wordlist::WordlistFilter::default().is_active());
+        let (words, removed) = filter.apply("admin\n  admin \n# comment\n\na\nbackups\nlogin\n");
+        assert_eq!("admin\nlogin", words);
+        assert_eq!(5, removed);
+    }
+
+    test filter_candidates_only() {
+        librustbuster::wordlist::set_filter(librustbuster::wordlist::WordlistFilter {
+            min_length: 4,
+            ..Default::default()
+        });
+        // the extensions, credentials and other lists keep their short entries
+        assert_eq!("php\nasp\nbackup", librustbuster::wordlist::read("inline:php,asp,backup").unwrap());
+        assert_eq!("backup", librustbuster::wordlist::read_filtered("inline:php,asp,backup").unwrap());
+        librustbuster::wordlist::set_filter(Default::default());
+    }
+}
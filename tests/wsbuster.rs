@@ -0,0 +1,36 @@
+use galvanic_test::test_suite;
+
+test_suite! {
+    name wsbuster;
+
+    use librustbuster::wsbuster::utils::{decode_frame, encode_frame, http_url, websocket_key};
+
+    test websocket_urls() {
+        assert_eq!("http://localhost/socket", http_url("ws://localhost/socket"));
+        assert_eq!("https://localhost:8443/", http_url("wss://localhost:8443/"));
+        assert_eq!("http://localhost/", http_url("http://localhost/"));
+    }
+
+    test handshake_key() {
+        assert_eq!(24, websocket_key().len());
+        assert!(websocket_key().ends_with("=="));
+    }
+
+    test masked_frames() {
+        let frame = encode_frame(b"hello", [1, 2, 3, 4]);
+        assert_eq!(vec![0x81, 0x85, 1, 2, 3, 4, b'h' ^ 1, b'e' ^ 2, b'l' ^ 3, b'l' ^ 4, b'o' ^ 1], frame);
+        assert_eq!(Some((1, b"hello".to_vec())), decode_frame(&frame));
+
+        let payload = vec![b'a'; 300];
+        let frame = encode_frame(&payload, [0, 0, 0, 0]);
+        assert_eq!(&[0x81, 0xfe, 1, 44][..], &frame[..4]);
+        assert_eq!(Some((1, payload)), decode_frame(&frame));
+    }
+
+    test server_frames() {
+        assert_eq!(Some((1, b"echo".to_vec())), decode_frame(&[0x81, 4, b'e', b'c', b'h', b'o']));
+        assert_eq!(Some((1, b"ec".to_vec())), decode_frame(&[0x81, 4, b'e', b'c']));
+        assert_eq!(Some((8, Vec::new())), decode_frame(&[0x88, 0]));
+        assert_eq!(None, decode_frame(&[0x81]));
+    }
+}